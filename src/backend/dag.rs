@@ -21,6 +21,10 @@ pub struct DagNode {
 pub struct ReputationDetails {
     pub score: u32,
     pub breakdown: ReputationBreakdown,
+    #[serde(default)]
+    pub endorsements_by_skill: Vec<(String, u32)>, // (skill, endorser count), most endorsed first
+    #[serde(default)]
+    pub governance_score: u64, // Lifetime governance participation, uncapped
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -29,6 +33,10 @@ pub struct ReputationBreakdown {
     pub storage: u32,
     pub content: u32,
     pub governance: u32,
+    #[serde(default)]
+    pub civic_participation: u32, // Proposal votes cast, plus 5 per proposal authored
+    #[serde(default)]
+    pub seller: u32, // 5 per listing sold, capped at 50
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -46,12 +54,28 @@ pub enum DagPayload {
     Post(PostPayload),
     #[serde(rename = "proof:v1")]
     Proof(ProofPayload),
+    #[serde(rename = "vouch_revocation:v1")]
+    VouchRevocation(VouchRevocationPayload),
     #[serde(rename = "message:v1")]
     Message(MessagePayload),
+    #[serde(rename = "message_receipt:v1")]
+    MessageReceipt(MessageReceiptPayload),
     #[serde(rename = "group:v1")]
     Group(GroupPayload),
+    #[serde(rename = "group_invite:v1")]
+    GroupInvite(GroupInvitePayload),
+    #[serde(rename = "group_invite_response:v1")]
+    GroupInviteResponse(GroupInviteResponsePayload),
+    #[serde(rename = "group_policy:v1")]
+    GroupPolicy(GroupPolicyPayload),
+    #[serde(rename = "group_key:v1")]
+    GroupKey(GroupKeyPayload),
     #[serde(rename = "token:v1")]
     Token(TokenPayload),
+    #[serde(rename = "multisig_proposal:v1")]
+    MultiSigProposal(MultiSigProposalPayload),
+    #[serde(rename = "multisig_approval:v1")]
+    MultiSigApproval(MultiSigApprovalPayload),
     #[serde(rename = "web:v1")]
     Web(WebPayload),
     #[serde(rename = "name:v1")]
@@ -64,14 +88,22 @@ pub enum DagPayload {
     Contract(ContractPayload),
     #[serde(rename = "contract_call:v1")]
     ContractCall(ContractCallPayload),
+    #[serde(rename = "milestone_completion:v1")]
+    MilestoneCompletion(MilestoneCompletionPayload),
     #[serde(rename = "proposal:v1")]
     Proposal(ProposalPayload),
     #[serde(rename = "vote:v1")]
     Vote(VotePayload),
+    #[serde(rename = "vote_delegation:v1")]
+    VoteDelegation(VoteDelegationPayload),
+    #[serde(rename = "proposal_outcome:v1")]
+    ProposalOutcome(ProposalOutcomePayload),
     #[serde(rename = "candidacy:v1")]
     Candidacy(CandidacyPayload),
     #[serde(rename = "candidacy_vote:v1")]
     CandidacyVote(CandidacyVotePayload),
+    #[serde(rename = "candidacy_withdrawal:v1")]
+    CandidacyWithdrawal(CandidacyWithdrawalPayload),
     #[serde(rename = "report:v1")]
     Report(ReportPayload),
     #[serde(rename = "file:v1")]
@@ -88,6 +120,8 @@ pub enum DagPayload {
     Comment(CommentPayload),
     #[serde(rename = "like:v1")]
     Like(LikePayload),
+    #[serde(rename = "reaction:v1")]
+    Reaction(ReactionPayload),
     #[serde(rename = "story:v1")]
     Story(StoryPayload),
     #[serde(rename = "follow:v1")]
@@ -101,11 +135,39 @@ pub enum DagPayload {
     ExamSubmission(ExamSubmissionPayload),
     #[serde(rename = "certification:v1")]
     Certification(CertificationPayload),
+    #[serde(rename = "cert_revocation:v1")]
+    CertRevocation(CertRevocationPayload),
     // Verification Application System
     #[serde(rename = "application:v1")]
     Application(ApplicationPayload),
     #[serde(rename = "application_vote:v1")]
     ApplicationVote(ApplicationVotePayload),
+    #[serde(rename = "post_visibility:v1")]
+    PostVisibility(PostVisibilityPayload),
+    #[serde(rename = "post_delete:v1")]
+    PostDelete(PostDeletePayload),
+    #[serde(rename = "share:v1")]
+    Share(SharePayload),
+    #[serde(rename = "poll:v1")]
+    Poll(PollPayload),
+    #[serde(rename = "poll_vote:v1")]
+    PollVote(PollVotePayload),
+    #[serde(rename = "event:v1")]
+    Event(EventPayload),
+    #[serde(rename = "rsvp:v1")]
+    Rsvp(RsvpPayload),
+    #[serde(rename = "petition:v1")]
+    Petition(PetitionPayload),
+    #[serde(rename = "signature:v1")]
+    Signature(SignaturePayload),
+    #[serde(rename = "web_view:v1")]
+    WebView(WebViewPayload),
+    #[serde(rename = "endorsement:v1")]
+    Endorsement(EndorsementPayload),
+    #[serde(rename = "escrow:v1")]
+    Escrow(EscrowPayload),
+    #[serde(rename = "escrow_settlement:v1")]
+    EscrowSettlement(EscrowSettlementPayload),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -131,6 +193,25 @@ pub struct LikePayload {
     pub remove: bool, // true if this is an "unlike" action (toggle off)
 }
 
+/// An emoji reaction. Each (author, emoji) pair is toggled independently, like a like.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReactionPayload {
+    pub target_id: String,
+    pub emoji: String, // One of `ReactionPayload::ALLOWED`
+    pub remove: bool,
+}
+
+impl ReactionPayload {
+    pub const ALLOWED: [&'static str; 8] = ["👍", "❤️", "😂", "😮", "😢", "😡", "🎉", "🙏"];
+
+    /// The allowed emoji `emoji` stands for, ignoring variation selectors ("❤" and "❤️" are the same)
+    pub fn canonical(emoji: &str) -> Option<&'static str> {
+        let bare = |e: &str| e.chars().filter(|c| !matches!(c, '\u{FE0E}' | '\u{FE0F}')).collect::<String>();
+        let wanted = bare(emoji.trim());
+        Self::ALLOWED.into_iter().find(|allowed| bare(allowed) == wanted)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct OversightCasePayload {
     pub case_id: String,
@@ -152,6 +233,159 @@ pub struct GroupPayload {
     pub owner: String, // Founder of the group
 }
 
+/// Invitation from a group's owner to join the group
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GroupInvitePayload {
+    pub group_id: String,
+    pub invitee_peer_id: String,
+}
+
+/// The invitee's answer to a group invitation; accepting makes them a member
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GroupInviteResponsePayload {
+    pub invite_id: String,
+    pub accept: bool,
+}
+
+/// Rules for who may join a group. Membership comes from accepted invitations, so these gate inviting and accepting.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GroupPolicy {
+    pub allow_new_members_join: bool, // false closes the group to new members
+    pub require_approval: bool, // Only the owner may invite; admins need the owner to do it
+    pub max_members: Option<usize>,
+    pub min_reputation: Option<i64>, // Minimum reputation score to be invited
+}
+
+impl Default for GroupPolicy {
+    fn default() -> Self {
+        Self { allow_new_members_join: true, require_approval: false, max_members: None, min_reputation: None }
+    }
+}
+
+/// A group owner's policy update; the latest one from the owner applies
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GroupPolicyPayload {
+    pub group_id: String,
+    pub policy: GroupPolicy,
+}
+
+/// A group session key from the owner, sealed separately for each member.
+/// The latest one from the owner is used for new messages.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GroupKeyPayload {
+    pub group_id: String,
+    pub sealed_keys: Vec<SealedGroupKey>,
+}
+
+/// The AES-256 group key encrypted to one member's X25519 key
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SealedGroupKey {
+    pub member: String, // PeerId string
+    pub ephemeral_pubkey: String, // Hex encoded
+    pub nonce: String, // Hex encoded
+    pub ciphertext: String, // Hex encoded
+}
+
+/// Audience a post is shared with
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub enum PostVisibility {
+    #[default]
+    Public,
+    FollowersOnly,
+    MembersOf(String), // group_id
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PostVisibilityPayload {
+    pub post_id: String,
+    pub visibility: PostVisibility,
+}
+
+/// A tombstone retracting one of the author's own posts. It is kept forever so
+/// peers keep suppressing the post even if they receive it again later.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PostDeletePayload {
+    pub target_cid: String,
+    pub reason: Option<String>,
+}
+
+/// A poll shown in the feed. Votes cast after `closes_at` are not counted
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PollPayload {
+    pub question: String,
+    pub options: Vec<String>,
+    pub closes_at: Option<u64>, // Unix seconds; None = open forever
+    pub allow_multiple: bool,
+}
+
+/// A voter's choices on a poll, by option index. A later vote from the same voter replaces an earlier one
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PollVotePayload {
+    pub poll_id: String,
+    pub choices: Vec<usize>,
+}
+
+/// A scheduled community event, optionally pinned to an area for local discovery
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EventPayload {
+    pub title: String,
+    pub description: String,
+    pub location: Option<String>, // Free-form venue text
+    pub geohash: Option<String>,
+    pub starts_at: u64, // Unix seconds
+    pub ends_at: u64,
+    pub capacity: Option<usize>, // None = unlimited
+}
+
+/// Whether the author is attending an event. A later RSVP from the same author replaces an earlier one
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RsvpPayload {
+    pub event_id: String,
+    pub attending: bool,
+}
+
+/// An informal petition. Once `threshold` peers have signed it, it can be turned into a proposal
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PetitionPayload {
+    pub title: String,
+    pub body: String,
+    pub target_ministry: Option<Ministry>,
+    pub threshold: usize,
+}
+
+/// The author's signature on a petition. Signing twice counts once
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SignaturePayload {
+    pub petition_id: String,
+}
+
+/// A repost of someone's post to the sharer's own followers
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SharePayload {
+    pub original_cid: String,
+    pub comment: Option<String>,
+}
+
+/// A member's role within a group, for role-aware rendering
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum GroupRole {
+    Owner,
+    Admin,
+    Member,
+}
+
+/// An entry in a group's combined activity feed
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum GroupActivityEvent {
+    MemberAdded(String),
+    #[allow(dead_code)]
+    MemberRemoved(String), // No membership-change node exists yet
+    PostPublished(Box<DagNode>),
+    #[allow(dead_code)]
+    DescriptionUpdated(String), // Groups have no description field yet
+    MessageMilestone(usize),
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct NamePayload {
     pub name: String,
@@ -176,6 +410,10 @@ pub struct PostPayload {
     pub geohash: Option<String>, // Optional location tag
     #[serde(default)]
     pub announcement: bool, // Exclusive for elected officials
+    #[serde(default)]
+    pub quote_of: Option<String>, // CID of the post being quoted/reposted
+    #[serde(default)]
+    pub publish_at: Option<u64>, // Unix time a scheduled post was queued for; it is held locally until then
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -219,6 +457,34 @@ pub struct ContractPayload {
     pub code: String, // Source code or WASM hex
     pub init_params: String, // JSON string
     pub status: ContractStatus, // Lifecycle status
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template_id: Option<String>, // CID of the contract this one was deployed from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_contract_id: Option<String>, // CID of the version this one upgrades
+}
+
+/// Tokens a contract's consumer locks for the provider until the contract completes.
+/// The amount leaves the depositor's balance until the escrow is refunded.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EscrowPayload {
+    pub contract_id: String,
+    pub amount: u64,
+    pub counterparty: String, // Peer who receives the tokens on release
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum EscrowStatus {
+    Locked,
+    Released, // Paid out to the counterparty
+    Refunded, // Returned to the depositor
+    Disputed, // Still locked, but a party has objected
+}
+
+/// Settles an escrow: the depositor may release it, the counterparty may refund it, and either may dispute it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EscrowSettlementPayload {
+    pub escrow_id: String, // CID of the escrow node
+    pub outcome: EscrowStatus,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -228,6 +494,24 @@ pub struct ContractCallPayload {
     pub params: String, // JSON string
 }
 
+/// A payable stage of a service contract, listed under `init_params["milestones"]`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Milestone {
+    pub description: String,
+    pub amount: u64,
+    #[serde(default)]
+    pub completed: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completion_cid: Option<String>, // Set by the VM when a completion node is replayed
+}
+
+/// Provider's declaration that a contract milestone has been delivered
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MilestoneCompletionPayload {
+    pub contract_id: String,
+    pub milestone_index: usize,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ProposalPayload {
     pub title: String,
@@ -235,6 +519,8 @@ pub struct ProposalPayload {
     pub r#type: ProposalType,
     #[serde(default)]
     pub pinned: bool,
+    #[serde(default)]
+    pub quadratic: bool, // Weigh each voter by sqrt(reputation score) instead of one vote each
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -244,6 +530,74 @@ pub enum ProposalType {
     Emergency,
     SetTax(u8), // Tax rate in percent (0-100)
     DefineMinistries(Vec<String>), // List of ministry names
+    SetElectionSchedule { ministry: Ministry, start_unix: u64, end_unix: u64 }, // Candidacy window
+}
+
+/// Each voter's latest vote on a proposal, counted by kind.
+/// The weights decide the outcome: they equal the counts for one-person-one-vote proposals,
+/// and sum each voter's sqrt(reputation score) for quadratic ones.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ProposalTally {
+    pub yes: usize,
+    pub no: usize,
+    pub abstain: usize,
+    pub petition: usize,
+    pub unique_voters: usize,
+    pub yes_weight: f64,
+    pub no_weight: f64,
+    pub abstain_weight: f64,
+}
+
+impl From<(usize, usize, usize, usize, usize)> for ProposalTally {
+    fn from((yes, no, abstain, petition, unique_voters): (usize, usize, usize, usize, usize)) -> Self {
+        Self {
+            yes,
+            no,
+            abstain,
+            petition,
+            unique_voters,
+            yes_weight: yes as f64,
+            no_weight: no as f64,
+            abstain_weight: abstain as f64,
+        }
+    }
+}
+
+impl ProposalTally {
+    /// (petition share of the network, voting window in hours, yes share of yes+no needed to pass)
+    pub fn thresholds(proposal_type: &ProposalType) -> (f64, i64, f64) {
+        match proposal_type {
+            ProposalType::Standard => (0.01, 168, 0.50), // 1% sigs, 1 week, >50% yes
+            ProposalType::Constitutional => (0.01, 168, 0.66), // 1% sigs, 1 week, >66% yes
+            ProposalType::Emergency => (0.05, 48, 0.50), // 5% sigs, 48 hours, >50% yes
+            ProposalType::SetTax(_) => (0.01, 168, 0.50), // Treat as Standard for now
+            ProposalType::DefineMinistries(_) => (0.01, 168, 0.50), // Standard requirements
+            ProposalType::SetElectionSchedule { .. } => (0.01, 168, 0.50), // Standard requirements
+        }
+    }
+
+    /// Supporters (petition signatures or yes votes) needed before a proposal goes to a vote
+    pub fn required_quorum(proposal_type: &ProposalType, network_size: usize) -> usize {
+        let (petition_share, _, _) = Self::thresholds(proposal_type);
+        (network_size.max(1) as f64 * petition_share).ceil() as usize
+    }
+
+    /// Yes votes needed to carry the proposal against the current no votes, never below the quorum
+    pub fn required_yes(&self, proposal_type: &ProposalType, network_size: usize) -> usize {
+        let (_, _, pass_share) = Self::thresholds(proposal_type);
+        let to_outvote = (self.no as f64 * pass_share / (1.0 - pass_share)).floor() as usize + 1;
+        to_outvote.max(Self::required_quorum(proposal_type, network_size))
+    }
+}
+
+/// What a passed proposal changed, recorded once it is seen to pass
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProposalOutcomePayload {
+    pub proposal_id: String,
+    pub action_taken: String, // e.g. "Tax rate changed"
+    pub previous_value: Option<String>,
+    pub new_value: Option<String>,
+    pub enacted_at: u64, // Unix timestamp
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -263,6 +617,37 @@ pub enum VoteType {
 /// Ministry identifier (e.g., "VerificationAndIdentity")
 pub type Ministry = String;
 
+/// A voter letting `delegate` vote on their behalf for proposals within `scope`.
+/// Delegating again for the same scope replaces the earlier delegation; an `expires_at`
+/// in the past revokes it. A direct vote always overrides the delegation.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VoteDelegationPayload {
+    pub delegate: String,
+    pub scope: DelegationScope,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum DelegationScope {
+    All,
+    Ministry(Ministry), // Election schedule proposals for this ministry
+    ProposalType(ProposalType), // Proposals of this kind, whatever their parameters
+}
+
+impl DelegationScope {
+    /// Whether a delegation with this scope covers `proposal`
+    pub fn covers(&self, proposal: &ProposalPayload) -> bool {
+        match self {
+            DelegationScope::All => true,
+            DelegationScope::Ministry(ministry) => matches!(&proposal.r#type, ProposalType::SetElectionSchedule { ministry: m, .. } if m == ministry),
+            DelegationScope::ProposalType(t) => std::mem::discriminant(t) == std::mem::discriminant(&proposal.r#type),
+        }
+    }
+}
+
+/// A scheduled candidacy window: (ministry, start_unix, end_unix)
+pub type ElectionWindow = (Ministry, u64, u64);
+
 /// A verified user declaring candidacy for a ministry position
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct CandidacyPayload {
@@ -270,6 +655,12 @@ pub struct CandidacyPayload {
     pub platform: String, // Candidate's platform/statement
 }
 
+/// A candidate dropping out of an election; only valid when authored by the candidate
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CandidacyWithdrawalPayload {
+    pub candidacy_id: String,
+}
+
 /// A vote for a specific candidate (identified by their candidacy node CID)
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct CandidacyVotePayload {
@@ -296,6 +687,21 @@ pub struct ProofPayload {
     pub target_pubkey: String, // Hex encoded public key of the person being verified
 }
 
+/// Withdrawal of a vouch by the peer who gave it. The proof no longer counts towards verification
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VouchRevocationPayload {
+    pub original_proof_cid: String,
+    pub reason: String,
+}
+
+/// A peer vouching for a specific skill of another peer, unlike a Proof which only vouches for identity
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EndorsementPayload {
+    pub target: String, // PeerId of the endorsed user
+    pub skill: String,
+    pub evidence: String, // Free text or a link backing the endorsement
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct MessagePayload {
     pub recipient: String, // PeerId string
@@ -303,6 +709,26 @@ pub struct MessagePayload {
     pub nonce: String, // Hex encoded nonce
     pub ephemeral_pubkey: String, // Hex encoded ephemeral public key of sender
     pub group_id: Option<String>, // Optional: CID of the group if this is a group message
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ratchet: Option<RatchetHeader>, // Set when `ephemeral_pubkey` is the sender's Double Ratchet key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group_key_id: Option<String>, // CID of the group key this group message is encrypted with
+}
+
+/// A recipient's acknowledgement that they read a direct message. Sent only to the message's author.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MessageReceiptPayload {
+    pub message_cid: String,
+    pub read_at: u64, // Unix timestamp
+}
+
+/// Position of a message within its sender's Double Ratchet chains
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct RatchetHeader {
+    pub counter: u32, // Index in the current sending chain
+    pub prev_counter: u32, // Length of the sender's previous sending chain
+    #[serde(default)]
+    pub epoch: u64, // Sender's session epoch; raised each time they restore their identity
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -314,6 +740,21 @@ pub struct TokenPayload {
     pub ref_cid: Option<String>, // Reference to a previous event (e.g., the burn event being claimed)
 }
 
+/// A transfer that only goes ahead once `threshold` of `required_signers` approve it
+/// before `expires_at`. The proposer's node then publishes one burn per recipient.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MultiSigProposalPayload {
+    pub recipients: Vec<(String, u64)>, // (Recipient peer ID, amount)
+    pub required_signers: Vec<String>,
+    pub threshold: usize,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MultiSigApprovalPayload {
+    pub proposal_cid: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum TokenAction {
     Mint,
@@ -323,6 +764,12 @@ pub enum TokenAction {
     MintReward,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WebViewPayload {
+    pub url: String,
+    pub viewer_peer_id_hash: String, // SHA-256 of the viewer's peer ID, for privacy
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct WebPayload {
     pub url: String, // e.g. sp://alice.super/home
@@ -407,6 +854,13 @@ pub struct CertificationPayload {
     pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+/// Withdrawal of a certification by its issuer, e.g. issued in error or obtained by cheating
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CertRevocationPayload {
+    pub cert_cid: String,
+    pub reason: String,
+}
+
 /// Join application submitted by new users seeking verification
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ApplicationPayload {
@@ -538,6 +992,9 @@ mod tests {
             content: "Hello World".to_string(),
             attachments: vec![],
             geohash: None,
+            announcement: false,
+            quote_of: None,
+            publish_at: None,
         });
 
         let node = DagNode::new(
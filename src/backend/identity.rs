@@ -1,8 +1,21 @@
+use aes_gcm::{
+    aead::{Aead, AeadCore, KeyInit},
+    Aes256Gcm, Key, Nonce,
+};
 use libp2p::identity::Keypair;
+use rand::rngs::OsRng;
+use rand::RngCore;
 use std::fs;
 use std::path::Path;
 use std::io::{Write, Read};
 
+/// Format tag of the first backup bundle layout:
+/// tag | Argon2id salt | AES-GCM nonce | ciphertext of (keypair length as u16 BE | keypair | encryption key)
+const BUNDLE_FORMAT_V1: u8 = 1;
+const BUNDLE_SALT_LEN: usize = 16;
+const BUNDLE_NONCE_LEN: usize = 12;
+const ENCRYPTION_KEY_LEN: usize = 32;
+
 pub fn load_identity(path: &Path) -> Result<Keypair, Box<dyn std::error::Error>> {
     if path.exists() {
         let mut file = fs::File::open(path)?;
@@ -29,6 +42,62 @@ pub fn save_identity(path: &Path, keypair: &Keypair) -> Result<(), Box<dyn std::
     Ok(())
 }
 
+/// Encrypt the identity keypair and the X25519 encryption key into one password-protected bundle
+pub fn export_bundle(keypair: &Keypair, encryption_key: &[u8; ENCRYPTION_KEY_LEN], password: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let keypair_bytes = keypair.to_protobuf_encoding()?;
+    let keypair_len = u16::try_from(keypair_bytes.len()).map_err(|_| "Keypair too large")?;
+    let mut plaintext = keypair_len.to_be_bytes().to_vec();
+    plaintext.extend_from_slice(&keypair_bytes);
+    plaintext.extend_from_slice(encryption_key);
+
+    let mut salt = [0u8; BUNDLE_SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let cipher = bundle_cipher(password, &salt)?;
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher.encrypt(&nonce, plaintext.as_ref()).map_err(|_| "Encryption failed")?;
+
+    let mut bundle = vec![BUNDLE_FORMAT_V1];
+    bundle.extend_from_slice(&salt);
+    bundle.extend_from_slice(&nonce);
+    bundle.extend_from_slice(&ciphertext);
+    Ok(bundle)
+}
+
+/// Decrypt a bundle from `export_bundle`, returning the keypair and encryption key.
+/// A wrong password fails authentication rather than yielding garbage keys.
+pub fn import_bundle(bundle: &[u8], password: &str) -> Result<(Keypair, [u8; ENCRYPTION_KEY_LEN]), Box<dyn std::error::Error>> {
+    let (&format, rest) = bundle.split_first().ok_or("Empty bundle")?;
+    if format != BUNDLE_FORMAT_V1 {
+        return Err(format!("Unsupported bundle format {}", format).into());
+    }
+    if rest.len() < BUNDLE_SALT_LEN + BUNDLE_NONCE_LEN {
+        return Err("Bundle is truncated".into());
+    }
+    let (salt, rest) = rest.split_at(BUNDLE_SALT_LEN);
+    let (nonce, ciphertext) = rest.split_at(BUNDLE_NONCE_LEN);
+    let nonce: [u8; BUNDLE_NONCE_LEN] = nonce.try_into().map_err(|_| "Bundle is truncated")?;
+    let cipher = bundle_cipher(password, salt)?;
+    let plaintext = cipher.decrypt(&Nonce::from(nonce), ciphertext).map_err(|_| "Wrong password or corrupted bundle")?;
+
+    if plaintext.len() < 2 {
+        return Err("Bundle is truncated".into());
+    }
+    let keypair_len = u16::from_be_bytes([plaintext[0], plaintext[1]]) as usize;
+    let (keypair_bytes, encryption_key) = plaintext[2..].split_at_checked(keypair_len).ok_or("Invalid keypair length")?;
+    let encryption_key: [u8; ENCRYPTION_KEY_LEN] = encryption_key.try_into().map_err(|_| "Invalid encryption key length")?;
+    let keypair = Keypair::from_protobuf_encoding(keypair_bytes).map_err(|_| "Failed to decode keypair")?;
+    Ok((keypair, encryption_key))
+}
+
+/// AES-256-GCM keyed by Argon2id over the password
+fn bundle_cipher(password: &str, salt: &[u8]) -> Result<Aes256Gcm, Box<dyn std::error::Error>> {
+    let mut key = [0u8; 32];
+    argon2::Argon2::default()
+        .hash_password_into(password.as_bytes(), salt, &mut key)
+        .map_err(|e| format!("Key derivation failed: {}", e))?;
+    Ok(Aes256Gcm::new(&Key::<Aes256Gcm>::from(key)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -50,4 +119,19 @@ mod tests {
             "Loaded public key should match original"
         );
     }
+
+    #[test]
+    fn test_identity_bundle_roundtrip() {
+        let keypair = Keypair::generate_ed25519();
+        let encryption_key = [7u8; ENCRYPTION_KEY_LEN];
+        let bundle = export_bundle(&keypair, &encryption_key, "correct horse").expect("Failed to export");
+        assert_eq!(bundle[0], BUNDLE_FORMAT_V1);
+
+        let (restored, restored_key) = import_bundle(&bundle, "correct horse").expect("Failed to import");
+        assert_eq!(restored.public(), keypair.public());
+        assert_eq!(restored_key, encryption_key);
+
+        assert!(import_bundle(&bundle, "wrong password").is_err());
+        assert!(import_bundle(&bundle[..10], "correct horse").is_err());
+    }
 }
@@ -5,6 +5,7 @@ pub mod store;
 pub mod identity;
 pub mod wasm;
 pub mod vm;
+pub mod ratchet;
 use vm::VM;
 
 use libp2p::{
@@ -32,27 +33,88 @@ use tokio::sync::mpsc;
 use std::time::Duration;
 use base64::{Engine as _, engine::general_purpose};
 
+/// Where the libp2p identity keypair is kept
+const IDENTITY_FILE: &str = "identity.pem";
+/// Where the X25519 key for encrypted messages is kept
+const ENCRYPTION_KEY_FILE: &str = "encryption.key";
+/// Node types pinned on arrival until the user sets a policy of their own
+pub const DEFAULT_PIN_POLICY: &[&str] = &["profile", "web"];
+/// Connected peers below which the backend re-dials the bootstrap nodes
+const DEFAULT_MIN_PEERS: usize = 3;
+/// Minimum gap between re-bootstrap attempts, so a lonely node doesn't flood the bootstrap nodes
+const REBOOTSTRAP_COOLDOWN_SECS: u64 = 30;
+/// SUPER minted per daily UBI claim
+const UBI_DAILY_AMOUNT: u64 = 10;
+/// Minimum seconds between UBI claims
+const UBI_CLAIM_INTERVAL_SECS: u64 = 86400;
+/// Days before a certification lapses that its holder starts getting warned
+const CERTIFICATION_EXPIRY_WARNING_DAYS: i64 = 14;
+/// Peer scores lose 10% of their acks and nacks this often, so bad peers can recover
+const PEER_SCORE_DECAY_INTERVAL_SECS: u64 = 3600;
+/// Nodes per `BlockResponse::SyncBatch`
+const SYNC_BATCH_SIZE: usize = 100;
+
 #[derive(Debug)]
 pub enum AppCmd {
     Init,
     PublishBlock(dag::DagNode),
     PublishProfile { name: String, bio: String, photo: Option<String> },
     Vouch { target_peer_id: String },
-    PublishPost { content: String, attachments: Vec<String>, geohash: Option<String>, announcement: bool },
+    FetchPendingVouches { peer_id: String },
+    RevokeVouch { proof_cid: String, reason: String }, // Only the original voucher
+    EndorseSkill { target_peer_id: String, skill: String, evidence: String },
+    FetchEndorsements { peer_id: String },
+    PublishPost { content: String, attachments: Vec<String>, geohash: Option<String>, announcement: bool, quote_of: Option<String>, publish_at: Option<u64> }, // A future publish_at queues the post locally
+    FetchScheduledPosts,
+    CancelScheduledPost { cid: String },
+    EditPost { original_cid: String, new_content: String, attachments: Vec<String> },
+    DeletePost { cid: String, reason: Option<String> },
+    SharePost { original_cid: String, comment: Option<String> },
+    CreatePoll { question: String, options: Vec<String>, closes_at: Option<u64>, allow_multiple: bool },
+    VotePoll { poll_id: String, choices: Vec<usize> }, // Option indices; a later vote replaces ours
+    FetchPollResults { poll_id: String },
+    CreateEvent { title: String, description: String, location: Option<String>, geohash: Option<String>, starts_at: u64, ends_at: u64, capacity: Option<usize> },
+    RsvpEvent { event_id: String, attending: bool }, // A later RSVP replaces ours
+    FetchEvents { geohash_prefix: String, from: u64, to: u64 },
+    FetchEventAttendees { event_id: String },
+    FetchShares { cid: String },
     PublishBlob { mime_type: String, data: String },
     FetchPosts,
     FetchLocalPosts { geohash_prefix: String },
+    FetchPersonalizedFeed { strategy: FeedStrategy, limit: usize, offset: usize },
+    FetchTrendingPosts { window_hours: u8, limit: usize },
     SendMessage { recipient: String, content: String, group_id: Option<String> },
+    FetchMessageThreads,
+    InitRatchet { peer_id: String },
+    MarkMessageRead { message_cid: String },
     FetchMessages { peer_id: String },
     CreateGroup { name: String, members: Vec<String> },
     FetchGroups,
+    FetchGroupsByOwner { peer_id: String },
+    FetchGroupMembers { group_id: String },
+    FetchGroupActivity { group_id: String },
+    FetchGroupMemberActivity { group_id: String, member_peer_id: String },
+    FetchGroupLeaderboard { group_id: String, window_days: u64 },
     FetchGroupMessages { group_id: String },
+    SetGroupPolicy { group_id: String, policy: dag::GroupPolicy },
+    FetchGroupPolicy { group_id: String },
+    InviteToGroup { group_id: String, invitee_peer_id: String },
+    RotateGroupKey { group_id: String },
+    RespondToGroupInvite { invite_id: String, accept: bool },
+    FetchGroupInvitations,
     FetchMyProfile,
     MintToken { amount: u64 },
     SendToken { recipient: String, amount: u64 },
+    SetSpendingLimit { daily_limit: u64 }, // 0 = unlimited
+    FetchSpendingLimit,
     ClaimToken { burn_cid: String },
+    ProposeMultiSigTransfer { recipients: Vec<(String, u64)>, required_signers: Vec<String>, threshold: usize, expires_at: chrono::DateTime<chrono::Utc> },
+    ApproveMultiSigTransfer { proposal_cid: String },
+    FetchMultiSigProposals, // Proposals I made or must sign
     FetchPendingTransfers,
+    FetchIncomingTransfers,
     FetchBalance,
+    FetchTransactionHistory { peer_id: String, offset: usize, limit: usize },
     AutoDetectGeohash,
     ClaimUbi,
     FetchUbiTimer,
@@ -61,49 +123,99 @@ pub enum AppCmd {
     AnnouncePresence { geohash: String },
     PublishWebPage { url: String, title: String, content: String, description: String, tags: Vec<String> },
     FetchWebPage { url: String },
+    FetchWebPageEmbeds { url: String },
+    RecordWebView { url: String },
+    FetchWebStats { url: String },
     RegisterName { name: String, target: String },
     ResolveName { name: String },
     FetchBlock { cid: String, peer_id: Option<String> },
+    DialPeer { multiaddr: String },  // Falls back to a relay circuit if the direct dial fails
+    RequestFullSync { peer_id: String, since: Option<u64> }, // Page through every block the peer has from `since` (default: all)
     FetchStorageStats,
+    FetchMyStorageQuotaUsageByType,
     SetStorageQuota { quota_mb: Option<u64> },  // None = unlimited
+    SetCompressionLevel { level: u8 },  // 0 = off, 1-22 = zstd level
     FetchStorageQuota,
+    FetchStoreRoot,
+    ExportIdentity { password: String },
+    ImportIdentity { bundle_b64: String, password: String }, // Replaces our identity and restarts the swarm
+    VacuumStore { target_free_mb: u64 }, // Evict unpinned blocks until this much is free under the quota
+    RebuildIndexes, // Recreate hashtag, mention, keyword and metadata indexes from the stored blocks
+    PinBlock { cid: String },
+    UnpinBlock { cid: String },
+    FetchPinnedBlocks,
+    SetPinPolicy { types: Vec<String> }, // Node types to pin automatically when received, e.g. "profile"
     CreateListing { title: String, description: String, price: u64, image_cid: Option<String>, category: Option<String>, geohash: Option<String> },
     BuyListing { listing_id: String },
+    FetchListingBuyerHistory { listing_id: String },
+    FetchPeerListingHistory { peer_id: String, include_closed: bool },
     UpdateListingStatus { listing_id: String, status: dag::ListingStatus },
     SearchListings { query: String },
     FetchListings,
     FetchLocalListings { geohash_prefix: String },
+    FetchLocalListingsByCategory { geohash_prefix: String, category: String, price_min: Option<u64>, price_max: Option<u64> },
 
     SearchWeb { query: String },
+    FetchWebSearchResultsFromPeer { query: String, peer_id: String },
     SearchFiles { query: String },
     DeployContract { code: String, init_params: String },
+    DeployFromTemplate { template_id: String, init_params: String },
+    FetchContractsByTemplate { template_id: String },
+    UpgradeContract { contract_id: String, code: String, init_params: String },
+    FetchContractVersionHistory { contract_id: String },
     CallContract {
         contract_id: String,
         method: String,
         params: String,
+        dry_run: bool, // Same as SimulateContractCall: nothing is published
     },
+    SimulateContractCall { contract_id: String, method: String, params: String },
     FetchContracts,
     FetchContractState {
         contract_id: String,
-    },
+    }, // Also replays every event the contract has emitted
+    SubscribeContractEvents { contract_id: String },
+    UnsubscribeContractEvents { contract_id: String },
+    SetVmGasLimit { limit: u64 },
+    SetMinPeers { count: usize }, // Re-bootstrap whenever fewer peers than this are connected
     FetchContractHistory { contract_id: String },
+    FetchContractSecurityAudit { contract_id: String },
+    FetchContractDeployerReputation { contract_id: String },
     AcceptContract { contract_id: String },
     RejectContract { contract_id: String },
     CancelContract { contract_id: String },
-    PayContract { contract_id: String, amount: u64 },
+    PayContract { contract_id: String, amount: u64, milestone_index: Option<usize> },
+    CompleteMilestone { contract_id: String, milestone_index: usize },
+    FetchContractMilestones { contract_id: String },
     FetchPendingContracts, // Contracts awaiting my acceptance
     FetchPublicLedger,
-    PublishProposal { title: String, description: String, r#type: dag::ProposalType, pinned: bool },
+    PublishProposal { title: String, description: String, r#type: dag::ProposalType, pinned: bool, quadratic: bool },
     VoteProposal { proposal_id: String, vote: dag::VoteType },
+    DelegateVote { delegate: String, scope: dag::DelegationScope, expires_at: Option<chrono::DateTime<chrono::Utc>> },
+    RevokeDelegation { scope: dag::DelegationScope },
+    FetchMyDelegations,
     FetchProposals,
     FetchProposalVotes { proposal_id: String },
     FetchProposalTally { proposal_id: String },
+    FetchProposalProgressToQuorum { proposal_id: String },
+    FetchProposalOutcomeActions { proposal_id: Option<String> }, // None for the full timeline
+    FetchProposalImpactEstimate { proposal_id: String },
+    FetchProposalSimilar { proposal_id: String },
+    FetchDraftProposalSimilar { title: String, description: String }, // Answered with an empty proposal_id
+    FetchMyProposalVoteHistory,
+    FetchProposalsByAuthor { peer_id: String },
+    FetchMyProposals,
+    FetchElectionSchedule { ministry: Option<dag::Ministry> },
     // Election commands
     DeclareCandidacy { ministry: dag::Ministry, platform: String },
     VoteForCandidate { candidacy_id: String },
     FetchCandidates,
     FetchCandidateTally { candidacy_id: String },
+    WithdrawCandidacy { candidacy_id: String },
+    FetchCandidacyWithdrawal { candidacy_id: String },
     FetchReputation { peer_id: String },
+    FetchReputations { peer_ids: Vec<String> },
+    FetchTrustScore { peer_id: String },
     FetchMyWebPages,
     FetchAllWebPages,
     ReportContent { target_id: String, reason: String, details: String },
@@ -114,15 +226,42 @@ pub enum AppCmd {
     VoteRecall { recall_id: String, vote: bool },
     FetchRecalls,
     FetchRecallTally { recall_id: String },
+    CreatePetition { title: String, body: String, target_ministry: Option<dag::Ministry>, threshold: usize },
+    SignPetition { petition_id: String },
+    FetchPetitions,
+    FetchPetitionSignatures { petition_id: String },
+    ConvertPetitionToProposal { petition_id: String }, // Once the threshold is reached; by the author or a signer
     EscalateReport { report_id: String },
     CastJuryVote { case_id: String, vote: String }, // "Uphold" or "Dismiss"
     PostComment { parent_id: String, content: String },
     FetchComments { parent_id: String },
     LikePost { target_id: String, remove: bool },
+    ReactToPost { target_id: String, emoji: String, remove: bool }, // emoji must be one of dag::ReactionPayload::ALLOWED
+    FetchReactions { target_id: String },
+    SetPostVisibility { post_id: String, visibility: dag::PostVisibility },
     FetchLikes { target_id: String },
+    FetchPostEngagementScore { post_id: String },
+    FetchCrossPostHistory { post_id: String },
+    FetchPostContextWindow { post_id: String, window: usize, same_author: bool },
     FetchOversightCases,
     FetchJuryDuty, // Fetch cases where I am a juror
+    FetchMyJuryStats,
+    FetchCertificationLeaderboard { cert_type: String },
+    FetchConnectedPeerProfiles,
+    FetchCandidatePlatform { candidacy_id: String },
+    FetchMutualFollowers { peer_id: String },
+    FetchContractParties { contract_id: String },
+    CreateEscrow { contract_id: String, amount: u64 },
+    SettleEscrow { contract_id: String, outcome: dag::EscrowStatus }, // Released, Refunded or Disputed
+    FetchContractEscrowStatus { contract_id: String },
+    FetchMyEscrows,
+    FetchFollowingSuggestions { limit: usize },
+    FetchPeersByGeohash { geohash_prefix: String, include_profiles: bool },
+    FetchOversightCasesByJuror { juror_peer_id: String },
+    FetchOversightStatsByMinistry,
     FetchMinistries,
+    FetchMyElectedPositions,
+    FetchActiveOfficials,
     PublishStory { media_cid: String, caption: String, geohash: Option<String> },
     FetchStories,
     FetchLocalStories { geohash_prefix: String },
@@ -130,102 +269,349 @@ pub enum AppCmd {
     FetchFollowing { target: String },
     FetchFollowers { target: String },
     FetchGivenUserPosts { peer_id: String },
+    FetchNodeByAuthorAndType { peer_id: String, node_type: String, limit: usize, offset: usize },
     FetchFollowingPosts,
+    FollowHashtag { tag: String, follow: bool },
+    FetchFollowingHashtags,
+    FetchFollowingHashtagPosts,
+    FetchMentions { limit: usize },
+    FetchByHashtag { tag: String },
+    BookmarkPost { cid: String, remove: bool },
+    FetchBookmarks,
+    MuteUser { peer_id: String, unmute: bool },
+    BlockUser { peer_id: String, unblock: bool },
+    FetchMutedUsers,
+    FetchBlockedUsers,
+    FetchNetworkDiagnostics,
+    FetchPeerScores,
     FetchTaxRate,
+    FetchSystemParameters,
+    FetchTokenInflationRate { window_days: u64 },
+    FetchNetworkGrowthStats { window_days: u64 },
     // Education System
     CreateCourse { title: String, description: String, content: String, category: String, prerequisites: Vec<String> },
     CreateExam { title: String, course_id: Option<String>, questions: Vec<(String, Vec<String>, usize)>, passing_score: u8, certification_type: String },
     SubmitExam { exam_id: String, answers: Vec<usize> },
     FetchCourses,
+    FetchCourse { course_id: String },
     FetchExams,
     FetchCertifications { peer_id: String },
+    FetchPeerCoursesCompleted { peer_id: String },
     FetchMyCertifications,
+    RenewCertification { cert_cid: String },  // Needs a pass of the same exam since the cert was issued
+    RevokeCertification { cert_cid: String, reason: String },  // Issuer only
     // Verification Application System
     SubmitApplication { name: String, bio: String, photo_cid: Option<String> },
     VoteApplication { application_id: String, approve: bool },
     FetchPendingApplications,
     FetchApplicationVotes { application_id: String },
+    FetchApplicationApprovers { application_id: String },
+    FetchMyApplicationStatus,
+    FetchPeerVerificationPath { peer_id: String },
+    FetchVerifiedPeerCount,
 }
 
+/// A peer ID paired with its profile, if one has been published
+pub type PeerProfile = (String, Option<dag::ProfilePayload>);
+/// A purchase of a listing: (buyer_peer_id, amount, timestamp)
+pub type ListingPurchase = (String, u64, u64);
+/// A course with its exam and the local user's exam scores: (course, exam, progress)
+pub type CourseDetail = (dag::DagNode, Option<dag::DagNode>, Vec<usize>);
+/// What a passed proposal changed: (action_taken, previous_value, new_value)
+pub type ProposalEnactment = (String, Option<String>, Option<String>);
+/// A peer's best exam attempt for a course: (course_node, exam_submission_node)
+pub type CourseResult = (dag::DagNode, dag::DagNode);
+/// A peer's course record: (completed, in_progress)
+pub type PeerCourseHistory = (Vec<CourseResult>, Vec<CourseResult>);
+/// A skill endorsement: (endorsement_node, endorser_name)
+pub type Endorsement = (dag::DagNode, String);
+/// An escrow node with its current status
+pub type EscrowRecord = (dag::DagNode, dag::EscrowStatus);
+/// A multi-sig proposal: (proposal_node, approving_signers, first_transfer_cid once executed)
+pub type MultiSigRecord = (dag::DagNode, Vec<String>, Option<String>);
+/// Escrow summary for a contract: (locked_amount, status, counterparty)
+pub type EscrowInfo = (u64, dag::EscrowStatus, String);
+/// Moderation health for one ministry: (open_cases, decided_cases, avg_resolution_hours)
+pub type OversightStats = (usize, usize, usize);
+/// Impact analysis for a proposal: (narrative, named metrics)
+pub type ProposalImpact = (String, HashMap<String, f64>);
+/// An unclaimed transfer to us: (burn_node, sender_profile, amount)
+pub type IncomingTransfer = (dag::DagNode, Option<dag::ProfilePayload>, u64);
+/// A "People You May Know" entry: (peer_id, profile, reason)
+pub type FollowSuggestion = (String, Option<dag::ProfilePayload>, String);
+/// The two sides of an agreement: (provider, consumer)
+pub type ContractParties = (PeerProfile, PeerProfile);
+/// Posts surrounding a focused post: (center_post_id, posts oldest first, center_index)
+pub type PostContext = (String, Vec<dag::DagNode>, usize);
+/// A proposal vote paired with the proposal title, when known
+pub type TitledVote = (dag::DagNode, Option<String>);
+/// A contract milestone: (description, amount, completed, completion_cid)
+pub type ContractMilestone = (String, u64, bool, Option<String>);
+/// A direct conversation: (peer_id, last_message, decrypted_preview)
+pub type MessageThread = (String, dag::DagNode, String);
+/// One member's footprint in a group: (message count, post count, reports against them by members, last active unix secs)
+pub type GroupMemberActivity = (usize, usize, usize, Option<u64>);
+/// Distance to passing: (tally, yes votes required, supporters required, seconds left to vote)
+pub type ProposalProgress = (dag::ProposalTally, usize, usize, Option<u64>);
+/// Reactions on one target: emoji -> (count, reacted_by_me)
+pub type ReactionCounts = HashMap<String, (usize, bool)>;
+/// A dry run of a contract call: (resulting_state, gas_consumed, error)
+pub type ContractSimulation = (String, u64, Option<String>);
+/// A poll's standing: (votes per option, closed)
+pub type PollResult = (Vec<(String, usize)>, bool);
+/// Raw vote counts for a proposal: (yes, no, abstain, petition, unique_voters)
+pub type ProposalVoteCounts = (usize, usize, usize, usize, usize);
+/// A proposal's tally as shown: (yes_weight, no_weight, abstain_weight, petition, unique_voters, status)
+pub type WeightedTally = (f64, f64, f64, usize, usize, String);
+/// Distance to passing as shown: (yes, no, abstain, yes votes required, supporters required, progress %, seconds left to vote)
+pub type QuorumProgress = (usize, usize, usize, usize, usize, u8, Option<u64>);
+/// Our latest verification application: (ID, approvals, rejections, approvals required, approved, rejected)
+pub type ApplicationStatus = (String, usize, usize, usize, bool, bool);
+/// A certified member: (peer_id, profile, issued_at)
+pub type CertifiedPeer = (String, Option<dag::ProfilePayload>, chrono::DateTime<chrono::Utc>);
+
 #[derive(Debug, Clone)]
 pub enum AppEvent {
     PeerDiscovered(String),
     PeerConnected(String),
+    RelayedConnectionEstablished { peer_id: String, relay: String },
+    ConnectedPeerProfilesFetched(Vec<PeerProfile>),
+    MutualFollowersFetched { peer_id: String, mutual: Vec<PeerProfile> },
+    FollowingSuggestionsFetched(Vec<FollowSuggestion>),
+    PeersByGeohashFetched { geohash_prefix: String, peers: Vec<PeerProfile> },
+    ContractPartiesFetched { contract_id: String, provider: PeerProfile, consumer: PeerProfile },
+    EscrowStatusFetched { contract_id: String, locked_amount: u64, status: dag::EscrowStatus, counterparty: String },
+    ContractMilestonesFetched { contract_id: String, milestones: Vec<ContractMilestone> },
+    SimilarProposalsFetched { proposal_id: String, similar: Vec<(dag::DagNode, f32)> },
+    CandidatePlatformFetched {
+        candidacy_id: String,
+        node: dag::DagNode,
+        profile: Option<dag::ProfilePayload>,
+        certifications: Vec<dag::DagNode>,
+        reputation: Option<dag::ReputationDetails>,
+        vote_history: Vec<TitledVote>,
+    },
     BlockReceived(dag::DagNode),
     BlockFetched { cid: String, node: Option<dag::DagNode> },
+    SyncProgress { received: usize, estimated_total: Option<usize> },
+    SyncComplete { received: usize },
     HistoryFetched(Vec<dag::DagNode>),
     MessageReceived(dag::DagNode, String), // Node + Decrypted Content
+    RatchetInitialized { peer_id: String },
+    ReceiptReceived { message_cid: String, reader_peer_id: String, timestamp: u64 },
     MessagesFetched(Vec<(dag::DagNode, String)>), // List of (Node, Decrypted Content)
     GroupsFetched(Vec<dag::DagNode>),
+    #[allow(dead_code)]
+    GroupMembersFetched { group_id: String, members: Vec<(String, dag::GroupRole)> },
+    GroupActivityFetched { group_id: String, events: Vec<dag::GroupActivityEvent> },
+    GroupMemberActivityFetched { group_id: String, member_peer_id: String, message_count: usize, post_count: usize, reports_against: usize, last_active: Option<u64>, moderation_flag: bool },
+    GroupLeaderboardFetched { group_id: String, rankings: Vec<(String, usize)> },
     GroupMessagesFetched(Vec<(dag::DagNode, String)>),
+    MessageThreadsFetched(Vec<MessageThread>),
+    WebPageEmbedsFetched { url: String, blob_cids: Vec<String> },
+    GroupInvitationsFetched(Vec<dag::DagNode>),
+    GroupPolicyFetched { group_id: String, policy: dag::GroupPolicy },
+    GroupKeyRotated { group_id: String },
     MyIdentity(String),
     ProfileFetched(Option<dag::ProfilePayload>),
     BalanceFetched(i64),
+    SpendingLimitFetched { daily_limit: Option<u64>, spent_today: u64 },
+    SpendingLimitExceeded { attempted: u64, limit: u64, spent_today: u64 },
+    TransactionHistoryFetched { peer_id: String, records: Vec<TransactionRecord>, total: usize },
     PendingTransfersFetched(Vec<dag::DagNode>),
+    MultiSigProposalsFetched(Vec<MultiSigRecord>),
+    MultiSigExecuted { proposal_cid: String, transfer_cid: String },
+    IncomingTransfersFetched { transfers: Vec<IncomingTransfer> },
     GeohashDetected(String),
     UbiTimerFetched(Option<u64>),
     VerificationStatus(VerificationStatus),
     UserProfileFetched(Option<dag::ProfilePayload>),
     WebPageFetched { url: String, content: Option<String> },
+    WebStatsFetched { url: String, total_views: usize, unique_viewers: usize, views_last_7_days: usize },
     Listening(String),
+    PeerCountLow { current: usize, minimum: usize },
     #[allow(dead_code)]
     NameResolved { name: String, target: Option<String> },
-    StorageStatsFetched { block_count: usize, total_bytes: usize },
+    IdentityExported { bundle_b64: String },
+    ImportFailed { reason: String },
+    VacuumComplete { blocks_removed: usize, bytes_freed: usize },
+    StoreRootFetched { root_hex: String, node_count: usize },
+    IndexRebuildStarted,
+    IndexRebuildComplete { nodes_scanned: usize, duration_ms: u64 },
+    PinnedBlocksFetched(Vec<String>),
+    StorageStatsFetched { block_count: usize, total_bytes: usize, compression_level: u8, compression_ratio: f64, saved_bytes: usize, sync_bytes_saved: usize },
+    MyStorageUsageFetched(HashMap<String, (usize, usize)>), // node type -> (count, bytes)
     #[allow(dead_code)]
     StorageQuotaFetched { quota_mb: Option<u64>, used_bytes: usize, percent: u8 },
     #[allow(dead_code)]
     StorageWarning { used_percent: u8, message: String },
     LocalPostsFetched(Vec<dag::DagNode>),
+    /// Posts ranked by `strategy`; the formula used is `strategy.description()`
+    PersonalizedFeedFetched { strategy: FeedStrategy, posts: Vec<dag::DagNode> },
+    TrendingPostsFetched(Vec<dag::DagNode>),
+    PostEdited(dag::DagNode),
+    PostDeleted { cid: String },
+    ScheduledPostsFetched(Vec<dag::DagNode>),
+    SharesFetched { cid: String, count: usize },
+    PollResultsFetched { poll_id: String, tally: Vec<(String, usize)>, closed: bool },
+    EventsFetched(Vec<dag::DagNode>),
+    AttendeesFetched { event_id: String, attendees: Vec<String> },
     ListingsFetched(Vec<dag::DagNode>),
     LocalListingsFetched(Vec<dag::DagNode>),
     WebSearchResults(Vec<dag::DagNode>),
     FileSearchResults(Vec<dag::DagNode>),
     ContractsFetched(Vec<dag::DagNode>),
+    TemplateDeploymentCountFetched { template_id: String, count: usize },
+    ContractVersionHistoryFetched { contract_id: String, versions: Vec<dag::DagNode> },
     ContractStateFetched {
         contract_id: String,
         state: String,
     },
+    ContractExecutionFailed { contract_id: String, reason: String },
+    ContractEvent { contract_id: String, event: vm::ContractEvent },
+    ContractSimulationResult { contract_id: String, resulting_state: String, gas_consumed: u64, error: Option<String> },
     #[allow(dead_code)]
     ContractHistoryFetched { contract_id: String, history: Vec<dag::DagNode> },
     PendingContractsFetched(Vec<dag::DagNode>),
+    ContractAuditFetched { contract_id: String, findings: Vec<vm::AuditFinding> },
+    ContractDeployerInfoFetched { contract_id: String, deployer_peer_id: String, reputation: Option<dag::ReputationDetails>, certifications: Vec<dag::DagNode>, vouch_count: usize },
     PublicLedgerFetched(Vec<dag::DagNode>),
     ProposalsFetched(Vec<dag::DagNode>),
     ProposalVotesFetched { proposal_id: String, votes: Vec<dag::DagNode> },
-    /// Vote tally: (yes, no, abstain, petition, unique_voters, status)
-    ProposalTallyFetched { proposal_id: String, yes: usize, no: usize, abstain: usize, petition: usize, unique_voters: usize, status: String },
+    DelegationActivated { delegator: String, delegate: String },
+    MyDelegationsFetched(Vec<dag::DagNode>),
+    /// Vote tally: (yes_weight, no_weight, abstain_weight, petition, unique_voters, status).
+    /// The weights are head counts, unless the proposal is quadratic and each voter adds sqrt(reputation score)
+    ProposalTallyFetched { proposal_id: String, yes_weight: f64, no_weight: f64, abstain_weight: f64, petition: usize, unique_voters: usize, status: String },
+    ProposalProgressFetched { proposal_id: String, yes: usize, no: usize, abstain: usize, required_yes: usize, required_unique_voters: usize, progress_pct: u8, time_remaining: Option<u64> },
+    ProposalOutcomesFetched(Vec<dag::DagNode>),
+    CertificationLeaderboardFetched { cert_type: String, certified_peers: Vec<CertifiedPeer> },
+    JuryStatsFetched { cases_assigned: usize, votes_cast: usize, cases_decided: usize, avg_time_to_vote_hours: f64 },
+    OversightStatsFetched { stats: HashMap<String, OversightStats> },
+    MyVoteHistoryFetched { votes: Vec<(dag::DagNode, Option<String>)> },
+    ProposalsByAuthorFetched { peer_id: String, record: GovernanceRecord },
+    ElectionScheduleFetched { schedule: Vec<dag::ElectionWindow> },
+    ProposalImpactEstimated { proposal_id: String, narrative: String, metrics: HashMap<String, f64> },
     // Election events
     CandidatesFetched(Vec<dag::DagNode>),
-    CandidateTallyFetched { candidacy_id: String, votes: usize },
+    CandidateTallyFetched { candidacy_id: String, votes: usize, withdrawn: bool },
+    CandidacyWithdrawalFetched { candidacy_id: String, withdrawn: bool, withdrawn_at: Option<u64> },
     ReputationFetched(dag::ReputationDetails),
+    ReputationsBatchFetched(std::collections::HashMap<String, dag::ReputationDetails>),
+    /// Trust score on [0, 1] with the per-component values it was weighted from
+    #[allow(dead_code)]
+    TrustScoreFetched { peer_id: String, score: f32, components: HashMap<String, f32> },
     MyWebPagesFetched(Vec<dag::DagNode>),
     AllWebPagesFetched(Vec<dag::DagNode>),
     ReportsFetched(Vec<dag::DagNode>),
     FileUploaded(dag::DagNode),
     MyFilesFetched(Vec<dag::DagNode>),
     RecallsFetched(Vec<dag::DagNode>),
+    PetitionsFetched(Vec<dag::DagNode>),
+    PetitionSignaturesFetched { petition_id: String, signers: Vec<String> },
+    PetitionThresholdReached { petition_id: String },
     RecallTallyFetched { recall_id: String, remove: usize, keep: usize, unique_voters: usize },
     OversightCasesFetched(Vec<dag::DagNode>),
     JuryDutyFetched(Vec<dag::DagNode>),
     CommentsFetched { parent_id: String, comments: Vec<dag::DagNode> },
     LikesFetched { target_id: String, count: usize, is_liked_by_me: bool },
+    ReactionsFetched { target_id: String, counts: ReactionCounts },
+    CrossPostHistoryFetched { original_id: String, reposts: Vec<dag::DagNode> },
+    PostContextFetched { center_post_id: String, posts: Vec<dag::DagNode>, center_index: usize },
+    #[allow(dead_code)]
+    PostEngagementFetched { post_id: String, likes: usize, comments: usize, score: u32 },
     MinistriesFetched(Vec<String>),
+    MyElectedPositionsFetched { positions: Vec<dag::Ministry> },
+    ActiveOfficialsFetched { officials: std::collections::HashMap<String, String> },
+    ListingBuyerHistoryFetched { listing_id: String, purchase_count: usize, buyers: Vec<ListingPurchase> },
     StoriesFetched(Vec<dag::DagNode>),
     LocalStoriesFetched(Vec<dag::DagNode>),
     FollowingFetched(Vec<String>),
     #[allow(dead_code)]
     FollowersFetched(Vec<String>),
     UserPostsFetched(Vec<dag::DagNode>),
+    #[allow(dead_code)]
+    NodesByAuthorTypeFetched { peer_id: String, node_type: String, nodes: Vec<dag::DagNode>, has_more: bool },
     FollowingPostsFetched(Vec<dag::DagNode>),
+    FollowingHashtagsFetched(Vec<String>),
+    HashtagPostsFetched(Vec<dag::DagNode>),
+    MentionsFetched(Vec<dag::DagNode>),
+    BookmarksFetched(Vec<dag::DagNode>),
+    MutedUsersFetched(Vec<String>),
+    NetworkDiagnostics(NetworkDiagnostics),
+    PeerScoresFetched(HashMap<String, PeerScore>),
+    BlockedUsersFetched(Vec<String>),
     TaxRateFetched(u8),
+    SystemParametersFetched { params: HashMap<String, serde_json::Value> },
+    NetworkGrowthStatsFetched(NetworkGrowthStats),
+    TokenInflationRateFetched { window_days: u64, total_minted: u64, total_burned: u64, net_issuance: i64, inflation_rate_pct: f64 },
     // Education System
     CoursesFetched(Vec<dag::DagNode>),
+    CourseFetched { node: dag::DagNode, exam: Option<dag::DagNode>, progress: Vec<usize> },
     ExamsFetched(Vec<dag::DagNode>),
     CertificationsFetched(Vec<dag::DagNode>),
+    CertificationExpired { peer_id: String, certification_type: String },  // Lapsed or about to
+    CertificationRevoked { cert_cid: String, peer_id: String, reason: String },
+    PeerCoursesCompletedFetched { peer_id: String, completed: Vec<CourseResult>, in_progress: Vec<CourseResult> },
     ExamSubmitted { exam_id: String, score: u8, passed: bool },
     // Application Verification System
     PendingApplicationsFetched(Vec<dag::DagNode>),
     #[allow(dead_code)]
     ApplicationVotesFetched { application_id: String, approvals: usize, rejections: usize, required: usize },
+    MyApplicationStatusFetched { application_id: String, approvals: usize, rejections: usize, required: usize, is_approved: bool, is_rejected: bool },
+    ApplicationApproversFetched { application_id: String, approvers: Vec<PeerProfile> },
+    /// Trust chain as (voucher, vouchee) pairs; empty when the peer is not verified
+    VerificationPathFetched { peer_id: String, path: Vec<(String, String)> },
+    /// Vouches given by a peer: (target_peer_id, is_now_verified)
+    #[allow(dead_code)]
+    PendingVouchesFetched { voucher_peer_id: String, vouches: Vec<(String, bool)> },
+    VouchRevoked { proof_cid: String },
+    EndorsementsFetched { peer_id: String, endorsements: Vec<Endorsement> },
+    #[allow(dead_code)]
+    VerifiedPeerCountFetched { total_profiles: usize, verified: usize, founders: usize },
+}
+
+/// How the main feed is ordered. Each strategy is a fixed, published formula that the
+/// user picks explicitly; `description` is the wording shown next to the feed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FeedStrategy {
+    /// Newest first
+    #[default]
+    Chronological,
+    /// (likes + 3 × comments) × recency decay, which halves every 24 hours
+    PopularityWeighted,
+    /// Author trust score (0 to 1) × recency decay, which halves every 24 hours
+    TrustWeighted,
+    /// Posts tagged in your area (same 5-character geohash, roughly 5 km) first, newest first within each group
+    LocalFirst,
+}
+
+impl FeedStrategy {
+    pub const ALL: [FeedStrategy; 4] = [
+        FeedStrategy::Chronological,
+        FeedStrategy::PopularityWeighted,
+        FeedStrategy::TrustWeighted,
+        FeedStrategy::LocalFirst,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FeedStrategy::Chronological => "Latest",
+            FeedStrategy::PopularityWeighted => "Popular",
+            FeedStrategy::TrustWeighted => "Trusted",
+            FeedStrategy::LocalFirst => "Local first",
+        }
+    }
+
+    /// Plain-language statement of the ranking formula and its weights
+    pub fn description(&self) -> &'static str {
+        match self {
+            FeedStrategy::Chronological => "Newest posts first. Nothing is boosted or hidden.",
+            FeedStrategy::PopularityWeighted => "Score = (likes + 3 × comments) × recency, where recency halves every 24 hours.",
+            FeedStrategy::TrustWeighted => "Score = author trust score (0 to 1) × recency, where recency halves every 24 hours.",
+            FeedStrategy::LocalFirst => "Posts tagged within about 5 km of you (same 5-character geohash) come first, then everything else. Newest first within each group.",
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -236,6 +622,109 @@ pub enum VerificationStatus {
     EligibleForFounder,
 }
 
+/// Token supply change over a time window, for the economy dashboard
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenInflationStats {
+    pub window_days: u64,
+    pub total_minted: u64,
+    pub total_burned: u64,
+    pub net_issuance: i64,
+    pub inflation_rate_pct: f64, // Net issuance relative to supply at the start of the window
+}
+
+/// Everything a voter needs to evaluate a candidate
+#[derive(Debug, Clone, PartialEq)]
+pub struct CandidatePlatform {
+    pub node: dag::DagNode,
+    pub profile: Option<dag::ProfilePayload>,
+    pub certifications: Vec<dag::DagNode>,
+    pub reputation: Option<dag::ReputationDetails>,
+    pub vote_history: Vec<TitledVote>,
+}
+
+/// Health of our P2P connections. Byte counts cover request-response traffic since startup.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NetworkDiagnostics {
+    pub connected_peers: usize,
+    pub inbound_bytes: u64,
+    pub outbound_bytes: u64,
+    pub kad_routing_table_size: usize,
+    pub gossipsub_mesh_size: usize,
+    pub pending_replications: usize,
+    pub timed_out_replications: usize,
+}
+
+/// How reliably a peer has answered our block requests. Replication prefers peers with
+/// the best ack ratio, so those under 0.2 only get blocks when nobody better is connected.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PeerScore {
+    pub cid_acks: u64,
+    pub cid_nacks: u64,
+    pub latency_ms_avg: u32,
+}
+
+impl PeerScore {
+    /// Share of requests answered, 0.5 for peers we have not heard from yet
+    pub fn reliability(&self) -> f64 {
+        let total = self.cid_acks + self.cid_nacks;
+        if total == 0 {
+            0.5
+        } else {
+            self.cid_acks as f64 / total as f64
+        }
+    }
+}
+
+/// One token movement in a peer's history. Tax burns carry a memo starting with
+/// `TAX_WITHHELD_MEMO` so they can be told apart from the amount actually sent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransactionRecord {
+    pub cid: String,
+    pub action: dag::TokenAction,
+    pub amount: u64,
+    pub counterparty: Option<String>, // The other side of a transfer or claim, if any
+    pub memo: Option<String>,
+    pub timestamp: u64,
+}
+
+pub const TAX_WITHHELD_MEMO: &str = "Tax withheld";
+
+/// A juror's performance record. Slow jurors may be weighted down in future jury selection.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JuryStats {
+    pub cases_assigned: usize,
+    pub votes_cast: usize,
+    pub cases_decided: usize,
+    pub avg_time_to_vote_hours: f64,
+}
+
+/// Daily growth series for governance dashboards, each entry is (unix_day_start, value)
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkGrowthStats {
+    pub window_days: u64,
+    pub daily_new_profiles: Vec<(u64, usize)>,
+    pub daily_new_verified: Vec<(u64, usize)>,
+    pub daily_active_peers: Vec<(u64, usize)>, // Distinct authors publishing any block that day
+    pub daily_tx_volume: Vec<(u64, u64)>,
+}
+
+/// Trust signals about whoever deployed a contract
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContractDeployerInfo {
+    pub deployer_peer_id: String,
+    pub reputation: Option<dag::ReputationDetails>,
+    pub certifications: Vec<dag::DagNode>,
+    pub vouch_count: usize,
+}
+
+/// A peer's governance track record for profile pages
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GovernanceRecord {
+    pub proposals: Vec<dag::DagNode>,
+    pub votes_cast: usize,
+    pub candidacies: Vec<dag::DagNode>,
+}
+
 pub struct Backend {
     swarm: libp2p::Swarm<MyBehaviour>,
     store: Store,
@@ -244,9 +733,30 @@ pub struct Backend {
     pending_requests: HashMap<OutboundRequestId, String>, // RequestId -> CID
     keypair: Keypair,
     encryption_keypair: x25519_dalek::StaticSecret,
+    owns_key_files: bool, // False when the caller injected the keypair; imports then leave the key files alone
     pending_replications: HashMap<String, (dag::DagNode, std::time::Instant)>,
+    peer_sync_filters: HashMap<PeerId, bloomfilter::Bloom<String>>, // Blocks each connected peer (probably) already has
+    relays: HashMap<PeerId, libp2p::Multiaddr>, // Relay servers we hold reservations with, and how to reach them
+    pending_dials: std::collections::HashSet<PeerId>, // Direct dials that should retry through a relay on failure
     current_geohash: Option<String>,
     last_heartbeat: std::time::Instant,
+    verified_count_cache: std::cell::Cell<Option<(usize, usize, usize, std::time::Instant)>>, // (total_profiles, verified, founders, computed_at)
+    counting_verified: std::cell::Cell<bool>, // Set while `compute_verified_peer_count` runs
+    vm_gas_limit: u64, // Gas budget for each contract state replay
+    event_subscriptions: std::collections::HashSet<String>, // Contracts whose new calls fire AppEvent::ContractEvent
+    inbound_bytes: u64, // Request-response payload received since startup
+    outbound_bytes: u64, // Request-response payload sent since startup
+    timed_out_replications: usize, // Blocks we gave up replicating since startup
+    evicted_blocks: std::collections::HashSet<String>, // Vacuumed CIDs, which are no longer worth replicating
+    pin_policy: std::collections::HashSet<String>, // Node types (without version) pinned as they arrive
+    min_peers: usize, // Re-bootstrap when fewer peers than this are connected
+    last_rebootstrap: Option<std::time::Instant>,
+    rebuilding_indexes: bool, // Read commands wait on `index_lock` while set
+    peer_scores: HashMap<PeerId, PeerScore>, // Replication targets are picked by these
+    request_sent_at: HashMap<OutboundRequestId, std::time::Instant>, // For peer response latency
+    last_score_decay: std::time::Instant,
+    index_lock: std::sync::Arc<tokio::sync::Mutex<()>>, // Held by a running index rebuild
+    full_syncs: HashMap<PeerId, (OutboundRequestId, Option<u64>, usize)>, // Peer -> (page in flight, oldest timestamp synced, new nodes stored)
 }
 
 impl Backend {
@@ -257,7 +767,8 @@ impl Backend {
         keypair_opt: Option<Keypair>,
     ) -> Result<Self, Box<dyn std::error::Error>> {
         // Load or generate identity
-        let identity_path = Path::new("identity.pem");
+        let identity_path = Path::new(IDENTITY_FILE);
+        let owns_key_files = keypair_opt.is_none();
         let keypair = if let Some(kp) = keypair_opt {
             kp
         } else {
@@ -276,7 +787,7 @@ impl Backend {
         };
 
         // Load or generate encryption key
-        let enc_key_path = Path::new("encryption.key");
+        let enc_key_path = Path::new(ENCRYPTION_KEY_FILE);
         let encryption_keypair = if enc_key_path.exists() {
              let bytes = std::fs::read(enc_key_path)?;
              let arr: [u8; 32] = bytes.try_into().map_err(|_| "Invalid key length")?;
@@ -287,17 +798,7 @@ impl Backend {
              key
         };
 
-        let mut swarm = network::create_swarm(keypair.clone())?;
-
-        // Start listening
-        #[cfg(not(target_arch = "wasm32"))]
-        swarm.listen_on("/ip4/0.0.0.0/tcp/0".parse()?)?;
-
-        // Subscribe to gossipsub topic
-        swarm
-            .behaviour_mut()
-            .gossipsub
-            .subscribe(&gossipsub::IdentTopic::new("blocks"))?;
+        let swarm = Self::start_swarm(&keypair)?;
 
         Ok(Self {
             swarm,
@@ -307,28 +808,104 @@ impl Backend {
             pending_requests: HashMap::new(),
             keypair,
             encryption_keypair,
+            owns_key_files,
             pending_replications: HashMap::new(),
+            peer_sync_filters: HashMap::new(),
+            relays: HashMap::new(),
+            pending_dials: std::collections::HashSet::new(),
             current_geohash: None,
             last_heartbeat: std::time::Instant::now(),
+            verified_count_cache: std::cell::Cell::new(None),
+            counting_verified: std::cell::Cell::new(false),
+            vm_gas_limit: vm::DEFAULT_GAS_LIMIT,
+            event_subscriptions: std::collections::HashSet::new(),
+            inbound_bytes: 0,
+            outbound_bytes: 0,
+            timed_out_replications: 0,
+            evicted_blocks: std::collections::HashSet::new(),
+            pin_policy: DEFAULT_PIN_POLICY.iter().map(|t| t.to_string()).collect(),
+            min_peers: DEFAULT_MIN_PEERS,
+            last_rebootstrap: None,
+            rebuilding_indexes: false,
+            peer_scores: HashMap::new(),
+            request_sent_at: HashMap::new(),
+            last_score_decay: std::time::Instant::now(),
+            index_lock: std::sync::Arc::new(tokio::sync::Mutex::new(())),
+            full_syncs: HashMap::new(),
         })
     }
 
-    pub async fn run(&mut self) {
+    /// A swarm for `keypair`, listening and subscribed to block announcements
+    fn start_swarm(keypair: &Keypair) -> Result<libp2p::Swarm<MyBehaviour>, Box<dyn std::error::Error>> {
+        let mut swarm = network::create_swarm(keypair.clone())?;
+
+        // Start listening
+        #[cfg(not(target_arch = "wasm32"))]
+        swarm.listen_on("/ip4/0.0.0.0/tcp/0".parse()?)?;
+
+        // Subscribe to gossipsub topic
+        swarm
+            .behaviour_mut()
+            .gossipsub
+            .subscribe(&gossipsub::IdentTopic::new("blocks"))?;
+        Ok(swarm)
+    }
+
+    /// Dial the bootstrap nodes and reserve relay slots on them. This counts as a
+    /// re-bootstrap, so `check_peer_count` gives the first connections a cooldown to arrive.
+    fn join_network(&mut self) {
+        self.last_rebootstrap = Some(std::time::Instant::now());
         #[cfg(not(target_arch = "wasm32"))]
         {
-            println!("Attempting to dial bootstrap nodes...");
+            self.dial_bootstrap_nodes();
+
+            // Bootstrap nodes double as relays, so peers behind NAT stay reachable through them
             for addr_str in network::BOOTSTRAP_NODES.iter() {
                 if let Ok(addr) = addr_str.parse::<libp2p::Multiaddr>() {
-                    match self.swarm.dial(addr.clone()) {
-                        Ok(_) => println!("Dialed bootstrap node: {}", addr),
-                        Err(e) => eprintln!("Failed to dial bootstrap node {}: {:?}", addr, e),
+                    if let Err(e) = self.listen_via_relay(addr.clone()) {
+                        eprintln!("Failed to listen via relay {}: {:?}", addr, e);
                     }
                 }
             }
-            if let Err(e) = self.swarm.behaviour_mut().kad.bootstrap() {
-                 eprintln!("Failed to bootstrap Kademlia: {:?}", e);
+        }
+    }
+
+    /// Dial every bootstrap node and refresh the Kademlia routing table through them
+    #[cfg(not(target_arch = "wasm32"))]
+    fn dial_bootstrap_nodes(&mut self) {
+        println!("Attempting to dial bootstrap nodes...");
+        for addr_str in network::BOOTSTRAP_NODES.iter() {
+            if let Ok(addr) = addr_str.parse::<libp2p::Multiaddr>() {
+                match self.swarm.dial(addr.clone()) {
+                    Ok(_) => println!("Dialed bootstrap node: {}", addr),
+                    Err(e) => eprintln!("Failed to dial bootstrap node {}: {:?}", addr, e),
+                }
             }
         }
+        if let Err(e) = self.swarm.behaviour_mut().kad.bootstrap() {
+             eprintln!("Failed to bootstrap Kademlia: {:?}", e);
+        }
+    }
+
+    /// Re-join the network when too few peers are connected, at most once per cooldown
+    fn check_peer_count(&mut self) {
+        let current = self.swarm.connected_peers().count();
+        if current >= self.min_peers {
+            return;
+        }
+        if self.last_rebootstrap.is_some_and(|at| at.elapsed() < Duration::from_secs(REBOOTSTRAP_COOLDOWN_SECS)) {
+            return;
+        }
+        self.last_rebootstrap = Some(std::time::Instant::now());
+
+        println!("Only {} peer(s) connected (minimum {}), re-bootstrapping", current, self.min_peers);
+        let _ = self.event_tx.send(AppEvent::PeerCountLow { current, minimum: self.min_peers });
+        #[cfg(not(target_arch = "wasm32"))]
+        self.dial_bootstrap_nodes();
+    }
+
+    pub async fn run(&mut self) {
+        self.join_network();
 
         #[cfg(not(target_arch = "wasm32"))]
         let mut replication_interval = tokio::time::interval(Duration::from_secs(5));
@@ -355,6 +932,10 @@ impl Backend {
                     }
                 } => {
                     self.check_pending_replications();
+                    self.publish_due_posts();
+                    if self.last_score_decay.elapsed() >= Duration::from_secs(PEER_SCORE_DECAY_INTERVAL_SECS) {
+                        self.decay_peer_scores();
+                    }
                     
                     // Heartbeat (every 60s)
                     if self.last_heartbeat.elapsed() > std::time::Duration::from_secs(60) {
@@ -382,6 +963,70 @@ impl Backend {
         Ok(())
     }
 
+    /// Stop finding peers on the local network, e.g. to reach them only through a relay
+    #[allow(dead_code)]
+    pub fn disable_local_discovery(&mut self) {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.swarm.behaviour_mut().mdns = None.into();
+        }
+    }
+
+    /// Reserve a slot on a relay server so peers can reach us through `<relay>/p2p-circuit`.
+    /// The relay is also remembered as a fallback route for `AppCmd::DialPeer`.
+    pub fn listen_via_relay(&mut self, relay_addr: libp2p::Multiaddr) -> Result<(), Box<dyn std::error::Error>> {
+        let relay = network::peer_id_of(&relay_addr).ok_or("Relay address must end in /p2p/<peer id>")?;
+        self.relays.insert(relay, relay_addr.clone());
+        self.swarm.listen_on(relay_addr.with(libp2p::multiaddr::Protocol::P2pCircuit))?;
+        Ok(())
+    }
+
+    fn dial_peer(&mut self, multiaddr: String) {
+        let addr = match multiaddr.parse::<libp2p::Multiaddr>() {
+            Ok(addr) => addr,
+            Err(e) => {
+                eprintln!("Invalid multiaddr {}: {:?}", multiaddr, e);
+                return;
+            }
+        };
+        // Without a peer ID there is nobody to look for behind a relay
+        let Some(peer) = network::peer_id_of(&addr) else {
+            if let Err(e) = self.swarm.dial(addr) {
+                eprintln!("Failed to dial {}: {:?}", multiaddr, e);
+            }
+            return;
+        };
+        if self.swarm.is_connected(&peer) {
+            return;
+        }
+        self.pending_dials.insert(peer);
+        let opts = libp2p::swarm::dial_opts::DialOpts::peer_id(peer).addresses(vec![addr]).build();
+        if let Err(e) = self.swarm.dial(opts) {
+            eprintln!("Direct dial to {} failed: {:?}", peer, e);
+            self.pending_dials.remove(&peer);
+            self.dial_via_relay(peer);
+        }
+    }
+
+    /// Dial `peer` through every relay we know, letting the swarm keep whichever circuit connects first
+    fn dial_via_relay(&mut self, peer: PeerId) {
+        let circuits: Vec<libp2p::Multiaddr> = self.relays.iter()
+            .filter(|(relay, _)| **relay != peer)
+            .map(|(_, addr)| addr.clone()
+                .with(libp2p::multiaddr::Protocol::P2pCircuit)
+                .with(libp2p::multiaddr::Protocol::P2p(peer)))
+            .collect();
+        if circuits.is_empty() {
+            eprintln!("No relays known to reach {}", peer);
+            return;
+        }
+        println!("Dialing {} through {} relays", peer, circuits.len());
+        let opts = libp2p::swarm::dial_opts::DialOpts::peer_id(peer).addresses(circuits).build();
+        if let Err(e) = self.swarm.dial(opts) {
+            eprintln!("Relayed dial to {} failed: {:?}", peer, e);
+        }
+    }
+
     pub fn local_peer_id(&self) -> PeerId {
         *self.swarm.local_peer_id()
     }
@@ -412,7 +1057,7 @@ impl Backend {
                 if app.author == peer_id {
                     // Check if this application has enough approvals
                     if let Ok(votes) = self.store.get_application_votes(&app.id) {
-                        let network_size = self.store.count_unique_profiles().unwrap_or(0);
+                        let network_size = self.verified_network_size();
                         let required = Self::required_approvals(network_size);
                         let approvals = votes.iter()
                             .filter(|v| {
@@ -428,10 +1073,11 @@ impl Backend {
             }
         }
 
-        // 3. Check proofs (legacy vouch chain - still valid)
+        // 3. Check proofs (legacy vouch chain - still valid), minus any the voucher revoked
+        let revoked = self.store.get_vouch_revocations(peer_id).unwrap_or_default();
         match self.store.get_proofs(peer_id) {
             Ok(proofs) => {
-                for proof in proofs {
+                for proof in proofs.into_iter().filter(|p| !revoked.contains(&p.id)) {
                     // Recursive check
                     if self.is_verified(&proof.author, visited) {
                         return true;
@@ -443,6 +1089,47 @@ impl Backend {
         }
     }
 
+    /// The trust chain behind `is_verified`, as (voucher, vouchee) pairs ending at `peer_id`.
+    /// Founders are vouched for by "network"; application approvals list every approver.
+    /// Returns None when the peer is not verified.
+    fn verification_path(&self, peer_id: &str, visited: &mut std::collections::HashSet<String>) -> Option<Vec<(String, String)>> {
+        if !visited.insert(peer_id.to_string()) {
+            return None;
+        }
+
+        if let Ok(Some(profile)) = self.store.get_profile(peer_id) {
+            if profile.founder_id.is_some_and(|fid| fid <= 100) {
+                return Some(vec![("network".to_string(), peer_id.to_string())]);
+            }
+        }
+
+        if let Ok(apps) = self.store.get_pending_applications() {
+            let required = Self::required_approvals(self.verified_network_size());
+            for app in apps.iter().filter(|a| a.author == peer_id) {
+                if let Ok(votes) = self.store.get_application_votes(&app.id) {
+                    let mut approvers: Vec<String> = Vec::new();
+                    for vote in votes {
+                        if matches!(vote.payload, dag::DagPayload::ApplicationVote(ref av) if av.approve) && !approvers.contains(&vote.author) {
+                            approvers.push(vote.author);
+                        }
+                    }
+                    if approvers.len() >= required {
+                        return Some(approvers.into_iter().map(|a| (a, peer_id.to_string())).collect());
+                    }
+                }
+            }
+        }
+
+        let revoked = self.store.get_vouch_revocations(peer_id).unwrap_or_default();
+        for proof in self.store.get_proofs(peer_id).unwrap_or_default().into_iter().filter(|p| !revoked.contains(&p.id)) {
+            if let Some(mut path) = self.verification_path(&proof.author, visited) {
+                path.push((proof.author, peer_id.to_string()));
+                return Some(path);
+            }
+        }
+        None
+    }
+
     fn is_caller_verified(&self) -> bool {
         let author_pubkey = self.keypair.public();
         let author_hex = libp2p::PeerId::from_public_key(&author_pubkey).to_string();
@@ -460,198 +1147,2206 @@ impl Backend {
         }
     }
 
-    fn has_certification(&self, peer_id: &str, cert_type: &str) -> bool {
-        match self.store.get_certifications(peer_id) {
-            Ok(nodes) => {
-                for node in nodes {
-                    if let dag::DagPayload::Certification(cert) = node.payload {
-                        if cert.certification_type == cert_type {
-                            return true;
-                        }
-                    }
-                }
-                false
-            }
-            Err(_) => false,
+    /// Size of the verified network used for quorum thresholds.
+    /// While the count itself is running, verification checks inside it use the previous
+    /// count, or zero (the bootstrap threshold) if there is none yet.
+    fn verified_network_size(&self) -> usize {
+        if self.counting_verified.get() {
+            return self.verified_count_cache.get().map_or(0, |(_, verified, _, _)| verified);
         }
+        self.compute_verified_peer_count().map_or(0, |(_, verified, _)| verified)
     }
 
-    fn try_decrypt(&self, node: &dag::DagNode) -> String {
-        if let dag::DagPayload::Message(msg) = &node.payload {
-            // If I am the recipient
-            let my_pubkey = self.keypair.public();
-            let my_hex = libp2p::PeerId::from_public_key(&my_pubkey).to_string();
-            
-            if msg.recipient == my_hex {
-                // Decrypt
-                if let Ok(ephemeral_pub_bytes) = hex::decode(&msg.ephemeral_pubkey) {
-                    if ephemeral_pub_bytes.len() == 32 {
-                        let mut arr = [0u8; 32];
-                        arr.copy_from_slice(&ephemeral_pub_bytes);
-                        let ephemeral_pk = x25519_dalek::PublicKey::from(arr);
-                        let shared_secret = self.encryption_keypair.diffie_hellman(&ephemeral_pk);
-                        
-                        let shared_secret_bytes = shared_secret.as_bytes();
-                        let key_array = aes_gcm::Key::<Aes256Gcm>::from_slice(shared_secret_bytes);
-                        let key = Aes256Gcm::new(key_array);
-                        
-                        if let Ok(nonce_bytes) = hex::decode(&msg.nonce) {
-                            if nonce_bytes.len() == 12 {
-                                let nonce = Nonce::from_slice(&nonce_bytes);
-                                if let Ok(ciphertext_bytes) = hex::decode(&msg.ciphertext) {
-                                    if let Ok(plaintext) = key.decrypt(nonce, ciphertext_bytes.as_ref()) {
-                                        return String::from_utf8_lossy(&plaintext).to_string();
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-                return "[Decryption Failed]".to_string();
-            } else if node.author == my_hex {
-                // I sent it. I can't decrypt it (ephemeral key lost), but I might have stored it?
-                // For now, return placeholder.
-                return "[Sent Message - Content Encrypted]".to_string();
+    /// Drop the cached verified count if `node` can change who is verified
+    fn note_verification_change(&self, node: &dag::DagNode) {
+        if matches!(node.payload,
+            dag::DagPayload::Profile(_) | dag::DagPayload::Proof(_) | dag::DagPayload::Application(_)
+            | dag::DagPayload::ApplicationVote(_) | dag::DagPayload::VouchRevocation(_)) {
+            self.verified_count_cache.set(None);
+        }
+    }
+
+    /// Count profiles, verified users and founders, cached for 10 minutes
+    /// since each verification check may walk the whole vouch chain.
+    fn compute_verified_peer_count(&self) -> Result<(usize, usize, usize), Box<dyn std::error::Error>> {
+        if let Some((total, verified, founders, computed_at)) = self.verified_count_cache.get() {
+            if computed_at.elapsed() < Duration::from_secs(600) {
+                return Ok((total, verified, founders));
             }
         }
-        "[Not a message]".to_string()
+
+        self.counting_verified.set(true);
+        let counted = self.count_verified_peers();
+        self.counting_verified.set(false);
+        let (total, verified, founders) = counted?;
+        self.verified_count_cache.set(Some((total, verified, founders, std::time::Instant::now())));
+        Ok((total, verified, founders))
     }
 
-    fn replicate_block(&mut self, node: &dag::DagNode) {
-        let connected_peers: Vec<PeerId> = self.swarm.connected_peers().cloned().collect();
-        let connected_count = connected_peers.len();
-        
-        let target_replication_count = 10;
-        let target_peers = connected_peers.clone();
+    fn count_verified_peers(&self) -> Result<(usize, usize, usize), Box<dyn std::error::Error>> {
 
-        // If we don't have enough connected peers, try to find more via DHT
-        if connected_count < target_replication_count {
-            println!("Not enough connected peers ({}/{}), querying DHT...", connected_count, target_replication_count);
-            self.swarm.behaviour_mut().kad.get_closest_peers(node.id.as_bytes().to_vec());
-            
-            // Add to pending replications to retry later
-            if !self.pending_replications.contains_key(&node.id) {
-                self.pending_replications.insert(node.id.clone(), (node.clone(), std::time::Instant::now()));
-                println!("Added block {} to pending replication queue", node.id);
+        let authors = self.store.get_profile_authors()?;
+        let mut verified = std::collections::HashSet::new();
+        let mut founders = 0;
+        for author in &authors {
+            if let Ok(Some(profile)) = self.store.get_profile(author) {
+                if profile.founder_id.is_some_and(|fid| fid <= 100) {
+                    founders += 1;
+                }
+            }
+            let mut visited = std::collections::HashSet::new();
+            if self.is_verified(author, &mut visited) {
+                verified.insert(author.clone());
             }
-        } else {
-            // We have enough peers, remove from pending if it was there
-            self.pending_replications.remove(&node.id);
         }
 
-        // Send to available peers anyway
-        let peers_to_send: Vec<PeerId> = target_peers.into_iter().take(target_replication_count).collect();
-        
-        if !peers_to_send.is_empty() {
-             println!("Replicating block {} to {} peers", node.id, peers_to_send.len());
-             
-             let data = match serde_json::to_vec(node) {
-                 Ok(d) => d,
-                 Err(e) => {
-                     eprintln!("Failed to serialize node for replication: {:?}", e);
-                     return;
-                 }
-             };
+        let verified_count = verified.len();
+        self.store.set_verified_peers(verified);
+        Ok((authors.len(), verified_count, founders))
+    }
 
-             let request = BlockRequest::Store(data);
-             
-             for peer in peers_to_send {
-                 self.swarm.behaviour_mut().request_response.send_request(&peer, request.clone());
-             }
+    /// Gather proposals, votes and candidacies authored by a peer.
+    /// Sent as its own event so the global proposal list isn't replaced by one author's.
+    fn send_governance_record(&self, peer_id: String) {
+        let record = self.store.get_proposals_by_author(&peer_id).and_then(|proposals| {
+            Ok(GovernanceRecord {
+                proposals,
+                votes_cast: self.store.get_votes_by_author(&peer_id)?.len(),
+                candidacies: self.store.get_candidacies_by_author(&peer_id)?,
+            })
+        });
+        match record {
+            Ok(record) => {
+                let _ = self.event_tx.send(AppEvent::ProposalsByAuthorFetched { peer_id, record });
+            }
+            Err(e) => eprintln!("Failed to fetch proposals by author: {:?}", e),
         }
     }
 
-    fn check_pending_replications(&mut self) {
-        let now = std::time::Instant::now();
-        let timeout = Duration::from_secs(60); // Stop trying after 60 seconds
-        
-        let mut to_remove = Vec::new();
-        let mut to_retry = Vec::new();
-
-        for (id, (node, started)) in &self.pending_replications {
-            if now.duration_since(*started) > timeout {
-                to_remove.push(id.clone());
-                println!("Replication timed out for block {}", id);
-            } else {
-                to_retry.push(node.clone());
+    /// (provider, consumer) peer IDs from a contract's `init_params["parties"]`
+    fn contract_parties(&self, contract_id: &str) -> Option<(String, String)> {
+        match self.store.get_node(contract_id) {
+            Ok(Some(dag::DagNode { payload: dag::DagPayload::Contract(c), .. })) => {
+                let params: serde_json::Value = serde_json::from_str(&c.init_params).ok()?;
+                let provider = params["parties"]["provider"].as_str()?.to_string();
+                let consumer = params["parties"]["consumer"].as_str()?.to_string();
+                Some((provider, consumer))
             }
+            _ => None,
         }
+    }
 
-        for id in to_remove {
-            self.pending_replications.remove(&id);
+    /// Proposals resembling `text`, excluding `proposal_id` itself; an empty id stands for an unsaved draft
+    fn send_similar_proposals(&self, proposal_id: String, text: &str) {
+        const MIN_SIMILARITY: f32 = 0.2;
+        const MAX_RESULTS: usize = 5;
+        let keywords = Store::extract_keywords(text);
+        match self.store.search_proposals_by_keywords(&keywords) {
+            Ok(matches) => {
+                let similar = matches.into_iter()
+                    .filter(|(node, score)| node.id != proposal_id && *score >= MIN_SIMILARITY)
+                    .take(MAX_RESULTS)
+                    .collect();
+                let _ = self.event_tx.send(AppEvent::SimilarProposalsFetched { proposal_id, similar });
+            }
+            Err(e) => eprintln!("Failed to search similar proposals: {:?}", e),
         }
+    }
 
-        for node in to_retry {
-            // This will check connected peers again and potentially re-send and re-query DHT
-            self.replicate_block(&node);
-        }
+    /// Current contract state: `init_params` with every call replayed by the VM
+    fn contract_state(&self, contract_id: &str) -> Result<(String, Vec<vm::ContractEvent>), vm::VmError> {
+        let (init_state, code) = match self.store.get_node(contract_id) {
+            Ok(Some(dag::DagNode { payload: dag::DagPayload::Contract(c), .. })) => (c.init_params, c.code),
+            _ => ("{}".to_string(), "".to_string()),
+        };
+        let calls = self.store.get_contract_calls(contract_id).unwrap_or_default();
+        VM::calculate_contract_state(&init_state, &code, &calls, &mut vm::GasMeter::new(self.vm_gas_limit))
     }
 
-    async fn process_publish_post(&mut self, content: String, attachments: Vec<String>, geohash: Option<String>, announcement: bool) {
-        if !self.is_caller_verified() {
-            eprintln!("Cannot publish post: User is not verified.");
-            return;
-        }
-        
-        // Permission check for announcements
-        if announcement {
-            let author_pubkey = self.keypair.public();
-            let author_hex = libp2p::PeerId::from_public_key(&author_pubkey).to_string();
-            let officials = self.store.get_active_officials().unwrap_or_default();
-            if !officials.values().any(|p| p == &author_hex) {
-                eprintln!("Cannot publish announcement: User is not an elected official.");
+    /// Replay a contract with one extra, unpublished call appended and report the outcome.
+    /// Nothing is stored; `gas_consumed` is what the extra call cost on top of the existing history.
+    fn simulate_contract_call(&self, contract_id: String, method: String, params: String) {
+        let (init_state, code) = match self.store.get_node(&contract_id) {
+            Ok(Some(dag::DagNode { payload: dag::DagPayload::Contract(c), .. })) => (c.init_params, c.code),
+            _ => {
+                let _ = self.event_tx.send(AppEvent::ContractSimulationResult { contract_id, resulting_state: String::new(), gas_consumed: 0, error: Some("Contract not found".to_string()) });
                 return;
             }
-        }
-        
-        let payload = dag::DagPayload::Post(dag::PostPayload { content, attachments, geohash, announcement });
-        
-        // Get previous head for this user if any
-        let author_pubkey = self.keypair.public();
-        let author_hex = libp2p::PeerId::from_public_key(&author_pubkey).to_string();
-        
-        let prev = match self.store.get_head(&author_hex) {
-            Ok(Some(cid)) => vec![cid],
-            Ok(None) => vec![],
+        };
+        let mut calls = self.store.get_contract_calls(&contract_id).unwrap_or_default();
+        let payload = dag::DagPayload::ContractCall(dag::ContractCallPayload { contract_id: contract_id.clone(), method, params });
+        match dag::DagNode::new("contract_call:v1".to_string(), payload, vec![], &self.keypair, 0) {
+            Ok(call) => calls.push(call),
             Err(e) => {
-                eprintln!("Failed to get head: {:?}", e);
-                vec![]
+                let _ = self.event_tx.send(AppEvent::ContractSimulationResult { contract_id, resulting_state: String::new(), gas_consumed: 0, error: Some(e.to_string()) });
+                return;
             }
+        }
+
+        let event_tx = self.event_tx.clone();
+        let gas_limit = self.vm_gas_limit;
+        let simulate = move || {
+            let mut baseline = vm::GasMeter::new(gas_limit);
+            let _ = VM::calculate_contract_state(&init_state, &code, &calls[..calls.len() - 1], &mut baseline);
+            let mut gas = vm::GasMeter::new(gas_limit);
+            let (resulting_state, error) = match VM::calculate_contract_state(&init_state, &code, &calls, &mut gas) {
+                Ok((state, _)) => (state, None),
+                Err(e) => (String::new(), Some(e.to_string())),
+            };
+            let gas_consumed = gas.consumed.min(gas_limit).saturating_sub(baseline.consumed.min(gas_limit));
+            let _ = event_tx.send(AppEvent::ContractSimulationResult { contract_id, resulting_state, gas_consumed, error });
         };
 
-        match dag::DagNode::new(
-            "post:v1".to_string(),
-            payload,
-            prev,
-            &self.keypair,
-            0
-        ) {
-            Ok(node) => {
-                println!("Created post node: {}", node.id);
-                // 1. Store locally
-                if let Err(e) = self.store.put_node(&node) {
-                    eprintln!("Failed to store post node: {:?}", e);
-                    return;
-                }
-                
-                // 2. Update head
+        #[cfg(not(target_arch = "wasm32"))]
+        tokio::task::spawn_blocking(simulate);
+
+        #[cfg(target_arch = "wasm32")]
+        simulate();
+    }
+
+    /// Commands that only read the store. New read commands must be listed here so they wait
+    /// for an index rebuild instead of reading half-built indexes.
+    fn is_read_command(cmd: &AppCmd) -> bool {
+        matches!(
+            cmd,
+            AppCmd::FetchPendingVouches { .. } | AppCmd::FetchEndorsements { .. } | AppCmd::FetchScheduledPosts
+            | AppCmd::FetchPollResults { .. } | AppCmd::FetchEvents { .. } | AppCmd::FetchEventAttendees { .. }
+            | AppCmd::FetchShares { .. } | AppCmd::FetchPosts | AppCmd::FetchLocalPosts { .. }
+            | AppCmd::FetchPersonalizedFeed { .. } | AppCmd::FetchTrendingPosts { .. } | AppCmd::FetchMessageThreads
+            | AppCmd::FetchMessages { .. } | AppCmd::FetchGroups | AppCmd::FetchGroupsByOwner { .. }
+            | AppCmd::FetchGroupMembers { .. } | AppCmd::FetchGroupActivity { .. } | AppCmd::FetchGroupMemberActivity { .. }
+            | AppCmd::FetchGroupLeaderboard { .. } | AppCmd::FetchGroupMessages { .. } | AppCmd::FetchGroupPolicy { .. }
+            | AppCmd::FetchGroupInvitations | AppCmd::FetchMyProfile | AppCmd::FetchSpendingLimit
+            | AppCmd::FetchMultiSigProposals | AppCmd::FetchPendingTransfers | AppCmd::FetchIncomingTransfers
+            | AppCmd::FetchBalance | AppCmd::FetchTransactionHistory { .. } | AppCmd::FetchUbiTimer
+            | AppCmd::CheckVerificationStatus | AppCmd::FetchUserProfile { .. } | AppCmd::FetchWebPage { .. }
+            | AppCmd::FetchWebPageEmbeds { .. } | AppCmd::FetchWebStats { .. } | AppCmd::ResolveName { .. }
+            | AppCmd::FetchBlock { .. } | AppCmd::FetchStorageStats | AppCmd::FetchMyStorageQuotaUsageByType
+            | AppCmd::FetchStorageQuota | AppCmd::FetchStoreRoot | AppCmd::FetchPinnedBlocks
+            | AppCmd::FetchListingBuyerHistory { .. } | AppCmd::FetchPeerListingHistory { .. }
+            | AppCmd::SearchListings { .. } | AppCmd::FetchListings | AppCmd::FetchLocalListings { .. }
+            | AppCmd::FetchLocalListingsByCategory { .. } | AppCmd::SearchWeb { .. }
+            | AppCmd::FetchWebSearchResultsFromPeer { .. } | AppCmd::SearchFiles { .. }
+            | AppCmd::FetchContractsByTemplate { .. } | AppCmd::FetchContractVersionHistory { .. } | AppCmd::FetchContracts
+            | AppCmd::FetchContractState { .. } | AppCmd::FetchContractHistory { .. }
+            | AppCmd::FetchContractSecurityAudit { .. } | AppCmd::FetchContractDeployerReputation { .. }
+            | AppCmd::FetchContractMilestones { .. } | AppCmd::FetchPendingContracts | AppCmd::FetchPublicLedger
+            | AppCmd::FetchMyDelegations | AppCmd::FetchProposals | AppCmd::FetchProposalVotes { .. }
+            | AppCmd::FetchProposalTally { .. } | AppCmd::FetchProposalProgressToQuorum { .. }
+            | AppCmd::FetchProposalOutcomeActions { .. } | AppCmd::FetchProposalImpactEstimate { .. }
+            | AppCmd::FetchProposalSimilar { .. } | AppCmd::FetchDraftProposalSimilar { .. }
+            | AppCmd::FetchMyProposalVoteHistory | AppCmd::FetchProposalsByAuthor { .. } | AppCmd::FetchMyProposals
+            | AppCmd::FetchElectionSchedule { .. } | AppCmd::FetchCandidates | AppCmd::FetchCandidateTally { .. }
+            | AppCmd::FetchCandidacyWithdrawal { .. } | AppCmd::FetchReputation { .. } | AppCmd::FetchReputations { .. }
+            | AppCmd::FetchTrustScore { .. } | AppCmd::FetchMyWebPages | AppCmd::FetchAllWebPages | AppCmd::FetchReports
+            | AppCmd::FetchMyFiles | AppCmd::FetchRecalls | AppCmd::FetchRecallTally { .. } | AppCmd::FetchPetitions
+            | AppCmd::FetchPetitionSignatures { .. } | AppCmd::FetchComments { .. } | AppCmd::FetchReactions { .. }
+            | AppCmd::FetchLikes { .. } | AppCmd::FetchPostEngagementScore { .. } | AppCmd::FetchCrossPostHistory { .. }
+            | AppCmd::FetchPostContextWindow { .. } | AppCmd::FetchOversightCases | AppCmd::FetchJuryDuty
+            | AppCmd::FetchMyJuryStats | AppCmd::FetchCertificationLeaderboard { .. } | AppCmd::FetchConnectedPeerProfiles
+            | AppCmd::FetchCandidatePlatform { .. } | AppCmd::FetchMutualFollowers { .. }
+            | AppCmd::FetchContractParties { .. } | AppCmd::FetchContractEscrowStatus { .. } | AppCmd::FetchMyEscrows
+            | AppCmd::FetchFollowingSuggestions { .. } | AppCmd::FetchPeersByGeohash { .. }
+            | AppCmd::FetchOversightCasesByJuror { .. } | AppCmd::FetchOversightStatsByMinistry | AppCmd::FetchMinistries
+            | AppCmd::FetchMyElectedPositions | AppCmd::FetchActiveOfficials | AppCmd::FetchStories
+            | AppCmd::FetchLocalStories { .. } | AppCmd::FetchFollowing { .. } | AppCmd::FetchFollowers { .. }
+            | AppCmd::FetchGivenUserPosts { .. } | AppCmd::FetchNodeByAuthorAndType { .. } | AppCmd::FetchFollowingPosts
+            | AppCmd::FetchFollowingHashtags | AppCmd::FetchFollowingHashtagPosts | AppCmd::FetchMentions { .. }
+            | AppCmd::FetchByHashtag { .. } | AppCmd::FetchBookmarks | AppCmd::FetchMutedUsers | AppCmd::FetchBlockedUsers
+            | AppCmd::FetchNetworkDiagnostics | AppCmd::FetchPeerScores | AppCmd::FetchTaxRate
+            | AppCmd::FetchSystemParameters | AppCmd::FetchTokenInflationRate { .. }
+            | AppCmd::FetchNetworkGrowthStats { .. } | AppCmd::FetchCourses | AppCmd::FetchCourse { .. }
+            | AppCmd::FetchExams | AppCmd::FetchCertifications { .. } | AppCmd::FetchPeerCoursesCompleted { .. }
+            | AppCmd::FetchMyCertifications | AppCmd::FetchPendingApplications | AppCmd::FetchApplicationVotes { .. }
+            | AppCmd::FetchApplicationApprovers { .. } | AppCmd::FetchMyApplicationStatus
+            | AppCmd::FetchPeerVerificationPath { .. } | AppCmd::FetchVerifiedPeerCount
+        )
+    }
+
+    /// Rebuild the store's secondary indexes off the event loop. The rebuild holds `index_lock`
+    /// until it finishes, so reads arriving in the meantime wait for it.
+    fn rebuild_indexes(&mut self) {
+        let Ok(guard) = self.index_lock.clone().try_lock_owned() else {
+            println!("Index rebuild already running");
+            return;
+        };
+        self.rebuilding_indexes = true;
+        let _ = self.event_tx.send(AppEvent::IndexRebuildStarted);
+
+        let store = self.store.clone();
+        let event_tx = self.event_tx.clone();
+        let rebuild = move || {
+            let started = std::time::Instant::now();
+            match store.rebuild_indexes() {
+                Ok(nodes_scanned) => {
+                    let duration_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+                    println!("Rebuilt indexes over {} blocks in {} ms", nodes_scanned, duration_ms);
+                    let _ = event_tx.send(AppEvent::IndexRebuildComplete { nodes_scanned, duration_ms });
+                }
+                Err(e) => eprintln!("Failed to rebuild indexes: {:?}", e),
+            }
+            drop(guard);
+        };
+
+        #[cfg(not(target_arch = "wasm32"))]
+        tokio::task::spawn_blocking(rebuild);
+
+        #[cfg(target_arch = "wasm32")]
+        rebuild();
+    }
+
+    /// Fire the events a newly received call emitted. Its events are the tail of a full replay
+    /// beyond what the replay without it produced
+    fn send_new_contract_events(&self, contract_id: &str, call_id: &str) {
+        let Ok((_, events)) = self.contract_state(contract_id) else { return };
+        let (init_state, code) = match self.store.get_node(contract_id) {
+            Ok(Some(dag::DagNode { payload: dag::DagPayload::Contract(c), .. })) => (c.init_params, c.code),
+            _ => return,
+        };
+        let earlier: Vec<dag::DagNode> = self.store.get_contract_calls(contract_id).unwrap_or_default()
+            .into_iter()
+            .filter(|c| c.id != call_id)
+            .collect();
+        let already = VM::calculate_contract_state(&init_state, &code, &earlier, &mut vm::GasMeter::new(self.vm_gas_limit))
+            .map(|(_, events)| events.len())
+            .unwrap_or(0);
+        for event in events.into_iter().skip(already) {
+            let _ = self.event_tx.send(AppEvent::ContractEvent { contract_id: contract_id.to_string(), event });
+        }
+    }
+
+    /// Milestones of a contract with completions applied; empty for contracts without milestones
+    fn contract_milestones(&self, contract_id: &str) -> Vec<dag::Milestone> {
+        self.contract_state(contract_id)
+            .ok()
+            .and_then(|(state, _)| serde_json::from_str::<serde_json::Value>(&state).ok())
+            .and_then(|state| serde_json::from_value(state[vm::MILESTONES_KEY].clone()).ok())
+            .unwrap_or_default()
+    }
+
+    fn send_contract_milestones(&self, contract_id: String) {
+        let milestones = self.contract_milestones(&contract_id)
+            .into_iter()
+            .map(|m| (m.description, m.amount, m.completed, m.completion_cid))
+            .collect();
+        let _ = self.event_tx.send(AppEvent::ContractMilestonesFetched { contract_id, milestones });
+    }
+
+    /// Proposal votes cast by a peer, each paired with the proposal title when it is known
+    fn vote_history_with_titles(&self, peer_id: &str) -> Result<Vec<TitledVote>, Box<dyn std::error::Error>> {
+        let vote_nodes = self.store.get_votes_by_author(peer_id)?;
+        Ok(vote_nodes.into_iter().map(|vote| {
+            let title = match &vote.payload {
+                dag::DagPayload::Vote(v) => match self.store.get_node(&v.proposal_id) {
+                    Ok(Some(dag::DagNode { payload: dag::DagPayload::Proposal(p), .. })) => Some(p.title),
+                    _ => None,
+                },
+                _ => None,
+            };
+            (vote, title)
+        }).collect())
+    }
+
+    fn has_certification(&self, peer_id: &str, cert_type: &str) -> bool {
+        let now = chrono::Utc::now();
+        match self.store.get_certifications(peer_id) {
+            Ok(nodes) => {
+                for node in nodes {
+                    if let dag::DagPayload::Certification(cert) = &node.payload {
+                        if cert.certification_type == cert_type && cert.expires_at.is_none_or(|e| e >= now) && !self.is_cert_revoked(&node.id) {
+                            return true;
+                        }
+                    }
+                }
+                false
+            }
+            Err(_) => false,
+        }
+    }
+
+    fn is_cert_revoked(&self, cert_cid: &str) -> bool {
+        matches!(self.store.get_cert_revocation(cert_cid), Ok(Some(_)))
+    }
+
+    /// Revoke a certification we issued. Holders lose it everywhere as the revocation replicates.
+    fn revoke_certification(&mut self, cert_cid: String, reason: String) {
+        let author_hex = self.local_peer_id().to_string();
+        let cert_node = match self.store.get_node(&cert_cid) {
+            Ok(Some(node)) if matches!(node.payload, dag::DagPayload::Certification(_)) => node,
+            _ => {
+                eprintln!("Cannot revoke {}: not a certification.", cert_cid);
+                return;
+            }
+        };
+        if Store::cert_issuer(&cert_node) != Some(author_hex.as_str()) {
+            eprintln!("Cannot revoke {}: only its issuer can.", cert_cid);
+            return;
+        }
+        if self.is_cert_revoked(&cert_cid) {
+            eprintln!("Certification {} is already revoked.", cert_cid);
+            return;
+        }
+        let dag::DagPayload::Certification(cert) = cert_node.payload else { return };
+
+        let payload = dag::DagPayload::CertRevocation(dag::CertRevocationPayload { cert_cid: cert_cid.clone(), reason: reason.clone() });
+        let prev = match self.store.get_head(&author_hex) {
+            Ok(Some(cid)) => vec![cid],
+            _ => vec![],
+        };
+        match dag::DagNode::new("cert_revocation:v1".to_string(), payload, prev, &self.keypair, 0) {
+            Ok(node) => {
+                if let Err(e) = self.store.put_node(&node) {
+                    eprintln!("Failed to store certification revocation: {:?}", e);
+                    return;
+                }
+                if let Err(e) = self.store.update_head(&author_hex, &node.id) {
+                    eprintln!("Failed to update head: {:?}", e);
+                }
+                let topic = gossipsub::IdentTopic::new("blocks");
+                let _ = self.swarm.behaviour_mut().gossipsub.publish(topic, node.id.as_bytes());
+                self.replicate_block(&node);
+                let _ = self.event_tx.send(AppEvent::CertificationRevoked { cert_cid, peer_id: cert.recipient, reason });
+            }
+            Err(e) => eprintln!("Failed to create certification revocation: {:?}", e),
+        }
+    }
+
+    /// Withdraw a vouch we gave. Anyone whose verification rested on it, directly or further down
+    /// the chain, loses it as the revocation replicates
+    fn revoke_vouch(&mut self, proof_cid: String, reason: String) {
+        let author_hex = self.local_peer_id().to_string();
+        let target = match self.store.get_node(&proof_cid) {
+            Ok(Some(dag::DagNode { author, payload: dag::DagPayload::Proof(proof), .. })) if author == author_hex => proof.target_pubkey,
+            Ok(Some(_)) => {
+                eprintln!("Cannot revoke {}: not a vouch of ours.", proof_cid);
+                return;
+            }
+            _ => {
+                eprintln!("Cannot revoke {}: vouch not found.", proof_cid);
+                return;
+            }
+        };
+        if self.store.get_vouch_revocations(&target).unwrap_or_default().contains(&proof_cid) {
+            eprintln!("Vouch {} is already revoked.", proof_cid);
+            return;
+        }
+
+        let payload = dag::DagPayload::VouchRevocation(dag::VouchRevocationPayload { original_proof_cid: proof_cid.clone(), reason });
+        if self.publish_node("vouch_revocation:v1", payload).is_some() {
+            let _ = self.event_tx.send(AppEvent::VouchRevoked { proof_cid });
+        }
+    }
+
+    /// The specific event a received node warrants, if any, in place of `BlockReceived`
+    fn received_node_event(&self, node: &dag::DagNode) -> Option<AppEvent> {
+        self.receipt_event(node)
+            .or_else(|| self.revocation_event(node))
+            .or_else(|| self.delegation_event(node))
+            .or_else(|| self.post_delete_event(node))
+            .or_else(|| self.vouch_revocation_event(node))
+            .or_else(|| self.post_edit_event(node))
+    }
+
+    /// `PostEdited` carrying the newest revision we hold, so an edit that arrives after a
+    /// later one never replaces it on screen
+    fn post_edit_event(&self, node: &dag::DagNode) -> Option<AppEvent> {
+        Store::edited_post_id(node)?;
+        let latest = self.store.get_latest_post_version(&node.id).ok()??;
+        Some(AppEvent::PostEdited(latest))
+    }
+
+    /// The event for a vouch revocation received from the original voucher
+    fn vouch_revocation_event(&self, node: &dag::DagNode) -> Option<AppEvent> {
+        let dag::DagPayload::VouchRevocation(revocation) = &node.payload else {
+            return None;
+        };
+        match self.store.get_node(&revocation.original_proof_cid) {
+            Ok(Some(proof)) if matches!(proof.payload, dag::DagPayload::Proof(_)) && proof.author == node.author => {
+                Some(AppEvent::VouchRevoked { proof_cid: revocation.original_proof_cid.clone() })
+            }
+            _ => None,
+        }
+    }
+
+    /// The event for a revocation received from its certification's issuer
+    fn revocation_event(&self, node: &dag::DagNode) -> Option<AppEvent> {
+        let dag::DagPayload::CertRevocation(revocation) = &node.payload else {
+            return None;
+        };
+        match (self.store.get_cert_revocation(&revocation.cert_cid), self.store.get_node(&revocation.cert_cid)) {
+            (Ok(Some(valid)), Ok(Some(dag::DagNode { payload: dag::DagPayload::Certification(cert), .. }))) if valid.id == node.id => {
+                Some(AppEvent::CertificationRevoked { cert_cid: revocation.cert_cid.clone(), peer_id: cert.recipient, reason: revocation.reason.clone() })
+            }
+            _ => None,
+        }
+    }
+
+    fn send_reactions(&self, target_id: String) {
+        let my_pubkey = self.local_peer_id().to_string();
+        match self.store.get_reactions_for_target(&target_id, &my_pubkey) {
+            Ok(counts) => {
+                let _ = self.event_tx.send(AppEvent::ReactionsFetched { target_id, counts });
+            }
+            Err(e) => eprintln!("Failed to fetch reactions: {:?}", e),
+        }
+    }
+
+    /// The event for a tombstone received from the deleted post's author
+    fn post_delete_event(&self, node: &dag::DagNode) -> Option<AppEvent> {
+        let dag::DagPayload::PostDelete(delete) = &node.payload else {
+            return None;
+        };
+        match self.store.get_node(&delete.target_cid) {
+            Ok(Some(post)) if post.r#type == "post:v1" && post.author == node.author => Some(AppEvent::PostDeleted { cid: delete.target_cid.clone() }),
+            _ => None,
+        }
+    }
+
+    /// The event for a delegation that is in force, as seen by either side of it
+    fn delegation_event(&self, node: &dag::DagNode) -> Option<AppEvent> {
+        let dag::DagPayload::VoteDelegation(delegation) = &node.payload else {
+            return None;
+        };
+        if delegation.expires_at.is_some_and(|t| t <= chrono::Utc::now()) {
+            return None;
+        }
+        Some(AppEvent::DelegationActivated { delegator: node.author.clone(), delegate: delegation.delegate.clone() })
+    }
+
+    /// Let `delegate` vote for us on proposals within `scope`, replacing any earlier delegation of that scope
+    fn delegate_vote(&mut self, delegate: String, scope: dag::DelegationScope, expires_at: Option<chrono::DateTime<chrono::Utc>>) {
+        let author_hex = self.local_peer_id().to_string();
+        if !self.is_caller_verified() {
+            eprintln!("Cannot delegate vote: User is not verified.");
+            return;
+        }
+        if delegate == author_hex {
+            eprintln!("Cannot delegate vote to yourself.");
+            return;
+        }
+        if !self.is_verified(&delegate, &mut std::collections::HashSet::new()) {
+            eprintln!("Cannot delegate vote: {} is not verified.", delegate);
+            return;
+        }
+        match self.store.delegation_creates_cycle(&author_hex, &delegate, &scope) {
+            Ok(false) => {}
+            Ok(true) => {
+                eprintln!("Cannot delegate vote to {}: the delegation would be circular.", delegate);
+                return;
+            }
+            Err(e) => {
+                eprintln!("Failed to check delegation chain: {:?}", e);
+                return;
+            }
+        }
+        let payload = dag::VoteDelegationPayload { delegate, scope, expires_at };
+        if let Some(node) = self.publish_node("vote_delegation:v1", dag::DagPayload::VoteDelegation(payload)) {
+            if let Some(event) = self.delegation_event(&node) {
+                let _ = self.event_tx.send(event);
+            }
+            self.send_my_delegations();
+        }
+    }
+
+    /// Revoke our delegation of `scope` by republishing it as already expired
+    fn revoke_delegation(&mut self, scope: dag::DelegationScope) {
+        let author_hex = self.local_peer_id().to_string();
+        let current = self.store.get_active_delegations().unwrap_or_default().into_iter().find_map(|n| match n.payload {
+            dag::DagPayload::VoteDelegation(d) if n.author == author_hex && d.scope == scope => Some(d),
+            _ => None,
+        });
+        let Some(current) = current else {
+            eprintln!("No active delegation to revoke for {:?}", scope);
+            return;
+        };
+        let payload = dag::VoteDelegationPayload { expires_at: Some(chrono::Utc::now()), ..current };
+        if self.publish_node("vote_delegation:v1", dag::DagPayload::VoteDelegation(payload)).is_some() {
+            self.send_my_delegations();
+        }
+    }
+
+    fn send_my_delegations(&self) {
+        let author_hex = self.local_peer_id().to_string();
+        match self.store.get_active_delegations() {
+            Ok(delegations) => {
+                let mine = delegations.into_iter().filter(|n| n.author == author_hex).collect();
+                let _ = self.event_tx.send(AppEvent::MyDelegationsFetched(mine));
+            }
+            Err(e) => eprintln!("Failed to fetch delegations: {:?}", e),
+        }
+    }
+
+    fn publish_node(&mut self, node_type: &str, payload: dag::DagPayload) -> Option<dag::DagNode> {
+        self.publish_linked_node(node_type, payload, &[])
+    }
+
+    /// Like `publish_node`, with extra `prev` links after our head
+    fn publish_linked_node(&mut self, node_type: &str, payload: dag::DagPayload, links: &[String]) -> Option<dag::DagNode> {
+        let author_hex = self.local_peer_id().to_string();
+        let mut prev = match self.store.get_head(&author_hex) {
+            Ok(Some(cid)) => vec![cid],
+            _ => vec![],
+        };
+        for link in links {
+            if !prev.contains(link) {
+                prev.push(link.clone());
+            }
+        }
+        match dag::DagNode::new(node_type.to_string(), payload, prev, &self.keypair, 0) {
+            Ok(node) => {
+                if let Err(e) = self.store.put_node(&node) {
+                    eprintln!("Failed to store {} node: {:?}", node_type, e);
+                    return None;
+                }
+                if let Err(e) = self.store.update_head(&author_hex, &node.id) {
+                    eprintln!("Failed to update head: {:?}", e);
+                }
+                self.note_verification_change(&node);
+                let topic = gossipsub::IdentTopic::new("blocks");
+                let _ = self.swarm.behaviour_mut().gossipsub.publish(topic, node.id.as_bytes());
+                self.replicate_block(&node);
+                Some(node)
+            }
+            Err(e) => {
+                eprintln!("Failed to create {} node: {:?}", node_type, e);
+                None
+            }
+        }
+    }
+
+    /// Propose a transfer that only goes ahead once `threshold` of `required_signers` approve it
+    fn propose_multisig_transfer(&mut self, recipients: Vec<(String, u64)>, required_signers: Vec<String>, threshold: usize, expires_at: chrono::DateTime<chrono::Utc>) {
+        let author_hex = self.local_peer_id().to_string();
+        if !self.is_caller_verified() {
+            eprintln!("Cannot propose multi-sig transfer: User is not verified.");
+            return;
+        }
+        if recipients.is_empty() || recipients.iter().any(|(_, amount)| *amount == 0) {
+            eprintln!("Cannot propose multi-sig transfer: every recipient needs a positive amount");
+            return;
+        }
+        let mut signers: Vec<String> = Vec::new();
+        for signer in required_signers {
+            if !signers.contains(&signer) {
+                signers.push(signer);
+            }
+        }
+        if signers.contains(&author_hex) {
+            eprintln!("Cannot propose multi-sig transfer: you cannot co-sign your own transfer");
+            return;
+        }
+        if threshold == 0 || threshold > signers.len() {
+            eprintln!("Cannot propose multi-sig transfer: threshold {} must be between 1 and {}", threshold, signers.len());
+            return;
+        }
+        if let Some(signer) = signers.iter().find(|s| !self.is_verified(s, &mut std::collections::HashSet::new())) {
+            eprintln!("Cannot propose multi-sig transfer: signer {} is not verified", signer);
+            return;
+        }
+        if expires_at <= chrono::Utc::now() {
+            eprintln!("Cannot propose multi-sig transfer: it would already be expired");
+            return;
+        }
+        let total = recipients.iter().try_fold(0u64, |sum, (_, amount)| sum.checked_add(*amount));
+        let balance = self.store.get_balance(&author_hex).unwrap_or(0);
+        let Some(total) = total.filter(|t| i64::try_from(*t).is_ok_and(|t| t <= balance)) else {
+            eprintln!("Cannot propose multi-sig transfer: insufficient balance ({})", balance);
+            return;
+        };
+
+        let payload = dag::DagPayload::MultiSigProposal(dag::MultiSigProposalPayload { recipients, required_signers: signers, threshold, expires_at });
+        if let Some(node) = self.publish_node("multisig_proposal:v1", payload) {
+            println!("Proposed multi-sig transfer {} of {} tokens", node.id, total);
+            self.send_multisig_proposals();
+        }
+    }
+
+    /// Approve a multi-sig transfer we are a required signer of
+    fn approve_multisig_transfer(&mut self, proposal_cid: String) {
+        let author_hex = self.local_peer_id().to_string();
+        if !self.is_caller_verified() {
+            eprintln!("Cannot approve multi-sig transfer: User is not verified.");
+            return;
+        }
+        let (node, approvers, transfer) = match self.store.get_multisig_proposal(&proposal_cid) {
+            Ok(Some(record)) => record,
+            Ok(None) => {
+                eprintln!("Cannot approve multi-sig transfer: proposal {} not found", proposal_cid);
+                return;
+            }
+            Err(e) => {
+                eprintln!("Failed to fetch multi-sig proposal: {:?}", e);
+                return;
+            }
+        };
+        let dag::DagPayload::MultiSigProposal(proposal) = node.payload else { return };
+        if !proposal.required_signers.contains(&author_hex) {
+            eprintln!("Cannot approve multi-sig transfer {}: you are not one of its signers", proposal_cid);
+            return;
+        }
+        if transfer.is_some() {
+            eprintln!("Multi-sig transfer {} has already executed", proposal_cid);
+            return;
+        }
+        if proposal.expires_at <= chrono::Utc::now() {
+            eprintln!("Cannot approve multi-sig transfer {}: it has expired", proposal_cid);
+            return;
+        }
+        if approvers.contains(&author_hex) {
+            eprintln!("Multi-sig transfer {} is already approved by you", proposal_cid);
+            return;
+        }
+
+        let payload = dag::DagPayload::MultiSigApproval(dag::MultiSigApprovalPayload { proposal_cid: proposal_cid.clone() });
+        if self.publish_node("multisig_approval:v1", payload).is_some() {
+            self.execute_multisig_if_ready(&proposal_cid);
+            self.send_multisig_proposals();
+        }
+    }
+
+    /// Publish a multi-sig proposal's transfers once enough signers have approved it.
+    /// Only the proposer can spend the tokens, so every other peer leaves this to them.
+    fn execute_multisig_if_ready(&mut self, proposal_cid: &str) {
+        let author_hex = self.local_peer_id().to_string();
+        let (node, approvers, transfer) = match self.store.get_multisig_proposal(proposal_cid) {
+            Ok(Some(record)) => record,
+            Ok(None) => return,
+            Err(e) => {
+                eprintln!("Failed to fetch multi-sig proposal: {:?}", e);
+                return;
+            }
+        };
+        let dag::DagPayload::MultiSigProposal(proposal) = node.payload else { return };
+        if node.author != author_hex || transfer.is_some() || approvers.len() < proposal.threshold {
+            return;
+        }
+        if proposal.expires_at <= chrono::Utc::now() {
+            println!("Multi-sig transfer {} expired before it was approved", proposal_cid);
+            return;
+        }
+        let total: u64 = proposal.recipients.iter().map(|(_, amount)| amount).sum();
+        let balance = self.store.get_balance(&author_hex).unwrap_or(0);
+        if !i64::try_from(total).is_ok_and(|t| t <= balance) {
+            eprintln!("Cannot execute multi-sig transfer {}: insufficient balance ({} < {})", proposal_cid, balance, total);
+            return;
+        }
+        if !self.within_spending_limit(total) {
+            return;
+        }
+
+        // Taxed like any other transfer, with one tax burn covering every recipient
+        let tax_rate = self.store.get_current_tax_rate().unwrap_or(0);
+        let mut tax_total = 0;
+        let mut first_transfer: Option<String> = None;
+        for (recipient, amount) in proposal.recipients {
+            let tax = (amount as u128 * tax_rate as u128 / 100) as u64;
+            let payload = dag::DagPayload::Token(dag::TokenPayload {
+                action: dag::TokenAction::Burn,
+                amount: amount - tax,
+                target: Some(recipient),
+                memo: Some("Multi-sig transfer".to_string()),
+                ref_cid: Some(proposal_cid.to_string()),
+            });
+            let Some(transfer_node) = self.publish_node("token:v1", payload) else { break };
+            let _ = self.event_tx.send(AppEvent::BlockReceived(transfer_node.clone()));
+            first_transfer.get_or_insert(transfer_node.id);
+            tax_total += tax;
+        }
+        let Some(transfer_cid) = first_transfer else { return };
+        if tax_total > 0 {
+            let payload = dag::DagPayload::Token(dag::TokenPayload {
+                action: dag::TokenAction::Burn,
+                amount: tax_total,
+                target: None, // Burn to network (System Tax)
+                memo: Some(format!("Tax ({}%)", tax_rate)),
+                ref_cid: Some(transfer_cid.clone()),
+            });
+            if let Some(tax_node) = self.publish_node("token:v1", payload) {
+                let _ = self.event_tx.send(AppEvent::BlockReceived(tax_node));
+            }
+        }
+        println!("Executed multi-sig transfer {} as {}", proposal_cid, transfer_cid);
+        let _ = self.event_tx.send(AppEvent::MultiSigExecuted { proposal_cid: proposal_cid.to_string(), transfer_cid });
+    }
+
+    /// A token node as seen by `peer_id`: incoming transfers name their sender, claims
+    /// name whoever sent the claimed transfer, and tax burns are tagged in the memo
+    fn transaction_record(&self, node: dag::DagNode, peer_id: &str) -> Option<TransactionRecord> {
+        let dag::DagPayload::Token(token) = node.payload else {
+            return None;
+        };
+        let is_tax = token.action == dag::TokenAction::Burn && token.target.is_none() && token.memo.as_deref().is_some_and(|m| m.starts_with("Tax ("));
+        let counterparty = if node.author != peer_id {
+            Some(node.author.clone())
+        } else if token.action == dag::TokenAction::TransferClaim {
+            token.ref_cid.as_deref().and_then(|cid| self.store.get_node(cid).ok().flatten()).map(|burn| burn.author)
+        } else {
+            token.target.clone()
+        };
+        let memo = if is_tax {
+            Some(format!("{}: {}", TAX_WITHHELD_MEMO, token.memo.unwrap_or_default()))
+        } else {
+            token.memo
+        };
+        Some(TransactionRecord {
+            cid: node.id,
+            action: token.action,
+            amount: token.amount,
+            counterparty,
+            memo,
+            timestamp: node.timestamp.timestamp() as u64,
+        })
+    }
+
+    /// Whether spending `amount` more stays within today's limit, if one is set.
+    /// Every outgoing path checks this, since all of them count toward `spent_today`.
+    fn within_spending_limit(&self, amount: u64) -> bool {
+        let Ok(Some(limit)) = self.store.get_spending_limit() else { return true };
+        let spent_today = self.store.get_total_spent_today(&self.local_peer_id().to_string()).unwrap_or(0);
+        if spent_today.saturating_add(amount) <= limit {
+            return true;
+        }
+        eprintln!("Cannot spend {}: it would exceed the daily limit of {} ({} spent today)", amount, limit, spent_today);
+        let _ = self.event_tx.send(AppEvent::SpendingLimitExceeded { attempted: amount, limit, spent_today });
+        false
+    }
+
+    fn send_spending_limit(&self) {
+        let author_hex = self.local_peer_id().to_string();
+        match (self.store.get_spending_limit(), self.store.get_total_spent_today(&author_hex)) {
+            (Ok(daily_limit), Ok(spent_today)) => {
+                let _ = self.event_tx.send(AppEvent::SpendingLimitFetched { daily_limit, spent_today });
+            }
+            (Err(e), _) | (_, Err(e)) => eprintln!("Failed to fetch spending limit: {:?}", e),
+        }
+    }
+
+    fn send_multisig_proposals(&self) {
+        match self.store.get_multisig_proposals(&self.local_peer_id().to_string()) {
+            Ok(records) => {
+                let _ = self.event_tx.send(AppEvent::MultiSigProposalsFetched(records));
+            }
+            Err(e) => eprintln!("Failed to fetch multi-sig proposals: {:?}", e),
+        }
+    }
+
+    /// Send our certifications, then warn about each type whose every cert has lapsed
+    /// or will within `CERTIFICATION_EXPIRY_WARNING_DAYS`
+    fn send_my_certifications(&self) {
+        let author_hex = self.local_peer_id().to_string();
+        let certs = match self.store.get_certifications(&author_hex) {
+            Ok(certs) => certs.into_iter().filter(|n| !self.is_cert_revoked(&n.id)).collect::<Vec<_>>(),
+            Err(e) => {
+                eprintln!("Failed to fetch my certifications: {:?}", e);
+                return;
+            }
+        };
+
+        // Latest expiry per type; None means some cert of that type never expires
+        let mut expiry: HashMap<String, Option<chrono::DateTime<chrono::Utc>>> = HashMap::new();
+        for node in &certs {
+            if let dag::DagPayload::Certification(cert) = &node.payload {
+                let latest = expiry.entry(cert.certification_type.clone()).or_insert(cert.expires_at);
+                *latest = match (*latest, cert.expires_at) {
+                    (Some(a), Some(b)) => Some(a.max(b)),
+                    _ => None,
+                };
+            }
+        }
+        let _ = self.event_tx.send(AppEvent::CertificationsFetched(certs));
+
+        let warn_after = chrono::Utc::now() + chrono::Duration::days(CERTIFICATION_EXPIRY_WARNING_DAYS);
+        for (certification_type, expires_at) in expiry {
+            if expires_at.is_some_and(|e| e < warn_after) {
+                let _ = self.event_tx.send(AppEvent::CertificationExpired { peer_id: author_hex.clone(), certification_type });
+            }
+        }
+    }
+
+    /// Re-issue one of our expiring certifications for another full term, provided its exam
+    /// was passed again after the cert was issued
+    fn renew_certification(&mut self, cert_cid: String) {
+        let author_hex = self.local_peer_id().to_string();
+        let cert = match self.store.get_node(&cert_cid) {
+            Ok(Some(dag::DagNode { payload: dag::DagPayload::Certification(cert), .. })) if cert.recipient == author_hex => cert,
+            _ => {
+                eprintln!("Cannot renew {}: not one of our certifications.", cert_cid);
+                return;
+            }
+        };
+        let (Some(exam_id), Some(expires_at)) = (&cert.exam_id, cert.expires_at) else {
+            eprintln!("Cannot renew {}: certification has no exam or never expires.", cert_cid);
+            return;
+        };
+        let retaken = self.store.get_exam_submissions(&author_hex).unwrap_or_default().iter().any(|n| {
+            n.timestamp > cert.issued_at && matches!(&n.payload, dag::DagPayload::ExamSubmission(s) if s.exam_id == *exam_id && s.passed)
+        });
+        if !retaken {
+            eprintln!("Cannot renew {}: pass exam {} again first.", cert_cid, exam_id);
+            return;
+        }
+
+        let issued_at = chrono::Utc::now();
+        let payload = dag::DagPayload::Certification(dag::CertificationPayload {
+            issuer_signatures: vec![],
+            issued_at,
+            expires_at: Some(issued_at + (expires_at - cert.issued_at)),
+            ..cert
+        });
+        let prev = match self.store.get_head(&author_hex) {
+            Ok(Some(cid)) => vec![cid],
+            _ => vec![],
+        };
+        match dag::DagNode::new("certification:v1".to_string(), payload, prev, &self.keypair, 0) {
+            Ok(node) => {
+                if let Err(e) = self.store.put_node(&node) {
+                    eprintln!("Failed to store renewed certification: {:?}", e);
+                    return;
+                }
+                if let Err(e) = self.store.update_head(&author_hex, &node.id) {
+                    eprintln!("Failed to update head: {:?}", e);
+                }
+                let topic = gossipsub::IdentTopic::new("blocks");
+                let _ = self.swarm.behaviour_mut().gossipsub.publish(topic, node.id.as_bytes());
+                let _ = self.event_tx.send(AppEvent::BlockReceived(node.clone()));
+                self.replicate_block(&node);
+                self.send_my_certifications();
+            }
+            Err(e) => eprintln!("Failed to create renewed certification: {:?}", e),
+        }
+    }
+
+    fn try_decrypt(&self, node: &dag::DagNode) -> String {
+        if let dag::DagPayload::Message(msg) = &node.payload {
+            // Ratchet keys are spent on first use, so anything already read comes from the local copy
+            if let Some(content) = self.load_message_copy(&node.id) {
+                return content;
+            }
+            let my_hex = self.local_peer_id().to_string();
+            
+            let plaintext = if let Some(key_id) = &msg.group_key_id {
+                self.group_decrypt(key_id, msg)
+            } else if msg.recipient == my_hex {
+                match msg.ratchet {
+                    Some(header) => self.ratchet_decrypt(&node.author, msg, header),
+                    None => self.legacy_decrypt(msg),
+                }
+            } else if node.author == my_hex {
+                // Sent before plaintexts were kept locally; the ephemeral key is gone
+                return "[Sent Message - Content Encrypted]".to_string();
+            } else {
+                return "[Not a message]".to_string();
+            };
+            if let Some(content) = plaintext {
+                self.save_message_copy(&node.id, &content);
+                return content;
+            }
+            return "[Decryption Failed]".to_string();
+        }
+        "[Not a message]".to_string()
+    }
+
+    /// Decrypt a group message with the group key it names
+    fn group_decrypt(&self, key_id: &str, msg: &dag::MessagePayload) -> Option<String> {
+        let key_node = self.store.get_node(key_id).ok().flatten()?;
+        if !matches!(&key_node.payload, dag::DagPayload::GroupKey(k) if Some(&k.group_id) == msg.group_id.as_ref()) {
+            return None;
+        }
+        let group_key = self.open_group_key(&key_node)?;
+        let cipher = Aes256Gcm::new(&aes_gcm::Key::<Aes256Gcm>::from(group_key));
+
+        let nonce_bytes: [u8; 12] = hex::decode(&msg.nonce).ok()?.try_into().ok()?;
+        let ciphertext_bytes = hex::decode(&msg.ciphertext).ok()?;
+        let plaintext = cipher.decrypt(&Nonce::from(nonce_bytes), ciphertext_bytes.as_ref()).ok()?;
+        Some(String::from_utf8_lossy(&plaintext).to_string())
+    }
+
+    /// Our copy of the session key in a `group_key:v1` node, if the group owner issued it to us
+    fn open_group_key(&self, key_node: &dag::DagNode) -> Option<[u8; 32]> {
+        let dag::DagPayload::GroupKey(payload) = &key_node.payload else {
+            return None;
+        };
+        match self.store.get_node(&payload.group_id) {
+            Ok(Some(dag::DagNode { payload: dag::DagPayload::Group(ref g), .. })) if g.owner == key_node.author => {}
+            _ => return None,
+        }
+        let my_hex = self.local_peer_id().to_string();
+        let sealed = payload.sealed_keys.iter().find(|k| k.member == my_hex)?;
+
+        let ephemeral_pub_bytes: [u8; 32] = hex::decode(&sealed.ephemeral_pubkey).ok()?.try_into().ok()?;
+        let shared_secret = self.encryption_keypair.diffie_hellman(&x25519_dalek::PublicKey::from(ephemeral_pub_bytes));
+        let cipher = Aes256Gcm::new(&aes_gcm::Key::<Aes256Gcm>::from(*shared_secret.as_bytes()));
+        let nonce_bytes: [u8; 12] = hex::decode(&sealed.nonce).ok()?.try_into().ok()?;
+        let ciphertext_bytes = hex::decode(&sealed.ciphertext).ok()?;
+        cipher.decrypt(&Nonce::from(nonce_bytes), ciphertext_bytes.as_ref()).ok()?.try_into().ok()
+    }
+
+    fn is_group_owner(&self, group_id: &str) -> bool {
+        let author_hex = self.local_peer_id().to_string();
+        matches!(self.store.get_node(group_id), Ok(Some(dag::DagNode { payload: dag::DagPayload::Group(ref g), .. })) if g.owner == author_hex)
+    }
+
+    /// Issue a fresh group session key, sealed for every current member with a published
+    /// encryption key. Only the group owner can do this.
+    fn publish_group_key(&mut self, group_id: &str) -> Option<dag::DagNode> {
+        if !self.is_group_owner(group_id) {
+            eprintln!("Cannot rotate group key: Only the group owner can issue keys.");
+            return None;
+        }
+        let members = match self.store.get_group_members(group_id) {
+            Ok(members) => members,
+            Err(e) => {
+                eprintln!("Failed to fetch group members: {:?}", e);
+                return None;
+            }
+        };
+
+        let author_hex = self.local_peer_id().to_string();
+        let mut group_key = [0u8; 32];
+        OsRng.fill(&mut group_key);
+        let mut sealed_keys = Vec::new();
+        for (member, _) in members {
+            let member_pk = if member == author_hex {
+                x25519_dalek::PublicKey::from(&self.encryption_keypair)
+            } else {
+                match self.peer_encryption_pubkey(&member) {
+                    Some(pk) => pk,
+                    None => {
+                        eprintln!("Group member {} has no encryption key published; skipping.", member);
+                        continue;
+                    }
+                }
+            };
+            let ephemeral_secret = x25519_dalek::StaticSecret::random_from_rng(OsRng);
+            let shared_secret = ephemeral_secret.diffie_hellman(&member_pk);
+            let cipher = Aes256Gcm::new(&aes_gcm::Key::<Aes256Gcm>::from(*shared_secret.as_bytes()));
+            let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+            match cipher.encrypt(&nonce, group_key.as_ref()) {
+                Ok(ciphertext) => sealed_keys.push(dag::SealedGroupKey {
+                    member,
+                    ephemeral_pubkey: hex::encode(x25519_dalek::PublicKey::from(&ephemeral_secret).to_bytes()),
+                    nonce: hex::encode(nonce),
+                    ciphertext: hex::encode(ciphertext),
+                }),
+                Err(e) => eprintln!("Failed to seal group key: {:?}", e),
+            }
+        }
+
+        let payload = dag::DagPayload::GroupKey(dag::GroupKeyPayload { group_id: group_id.to_string(), sealed_keys });
+        let prev = match self.store.get_head(&author_hex) {
+            Ok(Some(cid)) => vec![cid],
+            _ => vec![],
+        };
+        match dag::DagNode::new("group_key:v1".to_string(), payload, prev, &self.keypair, 0) {
+            Ok(node) => {
+                if let Err(e) = self.store.put_node(&node) {
+                    eprintln!("Failed to store group key node: {:?}", e);
+                    return None;
+                }
+                if let Err(e) = self.store.update_head(&author_hex, &node.id) {
+                    eprintln!("Failed to update head: {:?}", e);
+                }
+                let topic = gossipsub::IdentTopic::new("blocks");
+                if let Err(e) = self.swarm.behaviour_mut().gossipsub.publish(topic, node.id.as_bytes()) {
+                    eprintln!("Failed to publish group key CID: {:?}", e);
+                }
+                self.replicate_block(&node);
+                let _ = self.event_tx.send(AppEvent::GroupKeyRotated { group_id: group_id.to_string() });
+                Some(node)
+            }
+            Err(e) => {
+                eprintln!("Failed to create group key node: {:?}", e);
+                None
+            }
+        }
+    }
+
+    /// Messages from before Double Ratchet sessions: one ephemeral ECDH per message
+    fn legacy_decrypt(&self, msg: &dag::MessagePayload) -> Option<String> {
+        let ephemeral_pub_bytes: [u8; 32] = hex::decode(&msg.ephemeral_pubkey).ok()?.try_into().ok()?;
+        let ephemeral_pk = x25519_dalek::PublicKey::from(ephemeral_pub_bytes);
+        let shared_secret = self.encryption_keypair.diffie_hellman(&ephemeral_pk);
+        let key = Aes256Gcm::new(&aes_gcm::Key::<Aes256Gcm>::from(*shared_secret.as_bytes()));
+
+        let nonce_bytes: [u8; 12] = hex::decode(&msg.nonce).ok()?.try_into().ok()?;
+        let ciphertext_bytes = hex::decode(&msg.ciphertext).ok()?;
+        let plaintext = key.decrypt(&Nonce::from(nonce_bytes), ciphertext_bytes.as_ref()).ok()?;
+        Some(String::from_utf8_lossy(&plaintext).to_string())
+    }
+
+    /// Decrypt with the session for `sender`, starting one as responder if this is their first message.
+    /// A handshake is only accepted while no session exists, except when both sides started one
+    /// at once: then the peer with the lower ID stays the initiator and the other responds.
+    /// A sender who restored their identity since our session began starts over under a later
+    /// epoch, and their handshake then replaces the session.
+    fn ratchet_decrypt(&self, sender: &str, msg: &dag::MessagePayload, header: dag::RatchetHeader) -> Option<String> {
+        let ratchet_pubkey: [u8; 32] = hex::decode(&msg.ephemeral_pubkey).ok()?.try_into().ok()?;
+        let nonce = hex::decode(&msg.nonce).ok()?;
+        let ciphertext = hex::decode(&msg.ciphertext).ok()?;
+        let epoch = self.store.get_session_epoch().unwrap_or(0);
+
+        let mut state = match self.store.get_ratchet_state(sender).ok().flatten() {
+            Some(mut state) => match state.decrypt(ratchet_pubkey, header, &nonce, &ciphertext) {
+                Ok(plaintext) => return self.keep_ratchet(sender, &state, plaintext),
+                Err(_) if state.awaiting_reply() && sender < self.local_peer_id().to_string().as_str() => {
+                    ratchet::RatchetState::respond(&self.encryption_keypair, &self.peer_encryption_pubkey(sender)?, epoch)
+                }
+                Err(_) if !state.awaiting_reply() && state.superseded_by(&header) => {
+                    ratchet::RatchetState::respond(&self.encryption_keypair, &self.peer_encryption_pubkey(sender)?, epoch)
+                }
+                Err(e) => {
+                    eprintln!("Rejected message from {}: {}", sender, e);
+                    return None;
+                }
+            },
+            None => ratchet::RatchetState::respond(&self.encryption_keypair, &self.peer_encryption_pubkey(sender)?, epoch),
+        };
+        let plaintext = state.decrypt(ratchet_pubkey, header, &nonce, &ciphertext).ok()?;
+        self.keep_ratchet(sender, &state, plaintext)
+    }
+
+    /// Persist a session that just decrypted `plaintext`
+    fn keep_ratchet(&self, peer_id: &str, state: &ratchet::RatchetState, plaintext: Vec<u8>) -> Option<String> {
+        if let Err(e) = self.store.save_ratchet_state(peer_id, state) {
+            eprintln!("Failed to store ratchet state: {:?}", e);
+        }
+        Some(String::from_utf8_lossy(&plaintext).to_string())
+    }
+
+    /// Keep our own copy of a message we sent or read, sealed under our encryption key
+    fn save_message_copy(&self, cid: &str, content: &str) {
+        let saved = ratchet::seal_local(&self.encryption_keypair, content.as_bytes())
+            .map_err(|e| e.into())
+            .and_then(|sealed| self.store.save_message_copy(cid, &sealed));
+        if let Err(e) = saved {
+            eprintln!("Failed to store message copy: {:?}", e);
+        }
+    }
+
+    fn load_message_copy(&self, cid: &str) -> Option<String> {
+        let sealed = self.store.get_message_copy(cid).ok().flatten()?;
+        let plaintext = ratchet::open_local(&self.encryption_keypair, &sealed).ok()?;
+        Some(String::from_utf8_lossy(&plaintext).to_string())
+    }
+
+    /// The X25519 key a peer published in their profile
+    fn peer_encryption_pubkey(&self, peer_id: &str) -> Option<x25519_dalek::PublicKey> {
+        let profile = self.store.get_profile(peer_id).ok().flatten()?;
+        let bytes: [u8; 32] = hex::decode(profile.encryption_pubkey?).ok()?.try_into().ok()?;
+        Some(x25519_dalek::PublicKey::from(bytes))
+    }
+
+    /// A session we can send to `peer_id` on, and whether it was just started
+    fn sending_ratchet(&self, peer_id: &str) -> Option<(ratchet::RatchetState, bool)> {
+        if let Ok(Some(state)) = self.store.get_ratchet_state(peer_id) {
+            if state.can_send() {
+                return Some((state, false));
+            }
+        }
+        let remote = self.peer_encryption_pubkey(peer_id)?;
+        let epoch = self.store.get_session_epoch().unwrap_or(0);
+        Some((ratchet::RatchetState::initiate(&self.encryption_keypair, &remote, epoch), true))
+    }
+
+    fn replicate_block(&mut self, node: &dag::DagNode) {
+        let connected_peers: Vec<PeerId> = self.swarm.connected_peers().cloned().collect();
+        let connected_count = connected_peers.len();
+        
+        let target_replication_count = 10;
+        // Followers- and group-only posts are withheld from peers outside the author's audience
+        // Read receipts only go to the author of the message they acknowledge
+        let target_peers: Vec<PeerId> = match self.post_audience(node).or_else(|| self.receipt_audience(node)) {
+            Some(audience) => connected_peers.iter().filter(|p| audience.contains(&p.to_string())).cloned().collect(),
+            None => connected_peers.clone(),
+        };
+
+        // If we don't have enough connected peers, try to find more via DHT
+        if connected_count < target_replication_count {
+            println!("Not enough connected peers ({}/{}), querying DHT...", connected_count, target_replication_count);
+            self.swarm.behaviour_mut().kad.get_closest_peers(node.id.as_bytes().to_vec());
+            
+            // Add to pending replications to retry later
+            if !self.pending_replications.contains_key(&node.id) {
+                self.pending_replications.insert(node.id.clone(), (node.clone(), std::time::Instant::now()));
+                println!("Added block {} to pending replication queue", node.id);
+            }
+        } else {
+            // We have enough peers, remove from pending if it was there
+            self.pending_replications.remove(&node.id);
+        }
+
+        // Most reliable peers first, so poorly scored ones only fill leftover slots
+        let mut target_peers = target_peers;
+        target_peers.sort_by(|a, b| self.peer_reliability(b).total_cmp(&self.peer_reliability(a)));
+
+        // Send to available peers anyway, skipping those whose sync filter says they have it
+        let (peers_to_send, peers_with_block): (Vec<PeerId>, Vec<PeerId>) = target_peers.into_iter()
+            .take(target_replication_count)
+            .partition(|p| !self.peer_sync_filters.get(p).is_some_and(|filter| filter.check(&node.id)));
+
+        let data = match serde_json::to_vec(node) {
+            Ok(d) => d,
+            Err(e) => {
+                eprintln!("Failed to serialize node for replication: {:?}", e);
+                return;
+            }
+        };
+
+        if !peers_with_block.is_empty() {
+             println!("Skipping replication of {} to {} peers that already have it", node.id, peers_with_block.len());
+             let _ = self.store.record_sync_savings(data.len() * peers_with_block.len());
+        }
+        
+        if !peers_to_send.is_empty() {
+             println!("Replicating block {} to {} peers", node.id, peers_to_send.len());
+
+             let request = BlockRequest::Store(data);
+             
+             for peer in peers_to_send {
+                 self.send_block_request(&peer, request.clone());
+             }
+        }
+    }
+
+    /// Send a request, counting its payload towards outbound traffic
+    fn send_block_request(&mut self, peer: &PeerId, request: BlockRequest) -> OutboundRequestId {
+        self.outbound_bytes += request.payload_len() as u64;
+        let request_id = self.swarm.behaviour_mut().request_response.send_request(peer, request);
+        self.request_sent_at.insert(request_id, std::time::Instant::now());
+        request_id
+    }
+
+    fn peer_reliability(&self, peer: &PeerId) -> f64 {
+        self.peer_scores.get(peer).map(PeerScore::reliability).unwrap_or(0.5)
+    }
+
+    /// Count an ack or nack against `peer`, folding the response time into its latency average
+    fn record_peer_response(&mut self, peer: PeerId, acked: bool, sent_at: Option<std::time::Instant>) {
+        let score = self.peer_scores.entry(peer).or_default();
+        if let Some(sent_at) = sent_at {
+            let latency_ms = u64::try_from(sent_at.elapsed().as_millis()).unwrap_or(u64::MAX);
+            let average = if score.cid_acks + score.cid_nacks == 0 {
+                latency_ms
+            } else {
+                (u64::from(score.latency_ms_avg) * 3 + latency_ms) / 4
+            };
+            score.latency_ms_avg = u32::try_from(average).unwrap_or(u32::MAX);
+        }
+        if acked {
+            score.cid_acks += 1;
+        } else {
+            score.cid_nacks += 1;
+        }
+    }
+
+    /// Shrink every peer's counts by 10%, forgetting peers with nothing left
+    fn decay_peer_scores(&mut self) {
+        for score in self.peer_scores.values_mut() {
+            score.cid_acks = score.cid_acks * 9 / 10;
+            score.cid_nacks = score.cid_nacks * 9 / 10;
+        }
+        self.peer_scores.retain(|_, score| score.cid_acks + score.cid_nacks > 0);
+        self.last_score_decay = std::time::Instant::now();
+    }
+
+    /// Answer a request, counting the response payload towards outbound traffic
+    fn send_block_response(&mut self, channel: request_response::ResponseChannel<BlockResponse>, response: BlockResponse) -> Result<(), BlockResponse> {
+        self.outbound_bytes += response.payload_len() as u64;
+        self.swarm.behaviour_mut().request_response.send_response(channel, response)
+    }
+
+    fn network_diagnostics(&mut self) -> NetworkDiagnostics {
+        let kad_routing_table_size = self.swarm.behaviour_mut().kad.kbuckets().map(|bucket| bucket.num_entries()).sum();
+        NetworkDiagnostics {
+            connected_peers: self.swarm.connected_peers().count(),
+            inbound_bytes: self.inbound_bytes,
+            outbound_bytes: self.outbound_bytes,
+            kad_routing_table_size,
+            gossipsub_mesh_size: self.swarm.behaviour().gossipsub.all_mesh_peers().count(),
+            pending_replications: self.pending_replications.len(),
+            timed_out_replications: self.timed_out_replications,
+        }
+    }
+
+    /// Start a filter sync with `peer` unless its Merkle root shows we already hold the same blocks
+    fn compare_store_root(&mut self, peer: PeerId, root: Vec<u8>) {
+        match self.store.compute_merkle_root() {
+            Ok(ours) if ours.as_slice() == root.as_slice() => println!("Store root matches {}, skipping filter sync", peer),
+            _ => {
+                match self.store.build_bloom_filter() {
+                    Ok(filter) => {
+                        self.send_block_request(&peer, BlockRequest::SyncFilter(filter));
+                    }
+                    Err(e) => eprintln!("Failed to build sync filter: {:?}", e),
+                }
+            }
+        }
+    }
+
+    fn remember_sync_filter(&mut self, peer: PeerId, filter: Vec<u8>) {
+        match bloomfilter::Bloom::from_bytes(filter) {
+            Ok(bloom) => {
+                self.peer_sync_filters.insert(peer, bloom);
+            }
+            Err(e) => eprintln!("Ignoring malformed sync filter from {}: {}", peer, e),
+        }
+    }
+
+    /// Start paging through everything `peer` has stored since `since`
+    fn request_full_sync(&mut self, peer_id: &str, since: u64) {
+        match peer_id.parse::<PeerId>() {
+            Ok(peer) => {
+                println!("Requesting full sync from {} since {}", peer, since);
+                let request_id = self.send_block_request(&peer, BlockRequest::SyncFrom { since_timestamp: since, after_cid: None });
+                self.full_syncs.insert(peer, (request_id, None, 0));
+            }
+            Err(e) => eprintln!("Invalid peer ID {}: {:?}", peer_id, e),
+        }
+    }
+
+    /// Store the verified, new nodes of a full-sync page and ask for the next one.
+    /// The total is estimated by extrapolating from how much of the time span we have covered.
+    /// Nodes arrive as plain JSON, as in block responses, rather than the store's compressed encoding.
+    fn handle_sync_batch(&mut self, peer: PeerId, nodes: Vec<Vec<u8>>, has_more: bool, next_cursor: u64, next_cid: Option<String>) {
+        let Some((_, mut oldest, mut received)) = self.full_syncs.get(&peer).copied() else {
+            println!("Ignoring unrequested sync batch from {}", peer);
+            return;
+        };
+        for data in nodes {
+            let Ok(node) = serde_json::from_slice::<dag::DagNode>(&data) else { continue };
+            oldest = oldest.or(u64::try_from(node.timestamp.timestamp()).ok());
+            if !matches!(node.verify(), Ok(true)) || matches!(self.store.get_node(&node.id), Ok(Some(_))) {
+                continue;
+            }
+            match self.store.put_node(&node) {
+                Ok(()) => {
+                    self.apply_pin_policy(&node);
+                    received += 1;
+                }
+                Err(e) => eprintln!("Failed to store synced node {}: {:?}", node.id, e),
+            }
+        }
+
+        if has_more {
+            let now = chrono::Utc::now().timestamp() as u64;
+            let estimated_total = oldest
+                .filter(|oldest| next_cursor > *oldest && now > next_cursor)
+                .map(|oldest| (received as u64 * (now - oldest) / (next_cursor - oldest)) as usize);
+            let _ = self.event_tx.send(AppEvent::SyncProgress { received, estimated_total });
+            let request_id = self.send_block_request(&peer, BlockRequest::SyncFrom { since_timestamp: next_cursor, after_cid: next_cid });
+            self.full_syncs.insert(peer, (request_id, oldest, received));
+        } else {
+            self.full_syncs.remove(&peer);
+            println!("Full sync from {} stored {} new nodes", peer, received);
+            let _ = self.event_tx.send(AppEvent::SyncComplete { received });
+        }
+    }
+
+    /// The nodes of a full-sync page that `requester` may hold, serialized for `BlockResponse::SyncBatch`.
+    /// Restricted posts only go to their audience, messages to their participants and read
+    /// receipts to the message's sender, the same as when they are replicated.
+    fn sync_batch_nodes(&self, batch: &[dag::DagNode], requester: &str) -> Vec<Vec<u8>> {
+        let post_ids: Vec<String> = batch.iter().filter_map(Store::visibility_post_id).map(str::to_string).collect();
+        let Ok(visibilities) = self.store.get_post_visibilities(&post_ids) else {
+            return Vec::new();
+        };
+        // Follower and member lists each take a store scan, so look each one up once per page
+        let mut following: std::collections::HashMap<String, bool> = std::collections::HashMap::new();
+        let mut follows = |author: &str| *following.entry(author.to_string())
+            .or_insert_with(|| self.store.get_followers(author).unwrap_or_default().iter().any(|f| f == requester));
+        let mut membership: std::collections::HashMap<String, bool> = std::collections::HashMap::new();
+        let mut is_member = |group_id: &str| *membership.entry(group_id.to_string())
+            .or_insert_with(|| self.store.get_group_members(group_id).unwrap_or_default().iter().any(|(m, _)| m == requester));
+
+        let mut nodes = Vec::new();
+        for node in batch {
+            let allowed = node.author == requester || match &node.payload {
+                dag::DagPayload::Message(msg) => match &msg.group_id {
+                    Some(group_id) => is_member(group_id),
+                    None => msg.recipient == requester,
+                },
+                dag::DagPayload::MessageReceipt(_) => self.receipt_audience(node).is_some_and(|audience| audience.contains(requester)),
+                _ => match Store::visibility_post_id(node).and_then(|post_id| visibilities.get(post_id)) {
+                    None | Some(dag::PostVisibility::Public) => true,
+                    Some(dag::PostVisibility::FollowersOnly) => follows(&node.author),
+                    Some(dag::PostVisibility::MembersOf(group_id)) => is_member(group_id),
+                },
+            };
+            if let Some(data) = allowed.then(|| serde_json::to_vec(node).ok()).flatten() {
+                nodes.push(data);
+            }
+        }
+        nodes
+    }
+
+    /// If `node` is a read receipt, the sender of the message it acknowledges
+    fn receipt_audience(&self, node: &dag::DagNode) -> Option<std::collections::HashSet<String>> {
+        let dag::DagPayload::MessageReceipt(receipt) = &node.payload else {
+            return None;
+        };
+        let mut audience = std::collections::HashSet::new();
+        if let Ok(Some(message)) = self.store.get_node(&receipt.message_cid) {
+            audience.insert(message.author);
+        }
+        Some(audience)
+    }
+
+    /// Restore an exported identity: write its key files, then rebuild the swarm under it
+    fn import_identity(&mut self, bundle_b64: &str, password: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let bundle = general_purpose::STANDARD.decode(bundle_b64.trim()).map_err(|_| "Bundle is not valid Base64")?;
+        let (keypair, encryption_key) = identity::import_bundle(&bundle, password)?;
+        let swarm = Self::start_swarm(&keypair)?;
+
+        if self.owns_key_files {
+            identity::save_identity(Path::new(IDENTITY_FILE), &keypair)?;
+            std::fs::write(ENCRYPTION_KEY_FILE, encryption_key)?;
+        }
+        // Sessions were keyed to the old encryption key, so peers must start new ones
+        self.store.clear_ratchet_sessions()?;
+
+        self.swarm = swarm;
+        self.keypair = keypair;
+        self.encryption_keypair = x25519_dalek::StaticSecret::from(encryption_key);
+        // Requests and peer state belong to the old swarm's connections
+        self.pending_requests.clear();
+        self.request_sent_at.clear();
+        self.full_syncs.clear();
+        self.peer_sync_filters.clear();
+        self.relays.clear();
+        self.pending_dials.clear();
+        self.join_network();
+
+        let peer_id = self.local_peer_id().to_string();
+        println!("Restored identity {}", peer_id);
+        let _ = self.event_tx.send(AppEvent::MyIdentity(peer_id));
+        Ok(())
+    }
+
+    /// Pin a received block if its type is one the user keeps through vacuuming.
+    /// The pinned list on screen is refreshed only when a pin was actually added.
+    fn apply_pin_policy(&self, node: &dag::DagNode) {
+        let node_type = node.r#type.split(':').next().unwrap_or_default();
+        if !self.pin_policy.contains(node_type) || self.store.is_pinned(&node.id).unwrap_or(true) {
+            return;
+        }
+        match self.store.pin_block(&node.id) {
+            Ok(()) => self.send_pinned_blocks(),
+            Err(e) => eprintln!("Failed to pin {}: {:?}", node.id, e),
+        }
+    }
+
+    fn send_pinned_blocks(&self) {
+        match self.store.get_pinned_blocks() {
+            Ok(cids) => {
+                let _ = self.event_tx.send(AppEvent::PinnedBlocksFetched(cids));
+            }
+            Err(e) => eprintln!("Failed to fetch pinned blocks: {:?}", e),
+        }
+    }
+
+    fn send_storage_stats(&self) {
+        match self.store.get_storage_stats() {
+            Ok(stats) => {
+                let _ = self.event_tx.send(AppEvent::StorageStatsFetched {
+                    block_count: stats.total_nodes,
+                    total_bytes: stats.total_bytes,
+                    compression_level: self.store.get_compression_level().unwrap_or(0),
+                    compression_ratio: stats.compression.ratio,
+                    saved_bytes: stats.compression.saved_bytes,
+                    sync_bytes_saved: stats.sync_bytes_saved,
+                });
+            }
+            Err(e) => eprintln!("Failed to get storage stats: {:?}", e),
+        }
+    }
+
+    /// Posts tagged with `tag` that we may see, newest first, looked up in the hashtag index
+    fn send_hashtag_posts(&self, tag: &str) {
+        let my_pubkey = self.local_peer_id().to_string();
+        match self.store.get_posts_by_hashtags(&[tag.to_string()], 50, &my_pubkey) {
+            Ok(posts) => {
+                let _ = self.event_tx.send(AppEvent::HashtagPostsFetched(posts));
+            }
+            Err(e) => eprintln!("Failed to fetch posts for hashtag {}: {:?}", tag, e),
+        }
+    }
+
+    /// The event for a receipt acknowledging one of our messages, from that message's recipient
+    fn receipt_event(&self, node: &dag::DagNode) -> Option<AppEvent> {
+        let dag::DagPayload::MessageReceipt(receipt) = &node.payload else {
+            return None;
+        };
+        match self.store.get_node(&receipt.message_cid) {
+            Ok(Some(dag::DagNode { author, payload: dag::DagPayload::Message(msg), .. }))
+                if author == self.local_peer_id().to_string() && msg.recipient == node.author =>
+            {
+                Some(AppEvent::ReceiptReceived { message_cid: receipt.message_cid.clone(), reader_peer_id: node.author.clone(), timestamp: receipt.read_at })
+            }
+            _ => None,
+        }
+    }
+
+    /// Acknowledge a direct message addressed to us. The receipt is pushed to the sender only,
+    /// never gossiped, and sent at most once per message.
+    fn process_mark_message_read(&mut self, message_cid: String) {
+        let my_hex = self.local_peer_id().to_string();
+        match self.store.get_node(&message_cid) {
+            Ok(Some(dag::DagNode { payload: dag::DagPayload::Message(msg), .. })) if msg.recipient == my_hex && msg.group_id.is_none() => {}
+            Ok(_) => {
+                eprintln!("Cannot mark message read: {} is not a direct message to us.", message_cid);
+                return;
+            }
+            Err(e) => {
+                eprintln!("Failed to load message {}: {:?}", message_cid, e);
+                return;
+            }
+        }
+        if self.store.get_receipts_for_message(&message_cid).map(|r| !r.is_empty()).unwrap_or(false) {
+            return;
+        }
+
+        let payload = dag::DagPayload::MessageReceipt(dag::MessageReceiptPayload {
+            message_cid: message_cid.clone(),
+            read_at: chrono::Utc::now().timestamp() as u64,
+        });
+        // Linked to the message rather than our head, so the receipt stays off our public chain
+        match dag::DagNode::new("message_receipt:v1".to_string(), payload, vec![message_cid], &self.keypair, 0) {
+            Ok(node) => {
+                if let Err(e) = self.store.put_node(&node) {
+                    eprintln!("Failed to store read receipt: {:?}", e);
+                    return;
+                }
+                self.replicate_block(&node);
+            }
+            Err(e) => eprintln!("Failed to create read receipt node: {:?}", e),
+        }
+    }
+
+    /// If `node` is (or edits, or restricts) a post shared with followers or a group only, the set
+    /// of peers allowed to hold it. Edits are judged by their original post, which is what
+    /// visibility settings target.
+    fn post_audience(&self, node: &dag::DagNode) -> Option<std::collections::HashSet<String>> {
+        let post_id = Store::visibility_post_id(node)?;
+        let visibility = self.store.get_post_visibility(post_id).ok()?;
+        self.visibility_audience(&node.author, &visibility)
+    }
+
+    /// The author plus everyone a post with `visibility` is shared with, or None when it is public
+    fn visibility_audience(&self, author: &str, visibility: &dag::PostVisibility) -> Option<std::collections::HashSet<String>> {
+        let mut audience: std::collections::HashSet<String> = match visibility {
+            dag::PostVisibility::Public => return None,
+            dag::PostVisibility::FollowersOnly => self.store.get_followers(author).unwrap_or_default().into_iter().collect(),
+            dag::PostVisibility::MembersOf(group_id) => self.store.get_group_members(group_id).unwrap_or_default().into_iter().map(|(member, _)| member).collect(),
+        };
+        audience.insert(author.to_string());
+        Some(audience)
+    }
+
+    /// Record an anonymised view of someone else's web page
+    /// Create, store and publish a contract node, optionally recording the template it came from
+    /// and the earlier version it upgrades
+    fn deploy_contract(&mut self, code: String, init_params: String, template_id: Option<String>, parent_contract_id: Option<String>) {
+         if !self.is_caller_verified() {
+            eprintln!("Cannot deploy contract: User is not verified.");
+            return;
+        }
+        let payload = dag::DagPayload::Contract(dag::ContractPayload { 
+            code, 
+            init_params,
+            status: dag::ContractStatus::Pending,
+            template_id,
+            parent_contract_id,
+        });
+        let author_pubkey = self.keypair.public();
+        let author_hex = libp2p::PeerId::from_public_key(&author_pubkey).to_string();
+         let prev = match self.store.get_head(&author_hex) {
+            Ok(Some(cid)) => vec![cid],
+            Ok(None) => vec![],
+            Err(e) => {
+                eprintln!("Failed to get head: {:?}", e);
+                vec![]
+            }
+        };
+
+        match dag::DagNode::new(
+            "contract:v1".to_string(),
+            payload,
+            prev,
+            &self.keypair,
+            0
+        ) {
+            Ok(node) => {
+                println!("Created contract node: {}", node.id);
+                 if let Err(e) = self.store.put_node(&node) {
+                    eprintln!("Failed to store contract node: {:?}", e);
+                    return;
+                }
+                 if let Err(e) = self.store.update_head(&author_hex, &node.id) {
+                    eprintln!("Failed to update head: {:?}", e);
+                }
+                let topic = gossipsub::IdentTopic::new("blocks");
+                if let Err(e) = self.swarm.behaviour_mut().gossipsub.publish(topic, node.id.as_bytes()) {
+                    eprintln!("Failed to publish contract CID: {:?}", e);
+                }
+                self.replicate_block(&node);
+                
+                // Treat as block received to update UI if needed
+                let _ = self.event_tx.send(AppEvent::BlockReceived(node.clone()));
+            }
+            Err(e) => eprintln!("Failed to create contract node: {:?}", e),
+        }
+    }
+
+    /// Publish a proposal_outcome node the first time a proposal is seen to have passed
+    fn record_proposal_outcome(&mut self, proposal_id: &str) {
+        if !self.store.get_proposal_outcomes(Some(proposal_id)).unwrap_or_default().is_empty() {
+            return;
+        }
+        let proposal = match self.store.get_node(proposal_id) {
+            Ok(Some(node)) => node,
+            _ => return,
+        };
+        let (action_taken, previous_value, new_value) = match self.store.describe_proposal_enactment(&proposal) {
+            Ok(description) => description,
+            Err(e) => {
+                eprintln!("Failed to describe proposal outcome: {:?}", e);
+                return;
+            }
+        };
+
+        let author_hex = self.local_peer_id().to_string();
+        let prev = match self.store.get_head(&author_hex) {
+            Ok(Some(cid)) => vec![cid],
+            _ => vec![],
+        };
+        let payload = dag::DagPayload::ProposalOutcome(dag::ProposalOutcomePayload {
+            proposal_id: proposal_id.to_string(),
+            action_taken,
+            previous_value,
+            new_value,
+            enacted_at: chrono::Utc::now().timestamp() as u64,
+        });
+        match dag::DagNode::new("proposal_outcome:v1".to_string(), payload, prev, &self.keypair, 0) {
+            Ok(node) => {
+                if let Err(e) = self.store.put_node(&node) {
+                    eprintln!("Failed to store proposal outcome: {:?}", e);
+                    return;
+                }
+                let _ = self.store.update_head(&author_hex, &node.id);
+                let topic = gossipsub::IdentTopic::new("blocks");
+                let _ = self.swarm.behaviour_mut().gossipsub.publish(topic, node.id.as_bytes());
+                self.replicate_block(&node);
+                let _ = self.event_tx.send(AppEvent::BlockReceived(node.clone()));
+                let _ = self.event_tx.send(AppEvent::ProposalOutcomesFetched(vec![node]));
+            }
+            Err(e) => eprintln!("Failed to create proposal outcome node: {:?}", e),
+        }
+    }
+
+    /// Report the state of a contract's latest escrow, if it has one
+    fn send_escrow_status(&self, contract_id: String) {
+        match self.store.get_contract_escrow(&contract_id) {
+            Ok(Some((node, status))) => {
+                if let dag::DagPayload::Escrow(e) = node.payload {
+                    let _ = self.event_tx.send(AppEvent::EscrowStatusFetched { contract_id, locked_amount: e.amount, status, counterparty: e.counterparty });
+                }
+            }
+            Ok(None) => {}
+            Err(e) => eprintln!("Failed to fetch escrow status: {:?}", e),
+        }
+    }
+
+    /// Serve a block from the local store, or request it from a peer or the DHT
+    fn fetch_block(&mut self, cid: String, peer_id: Option<String>) {
+        // 1. Check local store
+        if let Ok(Some(node)) = self.store.get_node(&cid) {
+             let _ = self.event_tx.send(AppEvent::BlockFetched { cid, node: Some(node) });
+             return;
+        }
+        
+        // 2. Fetch from peer or DHT
+        let do_dht = if let Some(pid_str) = peer_id {
+            if pid_str.is_empty() {
+                true
+            } else {
+                if let Ok(peer) = pid_str.parse::<PeerId>() {
+                    let request_id = self.send_block_request(&peer, BlockRequest::Fetch(cid.clone()));
+                    self.pending_requests.insert(request_id, cid.clone());
+                    false
+                } else {
+                    eprintln!("Invalid peer id: {}", pid_str);
+                    true
+                }
+            }
+        } else {
+            true
+        };
+
+        if do_dht {
+             // Query DHT
+             println!("Querying DHT for block {}", cid);
+             let key = kad::RecordKey::new(&cid.as_bytes());
+             self.swarm.behaviour_mut().kad.get_providers(key);
+        }
+    }
+
+    fn record_web_view(&mut self, url: &str) {
+        use sha2::{Sha256, Digest};
+        let author_pubkey = self.keypair.public();
+        let author_hex = libp2p::PeerId::from_public_key(&author_pubkey).to_string();
+
+        // Authors browsing their own pages don't count
+        if let Ok(Some(page)) = self.store.get_web_page_node(url) {
+            if page.author == author_hex {
+                return;
+            }
+        }
+
+        let mut hasher = Sha256::new();
+        hasher.update(author_hex.as_bytes());
+        let viewer_peer_id_hash = hex::encode(hasher.finalize());
+
+        let payload = dag::DagPayload::WebView(dag::WebViewPayload { url: url.to_string(), viewer_peer_id_hash });
+        let prev = match self.store.get_head(&author_hex) {
+            Ok(Some(cid)) => vec![cid],
+            Ok(None) => vec![],
+            Err(e) => {
+                eprintln!("Failed to get head: {:?}", e);
+                vec![]
+            }
+        };
+
+        match dag::DagNode::new("web_view:v1".to_string(), payload, prev, &self.keypair, 0) {
+            Ok(node) => {
+                if let Err(e) = self.store.put_node(&node) {
+                    eprintln!("Failed to store web view node: {:?}", e);
+                    return;
+                }
+                if let Err(e) = self.store.update_head(&author_hex, &node.id) {
+                    eprintln!("Failed to update head: {:?}", e);
+                }
+                let topic = gossipsub::IdentTopic::new("blocks");
+                if let Err(e) = self.swarm.behaviour_mut().gossipsub.publish(topic, node.id.as_bytes()) {
+                    eprintln!("Failed to publish web view CID: {:?}", e);
+                }
+                self.replicate_block(&node);
+            }
+            Err(e) => eprintln!("Failed to create web view node: {:?}", e),
+        }
+    }
+
+    fn check_pending_replications(&mut self) {
+        let now = std::time::Instant::now();
+        let timeout = Duration::from_secs(60); // Stop trying after 60 seconds
+        
+        let mut to_remove = Vec::new();
+        let mut to_evict = Vec::new();
+        let mut to_retry = Vec::new();
+
+        for (id, (node, started)) in &self.pending_replications {
+            if self.evicted_blocks.contains(id) {
+                to_evict.push(id.clone());
+            } else if now.duration_since(*started) > timeout {
+                to_remove.push(id.clone());
+                println!("Replication timed out for block {}", id);
+            } else {
+                to_retry.push(node.clone());
+            }
+        }
+
+        self.timed_out_replications += to_remove.len();
+        for id in to_remove.into_iter().chain(to_evict) {
+            self.pending_replications.remove(&id);
+        }
+
+        for node in to_retry {
+            // This will check connected peers again and potentially re-send and re-query DHT
+            self.replicate_block(&node);
+        }
+
+        self.check_peer_count();
+    }
+
+    async fn process_publish_post(&mut self, content: String, attachments: Vec<String>, geohash: Option<String>, announcement: bool, quote_of: Option<String>, publish_at: Option<u64>) {
+        if !self.is_caller_verified() {
+            eprintln!("Cannot publish post: User is not verified.");
+            return;
+        }
+        
+        // Permission check for announcements
+        if announcement {
+            let author_pubkey = self.keypair.public();
+            let author_hex = libp2p::PeerId::from_public_key(&author_pubkey).to_string();
+            let officials = self.store.get_active_officials().unwrap_or_default();
+            if !officials.values().any(|p| p == &author_hex) {
+                eprintln!("Cannot publish announcement: User is not an elected official.");
+                return;
+            }
+        }
+        
+        let payload = dag::DagPayload::Post(dag::PostPayload { content, attachments, geohash, announcement, quote_of, publish_at });
+
+        // Posts for later are held locally; nothing is gossiped until the scheduler fires
+        if let Some(publish_at) = publish_at.filter(|t| *t > chrono::Utc::now().timestamp() as u64) {
+            match dag::DagNode::new("scheduled_post:v1".to_string(), payload, vec![], &self.keypair, 0) {
+                Ok(node) => {
+                    if let Err(e) = self.store.schedule_post(&node, publish_at) {
+                        eprintln!("Failed to schedule post: {:?}", e);
+                    }
+                    self.send_scheduled_posts();
+                }
+                Err(e) => eprintln!("Failed to create scheduled post node: {:?}", e),
+            }
+            return;
+        }
+        self.publish_post_payload(payload);
+    }
+
+    /// Publish a post payload as a `post:v1` node on our chain
+    fn publish_post_payload(&mut self, payload: dag::DagPayload) {
+        // Get previous head for this user if any
+        let author_pubkey = self.keypair.public();
+        let author_hex = libp2p::PeerId::from_public_key(&author_pubkey).to_string();
+        
+        let prev = match self.store.get_head(&author_hex) {
+            Ok(Some(cid)) => vec![cid],
+            Ok(None) => vec![],
+            Err(e) => {
+                eprintln!("Failed to get head: {:?}", e);
+                vec![]
+            }
+        };
+
+        match dag::DagNode::new(
+            "post:v1".to_string(),
+            payload,
+            prev,
+            &self.keypair,
+            0
+        ) {
+            Ok(node) => {
+                println!("Created post node: {}", node.id);
+                // 1. Store locally
+                if let Err(e) = self.store.put_node(&node) {
+                    eprintln!("Failed to store post node: {:?}", e);
+                    return;
+                }
+                
+                // 2. Update head
+                if let Err(e) = self.store.update_head(&author_hex, &node.id) {
+                    eprintln!("Failed to update head: {:?}", e);
+                }
+
+                // 3. Publish CID to gossipsub
+                let topic = gossipsub::IdentTopic::new("blocks");
+                if let Err(e) = self.swarm.behaviour_mut().gossipsub.publish(topic, node.id.as_bytes()) {
+                    eprintln!("Failed to publish post CID: {:?}", e);
+                }
+                
+                // 4. Notify frontend so it can display immediately
+                let _ = self.event_tx.send(AppEvent::BlockReceived(node.clone()));
+                
+                // 5. Replicate
+                self.replicate_block(&node);
+            }
+            Err(e) => eprintln!("Failed to create post node: {:?}", e),
+        }
+    }
+
+    /// Publish every scheduled post whose time has come
+    fn publish_due_posts(&mut self) {
+        let now = chrono::Utc::now().timestamp() as u64;
+        let due = match self.store.take_due_scheduled_posts(now) {
+            Ok(due) => due,
+            Err(e) => {
+                eprintln!("Failed to load scheduled posts: {:?}", e);
+                return;
+            }
+        };
+        if due.is_empty() {
+            return;
+        }
+        for node in due {
+            println!("Publishing scheduled post {}", node.id);
+            self.publish_post_payload(node.payload);
+        }
+        self.send_scheduled_posts();
+    }
+
+    fn send_scheduled_posts(&self) {
+        match self.store.get_scheduled_posts() {
+            Ok(posts) => {
+                let _ = self.event_tx.send(AppEvent::ScheduledPostsFetched(posts));
+            }
+            Err(e) => eprintln!("Failed to fetch scheduled posts: {:?}", e),
+        }
+    }
+
+    /// Publish a `post_delete:v1` tombstone for one of our own posts. Edits resolve to
+    /// the original post, which is what gets suppressed.
+    fn process_delete_post(&mut self, cid: String, reason: Option<String>) {
+        let author_hex = self.local_peer_id().to_string();
+        let original = match self.store.get_original_post(&cid) {
+            Ok(Some(node)) => node,
+            Ok(None) => {
+                eprintln!("Cannot delete post: {} not found.", cid);
+                return;
+            }
+            Err(e) => {
+                eprintln!("Failed to load post {}: {:?}", cid, e);
+                return;
+            }
+        };
+        if original.author != author_hex {
+            eprintln!("Cannot delete post: Only the original author can delete it.");
+            return;
+        }
+
+        let payload = dag::DagPayload::PostDelete(dag::PostDeletePayload { target_cid: original.id.clone(), reason });
+        if self.publish_node("post_delete:v1", payload).is_some() {
+            let _ = self.event_tx.send(AppEvent::PostDeleted { cid: original.id });
+        }
+    }
+
+    /// Repost someone's post to our followers as a `share:v1` node. Edits resolve to the original post.
+    fn process_share_post(&mut self, original_cid: String, comment: Option<String>) {
+        if !self.is_caller_verified() {
+            eprintln!("Cannot share post: User is not verified.");
+            return;
+        }
+        let original = match self.store.get_original_post(&original_cid) {
+            Ok(Some(node)) => node,
+            Ok(None) => {
+                eprintln!("Cannot share post: {} not found.", original_cid);
+                return;
+            }
+            Err(e) => {
+                eprintln!("Failed to load post {}: {:?}", original_cid, e);
+                return;
+            }
+        };
+
+        let comment = comment.map(|c| c.trim().to_string()).filter(|c| !c.is_empty());
+        let payload = dag::DagPayload::Share(dag::SharePayload { original_cid: original.id.clone(), comment });
+        if let Some(node) = self.publish_node("share:v1", payload) {
+            let _ = self.event_tx.send(AppEvent::BlockReceived(node));
+            if let Ok(count) = self.store.get_share_count(&original.id) {
+                let _ = self.event_tx.send(AppEvent::SharesFetched { cid: original.id, count });
+            }
+        }
+    }
+
+    /// Publish a `poll:v1` node to the feed. Needs a question and at least two distinct options
+    fn create_poll(&mut self, question: String, options: Vec<String>, closes_at: Option<u64>, allow_multiple: bool) {
+        if !self.is_caller_verified() {
+            eprintln!("Cannot create poll: User is not verified.");
+            return;
+        }
+        let question = question.trim().to_string();
+        let mut seen = std::collections::HashSet::new();
+        let options: Vec<String> = options.into_iter()
+            .map(|o| o.trim().to_string())
+            .filter(|o| !o.is_empty() && seen.insert(o.clone()))
+            .collect();
+        if question.is_empty() || options.len() < 2 {
+            eprintln!("Cannot create poll: a question and at least two options are required.");
+            return;
+        }
+        if closes_at.is_some_and(|t| t <= chrono::Utc::now().timestamp() as u64) {
+            eprintln!("Cannot create poll: it would already be closed.");
+            return;
+        }
+
+        let payload = dag::DagPayload::Poll(dag::PollPayload { question, options, closes_at, allow_multiple });
+        if let Some(node) = self.publish_node("poll:v1", payload) {
+            let _ = self.event_tx.send(AppEvent::BlockReceived(node.clone()));
+            self.send_poll_results(node.id);
+        }
+    }
+
+    /// Vote on an open poll, replacing any earlier vote of ours
+    fn vote_poll(&mut self, poll_id: String, choices: Vec<usize>) {
+        if !self.is_caller_verified() {
+            eprintln!("Cannot vote on poll: User is not verified.");
+            return;
+        }
+        let poll = match self.store.get_node(&poll_id) {
+            Ok(Some(dag::DagNode { payload: dag::DagPayload::Poll(poll), .. })) => poll,
+            _ => {
+                eprintln!("Cannot vote on poll: {} not found.", poll_id);
+                return;
+            }
+        };
+        if poll.closes_at.is_some_and(|t| t < chrono::Utc::now().timestamp() as u64) {
+            eprintln!("Cannot vote on poll: it has closed.");
+            return;
+        }
+        if !Store::is_valid_poll_choice(&poll, &choices) {
+            eprintln!("Cannot vote on poll: invalid choices {:?}.", choices);
+            return;
+        }
+
+        let payload = dag::DagPayload::PollVote(dag::PollVotePayload { poll_id: poll_id.clone(), choices });
+        if self.publish_node("poll_vote:v1", payload).is_some() {
+            self.send_poll_results(poll_id);
+        }
+    }
+
+    fn send_poll_results(&self, poll_id: String) {
+        let closes_at = match self.store.get_node(&poll_id) {
+            Ok(Some(dag::DagNode { payload: dag::DagPayload::Poll(poll), .. })) => poll.closes_at,
+            _ => return,
+        };
+        match self.store.get_poll_tally(&poll_id) {
+            Ok(tally) => {
+                let closed = closes_at.is_some_and(|t| t < chrono::Utc::now().timestamp() as u64);
+                let _ = self.event_tx.send(AppEvent::PollResultsFetched { poll_id, tally, closed });
+            }
+            Err(e) => eprintln!("Failed to tally poll {}: {:?}", poll_id, e),
+        }
+    }
+
+    /// Publish an `event:v1` node. Events need a title and must end after they start and after now
+    fn create_event(&mut self, mut event: dag::EventPayload) {
+        if !self.is_caller_verified() {
+            eprintln!("Cannot create event: User is not verified.");
+            return;
+        }
+        event.title = event.title.trim().to_string();
+        event.geohash = event.geohash.map(|gh| gh.trim().to_lowercase()).filter(|gh| !gh.is_empty());
+        event.location = event.location.map(|l| l.trim().to_string()).filter(|l| !l.is_empty());
+        if event.title.is_empty() {
+            eprintln!("Cannot create event: a title is required.");
+            return;
+        }
+        if event.ends_at < event.starts_at || event.ends_at <= chrono::Utc::now().timestamp() as u64 {
+            eprintln!("Cannot create event: it would end before it starts or is already over.");
+            return;
+        }
+        if event.capacity == Some(0) {
+            eprintln!("Cannot create event: capacity must be at least one.");
+            return;
+        }
+
+        if let Some(node) = self.publish_node("event:v1", dag::DagPayload::Event(event)) {
+            let _ = self.event_tx.send(AppEvent::BlockReceived(node));
+        }
+    }
+
+    /// RSVP to an event that has not ended yet. Saying yes to a full event is refused
+    fn rsvp_event(&mut self, event_id: String, attending: bool) {
+        let event = match self.store.get_node(&event_id) {
+            Ok(Some(dag::DagNode { payload: dag::DagPayload::Event(event), .. })) => event,
+            _ => {
+                eprintln!("Cannot RSVP: event {} not found.", event_id);
+                return;
+            }
+        };
+        if event.ends_at < chrono::Utc::now().timestamp() as u64 {
+            eprintln!("Cannot RSVP: the event is over.");
+            return;
+        }
+        if attending {
+            let me = self.local_peer_id().to_string();
+            let attendees = self.store.get_event_attendees(&event_id).unwrap_or_default();
+            if event.capacity.is_some_and(|cap| attendees.len() >= cap) && !attendees.contains(&me) {
+                eprintln!("Cannot RSVP: the event is full.");
+                return;
+            }
+        }
+
+        let payload = dag::DagPayload::Rsvp(dag::RsvpPayload { event_id: event_id.clone(), attending });
+        if self.publish_node("rsvp:v1", payload).is_some() {
+            self.send_event_attendees(event_id);
+        }
+    }
+
+    fn send_event_attendees(&self, event_id: String) {
+        match self.store.get_event_attendees(&event_id) {
+            Ok(attendees) => {
+                let _ = self.event_tx.send(AppEvent::AttendeesFetched { event_id, attendees });
+            }
+            Err(e) => eprintln!("Failed to fetch attendees for {}: {:?}", event_id, e),
+        }
+    }
+
+    fn create_petition(&mut self, title: String, body: String, target_ministry: Option<dag::Ministry>, threshold: usize) {
+        if !self.is_caller_verified() {
+            eprintln!("Cannot create petition: User is not verified.");
+            return;
+        }
+        let title = title.trim().to_string();
+        if title.is_empty() || threshold == 0 {
+            eprintln!("Cannot create petition: a title and a threshold of at least one signature are required.");
+            return;
+        }
+        let payload = dag::DagPayload::Petition(dag::PetitionPayload { title, body, target_ministry, threshold });
+        if let Some(node) = self.publish_node("petition:v1", payload) {
+            let _ = self.event_tx.send(AppEvent::BlockReceived(node));
+        }
+    }
+
+    fn sign_petition(&mut self, petition_id: String) {
+        if !self.is_caller_verified() {
+            eprintln!("Cannot sign petition: User is not verified.");
+            return;
+        }
+        if !matches!(self.store.get_node(&petition_id), Ok(Some(dag::DagNode { payload: dag::DagPayload::Petition(_), .. }))) {
+            eprintln!("Cannot sign petition: {} not found.", petition_id);
+            return;
+        }
+        let me = self.local_peer_id().to_string();
+        if self.store.get_petition_signatures(&petition_id).unwrap_or_default().contains(&me) {
+            eprintln!("Cannot sign petition: already signed.");
+            return;
+        }
+
+        let payload = dag::DagPayload::Signature(dag::SignaturePayload { petition_id: petition_id.clone() });
+        if let Some(node) = self.publish_node("signature:v1", payload) {
+            self.send_petition_signatures(petition_id);
+            if let Some(event) = self.petition_threshold_event(&node) {
+                let _ = self.event_tx.send(event);
+            }
+        }
+    }
+
+    fn send_petition_signatures(&self, petition_id: String) {
+        match self.store.get_petition_signatures(&petition_id) {
+            Ok(signers) => {
+                let _ = self.event_tx.send(AppEvent::PetitionSignaturesFetched { petition_id, signers });
+            }
+            Err(e) => eprintln!("Failed to fetch petition signatures: {:?}", e),
+        }
+    }
+
+    /// `PetitionThresholdReached` when this signature is the one that brought its petition to the threshold
+    fn petition_threshold_event(&self, node: &dag::DagNode) -> Option<AppEvent> {
+        let dag::DagPayload::Signature(signature) = &node.payload else {
+            return None;
+        };
+        let Ok(Some(dag::DagNode { payload: dag::DagPayload::Petition(petition), .. })) = self.store.get_node(&signature.petition_id) else {
+            return None;
+        };
+        // Only list the signers once the cheap count says this may be the signature that got there
+        if self.store.get_signature_count(&signature.petition_id).ok()? != petition.threshold {
+            return None;
+        }
+        let signers = self.store.get_petition_signatures(&signature.petition_id).ok()?;
+        (signers.last() == Some(&node.author))
+            .then(|| AppEvent::PetitionThresholdReached { petition_id: signature.petition_id.clone() })
+    }
+
+    /// Turn a petition that reached its threshold into a standard proposal linked back to it
+    fn convert_petition_to_proposal(&mut self, petition_id: String) {
+        if !self.is_caller_verified() {
+            eprintln!("Cannot convert petition: User is not verified.");
+            return;
+        }
+        let Ok(Some(petition_node)) = self.store.get_node(&petition_id) else {
+            eprintln!("Cannot convert petition: {} not found.", petition_id);
+            return;
+        };
+        let dag::DagPayload::Petition(petition) = petition_node.payload else {
+            eprintln!("Cannot convert petition: {} is not a petition.", petition_id);
+            return;
+        };
+        let me = self.local_peer_id().to_string();
+        let signers = self.store.get_petition_signatures(&petition_id).unwrap_or_default();
+        if petition_node.author != me && !signers.contains(&me) {
+            eprintln!("Cannot convert petition: only its author or a signer may.");
+            return;
+        }
+        if signers.len() < petition.threshold {
+            eprintln!("Cannot convert petition: {}/{} signatures.", signers.len(), petition.threshold);
+            return;
+        }
+        if let Ok(Some(proposal_id)) = self.store.get_petition_proposal(&petition_id) {
+            eprintln!("Cannot convert petition: already became proposal {}.", proposal_id);
+            return;
+        }
+
+        let description = match &petition.target_ministry {
+            Some(ministry) => format!("{}\n\nPetition to {} signed by {} citizens.", petition.body, ministry, signers.len()),
+            None => format!("{}\n\nPetition signed by {} citizens.", petition.body, signers.len()),
+        };
+        let payload = dag::DagPayload::Proposal(dag::ProposalPayload {
+            title: petition.title,
+            description,
+            r#type: dag::ProposalType::Standard,
+            pinned: false,
+            quadratic: false,
+        });
+        if let Some(node) = self.publish_linked_node("proposal:v1", payload, &[petition_id]) {
+            let _ = self.event_tx.send(AppEvent::BlockReceived(node));
+        }
+    }
+
+    /// Publish a `post:v2` revision. Its `prev` links back to the original post, so
+    /// clients that never receive the edit keep showing the version they have.
+    fn process_edit_post(&mut self, original_cid: String, new_content: String, attachments: Vec<String>) {
+        if !self.is_caller_verified() {
+            eprintln!("Cannot edit post: User is not verified.");
+            return;
+        }
+
+        let author_hex = self.local_peer_id().to_string();
+        let original = match self.store.get_original_post(&original_cid) {
+            Ok(Some(node)) => node,
+            Ok(None) => {
+                eprintln!("Cannot edit post: {} not found.", original_cid);
+                return;
+            }
+            Err(e) => {
+                eprintln!("Failed to load post {}: {:?}", original_cid, e);
+                return;
+            }
+        };
+        if original.author != author_hex {
+            eprintln!("Cannot edit post: Only the original author can edit it.");
+            return;
+        }
+        let post = match &original.payload {
+            dag::DagPayload::Post(p) => p,
+            _ => return,
+        };
+        let payload = dag::DagPayload::Post(dag::PostPayload {
+            content: new_content,
+            attachments,
+            geohash: post.geohash.clone(),
+            announcement: post.announcement,
+            quote_of: post.quote_of.clone(),
+            publish_at: None,
+        });
+
+        // The author's head stays first so the personal chain is unbroken
+        let mut prev = match self.store.get_head(&author_hex) {
+            Ok(Some(cid)) => vec![cid],
+            _ => vec![],
+        };
+        if !prev.contains(&original.id) {
+            prev.push(original.id.clone());
+        }
+
+        match dag::DagNode::new("post:v2".to_string(), payload, prev, &self.keypair, 0) {
+            Ok(node) => {
+                if let Err(e) = self.store.put_node(&node) {
+                    eprintln!("Failed to store post edit: {:?}", e);
+                    return;
+                }
                 if let Err(e) = self.store.update_head(&author_hex, &node.id) {
                     eprintln!("Failed to update head: {:?}", e);
                 }
-
-                // 3. Publish CID to gossipsub
-                let topic = gossipsub::IdentTopic::new("blocks");
-                if let Err(e) = self.swarm.behaviour_mut().gossipsub.publish(topic, node.id.as_bytes()) {
-                    eprintln!("Failed to publish post CID: {:?}", e);
+                // Edits of restricted posts only travel to their audience through replication
+                if self.post_audience(&node).is_none() {
+                    let topic = gossipsub::IdentTopic::new("blocks");
+                    if let Err(e) = self.swarm.behaviour_mut().gossipsub.publish(topic, node.id.as_bytes()) {
+                        eprintln!("Failed to publish post edit CID: {:?}", e);
+                    }
                 }
-                
-                // 4. Notify frontend so it can display immediately
-                let _ = self.event_tx.send(AppEvent::BlockReceived(node.clone()));
-                
-                // 5. Replicate
                 self.replicate_block(&node);
+                let _ = self.event_tx.send(AppEvent::PostEdited(node));
             }
-            Err(e) => eprintln!("Failed to create post node: {:?}", e),
+            Err(e) => eprintln!("Failed to create post edit node: {:?}", e),
         }
     }
 
@@ -660,100 +3355,136 @@ impl Backend {
             eprintln!("Cannot send message: User is not verified.");
             return;
         }
-        // 1. Fetch recipient's profile to get their public key
-        let recipient_pubkey_bytes = match self.store.get_profile(&recipient) {
-            Ok(Some(profile)) => {
-                if let Some(pk_hex) = profile.encryption_pubkey {
-                    match hex::decode(pk_hex) {
-                        Ok(bytes) => {
-                            if bytes.len() == 32 {
-                                let mut arr = [0u8; 32];
-                                arr.copy_from_slice(&bytes);
-                                Some(x25519_dalek::PublicKey::from(arr))
-                            } else {
-                                None
-                            }
-                        }
-                        Err(_) => None,
-                    }
-                } else {
-                    None
-                }
+        if let Some(group_id) = group_id {
+            self.process_send_group_message(group_id, content);
+            return;
+        }
+        // 1. Load the session with the recipient, or start one from their published key
+        let (mut ratchet, is_new) = match self.sending_ratchet(&recipient) {
+            Some(session) => session,
+            None => {
+                eprintln!("Recipient has no public key published or invalid.");
+                return;
             }
-            _ => None,
         };
 
-        if let Some(recipient_pk) = recipient_pubkey_bytes {
-            // 2. Generate ephemeral keypair
-            let ephemeral_secret = x25519_dalek::StaticSecret::random_from_rng(OsRng);
-            let ephemeral_public = x25519_dalek::PublicKey::from(&ephemeral_secret);
+        // 2. Encrypt with the next message key and persist the advanced chain before anything leaves
+        let encrypted = match ratchet.encrypt(content.as_bytes()) {
+            Ok(encrypted) => encrypted,
+            Err(e) => {
+                eprintln!("Failed to encrypt message: {}", e);
+                return;
+            }
+        };
+        if let Err(e) = self.store.save_ratchet_state(&recipient, &ratchet) {
+            eprintln!("Failed to store ratchet state: {:?}", e);
+            return;
+        }
+        if is_new {
+            let _ = self.event_tx.send(AppEvent::RatchetInitialized { peer_id: recipient.clone() });
+        }
 
-            // 3. Perform ECDH
-            let shared_secret = ephemeral_secret.diffie_hellman(&recipient_pk);
+        let payload = dag::DagPayload::Message(dag::MessagePayload {
+            recipient,
+            ciphertext: hex::encode(encrypted.ciphertext),
+            nonce: hex::encode(encrypted.nonce),
+            ephemeral_pubkey: hex::encode(encrypted.ratchet_pubkey),
+            group_id: None,
+            ratchet: Some(encrypted.header),
+            group_key_id: None,
+        });
+        self.publish_message(payload, &content);
+    }
 
-            // 4. Encrypt
-            let key = Aes256Gcm::new(aes_gcm::Key::<Aes256Gcm>::from_slice(shared_secret.as_bytes()));
-            let nonce = Aes256Gcm::generate_nonce(&mut OsRng); // 96-bits; unique per message
-            
-            match key.encrypt(&nonce, content.as_bytes()) {
-                Ok(ciphertext_bytes) => {
-                    let payload = dag::DagPayload::Message(dag::MessagePayload {
-                        recipient: recipient.clone(),
-                        ciphertext: hex::encode(ciphertext_bytes),
-                        nonce: hex::encode(nonce),
-                        ephemeral_pubkey: hex::encode(ephemeral_public.to_bytes()),
-                        group_id: group_id.clone(),
-                    });
+    /// Encrypt once with the group's current session key; every member holds a sealed copy
+    fn process_send_group_message(&mut self, group_id: String, content: String) {
+        let author_hex = self.local_peer_id().to_string();
+        let is_member = self.store.get_group_members(&group_id)
+            .map(|members| members.iter().any(|(m, _)| *m == author_hex))
+            .unwrap_or(false);
+        if !is_member {
+            eprintln!("Cannot send group message: Not a member of the group.");
+            return;
+        }
 
-                    // Get previous head for this user if any
-                    let author_pubkey = self.keypair.public();
-                    let author_hex = libp2p::PeerId::from_public_key(&author_pubkey).to_string();
-                    
-                    let prev = match self.store.get_head(&author_hex) {
-                        Ok(Some(cid)) => vec![cid],
-                        Ok(None) => vec![],
-                        Err(e) => {
-                            eprintln!("Failed to get head: {:?}", e);
-                            vec![]
-                        }
-                    };
+        let mut key_node = self.store.get_group_key(&group_id).ok().flatten();
+        // Groups created before group keys existed get their first key on the owner's next message
+        if key_node.is_none() && self.is_group_owner(&group_id) {
+            key_node = self.publish_group_key(&group_id);
+        }
+        let Some(key_node) = key_node else {
+            eprintln!("Cannot send group message: The group has no key yet.");
+            return;
+        };
+        let Some(group_key) = self.open_group_key(&key_node) else {
+            eprintln!("Cannot send group message: The current group key was not issued to us.");
+            return;
+        };
 
-                    match dag::DagNode::new(
-                        "message:v1".to_string(),
-                        payload,
-                        prev,
-                        &self.keypair,
-                        0
-                    ) {
-                        Ok(node) => {
-                            println!("Created encrypted message node: {}", node.id);
-                            // 1. Store locally
-                            if let Err(e) = self.store.put_node(&node) {
-                                eprintln!("Failed to store message node: {:?}", e);
-                                return;
-                            }
-                            
-                            // 2. Update head
-                            if let Err(e) = self.store.update_head(&author_hex, &node.id) {
-                                eprintln!("Failed to update head: {:?}", e);
-                            }
+        let cipher = Aes256Gcm::new(&aes_gcm::Key::<Aes256Gcm>::from(group_key));
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = match cipher.encrypt(&nonce, content.as_bytes()) {
+            Ok(ciphertext) => ciphertext,
+            Err(e) => {
+                eprintln!("Failed to encrypt group message: {:?}", e);
+                return;
+            }
+        };
+        let payload = dag::DagPayload::Message(dag::MessagePayload {
+            recipient: String::new(),
+            ciphertext: hex::encode(ciphertext),
+            nonce: hex::encode(nonce),
+            ephemeral_pubkey: String::new(),
+            group_id: Some(group_id),
+            ratchet: None,
+            group_key_id: Some(key_node.id),
+        });
+        self.publish_message(payload, &content);
+    }
 
-                            // 3. Publish CID to gossipsub
-                            let topic = gossipsub::IdentTopic::new("blocks");
-                            if let Err(e) = self.swarm.behaviour_mut().gossipsub.publish(topic, node.id.as_bytes()) {
-                                eprintln!("Failed to publish message CID: {:?}", e);
-                            }
-                            
-                            // 4. Replicate
-                            self.replicate_block(&node);
-                        }
-                        Err(e) => eprintln!("Failed to create message node: {:?}", e),
-                    }
+    /// Store, announce and replicate an encrypted message, keeping our own copy readable
+    fn publish_message(&mut self, payload: dag::DagPayload, content: &str) {
+        let author_hex = self.local_peer_id().to_string();
+        let prev = match self.store.get_head(&author_hex) {
+            Ok(Some(cid)) => vec![cid],
+            Ok(None) => vec![],
+            Err(e) => {
+                eprintln!("Failed to get head: {:?}", e);
+                vec![]
+            }
+        };
+
+        match dag::DagNode::new(
+            "message:v1".to_string(),
+            payload,
+            prev,
+            &self.keypair,
+            0
+        ) {
+            Ok(node) => {
+                println!("Created encrypted message node: {}", node.id);
+                // 1. Store locally
+                if let Err(e) = self.store.put_node(&node) {
+                    eprintln!("Failed to store message node: {:?}", e);
+                    return;
                 }
-                Err(e) => eprintln!("Failed to encrypt message: {:?}", e),
+                self.save_message_copy(&node.id, content);
+                
+                // 2. Update head
+                if let Err(e) = self.store.update_head(&author_hex, &node.id) {
+                    eprintln!("Failed to update head: {:?}", e);
+                }
+
+                // 3. Publish CID to gossipsub
+                let topic = gossipsub::IdentTopic::new("blocks");
+                if let Err(e) = self.swarm.behaviour_mut().gossipsub.publish(topic, node.id.as_bytes()) {
+                    eprintln!("Failed to publish message CID: {:?}", e);
+                }
+                
+                // 4. Replicate
+                self.replicate_block(&node);
             }
-        } else {
-            eprintln!("Recipient has no public key published or invalid.");
+            Err(e) => eprintln!("Failed to create message node: {:?}", e),
         }
     }
 
@@ -812,6 +3543,7 @@ impl Backend {
                     eprintln!("Failed to store profile node: {:?}", e);
                     return;
                 }
+                self.note_verification_change(&node);
                 
                 // 2. Update head
                 if let Err(e) = self.store.update_head(&author_hex, &node.id) {
@@ -834,6 +3566,75 @@ impl Backend {
         }
     }
 
+    async fn process_endorsement(&mut self, target_peer_id: String, skill: String, evidence: String) {
+        if !self.is_caller_verified() {
+            eprintln!("Cannot endorse: User is not verified.");
+            return;
+        }
+
+        let author_hex = self.local_peer_id().to_string();
+        if target_peer_id == author_hex {
+            eprintln!("Cannot endorse yourself.");
+            return;
+        }
+        let skill = skill.trim().to_string();
+        if skill.is_empty() {
+            eprintln!("Cannot endorse: Skill is required.");
+            return;
+        }
+
+        let payload = dag::DagPayload::Endorsement(dag::EndorsementPayload {
+            target: target_peer_id.clone(),
+            skill,
+            evidence: evidence.trim().to_string(),
+        });
+        let prev = match self.store.get_head(&author_hex) {
+            Ok(Some(cid)) => vec![cid],
+            _ => vec![],
+        };
+
+        match dag::DagNode::new(
+            "endorsement:v1".to_string(),
+            payload,
+            prev,
+            &self.keypair,
+            0
+        ) {
+            Ok(node) => {
+                println!("Created endorsement node: {}", node.id);
+                if let Err(e) = self.store.put_node(&node) {
+                    eprintln!("Failed to store endorsement node: {:?}", e);
+                    return;
+                }
+                if let Err(e) = self.store.update_head(&author_hex, &node.id) {
+                    eprintln!("Failed to update head: {:?}", e);
+                }
+                let topic = gossipsub::IdentTopic::new("blocks");
+                let _ = self.swarm.behaviour_mut().gossipsub.publish(topic, node.id.as_bytes());
+                self.replicate_block(&node);
+                let _ = self.event_tx.send(AppEvent::BlockReceived(node));
+                self.send_endorsements(target_peer_id);
+            }
+            Err(e) => eprintln!("Failed to create endorsement node: {:?}", e),
+        }
+    }
+
+    fn send_endorsements(&self, peer_id: String) {
+        match self.store.get_endorsements(&peer_id) {
+            Ok(nodes) => {
+                let endorsements = nodes.into_iter().map(|node| {
+                    let name = match self.store.get_profile(&node.author) {
+                        Ok(Some(profile)) => profile.name,
+                        _ => node.author.chars().take(12).collect(),
+                    };
+                    (node, name)
+                }).collect();
+                let _ = self.event_tx.send(AppEvent::EndorsementsFetched { peer_id, endorsements });
+            }
+            Err(e) => eprintln!("Failed to fetch endorsements: {:?}", e),
+        }
+    }
+
     async fn process_vouch(&mut self, target_peer_id: String) {
         if !self.is_caller_verified() {
             eprintln!("Cannot vouch: User is not verified.");
@@ -849,9 +3650,10 @@ impl Backend {
             return;
         }
 
-        // Check 2: Prevent duplicate vouch
+        // Check 2: Prevent duplicate vouch. A revoked vouch may be given again
         if let Ok(existing_proofs) = self.store.get_proofs(&target_peer_id) {
-            if existing_proofs.iter().any(|p| p.author == author_hex) {
+            let revoked = self.store.get_vouch_revocations(&target_peer_id).unwrap_or_default();
+            if existing_proofs.iter().any(|p| p.author == author_hex && !revoked.contains(&p.id)) {
                 eprintln!("Cannot vouch: You have already vouched for this user.");
                 return;
             }
@@ -883,6 +3685,7 @@ impl Backend {
                     eprintln!("Failed to store proof node: {:?}", e);
                     return;
                 }
+                self.note_verification_change(&node);
                 
                 // 2. Update head
                 if let Err(e) = self.store.update_head(&author_hex, &node.id) {
@@ -903,6 +3706,16 @@ impl Backend {
     }
 
     async fn handle_command(&mut self, cmd: AppCmd) {
+        if self.rebuilding_indexes && Self::is_read_command(&cmd) {
+            // Queue behind the rebuild rather than answer from half-built indexes
+            drop(self.index_lock.lock().await);
+            self.rebuilding_indexes = false;
+        }
+        // Governance tallies only follow delegations of verified peers, as of the last count
+        if let Err(e) = self.compute_verified_peer_count() {
+            eprintln!("Failed to count verified peers: {:?}", e);
+        }
+
         match cmd {
             AppCmd::Init => {
                 println!("Backend initialized");
@@ -929,8 +3742,40 @@ impl Backend {
             AppCmd::Vouch { target_peer_id } => {
                 self.process_vouch(target_peer_id).await;
             }
+            AppCmd::RevokeVouch { proof_cid, reason } => {
+                self.revoke_vouch(proof_cid, reason);
+            }
+            AppCmd::FetchPendingVouches { peer_id } => {
+                match self.store.get_vouches_by(&peer_id) {
+                    Ok(nodes) => {
+                        let mut vouches = Vec::new();
+                        for node in nodes {
+                            if let dag::DagPayload::Proof(proof) = node.payload {
+                                if vouches.iter().any(|(t, _)| t == &proof.target_pubkey) {
+                                    continue;
+                                }
+                                if self.store.get_vouch_revocations(&proof.target_pubkey).unwrap_or_default().contains(&node.id) {
+                                    continue;
+                                }
+                                let mut visited = std::collections::HashSet::new();
+                                let verified = self.is_verified(&proof.target_pubkey, &mut visited);
+                                vouches.push((proof.target_pubkey, verified));
+                            }
+                        }
+                        let _ = self.event_tx.send(AppEvent::PendingVouchesFetched { voucher_peer_id: peer_id, vouches });
+                    }
+                    Err(e) => eprintln!("Failed to fetch vouches: {:?}", e),
+                }
+            }
+            AppCmd::EndorseSkill { target_peer_id, skill, evidence } => {
+                self.process_endorsement(target_peer_id, skill, evidence).await;
+            }
+            AppCmd::FetchEndorsements { peer_id } => {
+                self.send_endorsements(peer_id);
+            }
             AppCmd::FetchPosts => {
-                match self.store.get_recent_posts(50) {
+                let my_pubkey = self.local_peer_id().to_string();
+                match self.store.get_recent_posts(50, &my_pubkey) {
                     Ok(posts) => {
                         let _ = self.event_tx.send(AppEvent::HistoryFetched(posts));
                     }
@@ -945,8 +3790,78 @@ impl Backend {
                     Err(e) => eprintln!("Failed to fetch local posts: {:?}", e),
                 }
             }
-            AppCmd::PublishPost { content, attachments, geohash, announcement } => {
-                self.process_publish_post(content, attachments, geohash, announcement).await;
+            AppCmd::FetchPersonalizedFeed { strategy, limit, offset } => {
+                let my_pubkey = self.local_peer_id().to_string();
+                match self.store.get_personalized_feed(strategy, limit, offset, &my_pubkey, self.current_geohash.as_deref()) {
+                    Ok(posts) => {
+                        let _ = self.event_tx.send(AppEvent::PersonalizedFeedFetched { strategy, posts });
+                    }
+                    Err(e) => eprintln!("Failed to fetch personalized feed: {:?}", e),
+                }
+            }
+            AppCmd::FetchTrendingPosts { window_hours, limit } => {
+                let my_pubkey = self.local_peer_id().to_string();
+                match self.store.get_trending_posts(window_hours as u64 * 3600, limit, &my_pubkey) {
+                    Ok(posts) => {
+                        let _ = self.event_tx.send(AppEvent::TrendingPostsFetched(posts));
+                    }
+                    Err(e) => eprintln!("Failed to fetch trending posts: {:?}", e),
+                }
+            }
+            AppCmd::PublishPost { content, attachments, geohash, announcement, quote_of, publish_at } => {
+                self.process_publish_post(content, attachments, geohash, announcement, quote_of, publish_at).await;
+            }
+            AppCmd::FetchScheduledPosts => {
+                self.send_scheduled_posts();
+            }
+            AppCmd::CancelScheduledPost { cid } => {
+                if let Err(e) = self.store.cancel_scheduled_post(&cid) {
+                    eprintln!("Failed to cancel scheduled post {}: {:?}", cid, e);
+                }
+                self.send_scheduled_posts();
+            }
+            AppCmd::EditPost { original_cid, new_content, attachments } => {
+                self.process_edit_post(original_cid, new_content, attachments);
+            }
+            AppCmd::DeletePost { cid, reason } => {
+                self.process_delete_post(cid, reason);
+            }
+            AppCmd::SharePost { original_cid, comment } => {
+                self.process_share_post(original_cid, comment);
+            }
+            AppCmd::CreatePoll { question, options, closes_at, allow_multiple } => {
+                self.create_poll(question, options, closes_at, allow_multiple);
+            }
+            AppCmd::VotePoll { poll_id, choices } => {
+                self.vote_poll(poll_id, choices);
+            }
+            AppCmd::FetchPollResults { poll_id } => {
+                self.send_poll_results(poll_id);
+            }
+            AppCmd::CreateEvent { title, description, location, geohash, starts_at, ends_at, capacity } => {
+                self.create_event(dag::EventPayload { title, description, location, geohash, starts_at, ends_at, capacity });
+            }
+            AppCmd::RsvpEvent { event_id, attending } => {
+                self.rsvp_event(event_id, attending);
+            }
+            AppCmd::FetchEvents { geohash_prefix, from, to } => {
+                match self.store.get_events(&geohash_prefix, from, to) {
+                    Ok(events) => {
+                        let _ = self.event_tx.send(AppEvent::EventsFetched(events));
+                    }
+                    Err(e) => eprintln!("Failed to fetch events: {:?}", e),
+                }
+            }
+            AppCmd::FetchEventAttendees { event_id } => {
+                self.send_event_attendees(event_id);
+            }
+            AppCmd::FetchShares { cid } => {
+                match self.store.get_share_count(&cid) {
+                    Ok(count) => {
+                        let _ = self.event_tx.send(AppEvent::SharesFetched { cid, count });
+                    }
+                    Err(e) => eprintln!("Failed to fetch shares: {:?}", e),
+                }
             }
             AppCmd::PublishBlob { mime_type, data } => {
                 if !self.is_caller_verified() {
@@ -994,27 +3909,79 @@ impl Backend {
                         if let Err(e) = self.swarm.behaviour_mut().gossipsub.publish(topic, node.id.as_bytes()) {
                             eprintln!("Failed to publish blob CID: {:?}", e);
                         }
-                        
-                        // Notify frontend so it knows the CID (we might need to send a specific event or generic BlockReceived)
-                        let _ = self.event_tx.send(AppEvent::BlockReceived(node.clone()));
-                        
-                        self.replicate_block(&node);
+                        
+                        // Notify frontend so it knows the CID (we might need to send a specific event or generic BlockReceived)
+                        let _ = self.event_tx.send(AppEvent::BlockReceived(node.clone()));
+                        
+                        self.replicate_block(&node);
+                    }
+                    Err(e) => eprintln!("Failed to create blob node: {:?}", e),
+                }
+            }
+            AppCmd::FetchMessages { peer_id } => {
+                let author_pubkey = self.keypair.public();
+                let author_hex = libp2p::PeerId::from_public_key(&author_pubkey).to_string();
+                match self.store.get_messages(&author_hex, &peer_id) {
+                    Ok(messages) => {
+                        let decrypted_messages: Vec<_> = messages.into_iter().map(|node| {
+                            let content = self.try_decrypt(&node);
+                            (node, content)
+                        }).collect();
+                        let sent: Vec<String> = decrypted_messages.iter()
+                            .filter(|(node, _)| node.author == author_hex)
+                            .map(|(node, _)| node.id.clone())
+                            .collect();
+                        let _ = self.event_tx.send(AppEvent::MessagesFetched(decrypted_messages));
+                        for cid in sent {
+                            if let Some(event) = self.store.get_receipts_for_message(&cid).ok()
+                                .and_then(|receipts| receipts.first().and_then(|r| self.receipt_event(r)))
+                            {
+                                let _ = self.event_tx.send(event);
+                            }
+                        }
+                    }
+                    Err(e) => eprintln!("Failed to fetch messages: {:?}", e),
+                }
+            }
+            AppCmd::MarkMessageRead { message_cid } => {
+                self.process_mark_message_read(message_cid);
+            }
+            AppCmd::InitRatchet { peer_id } => {
+                if !self.is_caller_verified() {
+                    eprintln!("Cannot start encrypted session: User is not verified.");
+                    return;
+                }
+                match self.sending_ratchet(&peer_id) {
+                    Some((state, is_new)) => {
+                        if is_new {
+                            if let Err(e) = self.store.save_ratchet_state(&peer_id, &state) {
+                                eprintln!("Failed to store ratchet state: {:?}", e);
+                                return;
+                            }
+                        }
+                        let _ = self.event_tx.send(AppEvent::RatchetInitialized { peer_id });
                     }
-                    Err(e) => eprintln!("Failed to create blob node: {:?}", e),
+                    None => eprintln!("Cannot start encrypted session: {} has no encryption key published.", peer_id),
                 }
             }
-            AppCmd::FetchMessages { peer_id } => {
-                let author_pubkey = self.keypair.public();
-                let author_hex = libp2p::PeerId::from_public_key(&author_pubkey).to_string();
-                match self.store.get_messages(&author_hex, &peer_id) {
-                    Ok(messages) => {
-                        let decrypted_messages = messages.into_iter().map(|node| {
+            AppCmd::FetchMessageThreads => {
+                const PREVIEW_CHARS: usize = 60;
+                match self.store.get_message_threads(&self.local_peer_id().to_string()) {
+                    Ok(threads) => {
+                        let threads = threads.into_iter().map(|(peer_id, node)| {
                             let content = self.try_decrypt(&node);
-                            (node, content)
+                            let preview = if content.contains("[FILE:") {
+                                "📎 Attachment".to_string()
+                            } else if content.chars().count() > PREVIEW_CHARS {
+                                format!("{}…", content.chars().take(PREVIEW_CHARS).collect::<String>())
+                            } else {
+                                content
+                            };
+                            (peer_id, node, preview)
                         }).collect();
-                        let _ = self.event_tx.send(AppEvent::MessagesFetched(decrypted_messages));
+                        let _ = self.event_tx.send(AppEvent::MessageThreadsFetched(threads));
                     }
-                    Err(e) => eprintln!("Failed to fetch messages: {:?}", e),
+                    Err(e) => eprintln!("Failed to fetch message threads: {:?}", e),
                 }
             }
             AppCmd::SendMessage { recipient, content, group_id } => {
@@ -1146,13 +4113,22 @@ impl Backend {
                 }
             }
             AppCmd::FetchGivenUserPosts { peer_id } => {
-                match self.store.get_posts_by_author(&peer_id, 50) {
+                let my_pubkey = self.local_peer_id().to_string();
+                match self.store.get_posts_by_author(&peer_id, 50, &my_pubkey) {
                     Ok(posts) => {
                          let _ = self.event_tx.send(AppEvent::UserPostsFetched(posts));
                     }
                      Err(e) => eprintln!("Failed to fetch user posts: {:?}", e),
                 }
             }
+            AppCmd::FetchNodeByAuthorAndType { peer_id, node_type, limit, offset } => {
+                match self.store.get_nodes_by_author_and_type(&peer_id, &node_type, limit, offset) {
+                    Ok((nodes, has_more)) => {
+                        let _ = self.event_tx.send(AppEvent::NodesByAuthorTypeFetched { peer_id, node_type, nodes, has_more });
+                    }
+                    Err(e) => eprintln!("Failed to fetch nodes by author and type: {:?}", e),
+                }
+            }
             AppCmd::FetchFollowingPosts => {
                 let author_pubkey = self.keypair.public();
                 let author_hex = libp2p::PeerId::from_public_key(&author_pubkey).to_string();
@@ -1163,6 +4139,107 @@ impl Backend {
                     Err(e) => eprintln!("Failed to fetch following posts: {:?}", e),
                 }
             }
+            AppCmd::FollowHashtag { tag, follow } => {
+                if let Err(e) = self.store.follow_hashtag(&tag, follow) {
+                    eprintln!("Failed to update followed hashtag: {:?}", e);
+                    return;
+                }
+                if let Ok(tags) = self.store.get_followed_hashtags() {
+                    let _ = self.event_tx.send(AppEvent::FollowingHashtagsFetched(tags));
+                }
+            }
+            AppCmd::FetchFollowingHashtags => {
+                match self.store.get_followed_hashtags() {
+                    Ok(tags) => {
+                        let _ = self.event_tx.send(AppEvent::FollowingHashtagsFetched(tags));
+                    }
+                    Err(e) => eprintln!("Failed to fetch followed hashtags: {:?}", e),
+                }
+            }
+            AppCmd::FetchFollowingHashtagPosts => {
+                let my_pubkey = self.local_peer_id().to_string();
+                let tags = self.store.get_followed_hashtags().unwrap_or_default();
+                match self.store.get_posts_by_hashtags(&tags, 50, &my_pubkey) {
+                    Ok(posts) => {
+                        let _ = self.event_tx.send(AppEvent::FollowingPostsFetched(posts));
+                    }
+                    Err(e) => eprintln!("Failed to fetch hashtag posts: {:?}", e),
+                }
+            }
+            AppCmd::FetchMentions { limit } => {
+                let my_pubkey = self.local_peer_id().to_string();
+                match self.store.get_mentions(&my_pubkey, limit) {
+                    Ok(mentions) => {
+                        let _ = self.event_tx.send(AppEvent::MentionsFetched(mentions));
+                    }
+                    Err(e) => eprintln!("Failed to fetch mentions: {:?}", e),
+                }
+            }
+            AppCmd::FetchByHashtag { tag } => {
+                self.send_hashtag_posts(&tag);
+            }
+            AppCmd::BookmarkPost { cid, remove } => {
+                // Bookmarks are local only: no node is created and nothing is gossiped
+                let result = if remove { self.store.remove_bookmark(&cid) } else { self.store.add_bookmark(&cid) };
+                if let Err(e) = result {
+                    eprintln!("Failed to update bookmark: {:?}", e);
+                    return;
+                }
+                if let Ok(bookmarks) = self.store.get_bookmarks() {
+                    let _ = self.event_tx.send(AppEvent::BookmarksFetched(bookmarks));
+                }
+            }
+            AppCmd::MuteUser { peer_id, unmute } => {
+                // Muting and blocking are local only: no node is created and nothing is gossiped
+                if let Err(e) = self.store.mute_peer(&peer_id, !unmute) {
+                    eprintln!("Failed to update muted user: {:?}", e);
+                    return;
+                }
+                if let Ok(muted) = self.store.get_muted_peers() {
+                    let _ = self.event_tx.send(AppEvent::MutedUsersFetched(muted));
+                }
+            }
+            AppCmd::BlockUser { peer_id, unblock } => {
+                if let Err(e) = self.store.block_peer(&peer_id, !unblock) {
+                    eprintln!("Failed to update blocked user: {:?}", e);
+                    return;
+                }
+                if let Ok(blocked) = self.store.get_blocked_peers() {
+                    let _ = self.event_tx.send(AppEvent::BlockedUsersFetched(blocked));
+                }
+            }
+            AppCmd::FetchMutedUsers => {
+                match self.store.get_muted_peers() {
+                    Ok(muted) => {
+                        let _ = self.event_tx.send(AppEvent::MutedUsersFetched(muted));
+                    }
+                    Err(e) => eprintln!("Failed to fetch muted users: {:?}", e),
+                }
+            }
+            AppCmd::FetchBlockedUsers => {
+                match self.store.get_blocked_peers() {
+                    Ok(blocked) => {
+                        let _ = self.event_tx.send(AppEvent::BlockedUsersFetched(blocked));
+                    }
+                    Err(e) => eprintln!("Failed to fetch blocked users: {:?}", e),
+                }
+            }
+            AppCmd::FetchNetworkDiagnostics => {
+                let diagnostics = self.network_diagnostics();
+                let _ = self.event_tx.send(AppEvent::NetworkDiagnostics(diagnostics));
+            }
+            AppCmd::FetchPeerScores => {
+                let scores = self.peer_scores.iter().map(|(peer, score)| (peer.to_string(), score.clone())).collect();
+                let _ = self.event_tx.send(AppEvent::PeerScoresFetched(scores));
+            }
+            AppCmd::FetchBookmarks => {
+                match self.store.get_bookmarks() {
+                    Ok(bookmarks) => {
+                        let _ = self.event_tx.send(AppEvent::BookmarksFetched(bookmarks));
+                    }
+                    Err(e) => eprintln!("Failed to fetch bookmarks: {:?}", e),
+                }
+            }
 
             // ========== EDUCATION SYSTEM ==========
             AppCmd::CreateCourse { title, description, content, category, prerequisites } => {
@@ -1394,6 +4471,25 @@ impl Backend {
                     Err(e) => eprintln!("Failed to fetch courses: {:?}", e),
                 }
             }
+
+            AppCmd::FetchCourse { course_id } => {
+                let node = match self.store.get_node(&course_id) {
+                    Ok(Some(node)) if matches!(node.payload, dag::DagPayload::Course(_)) => node,
+                    Ok(Some(_)) => {
+                        eprintln!("Node {} is not a course", course_id);
+                        return;
+                    }
+                    _ => {
+                        // Not replicated here yet; ask the network for it
+                        self.fetch_block(course_id, None);
+                        return;
+                    }
+                };
+                let exam = self.store.get_course_exam(&node).unwrap_or(None);
+                let my_id = self.local_peer_id().to_string();
+                let progress = self.store.get_course_progress(&course_id, &my_id).unwrap_or_default();
+                let _ = self.event_tx.send(AppEvent::CourseFetched { node, exam, progress });
+            }
             
             AppCmd::FetchExams => {
                 match self.store.get_exams(50) {
@@ -1407,23 +4503,36 @@ impl Backend {
             AppCmd::FetchCertifications { peer_id } => {
                 match self.store.get_certifications(&peer_id) {
                     Ok(certs) => {
+                        let certs = certs.into_iter().filter(|n| !self.is_cert_revoked(&n.id)).collect();
                         let _ = self.event_tx.send(AppEvent::CertificationsFetched(certs));
                     }
                     Err(e) => eprintln!("Failed to fetch certifications: {:?}", e),
                 }
             }
             
-            AppCmd::FetchMyCertifications => {
-                let author_pubkey = self.keypair.public();
-                let author_hex = libp2p::PeerId::from_public_key(&author_pubkey).to_string();
-                match self.store.get_certifications(&author_hex) {
-                    Ok(certs) => {
-                        let _ = self.event_tx.send(AppEvent::CertificationsFetched(certs));
+            AppCmd::FetchPeerCoursesCompleted { peer_id } => {
+                match self.store.get_peer_course_results(&peer_id) {
+                    Ok(results) => {
+                        let (completed, in_progress): (Vec<CourseResult>, Vec<CourseResult>) = results.into_iter()
+                            .partition(|(_, submission)| matches!(&submission.payload, dag::DagPayload::ExamSubmission(s) if s.passed));
+                        let _ = self.event_tx.send(AppEvent::PeerCoursesCompletedFetched { peer_id, completed, in_progress });
                     }
-                    Err(e) => eprintln!("Failed to fetch my certifications: {:?}", e),
+                    Err(e) => eprintln!("Failed to fetch completed courses: {:?}", e),
                 }
             }
 
+            AppCmd::FetchMyCertifications => {
+                self.send_my_certifications();
+            }
+
+            AppCmd::RenewCertification { cert_cid } => {
+                self.renew_certification(cert_cid);
+            }
+
+            AppCmd::RevokeCertification { cert_cid, reason } => {
+                self.revoke_certification(cert_cid, reason);
+            }
+
             // === Application-Based Verification System ===
             AppCmd::SubmitApplication { name, bio, photo_cid } => {
                 // Anyone can submit an application
@@ -1440,6 +4549,7 @@ impl Backend {
                     Ok(node) => {
                         println!("Created application node: {}", node.id);
                         let _ = self.store.put_node(&node);
+                        self.note_verification_change(&node);
                         let _ = self.store.update_head(&author_hex, &node.id);
                         let topic = gossipsub::IdentTopic::new("blocks");
                         let _ = self.swarm.behaviour_mut().gossipsub.publish(topic, node.id.as_bytes());
@@ -1486,6 +4596,7 @@ impl Backend {
                     Ok(node) => {
                         println!("Voted on application {}: approve={}", application_id, approve);
                         let _ = self.store.put_node(&node);
+                        self.note_verification_change(&node);
                         let _ = self.store.update_head(&author_hex, &node.id);
                         let topic = gossipsub::IdentTopic::new("blocks");
                         let _ = self.swarm.behaviour_mut().gossipsub.publish(topic, node.id.as_bytes());
@@ -1507,7 +4618,7 @@ impl Backend {
 
             AppCmd::FetchApplicationVotes { application_id } => {
                 // Count votes and determine required threshold
-                let network_size = self.store.count_unique_profiles().unwrap_or(0);
+                let network_size = self.verified_network_size();
                 let required = Self::required_approvals(network_size);
                 
                 match self.store.get_application_votes(&application_id) {
@@ -1527,6 +4638,74 @@ impl Backend {
                 }
             }
 
+            AppCmd::FetchMyApplicationStatus => {
+                let my_peer_id = self.local_peer_id().to_string();
+                let application_id = match self.store.get_applications_by_author(&my_peer_id) {
+                    Ok(apps) => match apps.into_iter().next() {
+                        Some(app) => app.id,
+                        None => return,
+                    },
+                    Err(e) => {
+                        eprintln!("Failed to fetch my applications: {:?}", e);
+                        return;
+                    }
+                };
+                let required = Self::required_approvals(self.verified_network_size());
+
+                match self.store.get_application_votes(&application_id) {
+                    Ok(votes) => {
+                        let approvals = votes.iter()
+                            .filter(|v| matches!(v.payload, dag::DagPayload::ApplicationVote(ref av) if av.approve))
+                            .count();
+                        let rejections = votes.len() - approvals;
+                        // Approval wins ties, matching how `is_verified` reads applications
+                        let is_approved = approvals >= required;
+                        let _ = self.event_tx.send(AppEvent::MyApplicationStatusFetched {
+                            application_id,
+                            approvals,
+                            rejections,
+                            required,
+                            is_approved,
+                            is_rejected: !is_approved && rejections >= required,
+                        });
+                    }
+                    Err(e) => eprintln!("Failed to fetch application votes: {:?}", e),
+                }
+            }
+
+            AppCmd::FetchVerifiedPeerCount => {
+                match self.compute_verified_peer_count() {
+                    Ok((total_profiles, verified, founders)) => {
+                        let _ = self.event_tx.send(AppEvent::VerifiedPeerCountFetched { total_profiles, verified, founders });
+                    }
+                    Err(e) => eprintln!("Failed to count verified peers: {:?}", e),
+                }
+            }
+
+            AppCmd::FetchApplicationApprovers { application_id } => {
+                match self.store.get_application_votes(&application_id) {
+                    Ok(votes) => {
+                        let mut approvers: Vec<PeerProfile> = Vec::new();
+                        for vote in votes {
+                            if let dag::DagPayload::ApplicationVote(ref av) = vote.payload {
+                                if av.approve && !approvers.iter().any(|(id, _)| id == &vote.author) {
+                                    let profile = self.store.get_profile(&vote.author).unwrap_or(None);
+                                    approvers.push((vote.author.clone(), profile));
+                                }
+                            }
+                        }
+                        let _ = self.event_tx.send(AppEvent::ApplicationApproversFetched { application_id, approvers });
+                    }
+                    Err(e) => eprintln!("Failed to fetch application approvers: {:?}", e),
+                }
+            }
+
+            AppCmd::FetchPeerVerificationPath { peer_id } => {
+                let mut visited = std::collections::HashSet::new();
+                let path = self.verification_path(&peer_id, &mut visited).unwrap_or_default();
+                let _ = self.event_tx.send(AppEvent::VerificationPathFetched { peer_id, path });
+            }
+
             AppCmd::FetchMyProfile => {
                 let author_pubkey = self.keypair.public();
                 let author_hex = libp2p::PeerId::from_public_key(&author_pubkey).to_string();
@@ -1632,6 +4811,20 @@ impl Backend {
 
 
 
+            AppCmd::FetchSystemParameters => {
+                let network_size = self.verified_network_size();
+                let mut params = HashMap::new();
+                match self.store.get_current_tax_rate() {
+                    Ok(rate) => { params.insert("tax_rate".to_string(), serde_json::json!(rate)); }
+                    Err(e) => eprintln!("Failed to fetch tax rate: {:?}", e),
+                }
+                params.insert("story_ttl_hours".to_string(), serde_json::json!(store::STORY_TTL_HOURS));
+                params.insert("verified_network_size".to_string(), serde_json::json!(network_size));
+                params.insert("required_approvals".to_string(), serde_json::json!(Self::required_approvals(network_size)));
+                params.insert("ubi_daily_amount".to_string(), serde_json::json!(UBI_DAILY_AMOUNT));
+                params.insert("ubi_claim_interval_secs".to_string(), serde_json::json!(UBI_CLAIM_INTERVAL_SECS));
+                let _ = self.event_tx.send(AppEvent::SystemParametersFetched { params });
+            }
             AppCmd::FetchTaxRate => {
                 match self.store.get_current_tax_rate() {
                     Ok(rate) => {
@@ -1641,6 +4834,48 @@ impl Backend {
                 }
             }
 
+            AppCmd::FetchTokenInflationRate { window_days } => {
+                let since = chrono::Utc::now().timestamp() - (window_days as i64) * 86400;
+                let flows = self.store.get_token_flows(since)
+                    .and_then(|window| Ok((window, self.store.get_token_flows(0)?)));
+                match flows {
+                    Ok(((total_minted, total_burned), (all_minted, all_burned))) => {
+                        let net_issuance = total_minted as i64 - total_burned as i64;
+                        let supply_now = all_minted as i64 - all_burned as i64;
+                        let supply_at_start = supply_now - net_issuance;
+                        let inflation_rate_pct = if supply_at_start > 0 {
+                            net_issuance as f64 / supply_at_start as f64 * 100.0
+                        } else {
+                            0.0
+                        };
+                        let _ = self.event_tx.send(AppEvent::TokenInflationRateFetched {
+                            window_days, total_minted, total_burned, net_issuance, inflation_rate_pct
+                        });
+                    }
+                    Err(e) => eprintln!("Failed to fetch token flows: {:?}", e),
+                }
+            }
+
+            AppCmd::FetchNetworkGrowthStats { window_days } => {
+                let evidence = self.store.get_verification_evidence_times();
+                match self.store.get_network_growth(window_days).and_then(|stats| Ok((stats, evidence?))) {
+                    Ok((mut stats, evidence)) => {
+                        // Attribute each verified peer to the day their first verification evidence appeared
+                        for (peer, ts) in evidence {
+                            let day = ts.div_euclid(86400) as u64 * 86400;
+                            if let Some(entry) = stats.daily_new_verified.iter_mut().find(|(d, _)| *d == day) {
+                                let mut visited = std::collections::HashSet::new();
+                                if self.is_verified(&peer, &mut visited) {
+                                    entry.1 += 1;
+                                }
+                            }
+                        }
+                        let _ = self.event_tx.send(AppEvent::NetworkGrowthStatsFetched(stats));
+                    }
+                    Err(e) => eprintln!("Failed to fetch network growth stats: {:?}", e),
+                }
+            }
+
             AppCmd::MintToken { amount } => {
                 if !self.is_caller_verified() {
                     eprintln!("Cannot mint token: User is not verified.");
@@ -1701,6 +4936,12 @@ impl Backend {
                     return;
                 }
 
+                // Tax is withheld from `amount`, so the whole of it counts against the daily limit
+                let author_hex = self.local_peer_id().to_string();
+                if !self.within_spending_limit(amount) {
+                    return;
+                }
+
                 // 1. Calculate Tax
                 let tax_rate = self.store.get_current_tax_rate().unwrap_or(0);
                 let tax_amount = if tax_rate > 0 {
@@ -1719,9 +4960,6 @@ impl Backend {
                     ref_cid: None,
                 });
                 
-                let author_pubkey = self.keypair.public();
-                let author_hex = libp2p::PeerId::from_public_key(&author_pubkey).to_string();
-                
                 let prev = match self.store.get_head(&author_hex) {
                     Ok(Some(cid)) => vec![cid],
                     Ok(None) => vec![],
@@ -1796,6 +5034,17 @@ impl Backend {
                 }
             }
 
+            AppCmd::SetSpendingLimit { daily_limit } => {
+                if let Err(e) = self.store.set_spending_limit(daily_limit) {
+                    eprintln!("Failed to set spending limit: {:?}", e);
+                    return;
+                }
+                self.send_spending_limit();
+            }
+            AppCmd::FetchSpendingLimit => {
+                self.send_spending_limit();
+            }
+
             AppCmd::ClaimToken { burn_cid } => {
                 if !self.is_caller_verified() {
                     eprintln!("Cannot claim token: User is not verified.");
@@ -1880,6 +5129,34 @@ impl Backend {
                 }
             }
 
+            AppCmd::FetchTransactionHistory { peer_id, offset, limit } => {
+                match self.store.get_token_history(&peer_id, offset, limit) {
+                    Ok((nodes, total)) => {
+                        let records = nodes.into_iter().filter_map(|n| self.transaction_record(n, &peer_id)).collect();
+                        let _ = self.event_tx.send(AppEvent::TransactionHistoryFetched { peer_id, records, total });
+                    }
+                    Err(e) => eprintln!("Failed to fetch transaction history: {:?}", e),
+                }
+            }
+            AppCmd::ProposeMultiSigTransfer { recipients, required_signers, threshold, expires_at } => {
+                self.propose_multisig_transfer(recipients, required_signers, threshold, expires_at);
+            }
+            AppCmd::ApproveMultiSigTransfer { proposal_cid } => {
+                self.approve_multisig_transfer(proposal_cid);
+            }
+            AppCmd::FetchMultiSigProposals => {
+                // Catch up on approvals that arrived by sync rather than as they were published
+                let author_hex = self.local_peer_id().to_string();
+                let ready: Vec<String> = self.store.get_multisig_proposals(&author_hex).unwrap_or_default()
+                    .into_iter()
+                    .filter(|(node, _, transfer)| node.author == author_hex && transfer.is_none())
+                    .map(|(node, _, _)| node.id)
+                    .collect();
+                for proposal_cid in ready {
+                    self.execute_multisig_if_ready(&proposal_cid);
+                }
+                self.send_multisig_proposals();
+            }
             AppCmd::FetchPendingTransfers => {
                 let author_pubkey = self.keypair.public();
                 let author_hex = libp2p::PeerId::from_public_key(&author_pubkey).to_string();
@@ -1890,6 +5167,28 @@ impl Backend {
                     Err(e) => eprintln!("Failed to fetch pending transfers: {:?}", e),
                 }
             }
+            AppCmd::FetchIncomingTransfers => {
+                let my_pubkey = self.local_peer_id().to_string();
+                match self.store.get_pending_transfers(&my_pubkey) {
+                    Ok(pending) => {
+                        // Look up each sender once, even if they sent several transfers
+                        let mut sender_profiles: std::collections::HashMap<String, Option<dag::ProfilePayload>> = std::collections::HashMap::new();
+                        let mut transfers = Vec::new();
+                        for node in pending {
+                            let amount = match &node.payload {
+                                dag::DagPayload::Token(t) => t.amount,
+                                _ => continue,
+                            };
+                            let profile = sender_profiles.entry(node.author.clone())
+                                .or_insert_with(|| self.store.get_profile(&node.author).ok().flatten())
+                                .clone();
+                            transfers.push((node, profile, amount));
+                        }
+                        let _ = self.event_tx.send(AppEvent::IncomingTransfersFetched { transfers });
+                    }
+                    Err(e) => eprintln!("Failed to fetch incoming transfers: {:?}", e),
+                }
+            }
 
             AppCmd::FetchBalance => {
                 let author_pubkey = self.keypair.public();
@@ -1943,11 +5242,10 @@ impl Backend {
                             .duration_since(std::time::UNIX_EPOCH)
                             .unwrap()
                             .as_secs();
-                        // 24 hours = 86400 seconds
-                        if now > last_ts + 86400 {
+                        if now > last_ts + UBI_CLAIM_INTERVAL_SECS {
                             true
                         } else {
-                            println!("Cannot claim UBI yet. Next claim in {} seconds", (last_ts + 86400) - now);
+                            println!("Cannot claim UBI yet. Next claim in {} seconds", (last_ts + UBI_CLAIM_INTERVAL_SECS) - now);
                             false
                         }
                     }
@@ -1961,7 +5259,7 @@ impl Backend {
                 if can_claim {
                     let payload = dag::DagPayload::Token(dag::TokenPayload {
                         action: dag::TokenAction::Mint,
-                        amount: 10,
+                        amount: UBI_DAILY_AMOUNT,
                         target: None,
                         memo: Some("UBI Daily Claim".to_string()),
                         ref_cid: None,
@@ -2078,8 +5376,40 @@ impl Backend {
                     Err(e) => eprintln!("Failed to fetch local listings: {:?}", e),
                 }
             }
+            AppCmd::FetchLocalListingsByCategory { geohash_prefix, category, price_min, price_max } => {
+                match self.store.get_listings_by_geohash_and_category(&geohash_prefix, &category, price_min, price_max, 50) {
+                    Ok(listings) => {
+                        let _ = self.event_tx.send(AppEvent::LocalListingsFetched(listings));
+                    }
+                    Err(e) => eprintln!("Failed to fetch filtered listings: {:?}", e),
+                }
+            }
+
+            AppCmd::FetchWebSearchResultsFromPeer { query, peer_id } => {
+                let local = self.store.search_web_pages_by_author(&query, &peer_id).unwrap_or_default();
+                if !local.is_empty() {
+                    let _ = self.event_tx.send(AppEvent::WebSearchResults(local));
+                    return;
+                }
+                // Nothing replicated here; ask the author directly
+                match peer_id.parse::<PeerId>() {
+                    Ok(peer) => {
+                        let _ = self.send_block_request(&peer, BlockRequest::LocalSearch(query, Some(peer_id)));
+                    }
+                    Err(_) => {
+                        eprintln!("Invalid peer id: {}", peer_id);
+                        let _ = self.event_tx.send(AppEvent::WebSearchResults(vec![]));
+                    }
+                }
+            }
 
             AppCmd::SearchWeb { query } => {
+                // A '#tag' query is answered from the local hashtag index
+                if query.trim_start().starts_with('#') {
+                    self.send_hashtag_posts(&query);
+                    return;
+                }
+
                 // 1. Local Search
                 match self.store.search_web_pages(&query) {
                     Ok(nodes) => {
@@ -2102,55 +5432,51 @@ impl Backend {
                 }
             }
             AppCmd::DeployContract { code, init_params } => {
-                 if !self.is_caller_verified() {
-                    eprintln!("Cannot deploy contract: User is not verified.");
-                    return;
+                self.deploy_contract(code, init_params, None, None);
+            }
+            AppCmd::DeployFromTemplate { template_id, init_params } => {
+                // The template is an existing contract whose code is reused as-is
+                match self.store.get_node(&template_id) {
+                    Ok(Some(dag::DagNode { payload: dag::DagPayload::Contract(template), .. })) => {
+                        self.deploy_contract(template.code, init_params, Some(template_id), None);
+                    }
+                    _ => eprintln!("Cannot deploy from template: {} is not a known contract", template_id),
                 }
-                let payload = dag::DagPayload::Contract(dag::ContractPayload { 
-                    code, 
-                    init_params,
-                    status: dag::ContractStatus::Pending,
-                });
-                let author_pubkey = self.keypair.public();
-                let author_hex = libp2p::PeerId::from_public_key(&author_pubkey).to_string();
-                 let prev = match self.store.get_head(&author_hex) {
-                    Ok(Some(cid)) => vec![cid],
-                    Ok(None) => vec![],
-                    Err(e) => {
-                        eprintln!("Failed to get head: {:?}", e);
-                        vec![]
+            }
+            AppCmd::UpgradeContract { contract_id, code, init_params } => {
+                // Only the deployer of a contract may publish a newer version of it
+                let my_peer_id = self.local_peer_id().to_string();
+                match self.store.get_node(&contract_id) {
+                    Ok(Some(dag::DagNode { payload: dag::DagPayload::Contract(parent), author, .. })) if author == my_peer_id => {
+                        self.deploy_contract(code, init_params, parent.template_id, Some(contract_id));
                     }
-                };
-
-                match dag::DagNode::new(
-                    "contract:v1".to_string(),
-                    payload,
-                    prev,
-                    &self.keypair,
-                    0
-                ) {
-                    Ok(node) => {
-                        println!("Created contract node: {}", node.id);
-                         if let Err(e) = self.store.put_node(&node) {
-                            eprintln!("Failed to store contract node: {:?}", e);
-                            return;
-                        }
-                         if let Err(e) = self.store.update_head(&author_hex, &node.id) {
-                            eprintln!("Failed to update head: {:?}", e);
-                        }
-                        let topic = gossipsub::IdentTopic::new("blocks");
-                        if let Err(e) = self.swarm.behaviour_mut().gossipsub.publish(topic, node.id.as_bytes()) {
-                            eprintln!("Failed to publish contract CID: {:?}", e);
-                        }
-                        self.replicate_block(&node);
-                        
-                        // Treat as block received to update UI if needed
-                        let _ = self.event_tx.send(AppEvent::BlockReceived(node.clone()));
+                    Ok(Some(dag::DagNode { payload: dag::DagPayload::Contract(_), .. })) => {
+                        eprintln!("Cannot upgrade contract: Only its deployer can publish a new version.");
+                    }
+                    _ => eprintln!("Cannot upgrade contract: {} is not a known contract", contract_id),
+                }
+            }
+            AppCmd::FetchContractVersionHistory { contract_id } => {
+                match self.store.get_contract_version_history(&contract_id) {
+                    Ok(versions) => {
+                        let _ = self.event_tx.send(AppEvent::ContractVersionHistoryFetched { contract_id, versions });
+                    }
+                    Err(e) => eprintln!("Failed to fetch contract version history: {:?}", e),
+                }
+            }
+            AppCmd::FetchContractsByTemplate { template_id } => {
+                match self.store.get_contracts_by_template(&template_id) {
+                    Ok(contracts) => {
+                        let _ = self.event_tx.send(AppEvent::TemplateDeploymentCountFetched { template_id, count: contracts.len() });
+                        let _ = self.event_tx.send(AppEvent::ContractsFetched(contracts));
                     }
-                    Err(e) => eprintln!("Failed to create contract node: {:?}", e),
+                    Err(e) => eprintln!("Failed to fetch contracts by template: {:?}", e),
                 }
             }
-            AppCmd::CallContract { contract_id, method, params } => {
+            AppCmd::SimulateContractCall { contract_id, method, params } | AppCmd::CallContract { contract_id, method, params, dry_run: true } => {
+                self.simulate_contract_call(contract_id, method, params);
+            }
+            AppCmd::CallContract { contract_id, method, params, dry_run: false } => {
                  if !self.is_caller_verified() {
                     eprintln!("Cannot call contract: User is not verified.");
                     return;
@@ -2200,33 +5526,116 @@ impl Backend {
                     Ok(contracts) => {
                         let _ = self.event_tx.send(AppEvent::ContractsFetched(contracts));
                     }
-                    Err(e) => eprintln!("Failed to fetch contracts: {:?}", e),
+                    Err(e) => eprintln!("Failed to fetch contracts: {:?}", e),
+                }
+            }
+
+            AppCmd::FetchContractState { contract_id } => {
+                let (final_state_str, events) = match self.contract_state(&contract_id) {
+                    Ok(replay) => replay,
+                    Err(e) => {
+                        eprintln!("Contract {} failed: {}", contract_id, e);
+                        let _ = self.event_tx.send(AppEvent::ContractExecutionFailed { contract_id: contract_id.clone(), reason: e.to_string() });
+                        (format!("ERROR: {}", e), Vec::new())
+                    }
+                };
+                let _ = self.event_tx.send(AppEvent::ContractStateFetched { contract_id: contract_id.clone(), state: final_state_str });
+                for event in events {
+                    let _ = self.event_tx.send(AppEvent::ContractEvent { contract_id: contract_id.clone(), event });
+                }
+            }
+            AppCmd::SubscribeContractEvents { contract_id } => {
+                self.event_subscriptions.insert(contract_id);
+            }
+            AppCmd::UnsubscribeContractEvents { contract_id } => {
+                self.event_subscriptions.remove(&contract_id);
+            }
+
+            AppCmd::SetVmGasLimit { limit } => {
+                self.vm_gas_limit = limit;
+            }
+
+            AppCmd::SetMinPeers { count } => {
+                self.min_peers = count;
+                self.check_peer_count();
+            }
+
+            AppCmd::FetchContractMilestones { contract_id } => {
+                self.send_contract_milestones(contract_id);
+            }
+
+            AppCmd::CompleteMilestone { contract_id, milestone_index } => {
+                let author_hex = self.local_peer_id().to_string();
+                match self.contract_parties(&contract_id) {
+                    Some((provider, _)) if provider == author_hex => {}
+                    _ => {
+                        eprintln!("Cannot complete milestone on {}: only the provider can", contract_id);
+                        return;
+                    }
+                }
+                match self.contract_milestones(&contract_id).get(milestone_index) {
+                    Some(m) if !m.completed => {}
+                    Some(_) => {
+                        eprintln!("Milestone {} of {} is already completed", milestone_index, contract_id);
+                        return;
+                    }
+                    None => {
+                        eprintln!("Contract {} has no milestone {}", contract_id, milestone_index);
+                        return;
+                    }
+                }
+
+                let payload = dag::DagPayload::MilestoneCompletion(dag::MilestoneCompletionPayload { contract_id: contract_id.clone(), milestone_index });
+                let prev = self.store.get_head(&author_hex).ok().flatten().map_or(vec![], |c| vec![c]);
+
+                match dag::DagNode::new("milestone_completion:v1".to_string(), payload, prev, &self.keypair, 0) {
+                    Ok(node) => {
+                        println!("Completed milestone {} of contract {}", milestone_index, contract_id);
+                        if let Err(e) = self.store.put_node(&node) {
+                            eprintln!("Failed to store milestone completion: {:?}", e);
+                            return;
+                        }
+                        let _ = self.store.update_head(&author_hex, &node.id);
+                        let topic = gossipsub::IdentTopic::new("blocks");
+                        let _ = self.swarm.behaviour_mut().gossipsub.publish(topic, node.id.as_bytes());
+                        self.replicate_block(&node);
+                        let _ = self.event_tx.send(AppEvent::BlockReceived(node));
+                        self.send_contract_milestones(contract_id);
+                    }
+                    Err(e) => eprintln!("Failed to create milestone completion: {:?}", e),
                 }
             }
 
-            AppCmd::FetchContractState { contract_id } => {
-                // 1. Get the contract itself to find initial state
-                let (init_state, code) = match self.store.get_node(&contract_id) {
+            AppCmd::FetchContractSecurityAudit { contract_id } => {
+                match self.store.get_node(&contract_id) {
                     Ok(Some(node)) => {
                         if let dag::DagPayload::Contract(c) = node.payload {
-                            (c.init_params, c.code)
+                            let findings = VM::audit_contract(&c.code);
+                            let _ = self.event_tx.send(AppEvent::ContractAuditFetched { contract_id, findings });
                         } else {
-                            ("{}".to_string(), "".to_string())
+                            eprintln!("Cannot audit {}: not a contract", contract_id);
                         }
-                    },
-                    _ => ("{}".to_string(), "".to_string()),
-                };
-
-                // 2. Get all calls
-                let calls = match self.store.get_contract_calls(&contract_id) {
-                     Ok(calls) => calls,
-                     Err(_) => vec![],
+                    }
+                    Ok(None) => eprintln!("Cannot audit {}: contract not found", contract_id),
+                    Err(e) => eprintln!("Failed to fetch contract for audit: {:?}", e),
+                }
+            }
+            AppCmd::FetchContractDeployerReputation { contract_id } => {
+                let deployer_peer_id = match self.store.get_node(&contract_id) {
+                    Ok(Some(node)) if matches!(node.payload, dag::DagPayload::Contract(_)) => node.author,
+                    Ok(_) => {
+                        eprintln!("Cannot fetch deployer of {}: contract not found", contract_id);
+                        return;
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to fetch contract: {:?}", e);
+                        return;
+                    }
                 };
-
-                // 3. Calculate State via VM
-                let final_state_str = VM::calculate_contract_state(&init_state, &code, &calls);
-
-                let _ = self.event_tx.send(AppEvent::ContractStateFetched { contract_id, state: final_state_str });
+                let reputation = self.store.get_reputation(&deployer_peer_id).ok();
+                let certifications = self.store.get_certifications(&deployer_peer_id).unwrap_or_default();
+                let vouch_count = self.store.get_proofs(&deployer_peer_id).map(|p| p.len()).unwrap_or(0);
+                let _ = self.event_tx.send(AppEvent::ContractDeployerInfoFetched { contract_id, deployer_peer_id, reputation, certifications, vouch_count });
             }
 
             AppCmd::FetchContractHistory { contract_id } => {
@@ -2311,22 +5720,52 @@ impl Backend {
                 }
             }
 
-            AppCmd::PayContract { contract_id, amount } => {
+            AppCmd::PayContract { contract_id, amount, milestone_index } => {
                 // Parse the contract to find the recipient (provider)
-                let recipient = match self.store.get_node(&contract_id) {
-                    Ok(Some(node)) => {
-                        if let dag::DagPayload::Contract(c) = &node.payload {
-                            let params: serde_json::Value = serde_json::from_str(&c.init_params).unwrap_or(serde_json::json!({}));
-                            params["parties"]["provider"].as_str().unwrap_or("").to_string()
-                        } else {
-                            "".to_string()
-                        }
+                let (recipient, consumer) = match self.contract_parties(&contract_id) {
+                    Some(parties) if !parties.0.is_empty() => parties,
+                    _ => {
+                        eprintln!("Could not determine recipient from contract");
+                        return;
                     }
-                    _ => "".to_string(),
                 };
 
-                if recipient.is_empty() {
-                    eprintln!("Could not determine recipient from contract");
+                // Only the consumer is expected to pay, not an uninvolved third party
+                if consumer != self.local_peer_id().to_string() {
+                    eprintln!("Cannot pay contract {}: you are not its consumer", contract_id);
+                    return;
+                }
+
+                // Milestone contracts only pay out for work the provider has marked complete
+                let milestones = self.contract_milestones(&contract_id);
+                let (amount, memo) = if milestones.is_empty() {
+                    (amount, format!("Contract payment: {}", contract_id))
+                } else {
+                    let Some(index) = milestone_index else {
+                        eprintln!("Cannot pay contract {}: a milestone must be specified", contract_id);
+                        return;
+                    };
+                    let milestone = match milestones.get(index) {
+                        Some(m) if m.completed => m,
+                        Some(_) => {
+                            eprintln!("Cannot pay contract {}: milestone {} is not completed", contract_id, index);
+                            return;
+                        }
+                        None => {
+                            eprintln!("Cannot pay contract {}: no milestone {}", contract_id, index);
+                            return;
+                        }
+                    };
+                    let memo = format!("Contract milestone {} payment: {}", index, contract_id);
+                    let already_paid = self.store.get_nodes_by_ref(&contract_id).unwrap_or_default().iter()
+                        .any(|n| matches!(&n.payload, dag::DagPayload::Token(t) if t.memo.as_deref() == Some(memo.as_str())));
+                    if already_paid {
+                        eprintln!("Cannot pay contract {}: milestone {} is already paid", contract_id, index);
+                        return;
+                    }
+                    (milestone.amount, memo)
+                };
+                if !self.within_spending_limit(amount) {
                     return;
                 }
 
@@ -2335,7 +5774,7 @@ impl Backend {
                     action: dag::TokenAction::Burn,
                     amount,
                     target: Some(recipient.clone()),
-                    memo: Some(format!("Contract payment: {}", contract_id)),
+                    memo: Some(memo),
                     ref_cid: Some(contract_id.clone()),
                 });
                 let author_pubkey = self.keypair.public();
@@ -2431,6 +5870,27 @@ impl Backend {
                 }
             }
 
+            AppCmd::CreatePetition { title, body, target_ministry, threshold } => {
+                self.create_petition(title, body, target_ministry, threshold);
+            }
+            AppCmd::SignPetition { petition_id } => {
+                self.sign_petition(petition_id);
+            }
+            AppCmd::FetchPetitions => {
+                match self.store.get_petitions() {
+                    Ok(petitions) => {
+                        let _ = self.event_tx.send(AppEvent::PetitionsFetched(petitions));
+                    }
+                    Err(e) => eprintln!("Failed to fetch petitions: {:?}", e),
+                }
+            }
+            AppCmd::FetchPetitionSignatures { petition_id } => {
+                self.send_petition_signatures(petition_id);
+            }
+            AppCmd::ConvertPetitionToProposal { petition_id } => {
+                self.convert_petition_to_proposal(petition_id);
+            }
+
             AppCmd::FetchRecalls => {
                 match self.store.get_recalls() {
                     Ok(recalls) => {
@@ -2469,6 +5929,18 @@ impl Backend {
                     }
                 }
                 
+                // Prefer jurors holding the ModerationJury certification when enough exist
+                if let Ok(certified) = self.store.get_certified_peers("ModerationJury") {
+                    if certified.len() >= 3 {
+                        candidates = certified.into_iter()
+                            .filter_map(|node| match node.payload {
+                                dag::DagPayload::Certification(cert) => Some(cert.recipient),
+                                _ => None,
+                            })
+                            .collect();
+                    }
+                }
+
                 // Randomly select 3
                 use rand::seq::SliceRandom;
                 let mut rng = rand::thread_rng();
@@ -2531,6 +6003,257 @@ impl Backend {
                 }
             }
 
+            AppCmd::FetchCandidatePlatform { candidacy_id } => {
+                let node = match self.store.get_node(&candidacy_id) {
+                    Ok(Some(node)) if matches!(node.payload, dag::DagPayload::Candidacy(_)) => node,
+                    Ok(_) => {
+                        eprintln!("Candidacy {} not found", candidacy_id);
+                        return;
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to fetch candidacy: {:?}", e);
+                        return;
+                    }
+                };
+                let candidate = node.author.clone();
+                let profile = self.store.get_profile(&candidate).ok().flatten();
+                let certifications = self.store.get_certifications(&candidate).unwrap_or_default();
+                let reputation = self.store.get_reputation(&candidate).ok();
+                let vote_history = self.vote_history_with_titles(&candidate).unwrap_or_default();
+                let _ = self.event_tx.send(AppEvent::CandidatePlatformFetched { candidacy_id, node, profile, certifications, reputation, vote_history });
+            }
+            AppCmd::FetchPeersByGeohash { geohash_prefix, include_profiles } => {
+                match self.store.get_peers_with_geohash_activity(&geohash_prefix) {
+                    Ok(peer_ids) => {
+                        let peers = peer_ids.into_iter()
+                            .map(|peer_id| {
+                                let profile = if include_profiles { self.store.get_profile(&peer_id).ok().flatten() } else { None };
+                                (peer_id, profile)
+                            })
+                            .collect();
+                        let _ = self.event_tx.send(AppEvent::PeersByGeohashFetched { geohash_prefix, peers });
+                    }
+                    Err(e) => eprintln!("Failed to fetch peers by geohash: {:?}", e),
+                }
+            }
+            AppCmd::FetchFollowingSuggestions { limit } => {
+                let my_pubkey = self.local_peer_id().to_string();
+                // Neighbourhood-sized area around our last announced location
+                let area = self.current_geohash.as_ref().map(|gh| gh.chars().take(4).collect::<String>());
+                match self.store.get_follow_suggestions(&my_pubkey, area.as_deref(), limit) {
+                    Ok(candidates) => {
+                        let suggestions = candidates.into_iter()
+                            .map(|(peer_id, reason)| {
+                                let profile = self.store.get_profile(&peer_id).ok().flatten();
+                                (peer_id, profile, reason)
+                            })
+                            .collect();
+                        let _ = self.event_tx.send(AppEvent::FollowingSuggestionsFetched(suggestions));
+                    }
+                    Err(e) => eprintln!("Failed to fetch following suggestions: {:?}", e),
+                }
+            }
+            AppCmd::FetchContractParties { contract_id } => {
+                match self.contract_parties(&contract_id) {
+                    Some((provider_id, consumer_id)) => {
+                        let provider_profile = self.store.get_profile(&provider_id).ok().flatten();
+                        let consumer_profile = self.store.get_profile(&consumer_id).ok().flatten();
+                        let _ = self.event_tx.send(AppEvent::ContractPartiesFetched {
+                            contract_id,
+                            provider: (provider_id, provider_profile),
+                            consumer: (consumer_id, consumer_profile),
+                        });
+                    }
+                    None => eprintln!("Could not determine parties for contract {}", contract_id),
+                }
+            }
+            AppCmd::CreateEscrow { contract_id, amount } => {
+                let author_hex = self.local_peer_id().to_string();
+                let provider = match self.contract_parties(&contract_id) {
+                    Some((provider, consumer)) if consumer == author_hex && !provider.is_empty() => provider,
+                    _ => {
+                        eprintln!("Cannot create escrow for contract {}: only its consumer can lock funds", contract_id);
+                        return;
+                    }
+                };
+                if amount == 0 {
+                    eprintln!("Cannot create escrow: amount must be positive");
+                    return;
+                }
+                if let Ok(Some((_, dag::EscrowStatus::Locked | dag::EscrowStatus::Disputed))) = self.store.get_contract_escrow(&contract_id) {
+                    eprintln!("Cannot create escrow for contract {}: funds are already locked", contract_id);
+                    return;
+                }
+                let balance = self.store.get_balance(&author_hex).unwrap_or(0);
+                if balance < amount as i64 {
+                    eprintln!("Cannot create escrow: insufficient balance ({} < {})", balance, amount);
+                    return;
+                }
+                if !self.within_spending_limit(amount) {
+                    return;
+                }
+
+                let payload = dag::DagPayload::Escrow(dag::EscrowPayload { contract_id: contract_id.clone(), amount, counterparty: provider });
+                let prev = self.store.get_head(&author_hex).ok().flatten().map_or(vec![], |c| vec![c]);
+                match dag::DagNode::new("escrow:v1".to_string(), payload, prev, &self.keypair, 0) {
+                    Ok(node) => {
+                        println!("Locked {} tokens in escrow for contract {}", amount, contract_id);
+                        if let Err(e) = self.store.put_node(&node) {
+                            eprintln!("Failed to store escrow node: {:?}", e);
+                            return;
+                        }
+                        let _ = self.store.update_head(&author_hex, &node.id);
+                        let topic = gossipsub::IdentTopic::new("blocks");
+                        let _ = self.swarm.behaviour_mut().gossipsub.publish(topic, node.id.as_bytes());
+                        self.replicate_block(&node);
+                        let _ = self.event_tx.send(AppEvent::BlockReceived(node));
+                        self.send_escrow_status(contract_id);
+                        if let Ok(balance) = self.store.get_balance(&author_hex) {
+                            let _ = self.event_tx.send(AppEvent::BalanceFetched(balance));
+                        }
+                    }
+                    Err(e) => eprintln!("Failed to create escrow node: {:?}", e),
+                }
+            }
+            AppCmd::SettleEscrow { contract_id, outcome } => {
+                let author_hex = self.local_peer_id().to_string();
+                let (escrow, status) = match self.store.get_contract_escrow(&contract_id) {
+                    Ok(Some(record)) => record,
+                    _ => {
+                        eprintln!("Cannot settle escrow: contract {} has none", contract_id);
+                        return;
+                    }
+                };
+                let counterparty = match &escrow.payload {
+                    dag::DagPayload::Escrow(e) => e.counterparty.clone(),
+                    _ => return,
+                };
+                if matches!(status, dag::EscrowStatus::Released | dag::EscrowStatus::Refunded) {
+                    eprintln!("Cannot settle escrow for contract {}: it is already {:?}", contract_id, status);
+                    return;
+                }
+                let allowed = match outcome {
+                    dag::EscrowStatus::Released => escrow.author == author_hex,
+                    dag::EscrowStatus::Refunded => counterparty == author_hex,
+                    dag::EscrowStatus::Disputed => escrow.author == author_hex || counterparty == author_hex,
+                    dag::EscrowStatus::Locked => false,
+                };
+                if !allowed {
+                    eprintln!("Cannot mark escrow for contract {} as {:?}", contract_id, outcome);
+                    return;
+                }
+
+                let payload = dag::DagPayload::EscrowSettlement(dag::EscrowSettlementPayload { escrow_id: escrow.id.clone(), outcome });
+                let prev = self.store.get_head(&author_hex).ok().flatten().map_or(vec![], |c| vec![c]);
+                match dag::DagNode::new("escrow_settlement:v1".to_string(), payload, prev, &self.keypair, 0) {
+                    Ok(node) => {
+                        println!("Escrow for contract {} marked {:?}", contract_id, outcome);
+                        if let Err(e) = self.store.put_node(&node) {
+                            eprintln!("Failed to store escrow settlement node: {:?}", e);
+                            return;
+                        }
+                        let _ = self.store.update_head(&author_hex, &node.id);
+                        let topic = gossipsub::IdentTopic::new("blocks");
+                        let _ = self.swarm.behaviour_mut().gossipsub.publish(topic, node.id.as_bytes());
+                        self.replicate_block(&node);
+                        let _ = self.event_tx.send(AppEvent::BlockReceived(node));
+                        self.send_escrow_status(contract_id);
+                        if let Ok(balance) = self.store.get_balance(&author_hex) {
+                            let _ = self.event_tx.send(AppEvent::BalanceFetched(balance));
+                        }
+                    }
+                    Err(e) => eprintln!("Failed to create escrow settlement node: {:?}", e),
+                }
+            }
+            AppCmd::FetchContractEscrowStatus { contract_id } => {
+                self.send_escrow_status(contract_id);
+            }
+            AppCmd::FetchMyEscrows => {
+                match self.store.get_escrows_for_peer(&self.local_peer_id().to_string()) {
+                    Ok(escrows) => {
+                        let mut seen = std::collections::HashSet::new();
+                        for (node, status) in escrows {
+                            if let dag::DagPayload::Escrow(e) = node.payload {
+                                // Newest first, so only each contract's latest escrow is reported
+                                if seen.insert(e.contract_id.clone()) {
+                                    let _ = self.event_tx.send(AppEvent::EscrowStatusFetched { contract_id: e.contract_id, locked_amount: e.amount, status, counterparty: e.counterparty });
+                                }
+                            }
+                        }
+                    }
+                    Err(e) => eprintln!("Failed to fetch escrows: {:?}", e),
+                }
+            }
+            AppCmd::FetchMutualFollowers { peer_id } => {
+                let my_pubkey = self.local_peer_id().to_string();
+                match self.store.get_mutual_followers(&my_pubkey, &peer_id) {
+                    Ok(ids) => {
+                        let mutual = ids.into_iter()
+                            .map(|id| {
+                                let profile = self.store.get_profile(&id).ok().flatten();
+                                (id, profile)
+                            })
+                            .collect();
+                        let _ = self.event_tx.send(AppEvent::MutualFollowersFetched { peer_id, mutual });
+                    }
+                    Err(e) => eprintln!("Failed to fetch mutual followers: {:?}", e),
+                }
+            }
+            AppCmd::FetchConnectedPeerProfiles => {
+                let peer_ids: Vec<String> = self.swarm.connected_peers().map(|p| p.to_string()).collect();
+                let profiles = peer_ids.into_iter()
+                    .map(|peer_id| {
+                        let profile = self.store.get_profile(&peer_id).ok().flatten();
+                        (peer_id, profile)
+                    })
+                    .collect();
+                let _ = self.event_tx.send(AppEvent::ConnectedPeerProfilesFetched(profiles));
+            }
+            AppCmd::FetchCertificationLeaderboard { cert_type } => {
+                match self.store.get_certified_peers(&cert_type) {
+                    Ok(certs) => {
+                        let certified_peers = certs.into_iter()
+                            .filter_map(|node| match node.payload {
+                                dag::DagPayload::Certification(cert) => {
+                                    let profile = self.store.get_profile(&cert.recipient).ok().flatten();
+                                    Some((cert.recipient, profile, cert.issued_at))
+                                }
+                                _ => None,
+                            })
+                            .collect();
+                        let _ = self.event_tx.send(AppEvent::CertificationLeaderboardFetched { cert_type, certified_peers });
+                    }
+                    Err(e) => eprintln!("Failed to fetch certified peers: {:?}", e),
+                }
+            }
+            AppCmd::FetchMyJuryStats => {
+                let my_pubkey = self.local_peer_id().to_string();
+                match self.store.get_jury_stats(&my_pubkey) {
+                    Ok((cases_assigned, votes_cast, cases_decided, avg_time_to_vote_hours)) => {
+                        let _ = self.event_tx.send(AppEvent::JuryStatsFetched { cases_assigned, votes_cast, cases_decided, avg_time_to_vote_hours });
+                    }
+                    Err(e) => eprintln!("Failed to fetch jury stats: {:?}", e),
+                }
+            }
+
+            AppCmd::FetchOversightCasesByJuror { juror_peer_id } => {
+                match self.store.get_cases_by_juror(&juror_peer_id) {
+                    Ok(cases) => {
+                        let _ = self.event_tx.send(AppEvent::OversightCasesFetched(cases));
+                    }
+                    Err(e) => eprintln!("Failed to fetch cases by juror: {:?}", e),
+                }
+            }
+
+            AppCmd::FetchOversightStatsByMinistry => {
+                match self.store.get_oversight_stats_by_ministry() {
+                    Ok(stats) => {
+                        let _ = self.event_tx.send(AppEvent::OversightStatsFetched { stats });
+                    }
+                    Err(e) => eprintln!("Failed to fetch oversight stats: {:?}", e),
+                }
+            }
+
             AppCmd::FetchPublicLedger => {
                 match self.store.get_public_ledger_events(50) {
                     Ok(events) => {
@@ -2550,6 +6273,7 @@ impl Backend {
                     Ok(Some(content)) => {
                         let final_content = process_content(content);
                         let _ = self.event_tx.send(AppEvent::WebPageFetched { url: url.clone(), content: Some(final_content) });
+                        self.record_web_view(&url);
                     }
                     Ok(None) => {
                         // 2. Try to resolve as name
@@ -2561,6 +6285,7 @@ impl Backend {
                                     Ok(Some(content)) => {
                                         let final_content = process_content(content);
                                         let _ = self.event_tx.send(AppEvent::WebPageFetched { url: url.clone(), content: Some(final_content) });
+                                        self.record_web_view(&target);
                                     }
                                     Ok(None) => {
                                         // Not found locally, try DHT for target
@@ -2594,6 +6319,27 @@ impl Backend {
                 }
             }
 
+            AppCmd::RecordWebView { url } => {
+                self.record_web_view(&url);
+            }
+
+            AppCmd::FetchWebStats { url } => {
+                let my_pubkey = self.local_peer_id().to_string();
+                match self.store.get_web_page_node(&url) {
+                    Ok(Some(page)) if page.author == my_pubkey => {}
+                    _ => {
+                        eprintln!("Cannot fetch web stats for {}: not the page author", url);
+                        return;
+                    }
+                }
+                match self.store.get_web_view_count(&url) {
+                    Ok((total_views, unique_viewers, views_last_7_days)) => {
+                        let _ = self.event_tx.send(AppEvent::WebStatsFetched { url, total_views, unique_viewers, views_last_7_days });
+                    }
+                    Err(e) => eprintln!("Failed to fetch web stats: {:?}", e),
+                }
+            }
+
             AppCmd::RegisterName { name, target } => {
                 if !self.is_caller_verified() {
                     eprintln!("Cannot register name: User is not verified.");
@@ -2654,47 +6400,110 @@ impl Backend {
             }
 
             AppCmd::FetchBlock { cid, peer_id } => {
-                // 1. Check local store
-                if let Ok(Some(node)) = self.store.get_node(&cid) {
-                     let _ = self.event_tx.send(AppEvent::BlockFetched { cid, node: Some(node) });
-                     return;
+                self.fetch_block(cid, peer_id);
+            }
+
+            AppCmd::RequestFullSync { peer_id, since } => self.request_full_sync(&peer_id, since.unwrap_or(0)),
+            AppCmd::DialPeer { multiaddr } => {
+                self.dial_peer(multiaddr);
+            }
+
+            AppCmd::FetchWebPageEmbeds { url } => {
+                // Follow a name record when the URL is not itself a page
+                let content = match self.store.get_web_page(&url) {
+                    Ok(Some(content)) => Some(content),
+                    _ => match self.store.get_name_record(&url) {
+                        Ok(Some(target)) => self.store.get_web_page(&target).ok().flatten(),
+                        _ => None,
+                    },
+                };
+                let blob_cids = content.map(|c| VM::extract_blob_references(&c)).unwrap_or_default();
+                for cid in &blob_cids {
+                    self.fetch_block(cid.clone(), None);
                 }
-                
-                // 2. Fetch from peer or DHT
-                let do_dht = if let Some(pid_str) = peer_id {
-                    if pid_str.is_empty() {
-                        true
-                    } else {
-                        if let Ok(peer) = pid_str.parse::<PeerId>() {
-                            let request_id = self.swarm.behaviour_mut().request_response.send_request(&peer, BlockRequest::Fetch(cid.clone()));
-                            self.pending_requests.insert(request_id, cid.clone());
-                            false
-                        } else {
-                            eprintln!("Invalid peer id: {}", pid_str);
-                            true
-                        }
+                let _ = self.event_tx.send(AppEvent::WebPageEmbedsFetched { url, blob_cids });
+            }
+
+            AppCmd::FetchStorageStats => {
+                self.send_storage_stats();
+            }
+
+            AppCmd::ExportIdentity { password } => {
+                match identity::export_bundle(&self.keypair, &self.encryption_keypair.to_bytes(), &password) {
+                    Ok(bundle) => {
+                        let _ = self.event_tx.send(AppEvent::IdentityExported { bundle_b64: general_purpose::STANDARD.encode(bundle) });
                     }
-                } else {
-                    true
+                    Err(e) => eprintln!("Failed to export identity: {:?}", e),
+                }
+            }
+
+            AppCmd::ImportIdentity { bundle_b64, password } => {
+                if let Err(e) = self.import_identity(&bundle_b64, &password) {
+                    eprintln!("Failed to import identity: {}", e);
+                    let _ = self.event_tx.send(AppEvent::ImportFailed { reason: e.to_string() });
+                }
+            }
+
+            AppCmd::VacuumStore { target_free_mb } => {
+                let target_free = usize::try_from(target_free_mb).unwrap_or(usize::MAX).saturating_mul(1024 * 1024);
+                // With a quota, free enough to leave `target_free` under it; without one, free that much outright
+                let target_bytes = match self.store.check_storage_quota() {
+                    Ok((used, Some(quota), _, _)) => used.saturating_add(target_free).saturating_sub(usize::try_from(quota).unwrap_or(usize::MAX)),
+                    _ => target_free,
                 };
+                match self.store.vacuum(target_bytes) {
+                    Ok((evicted, bytes_freed)) => {
+                        println!("Vacuum evicted {} blocks, freeing {} bytes", evicted.len(), bytes_freed);
+                        let blocks_removed = evicted.len();
+                        for cid in evicted {
+                            self.pending_replications.remove(&cid);
+                            self.evicted_blocks.insert(cid);
+                        }
+                        let _ = self.event_tx.send(AppEvent::VacuumComplete { blocks_removed, bytes_freed });
+                        self.send_storage_stats();
+                    }
+                    Err(e) => eprintln!("Failed to vacuum store: {:?}", e),
+                }
+            }
 
-                if do_dht {
-                     // Query DHT
-                     println!("Querying DHT for block {}", cid);
-                     let key = kad::RecordKey::new(&cid.as_bytes());
-                     self.swarm.behaviour_mut().kad.get_providers(key);
+            AppCmd::RebuildIndexes => self.rebuild_indexes(),
+
+            AppCmd::PinBlock { cid } => {
+                if let Err(e) = self.store.pin_block(&cid) {
+                    eprintln!("Failed to pin {}: {:?}", cid, e);
                 }
+                self.send_pinned_blocks();
             }
 
-            AppCmd::FetchStorageStats => {
-                match self.store.get_storage_stats() {
-                    Ok(stats) => {
-                        let _ = self.event_tx.send(AppEvent::StorageStatsFetched { 
-                            block_count: stats.total_nodes, 
-                            total_bytes: stats.total_bytes 
-                        });
+            AppCmd::UnpinBlock { cid } => {
+                if let Err(e) = self.store.unpin_block(&cid) {
+                    eprintln!("Failed to unpin {}: {:?}", cid, e);
+                }
+                self.send_pinned_blocks();
+            }
+
+            AppCmd::SetPinPolicy { types } => {
+                self.pin_policy = types.into_iter().map(|t| t.split(':').next().unwrap_or_default().to_string()).collect();
+            }
+
+            AppCmd::FetchPinnedBlocks => {
+                self.send_pinned_blocks();
+            }
+
+            AppCmd::SetCompressionLevel { level } => {
+                match self.store.set_compression_level(level) {
+                    // Re-send stats so the UI shows the new level
+                    Ok(()) => self.send_storage_stats(),
+                    Err(e) => eprintln!("Failed to set compression level: {:?}", e),
+                }
+            }
+
+            AppCmd::FetchMyStorageQuotaUsageByType => {
+                match self.store.get_size_by_author_and_type(&self.local_peer_id().to_string()) {
+                    Ok(usage) => {
+                        let _ = self.event_tx.send(AppEvent::MyStorageUsageFetched(usage));
                     }
-                    Err(e) => eprintln!("Failed to get storage stats: {:?}", e),
+                    Err(e) => eprintln!("Failed to get my storage usage: {:?}", e),
                 }
             }
 
@@ -2715,6 +6524,14 @@ impl Backend {
                 }
             }
 
+            AppCmd::FetchStoreRoot => {
+                match (self.store.compute_merkle_root(), self.store.get_storage_stats()) {
+                    (Ok(root), Ok(stats)) => {
+                        let _ = self.event_tx.send(AppEvent::StoreRootFetched { root_hex: hex::encode(root), node_count: stats.total_nodes });
+                    }
+                    (Err(e), _) | (_, Err(e)) => eprintln!("Failed to compute store root: {:?}", e),
+                }
+            }
             AppCmd::FetchStorageQuota => {
                 match self.store.check_storage_quota() {
                     Ok((used, quota, percent, over_quota)) => {
@@ -2816,6 +6633,9 @@ impl Backend {
                      Ok(Some(node)) => {
                          if let dag::DagPayload::Listing(listing) = node.payload {
                              // 2. Initiate Transfer
+                             if !self.within_spending_limit(listing.price) {
+                                 return;
+                             }
                              let memo = format!("Purchase: {}", listing_id);
                              let recipient = node.author.clone(); // Seller
                              
@@ -2857,6 +6677,36 @@ impl Backend {
                  }
             }
 
+            AppCmd::FetchPeerListingHistory { peer_id, include_closed } => {
+                match self.store.get_listings_by_author_all_statuses(&peer_id) {
+                    Ok(mut listings) => {
+                        if !include_closed {
+                            listings.retain(|n| !matches!(&n.payload, dag::DagPayload::Listing(l) if l.status == dag::ListingStatus::Cancelled));
+                        }
+                        let _ = self.event_tx.send(AppEvent::ListingsFetched(listings));
+                    }
+                    Err(e) => eprintln!("Failed to fetch listing history: {:?}", e),
+                }
+            }
+            AppCmd::FetchListingBuyerHistory { listing_id } => {
+                let seller = match self.store.get_node(&listing_id) {
+                    Ok(Some(node)) if matches!(node.payload, dag::DagPayload::Listing(_)) => node.author,
+                    _ => {
+                        eprintln!("Listing {} not found", listing_id);
+                        return;
+                    }
+                };
+                match self.store.get_listing_purchases(&listing_id, &seller) {
+                    Ok(purchases) => {
+                        // Only the seller gets to see who bought; everyone else just sees the count
+                        let purchase_count = purchases.len();
+                        let buyers = if seller == self.local_peer_id().to_string() { purchases } else { vec![] };
+                        let _ = self.event_tx.send(AppEvent::ListingBuyerHistoryFetched { listing_id, purchase_count, buyers });
+                    }
+                    Err(e) => eprintln!("Failed to fetch listing buyers: {:?}", e),
+                }
+            }
+
             AppCmd::UpdateListingStatus { listing_id, status } => {
                  if !self.is_caller_verified() {
                      eprintln!("Cannot update listing: User is not verified.");
@@ -2921,7 +6771,7 @@ impl Backend {
                     Err(e) => eprintln!("Failed to search listings: {:?}", e),
                 }
             }
-            AppCmd::PublishProposal { title, description, r#type, pinned } => {
+            AppCmd::PublishProposal { title, description, r#type, pinned, quadratic } => {
                 if !self.is_caller_verified() {
                     eprintln!("Cannot publish proposal: User is not verified.");
                     return;
@@ -2943,7 +6793,7 @@ impl Backend {
                 let author_hex = libp2p::PeerId::from_public_key(&author_pubkey).to_string();
 
                 match r#type {
-                    dag::ProposalType::Constitutional | dag::ProposalType::SetTax(_) | dag::ProposalType::DefineMinistries(_) => {
+                    dag::ProposalType::Constitutional | dag::ProposalType::SetTax(_) | dag::ProposalType::DefineMinistries(_) | dag::ProposalType::SetElectionSchedule { .. } => {
                          if !self.has_certification(&author_hex, "CivicLiteracy") {
                              eprintln!("Cannot publish sensitive proposal: Missing CivicLiteracy certification.");
                              return;
@@ -2952,7 +6802,7 @@ impl Backend {
                     _ => {}
                 }
 
-                let payload = dag::DagPayload::Proposal(dag::ProposalPayload { title, description, r#type, pinned });
+                let payload = dag::DagPayload::Proposal(dag::ProposalPayload { title, description, r#type, pinned, quadratic });
                 
                 let prev = match self.store.get_head(&author_hex) {
                     Ok(Some(cid)) => vec![cid],
@@ -3034,6 +6884,15 @@ impl Backend {
                     Err(e) => eprintln!("Failed to create vote node: {:?}", e),
                 }
             }
+            AppCmd::DelegateVote { delegate, scope, expires_at } => {
+                self.delegate_vote(delegate, scope, expires_at);
+            }
+            AppCmd::RevokeDelegation { scope } => {
+                self.revoke_delegation(scope);
+            }
+            AppCmd::FetchMyDelegations => {
+                self.send_my_delegations();
+            }
             AppCmd::FetchProposals => {
                 match self.store.get_proposals() {
                     Ok(proposals) => {
@@ -3047,26 +6906,147 @@ impl Backend {
                     Ok(votes) => {
                         let _ = self.event_tx.send(AppEvent::ProposalVotesFetched { proposal_id, votes });
                     }
-                    Err(e) => eprintln!("Failed to fetch votes for proposal: {:?}", e),
+                    Err(e) => eprintln!("Failed to fetch votes for proposal: {:?}", e),
+                }
+            }
+            AppCmd::FetchElectionSchedule { ministry } => {
+                match self.store.get_election_schedule(ministry.as_deref()) {
+                    Ok(schedule) => {
+                        let _ = self.event_tx.send(AppEvent::ElectionScheduleFetched { schedule });
+                    }
+                    Err(e) => eprintln!("Failed to fetch election schedule: {:?}", e),
+                }
+            }
+            AppCmd::FetchProposalsByAuthor { peer_id } => {
+                self.send_governance_record(peer_id);
+            }
+            AppCmd::FetchMyProposals => {
+                let my_pubkey = self.local_peer_id().to_string();
+                self.send_governance_record(my_pubkey);
+            }
+            AppCmd::FetchMyProposalVoteHistory => {
+                let my_pubkey = self.local_peer_id().to_string();
+                match self.vote_history_with_titles(&my_pubkey) {
+                    Ok(votes) => {
+                        let _ = self.event_tx.send(AppEvent::MyVoteHistoryFetched { votes });
+                    }
+                    Err(e) => eprintln!("Failed to fetch vote history: {:?}", e),
+                }
+            }
+            AppCmd::FetchProposalSimilar { proposal_id } => {
+                let text = match self.store.get_node(&proposal_id) {
+                    Ok(Some(dag::DagNode { payload: dag::DagPayload::Proposal(p), .. })) => format!("{} {}", p.title, p.description),
+                    Ok(_) => {
+                        eprintln!("Cannot find proposals similar to {}: proposal not found", proposal_id);
+                        return;
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to fetch proposal: {:?}", e);
+                        return;
+                    }
+                };
+                self.send_similar_proposals(proposal_id, &text);
+            }
+            AppCmd::FetchDraftProposalSimilar { title, description } => {
+                self.send_similar_proposals(String::new(), &format!("{} {}", title, description));
+            }
+            AppCmd::FetchProposalImpactEstimate { proposal_id } => {
+                let proposal_type = match self.store.get_node(&proposal_id) {
+                    Ok(Some(node)) => match node.payload {
+                        dag::DagPayload::Proposal(p) => p.r#type,
+                        _ => {
+                            eprintln!("Cannot estimate impact of {}: not a proposal", proposal_id);
+                            return;
+                        }
+                    },
+                    Ok(None) => {
+                        eprintln!("Cannot estimate impact of {}: proposal not found", proposal_id);
+                        return;
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to fetch proposal: {:?}", e);
+                        return;
+                    }
+                };
+
+                let mut metrics = HashMap::new();
+                let narrative = match proposal_type {
+                    dag::ProposalType::SetTax(proposed_rate) => {
+                        const WINDOW_DAYS: u64 = 30;
+                        let daily_volume = match self.store.get_daily_transfer_volume(WINDOW_DAYS) {
+                            Ok(v) => v,
+                            Err(e) => {
+                                eprintln!("Failed to compute transfer volume: {:?}", e);
+                                return;
+                            }
+                        };
+                        let current_rate = self.store.get_current_tax_rate().unwrap_or(0);
+                        let current_revenue = daily_volume * current_rate as f64 / 100.0;
+                        let proposed_revenue = daily_volume * proposed_rate as f64 / 100.0;
+
+                        metrics.insert("daily_volume".to_string(), daily_volume);
+                        metrics.insert("current_rate_pct".to_string(), current_rate as f64);
+                        metrics.insert("proposed_rate_pct".to_string(), proposed_rate as f64);
+                        metrics.insert("current_daily_revenue".to_string(), current_revenue);
+                        metrics.insert("proposed_daily_revenue".to_string(), proposed_revenue);
+                        metrics.insert("daily_revenue_change".to_string(), proposed_revenue - current_revenue);
+
+                        format!(
+                            "Over the last {} days the network moved about {:.1} tokens per day. Changing the tax from {}% to {}% would change daily tax revenue from {:.1} to {:.1} tokens ({:+.1}/day), assuming volume stays the same.",
+                            WINDOW_DAYS, daily_volume, current_rate, proposed_rate, current_revenue, proposed_revenue, proposed_revenue - current_revenue
+                        )
+                    }
+                    _ => "No quantitative impact model is available for this proposal type.".to_string(),
+                };
+
+                let _ = self.event_tx.send(AppEvent::ProposalImpactEstimated { proposal_id, narrative, metrics });
+            }
+            AppCmd::FetchProposalOutcomeActions { proposal_id } => {
+                match self.store.get_proposal_outcomes(proposal_id.as_deref()) {
+                    Ok(outcomes) => {
+                        let _ = self.event_tx.send(AppEvent::ProposalOutcomesFetched(outcomes));
+                    }
+                    Err(e) => eprintln!("Failed to fetch proposal outcomes: {:?}", e),
                 }
             }
             AppCmd::FetchProposalTally { proposal_id } => {
                 let status = self.store.get_proposal_status(&proposal_id).unwrap_or("Unknown".to_string());
-                match self.store.get_proposal_vote_tally(&proposal_id) {
-                    Ok((yes, no, abstain, petition, unique_voters)) => {
+                if status == "Passed" {
+                    self.record_proposal_outcome(&proposal_id);
+                }
+                match self.store.get_weighted_proposal_tally(&proposal_id) {
+                    Ok(tally) => {
                         let _ = self.event_tx.send(AppEvent::ProposalTallyFetched {
                             proposal_id,
-                            yes,
-                            no,
-                            abstain,
-                            petition,
-                            unique_voters,
+                            yes_weight: tally.yes_weight,
+                            no_weight: tally.no_weight,
+                            abstain_weight: tally.abstain_weight,
+                            petition: tally.petition,
+                            unique_voters: tally.unique_voters,
                             status,
                         });
                     }
                     Err(e) => eprintln!("Failed to fetch vote tally: {:?}", e),
                 }
             }
+            AppCmd::FetchProposalProgressToQuorum { proposal_id } => {
+                match self.store.get_proposal_progress(&proposal_id) {
+                    Ok((tally, required_yes, required_unique_voters, time_remaining)) => {
+                        let progress_pct = (tally.yes * 100 / required_yes.max(1)).min(100) as u8;
+                        let _ = self.event_tx.send(AppEvent::ProposalProgressFetched {
+                            proposal_id,
+                            yes: tally.yes,
+                            no: tally.no,
+                            abstain: tally.abstain,
+                            required_yes,
+                            required_unique_voters,
+                            progress_pct,
+                            time_remaining,
+                        });
+                    }
+                    Err(e) => eprintln!("Failed to fetch proposal progress: {:?}", e),
+                }
+            }
             // Election command handlers
             AppCmd::DeclareCandidacy { ministry, platform } => {
                 if !self.is_caller_verified() {
@@ -3082,6 +7062,15 @@ impl Backend {
                     return;
                 }
 
+                // If an election window has been scheduled for this ministry, candidacy must fall inside it
+                if let Some((_, start, end)) = self.store.get_election_schedule(Some(&ministry)).unwrap_or_default().into_iter().next() {
+                    let now = chrono::Utc::now().timestamp() as u64;
+                    if now < start || now > end {
+                        eprintln!("Cannot declare candidacy: No open election window for {}.", ministry);
+                        return;
+                    }
+                }
+
                 let payload = dag::DagPayload::Candidacy(dag::CandidacyPayload { ministry, platform });
                 
                 let author_pubkey = self.keypair.public();
@@ -3176,13 +7165,72 @@ impl Backend {
                 }
             }
             AppCmd::FetchCandidateTally { candidacy_id } => {
-                match self.store.get_candidate_tally(&candidacy_id) {
+                // Votes for a withdrawn candidate no longer count
+                let withdrawn = matches!(self.store.get_candidacy_withdrawal(&candidacy_id), Ok(Some(_)));
+                let tally = if withdrawn { Ok(0) } else { self.store.get_candidate_tally(&candidacy_id) };
+                match tally {
                     Ok(votes) => {
-                        let _ = self.event_tx.send(AppEvent::CandidateTallyFetched { candidacy_id, votes });
+                        let _ = self.event_tx.send(AppEvent::CandidateTallyFetched { candidacy_id, votes, withdrawn });
                     }
                     Err(e) => eprintln!("Failed to fetch candidate tally: {:?}", e),
                 }
             }
+            AppCmd::FetchCandidacyWithdrawal { candidacy_id } => {
+                match self.store.get_candidacy_withdrawal(&candidacy_id) {
+                    Ok(withdrawal) => {
+                        let withdrawn_at = withdrawal.as_ref().map(|n| n.timestamp.timestamp() as u64);
+                        let _ = self.event_tx.send(AppEvent::CandidacyWithdrawalFetched { candidacy_id, withdrawn: withdrawal.is_some(), withdrawn_at });
+                    }
+                    Err(e) => eprintln!("Failed to fetch candidacy withdrawal: {:?}", e),
+                }
+            }
+            AppCmd::WithdrawCandidacy { candidacy_id } => {
+                let author_hex = self.local_peer_id().to_string();
+                match self.store.get_node(&candidacy_id) {
+                    Ok(Some(node)) if matches!(node.payload, dag::DagPayload::Candidacy(_)) && node.author == author_hex => {}
+                    Ok(_) => {
+                        eprintln!("Cannot withdraw candidacy {}: not your candidacy", candidacy_id);
+                        return;
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to fetch candidacy: {:?}", e);
+                        return;
+                    }
+                }
+                if let Ok(Some(_)) = self.store.get_candidacy_withdrawal(&candidacy_id) {
+                    eprintln!("Candidacy {} is already withdrawn", candidacy_id);
+                    return;
+                }
+
+                let payload = dag::DagPayload::CandidacyWithdrawal(dag::CandidacyWithdrawalPayload { candidacy_id: candidacy_id.clone() });
+                let prev = match self.store.get_head(&author_hex) {
+                    Ok(Some(cid)) => vec![cid],
+                    _ => vec![],
+                };
+
+                match dag::DagNode::new("candidacy_withdrawal:v1".to_string(), payload, prev, &self.keypair, 0) {
+                    Ok(node) => {
+                        println!("Withdrew candidacy {}", candidacy_id);
+                        if let Err(e) = self.store.put_node(&node) {
+                            eprintln!("Failed to store candidacy withdrawal: {:?}", e);
+                            return;
+                        }
+                        if let Err(e) = self.store.update_head(&author_hex, &node.id) {
+                            eprintln!("Failed to update head: {:?}", e);
+                        }
+                        let topic = gossipsub::IdentTopic::new("blocks");
+                        let _ = self.swarm.behaviour_mut().gossipsub.publish(topic, node.id.as_bytes());
+                        self.replicate_block(&node);
+                        let withdrawn_at = Some(node.timestamp.timestamp() as u64);
+                        let _ = self.event_tx.send(AppEvent::BlockReceived(node));
+                        let _ = self.event_tx.send(AppEvent::CandidacyWithdrawalFetched { candidacy_id, withdrawn: true, withdrawn_at });
+                        if let Ok(candidates) = self.store.get_all_candidates() {
+                            let _ = self.event_tx.send(AppEvent::CandidatesFetched(candidates));
+                        }
+                    }
+                    Err(e) => eprintln!("Failed to create candidacy withdrawal: {:?}", e),
+                }
+            }
             AppCmd::FetchReputation { peer_id } => {
                 match self.store.get_reputation(&peer_id) {
                     Ok(details) => {
@@ -3191,6 +7239,23 @@ impl Backend {
                     Err(e) => eprintln!("Failed to fetch reputation: {:?}", e),
                 }
             }
+            AppCmd::FetchReputations { peer_ids } => {
+                match self.store.get_reputations_batch(&peer_ids) {
+                    Ok(reputations) => {
+                        let _ = self.event_tx.send(AppEvent::ReputationsBatchFetched(reputations));
+                    }
+                    Err(e) => eprintln!("Failed to fetch reputations: {:?}", e),
+                }
+            }
+            AppCmd::FetchTrustScore { peer_id } => {
+                match self.store.get_trust_score_components(&peer_id) {
+                    Ok(components) => {
+                        let score = Store::weighted_trust_score(&components);
+                        let _ = self.event_tx.send(AppEvent::TrustScoreFetched { peer_id, score, components });
+                    }
+                    Err(e) => eprintln!("Failed to compute trust score: {:?}", e),
+                }
+            }
             AppCmd::FetchMyWebPages => {
                 let author_pubkey = self.keypair.public();
                 let author_hex = libp2p::PeerId::from_public_key(&author_pubkey).to_string();
@@ -3258,6 +7323,8 @@ impl Backend {
                         
                         // Force refresh of my groups
                         let _ = self.event_tx.send(AppEvent::GroupsFetched(vec![node.clone()]));
+
+                        self.publish_group_key(&node.id);
                     }
                     Err(e) => eprintln!("Failed to create group node: {:?}", e),
                 }
@@ -3274,6 +7341,63 @@ impl Backend {
                 }
             }
             
+            AppCmd::FetchGroupsByOwner { peer_id } => {
+                match self.store.get_groups_by_owner(&peer_id) {
+                    Ok(groups) => {
+                        let _ = self.event_tx.send(AppEvent::GroupsFetched(groups));
+                    }
+                    Err(e) => eprintln!("Failed to fetch groups by owner: {:?}", e),
+                }
+            }
+
+            AppCmd::FetchGroupMembers { group_id } => {
+                match self.store.get_group_members(&group_id) {
+                    Ok(members) => {
+                        let _ = self.event_tx.send(AppEvent::GroupMembersFetched { group_id, members });
+                    }
+                    Err(e) => eprintln!("Failed to fetch group members: {:?}", e),
+                }
+            }
+            AppCmd::FetchGroupLeaderboard { group_id, window_days } => {
+                let since = chrono::Utc::now().timestamp() - (window_days as i64) * 86400;
+                match self.store.get_group_leaderboard(&group_id, since) {
+                    Ok(rankings) => {
+                        let _ = self.event_tx.send(AppEvent::GroupLeaderboardFetched { group_id, rankings });
+                    }
+                    Err(e) => eprintln!("Failed to fetch group leaderboard: {:?}", e),
+                }
+            }
+            AppCmd::FetchGroupActivity { group_id } => {
+                match self.store.get_group_activity(&group_id) {
+                    Ok(events) => {
+                        let _ = self.event_tx.send(AppEvent::GroupActivityFetched { group_id, events });
+                    }
+                    Err(e) => eprintln!("Failed to fetch group activity: {:?}", e),
+                }
+            }
+            AppCmd::FetchGroupMemberActivity { group_id, member_peer_id } => {
+                let author_hex = self.local_peer_id().to_string();
+                let is_owner = matches!(self.store.get_node(&group_id), Ok(Some(dag::DagNode { payload: dag::DagPayload::Group(ref g), .. })) if g.owner == author_hex);
+                if !is_owner {
+                    eprintln!("Cannot fetch member activity: Only the group owner can moderate members.");
+                    return;
+                }
+                match self.store.get_group_member_activity(&group_id, &member_peer_id) {
+                    Ok((message_count, post_count, reports_against, last_active)) => {
+                        let _ = self.event_tx.send(AppEvent::GroupMemberActivityFetched {
+                            group_id,
+                            member_peer_id,
+                            message_count,
+                            post_count,
+                            reports_against,
+                            last_active,
+                            moderation_flag: reports_against >= 3,
+                        });
+                    }
+                    Err(e) => eprintln!("Failed to fetch group member activity: {:?}", e),
+                }
+            }
+
             AppCmd::FetchGroupMessages { group_id } => {
                 match self.store.get_group_messages(&group_id) {
                      Ok(messages) => {
@@ -3286,6 +7410,207 @@ impl Backend {
                      Err(e) => eprintln!("Failed to fetch group messages: {:?}", e),
                 }
             }
+            AppCmd::RotateGroupKey { group_id } => {
+                if !self.is_caller_verified() {
+                    eprintln!("Cannot rotate group key: User is not verified.");
+                    return;
+                }
+                self.publish_group_key(&group_id);
+            }
+            AppCmd::InviteToGroup { group_id, invitee_peer_id } => {
+                if !self.is_caller_verified() {
+                    eprintln!("Cannot invite to group: User is not verified.");
+                    return;
+                }
+                let author_hex = self.local_peer_id().to_string();
+                let members = match self.store.get_group_members(&group_id) {
+                    Ok(members) => members,
+                    Err(e) => {
+                        eprintln!("Failed to fetch group members: {:?}", e);
+                        return;
+                    }
+                };
+                let can_invite = members.iter().any(|(m, role)| *m == author_hex && matches!(role, dag::GroupRole::Owner | dag::GroupRole::Admin));
+                if !can_invite {
+                    eprintln!("Cannot invite to group: Only the group owner or an admin can invite.");
+                    return;
+                }
+                if members.iter().any(|(m, _)| *m == invitee_peer_id) {
+                    eprintln!("Cannot invite to group: {} is already a member.", invitee_peer_id);
+                    return;
+                }
+                let policy = self.store.get_group_policy(&group_id).unwrap_or_default();
+                if !policy.allow_new_members_join {
+                    eprintln!("Cannot invite to group: The group is closed to new members.");
+                    return;
+                }
+                if policy.require_approval && !members.iter().any(|(m, role)| *m == author_hex && *role == dag::GroupRole::Owner) {
+                    eprintln!("Cannot invite to group: New members need the owner's approval.");
+                    return;
+                }
+                if policy.max_members.is_some_and(|max| members.len() >= max) {
+                    eprintln!("Cannot invite to group: The group is full.");
+                    return;
+                }
+                if let Some(min) = policy.min_reputation {
+                    let score = self.store.get_reputation(&invitee_peer_id).map(|r| r.score as i64).unwrap_or(0);
+                    if score < min {
+                        eprintln!("Cannot invite to group: {} has reputation {} (minimum {}).", invitee_peer_id, score, min);
+                        return;
+                    }
+                }
+
+                let payload = dag::DagPayload::GroupInvite(dag::GroupInvitePayload { group_id, invitee_peer_id });
+                let prev = match self.store.get_head(&author_hex) {
+                    Ok(Some(cid)) => vec![cid],
+                    _ => vec![],
+                };
+
+                match dag::DagNode::new(
+                    "group_invite:v1".to_string(),
+                    payload,
+                    prev,
+                    &self.keypair,
+                    0
+                ) {
+                    Ok(node) => {
+                        println!("Created group invite node: {}", node.id);
+                        if let Err(e) = self.store.put_node(&node) {
+                            eprintln!("Failed to store group invite node: {:?}", e);
+                            return;
+                        }
+                        if let Err(e) = self.store.update_head(&author_hex, &node.id) {
+                            eprintln!("Failed to update head: {:?}", e);
+                        }
+                        let topic = gossipsub::IdentTopic::new("blocks");
+                        let _ = self.swarm.behaviour_mut().gossipsub.publish(topic, node.id.as_bytes());
+                        self.replicate_block(&node);
+                        let _ = self.event_tx.send(AppEvent::BlockReceived(node));
+                    }
+                    Err(e) => eprintln!("Failed to create group invite node: {:?}", e),
+                }
+            }
+            AppCmd::RespondToGroupInvite { invite_id, accept } => {
+                let author_hex = self.local_peer_id().to_string();
+                let group_id = match self.store.get_node(&invite_id) {
+                    Ok(Some(dag::DagNode { payload: dag::DagPayload::GroupInvite(invite), .. })) if invite.invitee_peer_id == author_hex => invite.group_id,
+                    Ok(_) => {
+                        eprintln!("Cannot respond to invite: Invitation not found or not addressed to us.");
+                        return;
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to fetch invitation: {:?}", e);
+                        return;
+                    }
+                };
+                if accept {
+                    // The policy may have tightened since the invitation went out
+                    let policy = self.store.get_group_policy(&group_id).unwrap_or_default();
+                    let member_count = self.store.get_group_members(&group_id).map(|m| m.len()).unwrap_or(0);
+                    if !policy.allow_new_members_join || policy.max_members.is_some_and(|max| member_count >= max) {
+                        eprintln!("Cannot join group: The group is not accepting new members.");
+                        return;
+                    }
+                }
+
+                let payload = dag::DagPayload::GroupInviteResponse(dag::GroupInviteResponsePayload { invite_id, accept });
+                let prev = match self.store.get_head(&author_hex) {
+                    Ok(Some(cid)) => vec![cid],
+                    _ => vec![],
+                };
+
+                match dag::DagNode::new(
+                    "group_invite_response:v1".to_string(),
+                    payload,
+                    prev,
+                    &self.keypair,
+                    0
+                ) {
+                    Ok(node) => {
+                        println!("Created group invite response node: {}", node.id);
+                        if let Err(e) = self.store.put_node(&node) {
+                            eprintln!("Failed to store group invite response node: {:?}", e);
+                            return;
+                        }
+                        if let Err(e) = self.store.update_head(&author_hex, &node.id) {
+                            eprintln!("Failed to update head: {:?}", e);
+                        }
+                        let topic = gossipsub::IdentTopic::new("blocks");
+                        let _ = self.swarm.behaviour_mut().gossipsub.publish(topic, node.id.as_bytes());
+                        self.replicate_block(&node);
+                        let _ = self.event_tx.send(AppEvent::BlockReceived(node));
+
+                        if let Ok(invites) = self.store.get_group_invitations(&author_hex) {
+                            let _ = self.event_tx.send(AppEvent::GroupInvitationsFetched(invites));
+                        }
+                        // An accepted response is the membership record, so the group now counts us in
+                        if accept {
+                            if let Ok(groups) = self.store.get_my_groups(&author_hex) {
+                                let _ = self.event_tx.send(AppEvent::GroupsFetched(groups));
+                            }
+                            if let Ok(members) = self.store.get_group_members(&group_id) {
+                                let _ = self.event_tx.send(AppEvent::GroupMembersFetched { group_id, members });
+                            }
+                        }
+                    }
+                    Err(e) => eprintln!("Failed to create group invite response node: {:?}", e),
+                }
+            }
+            AppCmd::SetGroupPolicy { group_id, policy } => {
+                let author_hex = self.local_peer_id().to_string();
+                let is_owner = matches!(self.store.get_node(&group_id), Ok(Some(dag::DagNode { payload: dag::DagPayload::Group(ref g), .. })) if g.owner == author_hex);
+                if !is_owner {
+                    eprintln!("Cannot set group policy: Only the group owner can change it.");
+                    return;
+                }
+
+                let payload = dag::DagPayload::GroupPolicy(dag::GroupPolicyPayload { group_id: group_id.clone(), policy: policy.clone() });
+                let prev = match self.store.get_head(&author_hex) {
+                    Ok(Some(cid)) => vec![cid],
+                    _ => vec![],
+                };
+
+                match dag::DagNode::new(
+                    "group_policy:v1".to_string(),
+                    payload,
+                    prev,
+                    &self.keypair,
+                    0
+                ) {
+                    Ok(node) => {
+                        println!("Created group policy node: {}", node.id);
+                        if let Err(e) = self.store.put_node(&node) {
+                            eprintln!("Failed to store group policy node: {:?}", e);
+                            return;
+                        }
+                        if let Err(e) = self.store.update_head(&author_hex, &node.id) {
+                            eprintln!("Failed to update head: {:?}", e);
+                        }
+                        let topic = gossipsub::IdentTopic::new("blocks");
+                        let _ = self.swarm.behaviour_mut().gossipsub.publish(topic, node.id.as_bytes());
+                        self.replicate_block(&node);
+                        let _ = self.event_tx.send(AppEvent::BlockReceived(node));
+                        let _ = self.event_tx.send(AppEvent::GroupPolicyFetched { group_id, policy });
+                    }
+                    Err(e) => eprintln!("Failed to create group policy node: {:?}", e),
+                }
+            }
+            AppCmd::FetchGroupPolicy { group_id } => {
+                match self.store.get_group_policy(&group_id) {
+                    Ok(policy) => {
+                        let _ = self.event_tx.send(AppEvent::GroupPolicyFetched { group_id, policy });
+                    }
+                    Err(e) => eprintln!("Failed to fetch group policy: {:?}", e),
+                }
+            }
+            AppCmd::FetchGroupInvitations => {
+                match self.store.get_group_invitations(&self.local_peer_id().to_string()) {
+                    Ok(invites) => {
+                        let _ = self.event_tx.send(AppEvent::GroupInvitationsFetched(invites));
+                    }
+                    Err(e) => eprintln!("Failed to fetch group invitations: {:?}", e),
+                }
+            }
             AppCmd::ReportContent { target_id, reason, details } => {
                 if !self.is_caller_verified() {
                     eprintln!("Cannot report content: User is not verified.");
@@ -3479,11 +7804,64 @@ impl Backend {
                     eprintln!("Cannot like post: User is not verified.");
                     return;
                 }
-                let payload = dag::DagPayload::Like(dag::LikePayload { target_id: target_id.clone(), remove });
-                
-                let author_pubkey = self.keypair.public();
-                let author_hex = libp2p::PeerId::from_public_key(&author_pubkey).to_string();
-                
+                let payload = dag::DagPayload::Like(dag::LikePayload { target_id: target_id.clone(), remove });
+                
+                let author_pubkey = self.keypair.public();
+                let author_hex = libp2p::PeerId::from_public_key(&author_pubkey).to_string();
+                
+                let prev = match self.store.get_head(&author_hex) {
+                    Ok(Some(cid)) => vec![cid],
+                    Ok(None) => vec![],
+                    Err(e) => {
+                        eprintln!("Failed to get head: {:?}", e);
+                        vec![]
+                    }
+                };
+
+                match dag::DagNode::new(
+                    "like:v1".to_string(),
+                    payload,
+                    prev,
+                    &self.keypair,
+                    0
+                ) {
+                    Ok(node) => {
+                        println!("Created like node: {}", node.id);
+                        if let Err(e) = self.store.put_node(&node) {
+                            eprintln!("Failed to store like node: {:?}", e);
+                            return;
+                        }
+                        if let Err(e) = self.store.update_head(&author_hex, &node.id) {
+                            eprintln!("Failed to update head: {:?}", e);
+                        }
+                        let topic = gossipsub::IdentTopic::new("blocks");
+                        if let Err(e) = self.swarm.behaviour_mut().gossipsub.publish(topic, node.id.as_bytes()) {
+                            eprintln!("Failed to publish like CID: {:?}", e);
+                        }
+                        self.replicate_block(&node);
+                        
+                        // Immediately fetch updated likes to update UI
+                        let my_pubkey = self.local_peer_id().to_string();
+                        if let Ok((count, is_liked)) = self.store.get_likes_for_target(&target_id, &my_pubkey) {
+                             let _ = self.event_tx.send(AppEvent::LikesFetched { target_id, count, is_liked_by_me: is_liked });
+                        }
+                    }
+                    Err(e) => eprintln!("Failed to create like node: {:?}", e),
+                }
+            }
+            AppCmd::SetPostVisibility { post_id, visibility } => {
+                let author_pubkey = self.keypair.public();
+                let author_hex = libp2p::PeerId::from_public_key(&author_pubkey).to_string();
+
+                match self.store.get_node(&post_id) {
+                    Ok(Some(post)) if post.author == author_hex && matches!(post.payload, dag::DagPayload::Post(_)) => {}
+                    _ => {
+                        eprintln!("Cannot set visibility: post {} not found or not authored by us.", post_id);
+                        return;
+                    }
+                }
+
+                let payload = dag::DagPayload::PostVisibility(dag::PostVisibilityPayload { post_id: post_id.clone(), visibility });
                 let prev = match self.store.get_head(&author_hex) {
                     Ok(Some(cid)) => vec![cid],
                     Ok(None) => vec![],
@@ -3494,16 +7872,16 @@ impl Backend {
                 };
 
                 match dag::DagNode::new(
-                    "like:v1".to_string(),
+                    "post_visibility:v1".to_string(),
                     payload,
                     prev,
                     &self.keypair,
                     0
                 ) {
                     Ok(node) => {
-                        println!("Created like node: {}", node.id);
+                        println!("Created post visibility node: {}", node.id);
                         if let Err(e) = self.store.put_node(&node) {
-                            eprintln!("Failed to store like node: {:?}", e);
+                            eprintln!("Failed to store post visibility node: {:?}", e);
                             return;
                         }
                         if let Err(e) = self.store.update_head(&author_hex, &node.id) {
@@ -3511,19 +7889,31 @@ impl Backend {
                         }
                         let topic = gossipsub::IdentTopic::new("blocks");
                         if let Err(e) = self.swarm.behaviour_mut().gossipsub.publish(topic, node.id.as_bytes()) {
-                            eprintln!("Failed to publish like CID: {:?}", e);
+                            eprintln!("Failed to publish post visibility CID: {:?}", e);
                         }
+                        let _ = self.event_tx.send(AppEvent::BlockReceived(node.clone()));
                         self.replicate_block(&node);
-                        
-                        // Immediately fetch updated likes to update UI
-                        let my_pubkey = self.local_peer_id().to_string();
-                        if let Ok((count, is_liked)) = self.store.get_likes_for_target(&target_id, &my_pubkey) {
-                             let _ = self.event_tx.send(AppEvent::LikesFetched { target_id, count, is_liked_by_me: is_liked });
-                        }
                     }
-                    Err(e) => eprintln!("Failed to create like node: {:?}", e),
+                    Err(e) => eprintln!("Failed to create post visibility node: {:?}", e),
+                }
+            }
+            AppCmd::ReactToPost { target_id, emoji, remove } => {
+                if !self.is_caller_verified() {
+                    eprintln!("Cannot react to post: User is not verified.");
+                    return;
+                }
+                let Some(emoji) = dag::ReactionPayload::canonical(&emoji) else {
+                    eprintln!("Cannot react to post: {} is not an allowed reaction.", emoji);
+                    return;
+                };
+                let payload = dag::DagPayload::Reaction(dag::ReactionPayload { target_id: target_id.clone(), emoji: emoji.to_string(), remove });
+                if self.publish_node("reaction:v1", payload).is_some() {
+                    self.send_reactions(target_id);
                 }
             }
+            AppCmd::FetchReactions { target_id } => {
+                self.send_reactions(target_id);
+            }
             AppCmd::FetchLikes { target_id } => {
                 let my_pubkey = self.local_peer_id().to_string();
                 match self.store.get_likes_for_target(&target_id, &my_pubkey) {
@@ -3533,6 +7923,33 @@ impl Backend {
                     Err(e) => eprintln!("Failed to fetch likes: {:?}", e),
                 }
             }
+            AppCmd::FetchCrossPostHistory { post_id } => {
+                let my_pubkey = self.local_peer_id().to_string();
+                match self.store.get_cross_post_history(&post_id, &my_pubkey) {
+                    Ok(reposts) => {
+                        let _ = self.event_tx.send(AppEvent::CrossPostHistoryFetched { original_id: post_id, reposts });
+                    }
+                    Err(e) => eprintln!("Failed to fetch cross-post history: {:?}", e),
+                }
+            }
+            AppCmd::FetchPostContextWindow { post_id, window, same_author } => {
+                let my_pubkey = self.local_peer_id().to_string();
+                match self.store.get_post_context_window(&post_id, window, same_author, &my_pubkey) {
+                    Ok((posts, center_index)) => {
+                        let _ = self.event_tx.send(AppEvent::PostContextFetched { center_post_id: post_id, posts, center_index });
+                    }
+                    Err(e) => eprintln!("Failed to fetch post context: {:?}", e),
+                }
+            }
+            AppCmd::FetchPostEngagementScore { post_id } => {
+                match self.store.get_post_engagement(&post_id) {
+                    Ok((likes, comments)) => {
+                        let score = Store::engagement_score(likes, comments);
+                        let _ = self.event_tx.send(AppEvent::PostEngagementFetched { post_id, likes, comments, score });
+                    }
+                    Err(e) => eprintln!("Failed to fetch post engagement: {:?}", e),
+                }
+            }
             AppCmd::FetchMinistries => {
                 match self.store.get_active_ministries() {
                      Ok(m) => {
@@ -3541,6 +7958,28 @@ impl Backend {
                      Err(e) => eprintln!("Failed to fetch ministries: {:?}", e),
                 }
             }
+            AppCmd::FetchMyElectedPositions => {
+                let my_pubkey = self.local_peer_id().to_string();
+                match self.store.get_active_officials() {
+                    Ok(officials) => {
+                        let mut positions: Vec<dag::Ministry> = officials.into_iter()
+                            .filter(|(_, official)| *official == my_pubkey)
+                            .map(|(ministry, _)| ministry)
+                            .collect();
+                        positions.sort();
+                        let _ = self.event_tx.send(AppEvent::MyElectedPositionsFetched { positions });
+                    }
+                    Err(e) => eprintln!("Failed to fetch elected positions: {:?}", e),
+                }
+            }
+            AppCmd::FetchActiveOfficials => {
+                match self.store.get_active_officials() {
+                    Ok(officials) => {
+                        let _ = self.event_tx.send(AppEvent::ActiveOfficialsFetched { officials });
+                    }
+                    Err(e) => eprintln!("Failed to fetch active officials: {:?}", e),
+                }
+            }
 
         }
     }
@@ -3571,15 +8010,25 @@ impl Backend {
             }
             SwarmEvent::Behaviour(MyBehaviourEvent::RequestResponse(event)) => {
                 match event {
-                    request_response::Event::Message { peer: _peer, message } => {
+                    request_response::Event::Message { peer, message } => {
                         match message {
                             request_response::Message::Request { request, channel, .. } => {
+                                self.inbound_bytes += request.payload_len() as u64;
                                 match request {
                                     BlockRequest::Fetch(cid) => {
                                         println!("Received fetch request for block: {}", cid);
-                                        let response_data = match self.store.get_block_bytes(&cid) {
-                                            Ok(Some(bytes)) => bytes,
-                                            _ => vec![],
+                                        let withheld = match self.store.get_node(&cid) {
+                                            Ok(Some(node)) => self.post_audience(&node)
+                                                .is_some_and(|audience| !audience.contains(&peer.to_string())),
+                                            _ => false,
+                                        };
+                                        let response_data = if withheld {
+                                            vec![]
+                                        } else {
+                                            match self.store.get_block_bytes(&cid) {
+                                                Ok(Some(bytes)) => bytes,
+                                                _ => vec![],
+                                            }
                                         };
 
                                         let response = if response_data.is_empty() {
@@ -3587,18 +8036,48 @@ impl Backend {
                                         } else {
                                             BlockResponse::Block(response_data)
                                         };
-                                        let _ = self.swarm.behaviour_mut().request_response.send_response(channel, response);
+                                        let _ = self.send_block_response(channel, response);
                                     }
-                                    BlockRequest::LocalSearch(query) => {
+                                    BlockRequest::LocalSearch(query, author) => {
                                         println!("Received local search request for: {}", query);
-                                        let results = match self.store.search_web_pages(&query) {
+                                        let found = match author {
+                                            Some(author) => self.store.search_web_pages_by_author(&query, &author),
+                                            None => self.store.search_web_pages(&query),
+                                        };
+                                        let results = match found {
                                              Ok(nodes) => {
                                                  nodes.iter().filter_map(|n| serde_json::to_vec(n).ok()).collect()
                                              },
                                              _ => vec![],
                                         };
                                         let response = BlockResponse::SearchResults(results);
-                                        let _ = self.swarm.behaviour_mut().request_response.send_response(channel, response);
+                                        let _ = self.send_block_response(channel, response);
+                                    }
+                                    BlockRequest::SyncFilter(filter) => {
+                                        self.remember_sync_filter(peer, filter);
+                                        let response = match self.store.build_bloom_filter() {
+                                            Ok(filter) => BlockResponse::SyncFilter(filter),
+                                            Err(e) => BlockResponse::Error(e.to_string()),
+                                        };
+                                        let _ = self.send_block_response(channel, response);
+                                    }
+                                    BlockRequest::StoreRoot => {
+                                        let response = match self.store.compute_merkle_root() {
+                                            Ok(root) => BlockResponse::StoreRoot(root.to_vec()),
+                                            Err(e) => BlockResponse::Error(e.to_string()),
+                                        };
+                                        let _ = self.send_block_response(channel, response);
+                                    }
+                                    BlockRequest::SyncFrom { since_timestamp, after_cid } => {
+                                        let batch = self.store.get_nodes_since(since_timestamp, after_cid.as_deref(), SYNC_BATCH_SIZE).unwrap_or_default();
+                                        let has_more = batch.len() == SYNC_BATCH_SIZE;
+                                        // Resume right after the last node, so blocks sharing its second are neither skipped nor resent
+                                        let (next_cursor, next_cid) = match batch.last() {
+                                            Some(last) => (u64::try_from(last.timestamp.timestamp()).unwrap_or_default(), Some(last.id.clone())),
+                                            None => (since_timestamp, after_cid),
+                                        };
+                                        let nodes = self.sync_batch_nodes(&batch, &peer.to_string());
+                                        let _ = self.send_block_response(channel, BlockResponse::SyncBatch { nodes, has_more, next_cursor, next_cid });
                                     }
                                     BlockRequest::Store(data) => {
                                         println!("Received store request");
@@ -3606,39 +8085,73 @@ impl Backend {
                                             Ok(node) => {
                                                 // Verify and store
                                                 match node.verify() {
+                                                    Ok(true) if matches!(node.payload, dag::DagPayload::MessageReceipt(_)) && self.receipt_event(&node).is_none() => {
+                                                        // Receipts are for the message's sender only
+                                                        let _ = self.send_block_response(channel, BlockResponse::Error("Receipt is not for a message sent by this peer".to_string()));
+                                                    }
                                                     Ok(true) => {
                                                         if let Err(e) = self.store.put_node(&node) {
                                                             eprintln!("Failed to store pushed node: {:?}", e);
-                                                            let _ = self.swarm.behaviour_mut().request_response.send_response(channel, BlockResponse::Error(format!("Store failed: {:?}", e)));
+                                                            let _ = self.send_block_response(channel, BlockResponse::Error(format!("Store failed: {:?}", e)));
                                                         } else {
                                                             println!("Stored pushed node: {}", node.id);
+                                                            self.apply_pin_policy(&node);
                                                             // Also emit event so UI updates if relevant
-                                                            if let dag::DagPayload::Message(_) = node.payload {
+                                                            if self.store.is_blocked(&node.author).unwrap_or(false) {
+                                                                 println!("Ignoring node {} from blocked peer", node.id);
+                                                            } else if let dag::DagPayload::Message(_) = node.payload {
                                                                  let content = self.try_decrypt(&node);
                                                                  let _ = self.event_tx.send(AppEvent::MessageReceived(node.clone(), content));
+                                                            } else if let Some(event) = self.received_node_event(&node) {
+                                                                 let _ = self.event_tx.send(event);
                                                             } else {
                                                                  let _ = self.event_tx.send(AppEvent::BlockReceived(node.clone()));
                                                             }
-                                                            let _ = self.swarm.behaviour_mut().request_response.send_response(channel, BlockResponse::Ack);
+                                                            if let dag::DagPayload::MultiSigApproval(ref approval) = node.payload {
+                                                                self.execute_multisig_if_ready(&approval.proposal_cid);
+                                                            }
+                                                            if let Some(event) = self.petition_threshold_event(&node) {
+                                                                let _ = self.event_tx.send(event);
+                                                            }
+                                                            self.note_verification_change(&node);
+                                                            if let dag::DagPayload::ContractCall(ref call) = node.payload {
+                                                                if self.event_subscriptions.contains(&call.contract_id) {
+                                                                    self.send_new_contract_events(&call.contract_id, &node.id);
+                                                                }
+                                                            }
+                                                            let _ = self.send_block_response(channel, BlockResponse::Ack);
                                                         }
                                                     }
                                                     Ok(false) => {
-                                                        let _ = self.swarm.behaviour_mut().request_response.send_response(channel, BlockResponse::Error("Verification failed".to_string()));
+                                                        let _ = self.send_block_response(channel, BlockResponse::Error("Verification failed".to_string()));
                                                     }
                                                     Err(e) => {
-                                                        let _ = self.swarm.behaviour_mut().request_response.send_response(channel, BlockResponse::Error(e.to_string()));
+                                                        let _ = self.send_block_response(channel, BlockResponse::Error(e.to_string()));
                                                     }
                                                 }
                                             }
                                             Err(e) => {
-                                                let _ = self.swarm.behaviour_mut().request_response.send_response(channel, BlockResponse::Error(e.to_string()));
+                                                let _ = self.send_block_response(channel, BlockResponse::Error(e.to_string()));
                                             }
                                         }
                                     }
                                 }
                             }
                             request_response::Message::Response { request_id, response } => {
-                                if let Some(cid) = self.pending_requests.remove(&request_id) {
+                                self.inbound_bytes += response.payload_len() as u64;
+                                let sent_at = self.request_sent_at.remove(&request_id);
+                                match response {
+                                    BlockResponse::Ack => self.record_peer_response(peer, true, sent_at),
+                                    BlockResponse::NotFound => self.record_peer_response(peer, false, sent_at),
+                                    _ => {}
+                                }
+                                if let BlockResponse::SyncFilter(filter) = response {
+                                    self.remember_sync_filter(peer, filter);
+                                } else if let BlockResponse::SyncBatch { nodes, has_more, next_cursor, next_cid } = response {
+                                    self.handle_sync_batch(peer, nodes, has_more, next_cursor, next_cid);
+                                } else if let BlockResponse::StoreRoot(root) = response {
+                                    self.compare_store_root(peer, root);
+                                } else if let Some(cid) = self.pending_requests.remove(&request_id) {
                                     match response {
                                         BlockResponse::Block(data) => {
                                             println!("Received response for block {}", cid);
@@ -3687,13 +8200,21 @@ impl Backend {
                                                 let _ = self.event_tx.send(AppEvent::WebSearchResults(nodes));
                                             }
                                         }
+                                        BlockResponse::SyncFilter(_) | BlockResponse::SyncBatch { .. } | BlockResponse::StoreRoot(_) => {} // Handled above
                                     }
                                 }
                             }
                         }
                     }
-                    request_response::Event::OutboundFailure { request_id, error, .. } => {
+                    request_response::Event::OutboundFailure { peer, request_id, error, .. } => {
                         eprintln!("Request failed: {:?}", error);
+                        let sent_at = self.request_sent_at.remove(&request_id);
+                        self.record_peer_response(peer, false, sent_at);
+                        if let Some(&(_, _, received)) = self.full_syncs.get(&peer).filter(|(sync_request, _, _)| *sync_request == request_id) {
+                            self.full_syncs.remove(&peer);
+                            // Report what we got so the UI stops waiting; a new request resumes from scratch
+                            let _ = self.event_tx.send(AppEvent::SyncComplete { received });
+                        }
                         if let Some(cid) = self.pending_requests.remove(&request_id) {
                             let _ = self.event_tx.send(AppEvent::BlockFetched { cid, node: None });
                         }
@@ -3701,8 +8222,41 @@ impl Backend {
                     _ => {}
                 }
             }
-            SwarmEvent::ConnectionEstablished { peer_id, .. } => {
+            SwarmEvent::ConnectionEstablished { peer_id, endpoint, num_established, .. } => {
                 let _ = self.event_tx.send(AppEvent::PeerConnected(peer_id.to_string()));
+                self.pending_dials.remove(&peer_id);
+                let relay = match &endpoint {
+                    libp2p::core::ConnectedPoint::Dialer { address, .. } => network::relay_of(address),
+                    libp2p::core::ConnectedPoint::Listener { local_addr, send_back_addr } => {
+                        network::relay_of(local_addr).or_else(|| network::relay_of(send_back_addr))
+                    }
+                };
+                if let Some(relay) = relay {
+                    println!("Connected to {} through relay {}", peer_id, relay);
+                    let _ = self.event_tx.send(AppEvent::RelayedConnectionEstablished { peer_id: peer_id.to_string(), relay: relay.to_string() });
+                }
+                // Swap block filters with new peers so neither pushes what the other has;
+                // the dialer compares store roots first and only asks if they differ
+                if endpoint.is_dialer() && num_established.get() == 1 {
+                    self.send_block_request(&peer_id, BlockRequest::StoreRoot);
+                }
+            }
+            SwarmEvent::ConnectionClosed { peer_id, num_established: 0, .. } => {
+                self.peer_sync_filters.remove(&peer_id);
+            }
+            SwarmEvent::OutgoingConnectionError { peer_id: Some(peer_id), error, .. } if self.pending_dials.contains(&peer_id) => {
+                println!("Direct dial to {} failed ({}), retrying through a relay", peer_id, error);
+                self.pending_dials.remove(&peer_id);
+                self.dial_via_relay(peer_id);
+            }
+            SwarmEvent::Behaviour(MyBehaviourEvent::RelayClient(libp2p::relay::client::Event::ReservationReqAccepted { relay_peer_id, renewal: false, .. })) => {
+                println!("Relay {} accepted our reservation", relay_peer_id);
+            }
+            SwarmEvent::Behaviour(MyBehaviourEvent::RelayServer(libp2p::relay::Event::ReservationReqAccepted { src_peer_id, renewed: false })) => {
+                println!("Accepted relay reservation from {}", src_peer_id);
+            }
+            SwarmEvent::Behaviour(MyBehaviourEvent::RelayServer(libp2p::relay::Event::CircuitReqAccepted { src_peer_id, dst_peer_id })) => {
+                println!("Relaying circuit from {} to {}", src_peer_id, dst_peer_id);
             }
             SwarmEvent::Behaviour(MyBehaviourEvent::Kad(event)) => {
                 match event {
@@ -3717,7 +8271,7 @@ impl Backend {
                                     let query = url.trim_start_matches("search:term:").to_string();
                                     println!("Sending LocalSearch request for query: {}", query);
                                     for peer in providers {
-                                        let _request_id = self.swarm.behaviour_mut().request_response.send_request(&peer, BlockRequest::LocalSearch(query.clone()));
+                                        let _request_id = self.send_block_request(&peer, BlockRequest::LocalSearch(query.clone(), None));
                                         // We don't necessarily need to track this in pending_requests for a block CID, 
                                         // but we can track it to handle errors if we want.
                                         // But BlockResponse::SearchResults processing doesn't rely on pending_requests map for CID.
@@ -3725,7 +8279,7 @@ impl Backend {
                                 } else {
                                     // Normal content fetch
                                     for peer in providers {
-                                        let request_id = self.swarm.behaviour_mut().request_response.send_request(&peer, BlockRequest::Fetch(url.clone()));
+                                        let request_id = self.send_block_request(&peer, BlockRequest::Fetch(url.clone()));
                                         self.pending_requests.insert(request_id, url.clone());
                                     }
                                 }
@@ -3780,11 +8334,7 @@ impl Backend {
                     }
                     Ok(None) => {
                         println!("Block {} missing, requesting from {:?}", cid, propagation_source);
-                        let request_id = self
-                            .swarm
-                            .behaviour_mut()
-                            .request_response
-                            .send_request(&propagation_source, BlockRequest::Fetch(cid.clone()));
+                        let request_id = self.send_block_request(&propagation_source, BlockRequest::Fetch(cid.clone()));
                         self.pending_requests.insert(request_id, cid);
                     }
                     Err(e) => eprintln!("Store error: {:?}", e),
@@ -3805,28 +8355,56 @@ impl Backend {
         }
         
         println!("Stored block {}", cid);
+        self.apply_pin_policy(&node);
         
-        // Emit specific events based on type
-        if let dag::DagPayload::Message(_) = node.payload {
+        // Emit specific events based on type; blocked peers' nodes are kept for the DAG but stay silent
+        if self.store.is_blocked(&node.author).unwrap_or(false) {
+             println!("Ignoring block {} from blocked peer {}", cid, node.author);
+        } else if let dag::DagPayload::Message(_) = node.payload {
              let content = self.try_decrypt(&node);
              let _ = self.event_tx.send(AppEvent::MessageReceived(node.clone(), content));
         } else if let dag::DagPayload::Web(ref web) = node.payload {
              let _ = self.event_tx.send(AppEvent::WebPageFetched { url: web.url.clone(), content: Some(web.content.clone()) });
              let _ = self.event_tx.send(AppEvent::BlockReceived(node.clone()));
+        } else if let dag::DagPayload::GroupKey(ref key) = node.payload {
+             let _ = self.event_tx.send(AppEvent::GroupKeyRotated { group_id: key.group_id.clone() });
+        } else if let Some(event) = self.received_node_event(&node) {
+             let _ = self.event_tx.send(event);
         } else {
              let _ = self.event_tx.send(AppEvent::BlockReceived(node.clone()));
         }
 
+        // An approval may complete one of our multi-sig transfers
+        if let dag::DagPayload::MultiSigApproval(ref approval) = node.payload {
+            self.execute_multisig_if_ready(&approval.proposal_cid);
+        }
+        if let Some(event) = self.petition_threshold_event(&node) {
+            let _ = self.event_tx.send(event);
+        }
+        self.note_verification_change(&node);
+        if let dag::DagPayload::ContractCall(ref call) = node.payload {
+            if self.event_subscriptions.contains(&call.contract_id) {
+                self.send_new_contract_events(&call.contract_id, &node.id);
+            }
+        }
+
+        // Someone joined one of our groups: rotate so they can read new messages but not old ones
+        if let dag::DagPayload::GroupInviteResponse(ref response) = node.payload {
+            if response.accept {
+                if let Ok(Some(dag::DagNode { payload: dag::DagPayload::GroupInvite(invite), .. })) = self.store.get_node(&response.invite_id) {
+                    if invite.invitee_peer_id == node.author && self.is_group_owner(&invite.group_id) {
+                        self.publish_group_key(&invite.group_id);
+                    }
+                }
+            }
+        }
+
         // 2. Check for missing parents (Recursive Backfill)
         for parent_cid in node.prev {
             match self.store.get_node(&parent_cid) {
                 Ok(None) => {
                     println!("Parent {} missing, requesting from {:?}", parent_cid, source_peer);
-                    let request_id = self
-                        .swarm
-                        .behaviour_mut()
-                        .request_response
-                        .send_request(&source_peer, BlockRequest::Fetch(parent_cid.clone()));
+                    let request_id = self.send_block_request(&source_peer, BlockRequest::Fetch(parent_cid.clone()));
                     self.pending_requests.insert(request_id, parent_cid);
                 }
                 Ok(Some(_)) => {
@@ -3940,7 +8518,7 @@ mod tests {
 
         // Publish block on A
         let keypair = Keypair::generate_ed25519();
-        let payload = DagPayload::Post(PostPayload { content: "Replication Test".into(), attachments: vec![], geohash: None });
+        let payload = DagPayload::Post(PostPayload { content: "Replication Test".into(), attachments: vec![], geohash: None, announcement: false, quote_of: None, publish_at: None });
         let node = DagNode::new("post:v1".into(), payload, vec![], &keypair, 0).unwrap();
         
         println!("Publishing block {}", node.id);
@@ -3965,6 +8543,108 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_relayed_block_exchange() {
+        // Relay node: the only peer A and B can reach directly
+        let (_cmd_tx_r, cmd_rx_r) = mpsc::unbounded_channel();
+        let (event_tx_r, _event_rx_r) = mpsc::unbounded_channel();
+        let mut relay = Backend::new(Store::new_in_memory().unwrap(), cmd_rx_r, event_tx_r, Some(Keypair::generate_ed25519())).await.unwrap();
+        let relay_id = relay.local_peer_id();
+        let relay_addr = loop {
+            match tokio::time::timeout(Duration::from_secs(5), relay.swarm.select_next_some()).await {
+                Ok(SwarmEvent::NewListenAddr { address, .. }) => break address,
+                Ok(_) => {}
+                Err(_) => panic!("Timed out waiting for relay listener"),
+            }
+        };
+        let relay_addr: libp2p::Multiaddr = format!("{}/p2p/{}", relay_addr.to_string().replace("0.0.0.0", "127.0.0.1"), relay_id).parse().unwrap();
+        // Reservations hand out the relay's external addresses, so it needs one
+        relay.swarm.add_external_address(relay_addr.clone());
+        tokio::spawn(async move {
+            relay.run().await;
+        });
+
+        // Node A holds a block; it is stored directly so it can't spread by gossip
+        let (cmd_tx_a, cmd_rx_a) = mpsc::unbounded_channel();
+        let (event_tx_a, mut event_rx_a) = mpsc::unbounded_channel();
+        let store_a = Store::new_in_memory().unwrap();
+        let mut backend_a = Backend::new(store_a.clone(), cmd_rx_a, event_tx_a, Some(Keypair::generate_ed25519())).await.unwrap();
+        let peer_id_a = backend_a.local_peer_id().to_string();
+        // mDNS would let A and B find each other directly on the test host
+        backend_a.disable_local_discovery();
+        backend_a.listen_via_relay(relay_addr.clone()).unwrap();
+
+        let keypair = Keypair::generate_ed25519();
+        let payload = DagPayload::Post(PostPayload { content: "Relayed Block".into(), attachments: vec![], geohash: None, announcement: false, quote_of: None, publish_at: None });
+        let node = DagNode::new("post:v1".into(), payload, vec![], &keypair, 0).unwrap();
+        store_a.put_node(&node).unwrap();
+
+        // Node B is only reachable through its reservation on the relay
+        let (cmd_tx_b, cmd_rx_b) = mpsc::unbounded_channel();
+        let (event_tx_b, mut event_rx_b) = mpsc::unbounded_channel();
+        let mut backend_b = Backend::new(Store::new_in_memory().unwrap(), cmd_rx_b, event_tx_b, Some(Keypair::generate_ed25519())).await.unwrap();
+        let peer_id_b = backend_b.local_peer_id().to_string();
+        backend_b.disable_local_discovery();
+        backend_b.listen_via_relay(relay_addr).unwrap();
+
+        tokio::spawn(async move {
+            backend_a.run().await;
+        });
+        tokio::spawn(async move {
+            backend_b.run().await;
+        });
+
+        let start = std::time::Instant::now();
+        loop {
+            if start.elapsed() > Duration::from_secs(10) {
+                panic!("Timed out waiting for B's relay reservation");
+            }
+            if let Ok(Some(AppEvent::Listening(addr))) = tokio::time::timeout(Duration::from_millis(100), event_rx_b.recv()).await {
+                if addr.contains("p2p-circuit") {
+                    break;
+                }
+            }
+        }
+
+        // B's "direct" address is unreachable, as if behind NAT, so A must fall back to the relay
+        cmd_tx_a.send(AppCmd::DialPeer { multiaddr: format!("/ip4/127.0.0.1/tcp/1/p2p/{}", peer_id_b) }).unwrap();
+        let start = std::time::Instant::now();
+        loop {
+            if start.elapsed() > Duration::from_secs(10) {
+                panic!("Timed out waiting for relayed connection");
+            }
+            if let Ok(Some(AppEvent::RelayedConnectionEstablished { peer_id, relay })) = tokio::time::timeout(Duration::from_millis(100), event_rx_a.recv()).await {
+                assert_eq!(peer_id, peer_id_b);
+                assert_eq!(relay, relay_id.to_string());
+                break;
+            }
+        }
+        // B only routes requests over the circuit once its own swarm has reported it
+        let start = std::time::Instant::now();
+        loop {
+            if start.elapsed() > Duration::from_secs(10) {
+                panic!("Timed out waiting for B's side of the relayed connection");
+            }
+            if let Ok(Some(AppEvent::RelayedConnectionEstablished { peer_id, .. })) = tokio::time::timeout(Duration::from_millis(100), event_rx_b.recv()).await {
+                assert_eq!(peer_id, peer_id_a);
+                break;
+            }
+        }
+
+        cmd_tx_b.send(AppCmd::FetchBlock { cid: node.id.clone(), peer_id: Some(peer_id_a) }).unwrap();
+        let start = std::time::Instant::now();
+        loop {
+            if start.elapsed() > Duration::from_secs(10) {
+                panic!("Timed out waiting for BlockFetched");
+            }
+            if let Ok(Some(AppEvent::BlockFetched { cid, node: fetched })) = tokio::time::timeout(Duration::from_millis(100), event_rx_b.recv()).await {
+                assert_eq!(cid, node.id);
+                assert_eq!(fetched.expect("Block should arrive through the relay").id, node.id);
+                break;
+            }
+        }
+    }
+
     #[tokio::test]
     async fn test_profile_publishing() {
         let (cmd_tx, cmd_rx) = mpsc::unbounded_channel();
@@ -4007,6 +8687,290 @@ mod tests {
         tokio::time::sleep(Duration::from_secs(1)).await;
     }
 
+    #[tokio::test]
+    async fn test_import_identity() {
+        let (cmd_tx, cmd_rx) = mpsc::unbounded_channel();
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let store = Store::new_in_memory().unwrap();
+        let mine = x25519_dalek::StaticSecret::random_from_rng(OsRng);
+        let theirs = x25519_dalek::PublicKey::from(&x25519_dalek::StaticSecret::random_from_rng(OsRng));
+        store.save_ratchet_state("bob", &ratchet::RatchetState::initiate(&mine, &theirs, 0)).unwrap();
+        let key_file = std::fs::read(IDENTITY_FILE).ok();
+
+        let mut backend = Backend::new(store.clone(), cmd_rx, event_tx, Some(Keypair::generate_ed25519())).await.unwrap();
+        tokio::spawn(async move {
+            backend.run().await;
+        });
+
+        let restored = Keypair::generate_ed25519();
+        let bundle = identity::export_bundle(&restored, &[9u8; 32], "hunter2").unwrap();
+        let bundle_b64 = general_purpose::STANDARD.encode(bundle);
+        cmd_tx.send(AppCmd::ImportIdentity { bundle_b64: bundle_b64.clone(), password: "wrong".to_string() }).unwrap();
+        cmd_tx.send(AppCmd::ImportIdentity { bundle_b64, password: "hunter2".to_string() }).unwrap();
+
+        let start = std::time::Instant::now();
+        let mut failed = false;
+        loop {
+            if start.elapsed() > Duration::from_secs(5) {
+                panic!("Timed out waiting for the restored identity");
+            }
+            match tokio::time::timeout(Duration::from_millis(100), event_rx.recv()).await {
+                Ok(Some(AppEvent::ImportFailed { .. })) => failed = true,
+                Ok(Some(AppEvent::MyIdentity(peer_id))) if peer_id == restored.public().to_peer_id().to_string() => break,
+                _ => {}
+            }
+        }
+        assert!(failed, "A wrong password should be reported");
+        // Sessions from the old key are gone, and an injected identity never touches the key files
+        assert!(store.get_ratchet_state("bob").unwrap().is_none());
+        assert_eq!(std::fs::read(IDENTITY_FILE).ok(), key_file);
+    }
+
+    #[test]
+    fn test_read_commands() {
+        assert!(Backend::is_read_command(&AppCmd::FetchPosts));
+        assert!(Backend::is_read_command(&AppCmd::CheckVerificationStatus));
+        assert!(Backend::is_read_command(&AppCmd::SearchWeb { query: "p2p".to_string() }));
+        assert!(!Backend::is_read_command(&AppCmd::RebuildIndexes));
+        assert!(!Backend::is_read_command(&AppCmd::PinBlock { cid: "cid".to_string() }));
+    }
+
+    #[tokio::test]
+    async fn test_message_after_identity_restore() {
+        use crate::backend::dag::{MessagePayload, ProfilePayload};
+        let (_cmd_tx, cmd_rx) = mpsc::unbounded_channel();
+        let (event_tx, _event_rx) = mpsc::unbounded_channel();
+        let store = Store::new_in_memory().unwrap();
+        let alice = Keypair::generate_ed25519();
+        let alice_id = alice.public().to_peer_id().to_string();
+        let alice_key = x25519_dalek::StaticSecret::random_from_rng(OsRng);
+        let profile = DagPayload::Profile(ProfilePayload { name: "Alice".to_string(), bio: String::new(), founder_id: None, encryption_pubkey: Some(hex::encode(x25519_dalek::PublicKey::from(&alice_key).to_bytes())), photo: None });
+        let profile = DagNode::new("profile:v1".to_string(), profile, vec![], &alice, 0).unwrap();
+        store.put_node(&profile).unwrap();
+        store.update_head(&alice_id, &profile.id).unwrap();
+
+        let backend = Backend::new(store, cmd_rx, event_tx, Some(Keypair::generate_ed25519())).await.unwrap();
+        let bob_pubkey = x25519_dalek::PublicKey::from(&backend.encryption_keypair);
+        let send = |session: &mut ratchet::RatchetState, content: &str| {
+            let encrypted = session.encrypt(content.as_bytes()).unwrap();
+            let msg = MessagePayload { recipient: backend.local_peer_id().to_string(), ciphertext: hex::encode(encrypted.ciphertext), nonce: hex::encode(encrypted.nonce), ephemeral_pubkey: hex::encode(encrypted.ratchet_pubkey), group_id: None, ratchet: Some(encrypted.header), group_key_id: None };
+            (msg, encrypted.header)
+        };
+
+        let mut before = ratchet::RatchetState::initiate(&alice_key, &bob_pubkey, 0);
+        let (hello, header) = send(&mut before, "Hello");
+        assert_eq!(backend.ratchet_decrypt(&alice_id, &hello, header).as_deref(), Some("Hello"));
+
+        // Alice restores her identity on a fresh device, which has no sessions and a later epoch
+        let restored = Store::new_in_memory().unwrap();
+        restored.clear_ratchet_sessions().unwrap();
+        let mut after = ratchet::RatchetState::initiate(&alice_key, &bob_pubkey, restored.get_session_epoch().unwrap());
+        let (msg, header) = send(&mut after, "Restored");
+        assert_eq!(backend.ratchet_decrypt(&alice_id, &msg, header).as_deref(), Some("Restored"));
+
+        // A handshake from before the restore can't take the session back
+        let mut stale = ratchet::RatchetState::initiate(&alice_key, &bob_pubkey, 0);
+        let (msg, header) = send(&mut stale, "Stale");
+        assert!(backend.ratchet_decrypt(&alice_id, &msg, header).is_none());
+        assert!(backend.ratchet_decrypt(&alice_id, &hello, header).is_none());
+
+        // Bob answers on the new session
+        let mut session = backend.store.get_ratchet_state(&alice_id).unwrap().unwrap();
+        let reply = session.encrypt(b"Welcome back").unwrap();
+        assert_eq!(after.decrypt(reply.ratchet_pubkey, reply.header, &reply.nonce, &reply.ciphertext).unwrap(), b"Welcome back");
+    }
+
+    #[tokio::test]
+    async fn test_restricted_post_edits_stay_with_audience() {
+        use crate::backend::dag::{FollowPayload, PostVisibility, PostVisibilityPayload};
+        let (_cmd_tx, cmd_rx) = mpsc::unbounded_channel();
+        let (event_tx, _event_rx) = mpsc::unbounded_channel();
+        let store = Store::new_in_memory().unwrap();
+        let (alice, bob, stranger) = (Keypair::generate_ed25519(), Keypair::generate_ed25519(), Keypair::generate_ed25519());
+        let id = |k: &Keypair| k.public().to_peer_id().to_string();
+        let post = |content: &str, prev: Vec<String>, version: &str| {
+            let payload = DagPayload::Post(PostPayload { content: content.to_string(), attachments: vec![], geohash: None, announcement: false, quote_of: None, publish_at: None });
+            DagNode::new(format!("post:{}", version), payload, prev, &alice, 0).unwrap()
+        };
+
+        let original = post("Friends only", vec![], "v1");
+        let restrict = DagPayload::PostVisibility(PostVisibilityPayload { post_id: original.id.clone(), visibility: PostVisibility::FollowersOnly });
+        let restrict = DagNode::new("post_visibility:v1".to_string(), restrict, vec![original.id.clone()], &alice, 0).unwrap();
+        let follow = DagPayload::Follow(FollowPayload { target: id(&alice), follow: true });
+        let follow = DagNode::new("follow:v1".to_string(), follow, vec![], &bob, 0).unwrap();
+        // The author's head comes first in an edit's prev, the edited post last
+        let edit = post("Friends only, edited", vec![restrict.id.clone(), original.id.clone()], "v2");
+        for node in [&original, &restrict, &follow, &edit] {
+            store.put_node(node).unwrap();
+        }
+
+        let backend = Backend::new(store, cmd_rx, event_tx, Some(Keypair::generate_ed25519())).await.unwrap();
+        let audience = backend.post_audience(&edit).expect("An edit of a restricted post is restricted too");
+        assert!(audience.contains(&id(&bob)) && audience.contains(&id(&alice)));
+        assert!(backend.sync_batch_nodes(&[original.clone(), edit.clone()], &id(&stranger)).is_empty());
+        assert_eq!(backend.sync_batch_nodes(&[original, edit], &id(&bob)).len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_group_post_audience() {
+        use crate::backend::dag::{GroupPayload, PostVisibility, PostVisibilityPayload};
+        let (_cmd_tx, cmd_rx) = mpsc::unbounded_channel();
+        let (event_tx, _event_rx) = mpsc::unbounded_channel();
+        let store = Store::new_in_memory().unwrap();
+        let (alice, carol, stranger) = (Keypair::generate_ed25519(), Keypair::generate_ed25519(), Keypair::generate_ed25519());
+        let id = |k: &Keypair| k.public().to_peer_id().to_string();
+
+        let group = DagPayload::Group(GroupPayload { name: "Club".to_string(), members: vec![id(&carol)], owner: id(&alice) });
+        let group = DagNode::new("group:v1".to_string(), group, vec![], &alice, 0).unwrap();
+        let payload = DagPayload::Post(PostPayload { content: "Members only".to_string(), attachments: vec![], geohash: None, announcement: false, quote_of: None, publish_at: None });
+        let post = DagNode::new("post:v1".to_string(), payload, vec![], &alice, 0).unwrap();
+        let restrict = DagPayload::PostVisibility(PostVisibilityPayload { post_id: post.id.clone(), visibility: PostVisibility::MembersOf(group.id.clone()) });
+        let restrict = DagNode::new("post_visibility:v1".to_string(), restrict, vec![post.id.clone()], &alice, 0).unwrap();
+        for node in [&group, &post, &restrict] {
+            store.put_node(node).unwrap();
+        }
+
+        let backend = Backend::new(store, cmd_rx, event_tx, Some(Keypair::generate_ed25519())).await.unwrap();
+        let audience = backend.post_audience(&post).expect("A group-only post is restricted");
+        assert!(audience.contains(&id(&carol)) && !audience.contains(&id(&stranger)));
+        assert!(backend.sync_batch_nodes(&[post.clone(), restrict.clone()], &id(&stranger)).is_empty());
+        assert_eq!(backend.sync_batch_nodes(&[post, restrict], &id(&carol)).len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_sync_withholds_messages_and_receipts() {
+        use crate::backend::dag::{MessagePayload, MessageReceiptPayload};
+        let (_cmd_tx, cmd_rx) = mpsc::unbounded_channel();
+        let (event_tx, _event_rx) = mpsc::unbounded_channel();
+        let store = Store::new_in_memory().unwrap();
+        let (alice, bob, stranger) = (Keypair::generate_ed25519(), Keypair::generate_ed25519(), Keypair::generate_ed25519());
+        let id = |k: &Keypair| k.public().to_peer_id().to_string();
+
+        let message = DagPayload::Message(MessagePayload { recipient: id(&bob), ciphertext: "00".to_string(), nonce: "00".to_string(), ephemeral_pubkey: "00".to_string(), group_id: None, ratchet: None, group_key_id: None });
+        let message = DagNode::new("message:v1".to_string(), message, vec![], &alice, 0).unwrap();
+        let receipt = DagPayload::MessageReceipt(MessageReceiptPayload { message_cid: message.id.clone(), read_at: 0 });
+        let receipt = DagNode::new("message_receipt:v1".to_string(), receipt, vec![], &bob, 0).unwrap();
+        for node in [&message, &receipt] {
+            store.put_node(node).unwrap();
+        }
+
+        let backend = Backend::new(store, cmd_rx, event_tx, Some(Keypair::generate_ed25519())).await.unwrap();
+        let batch = [message, receipt];
+        assert!(backend.sync_batch_nodes(&batch, &id(&stranger)).is_empty());
+        assert_eq!(backend.sync_batch_nodes(&batch, &id(&alice)).len(), 2);
+        assert_eq!(backend.sync_batch_nodes(&batch, &id(&bob)).len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_late_post_edit_event() {
+        let (_cmd_tx, cmd_rx) = mpsc::unbounded_channel();
+        let (event_tx, _event_rx) = mpsc::unbounded_channel();
+        let store = Store::new_in_memory().unwrap();
+        let alice = Keypair::generate_ed25519();
+        let post = |content: &str, prev: Vec<String>, version: &str, age_mins: i64| {
+            let payload = DagPayload::Post(PostPayload { content: content.to_string(), attachments: vec![], geohash: None, announcement: false, quote_of: None, publish_at: None });
+            let mut node = DagNode::new(format!("post:{}", version), payload, prev, &alice, 0).unwrap();
+            node.timestamp = chrono::Utc::now() - chrono::Duration::minutes(age_mins);
+            node.id = node.calculate_cid().unwrap();
+            node
+        };
+        let original = post("Frist", vec![], "v1", 10);
+        let newer = post("First!", vec![original.id.clone()], "v2", 1);
+        let older = post("First", vec![original.id.clone()], "v2", 5);
+        for node in [&original, &newer, &older] {
+            store.put_node(node).unwrap();
+        }
+
+        // The older edit arrives last but the newer one stays on screen
+        let backend = Backend::new(store, cmd_rx, event_tx, Some(Keypair::generate_ed25519())).await.unwrap();
+        match backend.received_node_event(&older) {
+            Some(AppEvent::PostEdited(latest)) => assert_eq!(latest.id, newer.id),
+            other => panic!("Expected PostEdited, got {:?}", other),
+        }
+        assert!(backend.received_node_event(&original).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_verified_peer_count() {
+        let (cmd_tx, cmd_rx) = mpsc::unbounded_channel();
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let store = Store::new_in_memory().unwrap();
+
+        let mut backend = Backend::new(store, cmd_rx, event_tx, Some(Keypair::generate_ed25519())).await.unwrap();
+
+        tokio::spawn(async move {
+            backend.run().await;
+        });
+
+        // First profile on an empty network becomes founder #1
+        cmd_tx.send(AppCmd::PublishProfile {
+            name: "Founder".to_string(),
+            bio: "First".to_string(),
+            photo: None,
+        }).unwrap();
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        cmd_tx.send(AppCmd::FetchVerifiedPeerCount).unwrap();
+
+        let start = std::time::Instant::now();
+        loop {
+            if start.elapsed() > Duration::from_secs(2) {
+                panic!("Timed out waiting for VerifiedPeerCountFetched");
+            }
+            if let Ok(Some(AppEvent::VerifiedPeerCountFetched { total_profiles, verified, founders })) =
+                tokio::time::timeout(Duration::from_millis(100), event_rx.recv()).await
+            {
+                assert_eq!(total_profiles, 1);
+                assert_eq!(verified, 1);
+                assert_eq!(founders, 1);
+                break;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_verification_path() {
+        let (cmd_tx, cmd_rx) = mpsc::unbounded_channel();
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let store = Store::new_in_memory().unwrap();
+        let keypair = Keypair::generate_ed25519();
+        let my_id = libp2p::PeerId::from_public_key(&keypair.public()).to_string();
+
+        let mut backend = Backend::new(store, cmd_rx, event_tx, Some(keypair)).await.unwrap();
+
+        tokio::spawn(async move {
+            backend.run().await;
+        });
+
+        // Founder #1 vouches for a newcomer
+        cmd_tx.send(AppCmd::PublishProfile {
+            name: "Founder".to_string(),
+            bio: "First".to_string(),
+            photo: None,
+        }).unwrap();
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        let newcomer = libp2p::PeerId::random().to_string();
+        cmd_tx.send(AppCmd::Vouch { target_peer_id: newcomer.clone() }).unwrap();
+        cmd_tx.send(AppCmd::FetchPeerVerificationPath { peer_id: newcomer.clone() }).unwrap();
+
+        let start = std::time::Instant::now();
+        loop {
+            if start.elapsed() > Duration::from_secs(2) {
+                panic!("Timed out waiting for VerificationPathFetched");
+            }
+            if let Ok(Some(AppEvent::VerificationPathFetched { peer_id, path })) =
+                tokio::time::timeout(Duration::from_millis(100), event_rx.recv()).await
+            {
+                assert_eq!(peer_id, newcomer);
+                assert_eq!(path, vec![
+                    ("network".to_string(), my_id.clone()),
+                    (my_id.clone(), newcomer.clone()),
+                ]);
+                break;
+            }
+        }
+    }
+
     #[tokio::test]
     async fn test_web_page_publishing() {
         let (cmd_tx, cmd_rx) = mpsc::unbounded_channel();
@@ -4063,6 +9027,10 @@ mod tests {
             }
             if let Ok(Some(event)) = tokio::time::timeout(Duration::from_millis(100), event_rx.recv()).await {
                 if let AppEvent::WebPageFetched { url: u, content: c } = event {
+                    // The publish confirmation may still be queued behind the profile event
+                    if c.as_deref() == Some("<h1>Published!</h1>") {
+                        continue;
+                    }
                     assert_eq!(u, url);
                     assert_eq!(c, Some(content.clone()));
                     break;
@@ -4204,7 +9172,7 @@ mod tests {
 
         // 1. Create a block on A (manually, to avoid gossip)
         let keypair = Keypair::generate_ed25519();
-        let payload = DagPayload::Post(PostPayload { content: "Secret Block".into(), attachments: vec![], geohash: None });
+        let payload = DagPayload::Post(PostPayload { content: "Secret Block".into(), attachments: vec![], geohash: None, announcement: false, quote_of: None, publish_at: None });
         let node = DagNode::new("post:v1".into(), payload, vec![], &keypair, 0).unwrap();
         
         // We need to access store_a to put the node, but backend_a owns it.
@@ -4397,12 +9365,38 @@ mod tests {
         assert!(!contract_id.is_empty(), "Failed to receive contract block");
         println!("Contract deployed: {}", contract_id);
 
+        // A dry run reports the would-be state without publishing a call
+        cmd_tx.send(AppCmd::CallContract {
+            contract_id: contract_id.clone(),
+            method: "set".to_string(),
+            params: r#"{"key": "foo", "value": "draft"}"#.to_string(),
+            dry_run: true,
+        }).unwrap();
+        let mut simulated = None;
+        let start = std::time::Instant::now();
+        while start.elapsed() < Duration::from_secs(5) && simulated.is_none() {
+             if let Ok(Some(event)) = tokio::time::timeout(Duration::from_millis(100), event_rx.recv()).await {
+                 match event {
+                     AppEvent::ContractSimulationResult { contract_id: cid, resulting_state, gas_consumed, error } if cid == contract_id => {
+                         simulated = Some((resulting_state, gas_consumed, error));
+                     }
+                     AppEvent::BlockReceived(node) => assert_ne!(node.r#type, "contract_call:v1", "Dry run published a call"),
+                     _ => {}
+                 }
+             }
+        }
+        let (resulting_state, gas_consumed, error) = simulated.expect("No simulation result");
+        assert!(resulting_state.contains(r#""foo": "draft""#));
+        assert!(gas_consumed > 0);
+        assert_eq!(error, None);
+
         // 3. Call Contract (Set)
         let set_params = r#"{"key": "foo", "value": "bar"}"#.to_string();
         cmd_tx.send(AppCmd::CallContract {
             contract_id: contract_id.clone(),
             method: "set".to_string(),
             params: set_params,
+            dry_run: false,
         }).unwrap();
 
         // Wait for call block
@@ -4449,6 +9443,7 @@ mod tests {
             contract_id: contract_id.clone(),
             method: "delete".to_string(),
             params: delete_params,
+            dry_run: false,
         }).unwrap();
 
         // Wait for call block
@@ -4630,7 +9625,7 @@ mod tests {
         tokio::time::sleep(Duration::from_secs(1)).await;
 
         // Verify B is following A
-        cmd_tx_b.send(AppCmd::FetchFollowing { target: peer_id_b.clone() });
+        cmd_tx_b.send(AppCmd::FetchFollowing { target: peer_id_b.clone() }).unwrap();
         
         let mut following_verified = false;
         let start = std::time::Instant::now();
@@ -4651,13 +9646,16 @@ mod tests {
             content: "Hello from Alice".to_string(),
             attachments: vec![],
             geohash: None,
+            announcement: false,
+            quote_of: None,
+            publish_at: None,
         }).unwrap();
         println!("A published post");
 
         tokio::time::sleep(Duration::from_secs(2)).await;
 
         // B Fetches Following Posts
-        cmd_tx_b.send(AppCmd::FetchFollowingPosts);
+        cmd_tx_b.send(AppCmd::FetchFollowingPosts).unwrap();
         println!("B fetching following posts");
 
         let mut post_found = false;
@@ -4691,7 +9689,7 @@ mod tests {
         tokio::time::sleep(Duration::from_secs(2)).await;
         
         // B Fetches Stories
-        cmd_tx_b.send(AppCmd::FetchStories);
+        cmd_tx_b.send(AppCmd::FetchStories).unwrap();
         println!("B fetching stories");
         
         let mut story_found = false;
@@ -1,10 +1,10 @@
 use libp2p::{
-    gossipsub, request_response, kad,
+    gossipsub, request_response, kad, relay,
     swarm::{NetworkBehaviour, Swarm},
     Multiaddr, PeerId,
 };
 #[cfg(not(target_arch = "wasm32"))]
-use libp2p::mdns;
+use libp2p::{mdns, swarm::behaviour::toggle::Toggle};
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::DefaultHasher;
 use std::error::Error;
@@ -24,26 +24,60 @@ pub const BOOTSTRAP_NODES: [&str; 4] = [
 pub enum BlockRequest {
     Fetch(String),
     Store(Vec<u8>),
-    LocalSearch(String),
+    LocalSearch(String, Option<String>), // (query, author filter)
+    SyncFilter(Vec<u8>), // Bloom filter of the sender's block CIDs
+    SyncFrom { since_timestamp: u64, after_cid: Option<String> }, // The next page of blocks after this (Unix time, CID) cursor
+    StoreRoot, // Ask for the peer's Merkle root, to see whether a filter sync is needed
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum BlockResponse {
     Block(Vec<u8>),
     SearchResults(Vec<Vec<u8>>),
+    SyncFilter(Vec<u8>),
+    // Nodes are plain JSON like `Block`; zstd compression is a local storage detail and never sent
+    SyncBatch { nodes: Vec<Vec<u8>>, has_more: bool, next_cursor: u64, next_cid: Option<String> },
+    StoreRoot(Vec<u8>),
     Ack,
     NotFound,
     Error(String),
 }
 
+impl BlockRequest {
+    /// Bytes of data carried, not counting CBOR and stream framing
+    pub fn payload_len(&self) -> usize {
+        match self {
+            BlockRequest::Fetch(cid) => cid.len(),
+            BlockRequest::Store(data) | BlockRequest::SyncFilter(data) => data.len(),
+            BlockRequest::LocalSearch(query, author) => query.len() + author.as_ref().map_or(0, |a| a.len()),
+            BlockRequest::SyncFrom { after_cid, .. } => std::mem::size_of::<u64>() + after_cid.as_ref().map_or(0, |c| c.len()),
+            BlockRequest::StoreRoot => 0,
+        }
+    }
+}
+
+impl BlockResponse {
+    /// Bytes of data carried, not counting CBOR and stream framing
+    pub fn payload_len(&self) -> usize {
+        match self {
+            BlockResponse::Block(data) | BlockResponse::SyncFilter(data) | BlockResponse::StoreRoot(data) => data.len(),
+            BlockResponse::SearchResults(results) | BlockResponse::SyncBatch { nodes: results, .. } => results.iter().map(|r| r.len()).sum(),
+            BlockResponse::Error(message) => message.len(),
+            BlockResponse::Ack | BlockResponse::NotFound => 0,
+        }
+    }
+}
+
 #[derive(NetworkBehaviour)]
 #[behaviour(to_swarm = "MyBehaviourEvent")]
 pub struct MyBehaviour {
     #[cfg(not(target_arch = "wasm32"))]
-    pub mdns: mdns::tokio::Behaviour,
+    pub mdns: Toggle<mdns::tokio::Behaviour>,
     pub gossipsub: gossipsub::Behaviour,
     pub request_response: request_response::cbor::Behaviour<BlockRequest, BlockResponse>,
     pub kad: kad::Behaviour<kad::store::MemoryStore>,
+    pub relay_client: relay::client::Behaviour,
+    pub relay_server: relay::Behaviour,
 }
 
 #[derive(Debug)]
@@ -53,6 +87,8 @@ pub enum MyBehaviourEvent {
     Gossipsub(gossipsub::Event),
     RequestResponse(request_response::Event<BlockRequest, BlockResponse>),
     Kad(kad::Event),
+    RelayClient(relay::client::Event),
+    RelayServer(relay::Event),
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -80,6 +116,39 @@ impl From<kad::Event> for MyBehaviourEvent {
     }
 }
 
+impl From<relay::client::Event> for MyBehaviourEvent {
+    fn from(event: relay::client::Event) -> Self {
+        MyBehaviourEvent::RelayClient(event)
+    }
+}
+
+impl From<relay::Event> for MyBehaviourEvent {
+    fn from(event: relay::Event) -> Self {
+        MyBehaviourEvent::RelayServer(event)
+    }
+}
+
+/// The peer ID at the end of a multiaddr, if it names one
+pub fn peer_id_of(addr: &Multiaddr) -> Option<PeerId> {
+    addr.iter().filter_map(|p| match p {
+        libp2p::multiaddr::Protocol::P2p(peer_id) => Some(peer_id),
+        _ => None,
+    }).last()
+}
+
+/// The relay a `/p2p-circuit` address goes through, i.e. the peer named just before the circuit
+pub fn relay_of(addr: &Multiaddr) -> Option<PeerId> {
+    let mut relay = None;
+    for protocol in addr.iter() {
+        match protocol {
+            libp2p::multiaddr::Protocol::P2p(peer_id) => relay = Some(peer_id),
+            libp2p::multiaddr::Protocol::P2pCircuit => return relay,
+            _ => {}
+        }
+    }
+    None
+}
+
 pub fn create_swarm(keypair: libp2p::identity::Keypair) -> Result<Swarm<MyBehaviour>, Box<dyn Error>> {
     #[cfg(not(target_arch = "wasm32"))]
     let builder = libp2p::SwarmBuilder::with_existing_identity(keypair)
@@ -89,13 +158,14 @@ pub fn create_swarm(keypair: libp2p::identity::Keypair) -> Result<Swarm<MyBehavi
             libp2p::noise::Config::new,
             libp2p::yamux::Config::default,
         )?
-        .with_dns()?;
+        .with_dns()?
+        .with_relay_client(libp2p::noise::Config::new, libp2p::yamux::Config::default)?;
     
     #[cfg(target_arch = "wasm32")]
     return Err("WASM networking not fully implemented. Please use Desktop for now.".into());
 
     #[cfg(not(target_arch = "wasm32"))]
-    let swarm = builder.with_behaviour(|key| {
+    let swarm = builder.with_behaviour(|key, relay_client| {
             // mDNS
             let mdns = mdns::tokio::Behaviour::new(mdns::Config::default(), PeerId::from(key.public()))
                 .expect("Failed to create mDNS behaviour");
@@ -152,11 +222,17 @@ pub fn create_swarm(keypair: libp2p::identity::Keypair) -> Result<Swarm<MyBehavi
                 }
             }
 
+            // Relay v2 server, so publicly reachable nodes such as the bootstrap
+            // nodes can carry circuits between peers stuck behind NAT
+            let relay_server = relay::Behaviour::new(PeerId::from(key.public()), relay::Config::default());
+
             MyBehaviour {
-                mdns,
+                mdns: Toggle::from(Some(mdns)),
                 gossipsub,
                 request_response,
                 kad,
+                relay_client,
+                relay_server,
             }
         })?
         .build();
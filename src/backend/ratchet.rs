@@ -0,0 +1,345 @@
+//! Double Ratchet sessions for direct messages.
+//!
+//! Every message is encrypted with its own key from a symmetric chain, and each
+//! reply moves both chains forward with a fresh X25519 exchange. Keys are deleted
+//! once used, so captured ciphertext can't be read with a later stolen chain key.
+//!
+//! What this does not give:
+//! - Messages sent before the first reply are keyed from the recipient's long-term
+//!   encryption key (there are no one-time prekeys), so that key decrypts them.
+//! - Each side keeps a readable copy of every message, sealed under a key derived from
+//!   its long-term encryption key (`seal_local`). Anyone holding that key and the
+//!   local store can read the stored history.
+use aes_gcm::{
+    aead::{Aead, AeadCore, KeyInit, Payload},
+    Aes256Gcm, Key, Nonce,
+};
+use hmac::{Hmac, Mac};
+use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use x25519_dalek::{PublicKey, StaticSecret};
+
+use crate::backend::dag::RatchetHeader;
+
+/// Most message keys kept for messages that have not arrived yet
+const MAX_SKIPPED_KEYS: usize = 256;
+
+/// A message key held for a message that has not arrived yet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkippedKey {
+    pub ratchet_pubkey: [u8; 32],
+    pub counter: u32,
+    pub key: [u8; 32],
+}
+
+/// One side of a conversation's Double Ratchet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RatchetState {
+    pub root_key: [u8; 32],
+    pub chain_key: Option<[u8; 32]>, // Sending chain; None until the first exchange completes
+    pub recv_chain_key: Option<[u8; 32]>,
+    pub message_keys: Vec<SkippedKey>, // Keys for skipped messages, oldest first
+    pub send_counter: u32,
+    pub recv_counter: u32,
+    pub prev_send_counter: u32,
+    dh_secret: [u8; 32],
+    remote_pubkey: Option<[u8; 32]>,
+    #[serde(default)]
+    pub epoch: u64, // Our session epoch, stamped on every header we send
+    #[serde(default)]
+    pub remote_epoch: u64, // The peer's session epoch this session was started under
+}
+
+/// A message encrypted by a ratchet, ready for a `MessagePayload`
+pub struct RatchetCiphertext {
+    pub ratchet_pubkey: [u8; 32],
+    pub header: RatchetHeader,
+    pub nonce: Vec<u8>,
+    pub ciphertext: Vec<u8>,
+}
+
+impl RatchetState {
+    /// Start a session with a peer from their published encryption key (X3DH-like).
+    /// The first sending chain mixes a fresh ephemeral key into the identity secret.
+    pub fn initiate(identity: &StaticSecret, remote_identity: &PublicKey, epoch: u64) -> Self {
+        let shared = handshake_secret(identity, remote_identity);
+        let dh_secret = StaticSecret::random_from_rng(OsRng);
+        let (root_key, chain_key) = kdf_root(&shared, dh_secret.diffie_hellman(remote_identity).as_bytes());
+        Self {
+            root_key,
+            chain_key: Some(chain_key),
+            recv_chain_key: None,
+            message_keys: Vec::new(),
+            send_counter: 0,
+            recv_counter: 0,
+            prev_send_counter: 0,
+            dh_secret: dh_secret.to_bytes(),
+            remote_pubkey: Some(remote_identity.to_bytes()),
+            epoch,
+            remote_epoch: 0,
+        }
+    }
+
+    /// The other half of `initiate`, built when the peer's first message arrives
+    pub fn respond(identity: &StaticSecret, remote_identity: &PublicKey, epoch: u64) -> Self {
+        Self {
+            root_key: handshake_secret(identity, remote_identity),
+            chain_key: None,
+            recv_chain_key: None,
+            message_keys: Vec::new(),
+            send_counter: 0,
+            recv_counter: 0,
+            prev_send_counter: 0,
+            dh_secret: identity.to_bytes(),
+            remote_pubkey: None,
+            epoch,
+            remote_epoch: 0,
+        }
+    }
+
+    /// Whether this side can send yet. A responder can only reply after a message arrives.
+    pub fn can_send(&self) -> bool {
+        self.chain_key.is_some()
+    }
+
+    /// Whether we started this session and have not heard back on it yet
+    pub fn awaiting_reply(&self) -> bool {
+        self.chain_key.is_some() && self.recv_chain_key.is_none()
+    }
+
+    /// Whether a message comes from a later session epoch than this session's, i.e. the peer
+    /// restored their identity since and may be starting over with a new handshake
+    pub fn superseded_by(&self, header: &RatchetHeader) -> bool {
+        header.epoch > self.remote_epoch
+    }
+
+    pub fn encrypt(&mut self, plaintext: &[u8]) -> Result<RatchetCiphertext, String> {
+        let chain_key = self.chain_key.ok_or("Ratchet has no sending chain yet")?;
+        let (next_chain_key, message_key) = kdf_chain(&chain_key);
+        let ratchet_pubkey = PublicKey::from(&StaticSecret::from(self.dh_secret)).to_bytes();
+        let header = RatchetHeader { counter: self.send_counter, prev_counter: self.prev_send_counter, epoch: self.epoch };
+
+        let cipher = Aes256Gcm::new(&Key::<Aes256Gcm>::from(message_key));
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let aad = associated_data(&ratchet_pubkey, &header);
+        let ciphertext = cipher
+            .encrypt(&nonce, Payload { msg: plaintext, aad: &aad })
+            .map_err(|e| format!("Encryption failed: {:?}", e))?;
+
+        self.chain_key = Some(next_chain_key);
+        self.send_counter += 1;
+        Ok(RatchetCiphertext { ratchet_pubkey, header, nonce: nonce.to_vec(), ciphertext })
+    }
+
+    /// Decrypt a message and advance the ratchet. The state is left untouched on failure.
+    pub fn decrypt(&mut self, ratchet_pubkey: [u8; 32], header: RatchetHeader, nonce: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, String> {
+        let nonce: [u8; 12] = nonce.try_into().map_err(|_| "Invalid nonce length".to_string())?;
+        let aad = associated_data(&ratchet_pubkey, &header);
+
+        if let Some(pos) = self.message_keys.iter().position(|k| k.ratchet_pubkey == ratchet_pubkey && k.counter == header.counter) {
+            let plaintext = open(&self.message_keys[pos].key, &nonce, ciphertext, &aad)?;
+            self.message_keys.remove(pos);
+            return Ok(plaintext);
+        }
+
+        let mut next = self.clone();
+        if next.remote_pubkey != Some(ratchet_pubkey) {
+            next.skip_message_keys(header.prev_counter)?;
+            next.dh_ratchet(ratchet_pubkey);
+        }
+        next.skip_message_keys(header.counter)?;
+        let chain_key = next.recv_chain_key.ok_or("Ratchet has no receiving chain")?;
+        let (next_chain_key, message_key) = kdf_chain(&chain_key);
+        next.recv_chain_key = Some(next_chain_key);
+        next.recv_counter += 1;
+
+        let plaintext = open(&message_key, &nonce, ciphertext, &aad)?;
+        next.remote_epoch = next.remote_epoch.max(header.epoch);
+        *self = next;
+        Ok(plaintext)
+    }
+
+    /// Hold keys for messages up to `until` in the current receiving chain
+    fn skip_message_keys(&mut self, until: u32) -> Result<(), String> {
+        let (Some(mut chain_key), Some(ratchet_pubkey)) = (self.recv_chain_key, self.remote_pubkey) else {
+            return Ok(());
+        };
+        if until.saturating_sub(self.recv_counter) as usize > MAX_SKIPPED_KEYS {
+            return Err("Too many skipped messages".to_string());
+        }
+        while self.recv_counter < until {
+            let (next_chain_key, key) = kdf_chain(&chain_key);
+            self.message_keys.push(SkippedKey { ratchet_pubkey, counter: self.recv_counter, key });
+            chain_key = next_chain_key;
+            self.recv_counter += 1;
+        }
+        self.recv_chain_key = Some(chain_key);
+        if self.message_keys.len() > MAX_SKIPPED_KEYS {
+            let excess = self.message_keys.len() - MAX_SKIPPED_KEYS;
+            self.message_keys.drain(..excess);
+        }
+        Ok(())
+    }
+
+    /// Derive new receiving and sending chains from the peer's new ratchet key
+    fn dh_ratchet(&mut self, remote_pubkey: [u8; 32]) {
+        let remote = PublicKey::from(remote_pubkey);
+        self.prev_send_counter = self.send_counter;
+        self.send_counter = 0;
+        self.recv_counter = 0;
+        self.remote_pubkey = Some(remote_pubkey);
+
+        let (root_key, recv_chain_key) = kdf_root(&self.root_key, StaticSecret::from(self.dh_secret).diffie_hellman(&remote).as_bytes());
+        let dh_secret = StaticSecret::random_from_rng(OsRng);
+        let (root_key, chain_key) = kdf_root(&root_key, dh_secret.diffie_hellman(&remote).as_bytes());
+
+        self.root_key = root_key;
+        self.recv_chain_key = Some(recv_chain_key);
+        self.chain_key = Some(chain_key);
+        self.dh_secret = dh_secret.to_bytes();
+    }
+}
+
+fn handshake_secret(identity: &StaticSecret, remote_identity: &PublicKey) -> [u8; 32] {
+    hmac_sha256(b"superapp-ratchet-handshake", identity.diffie_hellman(remote_identity).as_bytes())
+}
+
+fn kdf_root(root_key: &[u8; 32], dh_output: &[u8]) -> ([u8; 32], [u8; 32]) {
+    let prk = hmac_sha256(root_key, dh_output);
+    (hmac_sha256(&prk, &[1]), hmac_sha256(&prk, &[2]))
+}
+
+/// Next chain key and the message key for the current step
+fn kdf_chain(chain_key: &[u8; 32]) -> ([u8; 32], [u8; 32]) {
+    (hmac_sha256(chain_key, &[2]), hmac_sha256(chain_key, &[1]))
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; 32] {
+    <Hmac<Sha256> as Mac>::new_from_slice(key)
+        .expect("HMAC accepts keys of any length")
+        .chain_update(data)
+        .finalize()
+        .into_bytes()
+        .into()
+}
+
+/// Encrypt our own copy of a message for the local store, under a key derived from our
+/// encryption secret. Spent ratchet keys can't recover it, so this copy is all that's left.
+pub fn seal_local(identity: &StaticSecret, plaintext: &[u8]) -> Result<Vec<u8>, String> {
+    let cipher = Aes256Gcm::new(&Key::<Aes256Gcm>::from(local_key(identity)));
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext)
+        .map_err(|e| format!("Encryption failed: {:?}", e))?;
+    let mut sealed = nonce.to_vec();
+    sealed.extend_from_slice(&ciphertext);
+    Ok(sealed)
+}
+
+/// The other half of `seal_local`
+pub fn open_local(identity: &StaticSecret, sealed: &[u8]) -> Result<Vec<u8>, String> {
+    let (nonce, ciphertext) = sealed.split_at_checked(12).ok_or("Sealed copy too short")?;
+    let nonce: [u8; 12] = nonce.try_into().map_err(|_| "Invalid nonce length".to_string())?;
+    open(&local_key(identity), &nonce, ciphertext, &[])
+}
+
+fn local_key(identity: &StaticSecret) -> [u8; 32] {
+    hmac_sha256(b"superapp-local-messages", identity.as_bytes())
+}
+
+fn associated_data(ratchet_pubkey: &[u8; 32], header: &RatchetHeader) -> Vec<u8> {
+    let mut aad = ratchet_pubkey.to_vec();
+    aad.extend_from_slice(&header.counter.to_be_bytes());
+    aad.extend_from_slice(&header.prev_counter.to_be_bytes());
+    aad.extend_from_slice(&header.epoch.to_be_bytes());
+    aad
+}
+
+fn open(key: &[u8; 32], nonce: &[u8; 12], ciphertext: &[u8], aad: &[u8]) -> Result<Vec<u8>, String> {
+    let cipher = Aes256Gcm::new(&Key::<Aes256Gcm>::from(*key));
+    cipher
+        .decrypt(&Nonce::from(*nonce), Payload { msg: ciphertext, aad })
+        .map_err(|_| "Decryption failed".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pair() -> (StaticSecret, StaticSecret) {
+        (StaticSecret::random_from_rng(OsRng), StaticSecret::random_from_rng(OsRng))
+    }
+
+    fn receive(state: &mut RatchetState, msg: &RatchetCiphertext) -> Result<String, String> {
+        state.decrypt(msg.ratchet_pubkey, msg.header, &msg.nonce, &msg.ciphertext)
+            .map(|p| String::from_utf8(p).unwrap())
+    }
+
+    #[test]
+    fn test_ratchet_conversation() {
+        let (alice_key, bob_key) = pair();
+        let mut alice = RatchetState::initiate(&alice_key, &PublicKey::from(&bob_key), 0);
+        let mut bob = RatchetState::respond(&bob_key, &PublicKey::from(&alice_key), 0);
+        assert!(!bob.can_send());
+
+        let hello = alice.encrypt(b"Hello Bob").unwrap();
+        assert_eq!(receive(&mut bob, &hello).unwrap(), "Hello Bob");
+        // A message key works only once
+        assert!(receive(&mut bob, &hello).is_err());
+
+        let reply = bob.encrypt(b"Hi Alice").unwrap();
+        assert_ne!(reply.ratchet_pubkey, hello.ratchet_pubkey);
+        assert_eq!(receive(&mut alice, &reply).unwrap(), "Hi Alice");
+
+        // Out of order delivery within and across ratchet steps
+        let first = alice.encrypt(b"one").unwrap();
+        let second = alice.encrypt(b"two").unwrap();
+        // Alice's previous chain carried only the greeting
+        assert_eq!(second.header, RatchetHeader { counter: 1, prev_counter: 1, epoch: 0 });
+        assert_eq!(receive(&mut bob, &second).unwrap(), "two");
+        let answer = bob.encrypt(b"three").unwrap();
+        assert_eq!(receive(&mut alice, &answer).unwrap(), "three");
+        assert_eq!(receive(&mut bob, &first).unwrap(), "one");
+        assert!(bob.message_keys.is_empty());
+    }
+
+    #[test]
+    fn test_simultaneous_initiation() {
+        let (alice_key, bob_key) = pair();
+        let mut alice = RatchetState::initiate(&alice_key, &PublicKey::from(&bob_key), 0);
+        let mut bob = RatchetState::initiate(&bob_key, &PublicKey::from(&alice_key), 0);
+        assert!(alice.awaiting_reply() && bob.awaiting_reply());
+
+        // Neither pending session reads the other's handshake
+        let from_alice = alice.encrypt(b"from Alice").unwrap();
+        let from_bob = bob.encrypt(b"from Bob").unwrap();
+        assert!(receive(&mut alice, &from_bob).is_err());
+        assert!(receive(&mut bob, &from_alice).is_err());
+
+        // Bob gives way and responds; Alice's original session carries on
+        let mut bob = RatchetState::respond(&bob_key, &PublicKey::from(&alice_key), 0);
+        assert_eq!(receive(&mut bob, &from_alice).unwrap(), "from Alice");
+        let reply = bob.encrypt(b"reply").unwrap();
+        assert_eq!(receive(&mut alice, &reply).unwrap(), "reply");
+        assert!(!alice.awaiting_reply());
+    }
+
+    #[test]
+    fn test_ratchet_rejects_tampering() {
+        let (alice_key, bob_key) = pair();
+        let eve_key = StaticSecret::random_from_rng(OsRng);
+        let mut alice = RatchetState::initiate(&alice_key, &PublicKey::from(&bob_key), 0);
+
+        let mut msg = alice.encrypt(b"secret").unwrap();
+        let mut eve = RatchetState::respond(&eve_key, &PublicKey::from(&alice_key), 0);
+        assert!(receive(&mut eve, &msg).is_err());
+
+        let mut bob = RatchetState::respond(&bob_key, &PublicKey::from(&alice_key), 0);
+        msg.header.counter = 1;
+        assert!(receive(&mut bob, &msg).is_err());
+        // The failed attempt did not advance Bob's ratchet
+        msg.header.counter = 0;
+        assert_eq!(receive(&mut bob, &msg).unwrap(), "secret");
+    }
+}
@@ -1,14 +1,94 @@
 use std::collections::HashMap;
 use crate::backend::dag::{DagNode, DagPayload};
 use crate::backend::wasm::WasmRuntime;
+use serde::{Deserialize, Serialize};
 use serde_json;
 use hex;
 
 pub struct VM;
 
+/// State key holding a contract's `Milestone` list
+pub const MILESTONES_KEY: &str = "milestones";
+
+/// Gas available to one contract state replay unless the user picks another limit
+pub const DEFAULT_GAS_LIMIT: u64 = 1_000_000;
+
+/// Gas charged for reading or writing one state key
+pub const GAS_PER_KV_OP: u64 = 1;
+
+/// Gas charged for parsing a JSON document (initial state or call params)
+const GAS_PER_JSON_PARSE: u64 = 10;
+
+/// Compute budget for a contract replay, so a contract that never terminates can't stall the backend
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GasMeter {
+    pub limit: u64,
+    pub consumed: u64,
+}
+
+impl GasMeter {
+    pub fn new(limit: u64) -> Self {
+        Self { limit, consumed: 0 }
+    }
+
+    pub fn remaining(&self) -> u64 {
+        self.limit.saturating_sub(self.consumed)
+    }
+
+    /// Use up `amount` gas, failing once more has been used than the limit allows
+    pub fn charge(&mut self, amount: u64) -> Result<(), VmError> {
+        self.consumed = self.consumed.saturating_add(amount);
+        if self.consumed > self.limit {
+            return Err(VmError::OutOfGas);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum VmError {
+    OutOfGas,
+    Execution(String), // Trap or invalid module; the call is skipped rather than aborting the replay
+}
+
+impl std::fmt::Display for VmError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VmError::OutOfGas => write!(f, "out of gas"),
+            VmError::Execution(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+/// Something a contract call announced, for subscribers that don't want to poll its state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContractEvent {
+    pub name: String,
+    pub data: serde_json::Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AuditSeverity {
+    Info,
+    Low,
+    Medium,
+    High,
+}
+
+/// A potential vulnerability found by static analysis of contract code
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditFinding {
+    pub severity: AuditSeverity,
+    pub description: String,
+    pub line_hint: Option<usize>, // 1-based source line, if known
+}
+
 impl VM {
-    /// Calculate the current state of a contract by replaying all calls against the initial state.
-    pub fn calculate_contract_state(init_state: &str, code: &str, calls: &[DagNode]) -> String {
+    /// Calculate the current state of a contract by replaying all calls against the initial state,
+    /// along with the events those calls emitted, in call order.
+    /// Every operation is charged to `gas`; the replay stops with `VmError::OutOfGas` when it runs out.
+    pub fn calculate_contract_state(init_state: &str, code: &str, calls: &[DagNode], gas: &mut GasMeter) -> Result<(String, Vec<ContractEvent>), VmError> {
+        let mut events = Vec::new();
         // WASM Detection: Check for magic bytes or hex-encoded magic bytes
         let wasm_bytes = if code.starts_with("\0asm") {
             code.as_bytes().to_vec()
@@ -32,6 +112,7 @@ impl VM {
             println!("Executing WASM contract");
             
             let runtime = WasmRuntime::new();
+            gas.charge(GAS_PER_JSON_PARSE)?;
             let mut state: HashMap<String, Vec<u8>> = serde_json::from_str(init_state).unwrap_or_default();
             
             // Try to parse init_state as simple JSON map first if the above failed or produced empty for non-byte map
@@ -46,8 +127,12 @@ impl VM {
             for call_node in calls {
                  if let DagPayload::ContractCall(call) = &call_node.payload {
                      let params = call.params.as_bytes();
-                     match runtime.execute(&wasm_bytes, &call.method, params, &state) {
-                         Ok(new_state) => state = new_state,
+                     match runtime.execute(&wasm_bytes, &call.method, params, &state, gas) {
+                         Ok((new_state, emitted)) => {
+                             state = new_state;
+                             events.extend(emitted);
+                         }
+                         Err(VmError::OutOfGas) => return Err(VmError::OutOfGas),
                          Err(e) => println!("WASM execution error: {}", e),
                      }
                  }
@@ -58,16 +143,36 @@ impl VM {
             for (k, v) in state {
                 json_map.insert(k, String::from_utf8_lossy(&v).to_string());
             }
-            serde_json::to_string_pretty(&json_map).unwrap_or("{}".to_string())
+            Ok((serde_json::to_string_pretty(&json_map).unwrap_or("{}".to_string()), events))
         } else {
             // KV Logic (Legacy / Default)
+             gas.charge(GAS_PER_JSON_PARSE)?;
              let mut state_val: serde_json::Value = serde_json::from_str(init_state).unwrap_or(serde_json::json!({}));
+             // Only the provider named at deployment may complete milestones
+             let provider = state_val["parties"]["provider"].as_str().unwrap_or_default().to_string();
 
             for call_node in calls {
-                if let DagPayload::ContractCall(call) = &call_node.payload {
+                if let DagPayload::MilestoneCompletion(mc) = &call_node.payload {
+                    gas.charge(GAS_PER_KV_OP)?;
+                    if call_node.author == provider {
+                        if let Some(milestone) = state_val.get_mut(MILESTONES_KEY).and_then(|m| m.get_mut(mc.milestone_index)) {
+                            if milestone["completed"].as_bool() != Some(true) {
+                                milestone["completed"] = serde_json::json!(true);
+                                milestone["completion_cid"] = serde_json::json!(call_node.id);
+                            }
+                        }
+                    }
+                } else if let DagPayload::ContractCall(call) = &call_node.payload {
+                     if call.method == "set" || call.method == "delete" {
+                         gas.charge(GAS_PER_JSON_PARSE + GAS_PER_KV_OP)?;
+                     } else if call.method == "emit" {
+                         gas.charge(GAS_PER_JSON_PARSE)?;
+                     }
                      if call.method == "set" {
                          if let Ok(params) = serde_json::from_str::<serde_json::Value>(&call.params) {
                              if let (Some(k), Some(v)) = (params.get("key").and_then(|s| s.as_str()), params.get("value")) {
+                                 // Milestones only change through completion nodes
+                                 if k == MILESTONES_KEY { continue; }
                                  if let Some(obj) = state_val.as_object_mut() {
                                      obj.insert(k.to_string(), v.clone());
                                  }
@@ -76,16 +181,155 @@ impl VM {
                      } else if call.method == "delete" {
                          if let Ok(params) = serde_json::from_str::<serde_json::Value>(&call.params) {
                              if let Some(k) = params.get("key").and_then(|s| s.as_str()) {
+                                 if k == MILESTONES_KEY { continue; }
                                  if let Some(obj) = state_val.as_object_mut() {
                                      obj.remove(k);
                                  }
                              }
                          }
+                     } else if call.method == "emit" {
+                         // {"name": "...", "data": ...}; leaves the state untouched
+                         if let Ok(params) = serde_json::from_str::<serde_json::Value>(&call.params) {
+                             if let Some(name) = params.get("name").and_then(|s| s.as_str()) {
+                                 events.push(ContractEvent { name: name.to_string(), data: params["data"].clone() });
+                             }
+                         }
                      }
                 }
             }
-            serde_json::to_string_pretty(&state_val).unwrap_or("{}".to_string())
+            Ok((serde_json::to_string_pretty(&state_val).unwrap_or("{}".to_string()), events))
+        }
+    }
+
+    /// Statically analyse contract code for obvious vulnerabilities without executing it.
+    pub fn audit_contract(code: &str) -> Vec<AuditFinding> {
+        let mut findings = Vec::new();
+
+        // Empty code is the built-in KV contract, which has no custom logic
+        if code.trim().is_empty() {
+            return findings;
+        }
+
+        let wasm_bytes = if code.starts_with("\0asm") {
+            code.as_bytes().to_vec()
+        } else if code.len() > 8 {
+            match hex::decode(code) {
+                Ok(bytes) if bytes.starts_with(b"\0asm") => bytes,
+                _ => Vec::new(),
+            }
+        } else {
+            Vec::new()
+        };
+
+        if !wasm_bytes.is_empty() {
+            // Compiled code: only host imports are visible without a full decoder
+            let contains = |needle: &[u8]| wasm_bytes.windows(needle.len()).any(|w| w == needle);
+            if contains(b"db_set") && !contains(b"db_get") {
+                findings.push(AuditFinding {
+                    severity: AuditSeverity::High,
+                    description: "Contract writes state without ever reading it; balances are not checked before updates".to_string(),
+                    line_hint: None,
+                });
+            }
+            findings.push(AuditFinding {
+                severity: AuditSeverity::Info,
+                description: "Compiled WASM: loop bounds and recursion cannot be verified statically".to_string(),
+                line_hint: None,
+            });
+            return findings;
+        }
+
+        let lines: Vec<&str> = code.lines().collect();
+
+        // Returns the index of the line closing the block opened on `start`
+        let block_end = |start: usize| -> usize {
+            let mut depth = 0i32;
+            let mut opened = false;
+            for (i, line) in lines.iter().enumerate().skip(start) {
+                for ch in line.chars() {
+                    if ch == '{' {
+                        depth += 1;
+                        opened = true;
+                    } else if ch == '}' {
+                        depth -= 1;
+                    }
+                }
+                if opened && depth <= 0 {
+                    return i;
+                }
+            }
+            lines.len().saturating_sub(1)
+        };
+
+        let mut balance_checked = false;
+        for (i, line) in lines.iter().enumerate() {
+            let trimmed = line.trim();
+            let lower = trimmed.to_lowercase();
+
+            // 1. Unbounded loops: infinite loop constructs with no exit in their body
+            let is_infinite_loop = trimmed.starts_with("loop")
+                || lower.replace(' ', "").starts_with("while(true)")
+                || lower.starts_with("while true")
+                || lower.replace(' ', "").starts_with("for(;;)");
+            if is_infinite_loop {
+                let end = block_end(i);
+                let has_exit = lines[i..=end].iter().any(|l| l.contains("break") || l.contains("return"));
+                if !has_exit {
+                    findings.push(AuditFinding {
+                        severity: AuditSeverity::High,
+                        description: "Unbounded loop with no break or return; execution may never terminate".to_string(),
+                        line_hint: Some(i + 1),
+                    });
+                }
+            }
+
+            // 2. Transfers must be preceded by a balance check
+            if lower.contains("balance") {
+                balance_checked = true;
+            }
+            if lower.contains("transfer") && !lower.contains("balance") && !balance_checked {
+                findings.push(AuditFinding {
+                    severity: AuditSeverity::High,
+                    description: "Transfer without a preceding balance check".to_string(),
+                    line_hint: Some(i + 1),
+                });
+            }
+
+            // 3. Self-referential recursion
+            let fn_name = trimmed
+                .strip_prefix("pub fn ")
+                .or_else(|| trimmed.strip_prefix("fn "))
+                .or_else(|| trimmed.strip_prefix("function "))
+                .and_then(|rest| rest.split('(').next())
+                .map(|name| name.trim().to_string());
+            if let Some(name) = fn_name.filter(|n| !n.is_empty()) {
+                let end = block_end(i);
+                let call = format!("{}(", name);
+                let recursive = lines.iter().enumerate().take(end + 1).skip(i + 1)
+                    .find(|(_, l)| l.contains(&call));
+                if let Some((call_line, _)) = recursive {
+                    findings.push(AuditFinding {
+                        severity: AuditSeverity::Medium,
+                        description: format!("Function `{}` calls itself; unbounded recursion may exhaust the stack", name),
+                        line_hint: Some(call_line + 1),
+                    });
+                }
+            }
+        }
+
+        findings
+    }
+
+    /// CIDs referenced as `blob://CID` in page content, in order of first appearance
+    pub fn extract_blob_references(content: &str) -> Vec<String> {
+        let mut cids: Vec<String> = Vec::new();
+        for rest in content.split("blob://").skip(1) {
+            let cid: String = rest.chars().take_while(|c| c.is_ascii_alphanumeric()).collect();
+            if !cid.is_empty() && !cids.contains(&cid) {
+                cids.push(cid);
+            }
         }
+        cids
     }
 
     /// Render a web page content, processing WASM if detected.
@@ -147,7 +391,7 @@ mod tests {
 
         let calls = vec![call1, call2, call3];
 
-        let state_json = VM::calculate_contract_state(init_state, code, &calls);
+        let (state_json, _) = VM::calculate_contract_state(init_state, code, &calls, &mut GasMeter::new(DEFAULT_GAS_LIMIT)).unwrap();
         let state: serde_json::Value = serde_json::from_str(&state_json).unwrap();
 
         assert_eq!(state["count"], "0");
@@ -155,6 +399,92 @@ mod tests {
         assert!(state.get("test_key").is_none());
     }
 
+    #[test]
+    fn test_milestone_completion_replay() {
+        use crate::backend::dag::MilestoneCompletionPayload;
+        let provider = Keypair::generate_ed25519();
+        let provider_id = libp2p::PeerId::from_public_key(&provider.public()).to_string();
+        let init_state = serde_json::json!({
+            "parties": { "provider": provider_id, "consumer": "consumer" },
+            "milestones": [
+                { "description": "Design", "amount": 100, "completed": false },
+                { "description": "Build", "amount": 300, "completed": false }
+            ]
+        }).to_string();
+
+        let complete = |author: &Keypair, index: usize| {
+            let payload = DagPayload::MilestoneCompletion(MilestoneCompletionPayload { contract_id: "test".to_string(), milestone_index: index });
+            DagNode::new("milestone_completion:v1".to_string(), payload, vec![], author, 0).unwrap()
+        };
+        let by_provider = complete(&provider, 0);
+        let by_stranger = complete(&Keypair::generate_ed25519(), 1);
+        let overwrite = create_dummy_call("set", r#"{"key": "milestones", "value": []}"#);
+
+        let calls = vec![by_provider.clone(), by_stranger, overwrite];
+        let state: serde_json::Value = serde_json::from_str(&VM::calculate_contract_state(&init_state, "", &calls, &mut GasMeter::new(DEFAULT_GAS_LIMIT)).unwrap().0).unwrap();
+
+        assert_eq!(state["milestones"][0]["completed"], true);
+        assert_eq!(state["milestones"][0]["completion_cid"], by_provider.id.as_str());
+        assert_eq!(state["milestones"][1]["completed"], false);
+    }
+
+    #[test]
+    fn test_kv_contract_events() {
+        let calls = vec![
+            create_dummy_call("emit", r#"{"name": "Opened", "data": {"by": "alice"}}"#),
+            create_dummy_call("set", r#"{"key": "status", "value": "open"}"#),
+            create_dummy_call("emit", r#"{"data": "no name"}"#),
+            create_dummy_call("emit", r#"{"name": "Pinged"}"#),
+        ];
+
+        let (state_json, events) = VM::calculate_contract_state("{}", "", &calls, &mut GasMeter::new(DEFAULT_GAS_LIMIT)).unwrap();
+        let state: serde_json::Value = serde_json::from_str(&state_json).unwrap();
+        assert_eq!(state, serde_json::json!({ "status": "open" }));
+        assert_eq!(events, vec![
+            ContractEvent { name: "Opened".to_string(), data: serde_json::json!({ "by": "alice" }) },
+            ContractEvent { name: "Pinged".to_string(), data: serde_json::Value::Null },
+        ]);
+    }
+
+    #[test]
+    fn test_gas_metering() {
+        let calls: Vec<DagNode> = (0..3)
+            .map(|i| create_dummy_call("set", &format!(r#"{{"key": "k{}", "value": "v"}}"#, i)))
+            .collect();
+        // One parse for the initial state, then a parse and a write per call
+        let needed = GAS_PER_JSON_PARSE + 3 * (GAS_PER_JSON_PARSE + GAS_PER_KV_OP);
+
+        let mut gas = GasMeter::new(needed);
+        assert!(VM::calculate_contract_state("{}", "", &calls, &mut gas).is_ok());
+        assert_eq!(gas.consumed, needed);
+        assert_eq!(gas.remaining(), 0);
+
+        let mut gas = GasMeter::new(needed - 1);
+        assert_eq!(VM::calculate_contract_state("{}", "", &calls, &mut gas), Err(VmError::OutOfGas));
+    }
+
+    #[test]
+    fn test_audit_contract_findings() {
+        assert!(VM::audit_contract("").is_empty());
+
+        let code = "fn pay(to, amount) {\n    transfer(to, amount);\n}\nfn spin() {\n    loop {\n        tick();\n    }\n}\nfn walk(n) {\n    walk(n + 1);\n}";
+        let findings = VM::audit_contract(code);
+
+        assert!(findings.iter().any(|f| f.severity == AuditSeverity::High && f.line_hint == Some(2)));
+        assert!(findings.iter().any(|f| f.severity == AuditSeverity::High && f.line_hint == Some(5)));
+        assert!(findings.iter().any(|f| f.severity == AuditSeverity::Medium && f.line_hint == Some(10)));
+
+        let safe = "fn pay(to, amount) {\n    if balance() < amount { return; }\n    transfer(to, amount);\n}";
+        assert!(VM::audit_contract(safe).is_empty());
+    }
+
+    #[test]
+    fn test_extract_blob_references() {
+        let content = r#"<img src="blob://QmAbc123"><a href="blob://QmDef456">file</a><img src='blob://QmAbc123'> blob://"#;
+        assert_eq!(VM::extract_blob_references(content), vec!["QmAbc123".to_string(), "QmDef456".to_string()]);
+        assert!(VM::extract_blob_references("<p>No embeds</p>").is_empty());
+    }
+
     #[test]
     fn test_render_static_web_page() {
         let content = "<h1>Hello</h1>";
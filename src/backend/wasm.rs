@@ -1,5 +1,9 @@
-use wasmi::{Engine, Linker, Module, Store, Caller};
+use wasmi::{Config, Engine, Linker, Module, Store, Caller};
 use std::collections::HashMap;
+use crate::backend::vm::{ContractEvent, GasMeter, VmError, DEFAULT_GAS_LIMIT, GAS_PER_KV_OP};
+
+/// The outcome of a contract call: (new key-value state, emitted events)
+pub type ExecutionResult = (HashMap<String, Vec<u8>>, Vec<ContractEvent>);
 
 pub struct WasmRuntime {
     engine: Engine,
@@ -7,7 +11,10 @@ pub struct WasmRuntime {
 
 impl WasmRuntime {
     pub fn new() -> Self {
-        let engine = Engine::default();
+        // Every instruction burns fuel, so runaway contracts trap instead of looping forever
+        let mut config = Config::default();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config);
         Self { engine }
     }
 
@@ -17,15 +24,19 @@ impl WasmRuntime {
         method: &str,
         params: &[u8],
         state: &HashMap<String, Vec<u8>>,
-    ) -> Result<HashMap<String, Vec<u8>>, String> {
+        gas: &mut GasMeter,
+    ) -> Result<ExecutionResult, VmError> {
         let store_data = StoreData {
             state: state.clone(),
             params: params.to_vec(),
             result: Vec::new(),
             error: None,
+            kv_ops: 0,
+            events: Vec::new(),
         };
 
         let mut store = Store::new(&self.engine, store_data);
+        store.add_fuel(gas.remaining()).map_err(|e| VmError::Execution(e.to_string()))?;
         let mut linker = Linker::new(&self.engine);
 
         // Host functions
@@ -41,6 +52,7 @@ impl WasmRuntime {
                  return -1;
             }
             let key = String::from_utf8_lossy(&key_buf).to_string();
+            caller.data_mut().kv_ops += 1;
             
             // Clone the value to drop the borrow on caller
             let value = if let Some(val) = caller.data().state.get(&key) {
@@ -58,7 +70,7 @@ impl WasmRuntime {
             } else {
                 0
             }
-        }).map_err(|e| e.to_string())?;
+        }).map_err(|e| VmError::Execution(e.to_string()))?;
 
         // db_set(key_ptr, key_len, value_ptr, value_len)
         linker.func_wrap("env", "db_set", |mut caller: Caller<'_, StoreData>, key_ptr: i32, key_len: i32, value_ptr: i32, value_len: i32| {
@@ -74,8 +86,9 @@ impl WasmRuntime {
             let mut value_buf = vec![0u8; value_len as usize];
             if memory.read(&caller, value_ptr as usize, &mut value_buf).is_err() { return; }
             
+            caller.data_mut().kv_ops += 1;
             caller.data_mut().state.insert(key, value_buf);
-        }).map_err(|e| e.to_string())?;
+        }).map_err(|e| VmError::Execution(e.to_string()))?;
 
         // response_write(ptr, len)
         linker.func_wrap("env", "response_write", |mut caller: Caller<'_, StoreData>, ptr: i32, len: i32| {
@@ -86,7 +99,7 @@ impl WasmRuntime {
             let mut buf = vec![0u8; len as usize];
             if memory.read(&caller, ptr as usize, &mut buf).is_err() { return; }
             caller.data_mut().result.extend_from_slice(&buf);
-        }).map_err(|e| e.to_string())?;
+        }).map_err(|e| VmError::Execution(e.to_string()))?;
 
         // db_remove(key_ptr, key_len)
         linker.func_wrap("env", "db_remove", |mut caller: Caller<'_, StoreData>, key_ptr: i32, key_len: i32| {
@@ -97,8 +110,9 @@ impl WasmRuntime {
             let mut key_buf = vec![0u8; key_len as usize];
             if memory.read(&caller, key_ptr as usize, &mut key_buf).is_err() { return; }
             let key = String::from_utf8_lossy(&key_buf).to_string();
+            caller.data_mut().kv_ops += 1;
             caller.data_mut().state.remove(&key);
-        }).map_err(|e| e.to_string())?;
+        }).map_err(|e| VmError::Execution(e.to_string()))?;
         
         // get_params(ptr) -> len
          linker.func_wrap("env", "get_params", |mut caller: Caller<'_, StoreData>, ptr: i32| -> i32 {
@@ -111,15 +125,42 @@ impl WasmRuntime {
                 return -1;
             }
             params.len() as i32
-        }).map_err(|e| e.to_string())?;
+        }).map_err(|e| VmError::Execution(e.to_string()))?;
 
-        let module = Module::new(&self.engine, wasm_bytes).map_err(|e| e.to_string())?;
-        let instance = linker.instantiate(&mut store, &module).map_err(|e| e.to_string())?.start(&mut store).map_err(|e| e.to_string())?;
+        // emit_event(name_ptr, name_len, data_ptr, data_len); data that isn't JSON is kept as a string
+        linker.func_wrap("env", "emit_event", |mut caller: Caller<'_, StoreData>, name_ptr: i32, name_len: i32, data_ptr: i32, data_len: i32| {
+            let memory = match caller.get_export("memory") {
+                Some(wasmi::Extern::Memory(m)) => m,
+                _ => return,
+            };
+            let mut name_buf = vec![0u8; name_len as usize];
+            if memory.read(&caller, name_ptr as usize, &mut name_buf).is_err() { return; }
+            let mut data_buf = vec![0u8; data_len as usize];
+            if memory.read(&caller, data_ptr as usize, &mut data_buf).is_err() { return; }
 
-        let run = instance.get_typed_func::<(), ()>(&store, method).map_err(|e| e.to_string())?;
-        run.call(&mut store, ()).map_err(|e| e.to_string())?;
+            let data = serde_json::from_slice(&data_buf)
+                .unwrap_or_else(|_| serde_json::Value::String(String::from_utf8_lossy(&data_buf).to_string()));
+            caller.data_mut().kv_ops += 1;
+            caller.data_mut().events.push(ContractEvent { name: String::from_utf8_lossy(&name_buf).to_string(), data });
+        }).map_err(|e| VmError::Execution(e.to_string()))?;
+
+        let module = Module::new(&self.engine, wasm_bytes).map_err(|e| VmError::Execution(e.to_string()))?;
+        let instance = linker.instantiate(&mut store, &module).map_err(|e| VmError::Execution(e.to_string()))?.start(&mut store).map_err(|e| VmError::Execution(e.to_string()))?;
 
-        Ok(store.into_data().state)
+        let run = instance.get_typed_func::<(), ()>(&store, method).map_err(|e| VmError::Execution(e.to_string()))?;
+        let outcome = run.call(&mut store, ());
+
+        let used = store.fuel_consumed().unwrap_or(0) + store.data().kv_ops * GAS_PER_KV_OP;
+        gas.charge(used)?;
+        match outcome {
+            // Running dry traps exactly when the budget hits zero
+            Err(_) if gas.remaining() == 0 => Err(VmError::OutOfGas),
+            Err(e) => Err(VmError::Execution(e.to_string())),
+            Ok(()) => {
+                let data = store.into_data();
+                Ok((data.state, data.events))
+            }
+        }
     }
 
     pub fn render(
@@ -133,9 +174,12 @@ impl WasmRuntime {
             params: params.to_vec(),
             result: Vec::new(),
             error: None,
+            kv_ops: 0,
+            events: Vec::new(),
         };
 
         let mut store = Store::new(&self.engine, store_data);
+        store.add_fuel(DEFAULT_GAS_LIMIT).map_err(|e| e.to_string())?;
         let mut linker = Linker::new(&self.engine);
 
         // Host functions (same as execute for DB access if needed)
@@ -210,4 +254,6 @@ struct StoreData {
     params: Vec<u8>,
     result: Vec<u8>,
     error: Option<String>,
+    kv_ops: u64, // State reads and writes, charged as gas after the call
+    events: Vec<ContractEvent>,
 }
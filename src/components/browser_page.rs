@@ -232,6 +232,24 @@ pub fn BrowserComponent() -> Element {
                             }
                         }
                     }
+                } else if search_mode() == "web" && url_input().trim_start().starts_with('#') && !app_state.hashtag_posts.read().is_empty() {
+                    div { class: "space-y-4",
+                        h3 { class: "text-lg font-bold mb-2", "Posts tagged {url_input().trim()}" }
+                        for node in app_state.hashtag_posts.read().iter() {
+                            if let crate::backend::dag::DagPayload::Post(post) = &node.payload {
+                                {
+                                    let author: String = node.author.chars().take(12).collect();
+                                    let when = node.timestamp.format("%Y-%m-%d %H:%M").to_string();
+                                    rsx! {
+                                        div { key: "{node.id}", class: "card",
+                                            p { class: "whitespace-pre-wrap", "{post.content}" }
+                                            p { class: "text-xs font-mono mt-1 text-[var(--text-muted)]", "{author}… • {when}" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
                 } else if search_mode() == "web" && !app_state.web_search_results.read().is_empty() {
                     div { class: "space-y-4",
                         h3 { class: "text-lg font-bold mb-2", "Web Search Results" }
@@ -2,6 +2,43 @@ use dioxus::prelude::*;
 use crate::backend::AppCmd;
 use crate::backend::dag::{DagNode, DagPayload};
 
+/// Ministries currently held by `peer_id`, sorted for stable display
+pub fn offices_held(officials: &std::collections::HashMap<String, String>, peer_id: &str) -> Vec<String> {
+    let mut offices: Vec<String> = officials.iter()
+        .filter(|(_, official)| official.as_str() == peer_id)
+        .map(|(ministry, _)| ministry.clone())
+        .collect();
+    offices.sort();
+    offices
+}
+
+/// Collapse a (voucher, vouchee) trust path into display steps: the root vouchers, then each vouchee in turn
+pub fn trust_chain_steps(path: &[(String, String)]) -> Vec<Vec<String>> {
+    let mut steps: Vec<Vec<String>> = Vec::new();
+    for (voucher, vouchee) in path {
+        if steps.is_empty() {
+            steps.push(vec![voucher.clone()]);
+            steps.push(vec![vouchee.clone()]);
+        } else if steps.len() == 2 && steps[1][0] == *vouchee {
+            // Several approvers verifying the same root peer
+            steps[0].push(voucher.clone());
+        } else {
+            steps.push(vec![vouchee.clone()]);
+        }
+    }
+    steps
+}
+
+/// Point `blob://CID` references at cached blob data, leaving uncached ones untouched
+pub fn inline_blob_embeds(html: &str, cache: &std::collections::HashMap<String, String>) -> String {
+    crate::backend::vm::VM::extract_blob_references(html)
+        .into_iter()
+        .fold(html.to_string(), |html, cid| match cache.get(&cid) {
+            Some(data_url) => html.replace(&format!("blob://{}", cid), data_url),
+            None => html,
+        })
+}
+
 #[component]
 pub fn BlobImage(cid: String, class: Option<String>) -> Element {
     let app_state = use_context::<crate::components::AppState>();
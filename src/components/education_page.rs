@@ -194,6 +194,7 @@ pub fn EducationComponent() -> Element {
                 ExamsList { on_take_exam: start_exam }
             } else {
                 CertificationsList {}
+                CertifiedMembersShowcase {}
             }
             
             // Exam Taking Modal
@@ -276,10 +277,21 @@ fn CourseDetailComponent(
     on_take_exam: EventHandler<crate::backend::dag::DagNode>
 ) -> Element {
     let app_state = use_context::<AppState>();
+    let cmd_tx = use_context::<tokio::sync::mpsc::UnboundedSender<AppCmd>>();
     let exams = app_state.exams.read();
     
     let mut show_create_exam = use_signal(|| false);
 
+    // List views only carry what they need, so load the full course on open
+    let course_id = course_node.id.clone();
+    use_effect(move || {
+        let _ = cmd_tx.send(AppCmd::FetchCourse { course_id: course_id.clone() });
+    });
+    let (course_node, progress) = match app_state.active_course.read().clone() {
+        Some((node, _, progress)) if node.id == course_node.id => (node, progress),
+        _ => (course_node, vec![]),
+    };
+
     if let DagPayload::Course(course) = &course_node.payload {
         // Filter exams for this course
         let course_exams: Vec<crate::backend::dag::DagNode> = exams.iter()
@@ -328,6 +340,11 @@ fn CourseDetailComponent(
                         div { class: "prose prose-invert max-w-none p-4 bg-[var(--bg-secondary)] rounded-lg whitespace-pre-wrap",
                             "{content}"
                         }
+                        if let Some(best) = progress.iter().max() {
+                            p { class: "text-sm text-[var(--text-secondary)] mt-3",
+                                "Your progress: best score {best}% across {progress.len()} attempt(s)"
+                            }
+                        }
                     }
                     
                     // Exams Section
@@ -585,7 +602,9 @@ fn ExamsList(on_take_exam: EventHandler<crate::backend::dag::DagNode>) -> Elemen
 #[component]
 fn CertificationsList() -> Element {
     let app_state = use_context::<AppState>();
+    let cmd_tx = use_context::<tokio::sync::mpsc::UnboundedSender<AppCmd>>();
     let certifications = app_state.certifications.read();
+    let expiring = app_state.expiring_certifications.read();
     
     rsx! {
         div { class: "grid gap-4",
@@ -600,6 +619,11 @@ fn CertificationsList() -> Element {
                     if let DagPayload::Certification(cert) = &node.payload {
                         {
                             let issued_date = cert.issued_at.format("%Y-%m-%d").to_string();
+                            let expiry_date = cert.expires_at.map(|e| e.format("%Y-%m-%d").to_string());
+                            let lapsed = cert.expires_at.is_some_and(|e| e < chrono::Utc::now());
+                            let warn = expiring.contains(&cert.certification_type) && cert.exam_id.is_some();
+                            let cmd_tx_renew = cmd_tx.clone();
+                            let cert_cid = node.id.clone();
                             rsx! {
                                 div { 
                                     key: "{node.id}",
@@ -611,8 +635,73 @@ fn CertificationsList() -> Element {
                                             p { class: "text-sm text-[var(--text-secondary)]", 
                                                 "Issued: {issued_date}" 
                                             }
+                                            if let Some(expiry_date) = expiry_date {
+                                                p { class: if lapsed { "text-sm text-[var(--error)]" } else { "text-sm text-[var(--text-secondary)]" },
+                                                    if lapsed { "Expired: {expiry_date}" } else { "Expires: {expiry_date}" }
+                                                }
+                                            }
                                         }
                                     }
+                                    if warn {
+                                        div { class: "flex items-center justify-between gap-2 mt-3 pt-3 border-t border-[var(--border-subtle)]",
+                                            p { class: "text-sm text-[var(--warning)]", "⚠️ Retake the exam, then renew to keep this certification." }
+                                            button {
+                                                class: "btn btn-sm btn-primary",
+                                                onclick: move |_| { let _ = cmd_tx_renew.send(AppCmd::RenewCertification { cert_cid: cert_cid.clone() }); },
+                                                "Renew"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+// "Our Certified Members" - everyone holding a given certification, earliest first
+#[component]
+fn CertifiedMembersShowcase() -> Element {
+    let app_state = use_context::<AppState>();
+    let cmd_tx = use_context::<tokio::sync::mpsc::UnboundedSender<AppCmd>>();
+    let mut cert_type = use_signal(|| "CivicLiteracy".to_string());
+
+    use_effect(move || {
+        let _ = cmd_tx.send(AppCmd::FetchCertificationLeaderboard { cert_type: cert_type() });
+    });
+
+    let members = app_state.cert_leaderboard.read().get(&cert_type()).cloned().unwrap_or_default();
+
+    rsx! {
+        div { class: "panel mt-6",
+            div { class: "panel-header flex justify-between items-center",
+                h2 { class: "panel-title", "🎓 Our Certified Members" }
+                select {
+                    class: "input w-auto",
+                    value: "{cert_type}",
+                    onchange: move |e| cert_type.set(e.value()),
+                    option { value: "CivicLiteracy", "Civic Literacy" }
+                    option { value: "GovernanceRoles", "Governance Roles" }
+                    option { value: "TechnicalSkills", "Technical Skills" }
+                    option { value: "TradeQualifications", "Trade Qualifications" }
+                    option { value: "ModerationJury", "Jury Qualified" }
+                }
+            }
+            if members.is_empty() {
+                p { class: "text-[var(--text-muted)] py-4", "No members hold this certification yet." }
+            } else {
+                div { class: "grid gap-2",
+                    for (rank, (peer_id, profile, issued_at)) in members.iter().enumerate() {
+                        {
+                            let name = profile.as_ref().map(|p| p.name.clone()).unwrap_or_else(|| format!("{}...", peer_id.get(0..8).unwrap_or(peer_id)));
+                            let issued_date = issued_at.format("%Y-%m-%d").to_string();
+                            rsx! {
+                                div { key: "{peer_id}", class: "card flex justify-between items-center",
+                                    span { class: "font-bold", "#{rank + 1} {name}" }
+                                    span { class: "text-xs text-[var(--text-muted)]", "Since {issued_date}" }
                                 }
                             }
                         }
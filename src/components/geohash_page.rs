@@ -1,7 +1,7 @@
 use dioxus::prelude::*;
-use crate::backend::{AppCmd, dag::{DagPayload, PostPayload, DagNode}};
+use crate::backend::{AppCmd, dag::{DagPayload, PostPayload, DagNode, EventPayload}};
 use crate::components::AppState;
-use crate::components::common::{StoryCircle, StoryViewer};
+use crate::components::common::{BlobImage, StoryCircle, StoryViewer};
 
 #[component]
 pub fn GeohashComponent() -> Element {
@@ -13,6 +13,13 @@ pub fn GeohashComponent() -> Element {
 
     let mut precision = use_signal(|| 3usize); // Default: country level
     let mut new_local_post = use_signal(|| String::new());
+    let mut show_event_form = use_signal(|| false);
+    let mut event_title = use_signal(String::new);
+    let mut event_description = use_signal(String::new);
+    let mut event_location = use_signal(String::new);
+    let mut event_start = use_signal(String::new); // datetime-local value, in UTC
+    let mut event_hours = use_signal(|| 2u64);
+    let mut event_capacity = use_signal(String::new); // empty = unlimited
     
     let current_geohash = app_state.geohash.read().clone();
     let geohash_prefix = if current_geohash == "Global" {
@@ -45,9 +52,42 @@ pub fn GeohashComponent() -> Element {
         if !geohash_prefix_fetch.is_empty() {
             let _ = cmd_tx_fetch.send(AppCmd::FetchLocalPosts { geohash_prefix: geohash_prefix_fetch.clone() });
             let _ = cmd_tx_fetch.send(AppCmd::FetchLocalStories { geohash_prefix: geohash_prefix_fetch.clone() });
+            let _ = cmd_tx_fetch.send(AppCmd::FetchPeersByGeohash { geohash_prefix: geohash_prefix_fetch.clone(), include_profiles: true });
+            let now = chrono::Utc::now().timestamp() as u64;
+            let _ = cmd_tx_fetch.send(AppCmd::FetchEvents { geohash_prefix: geohash_prefix_fetch.clone(), from: now, to: now + 30 * 86400 });
         }
     });
 
+    let on_create_event = {
+        let cmd_tx = cmd_tx.clone();
+        let geohash_prefix = geohash_prefix.clone();
+        move |_| {
+            let Some(starts_at) = chrono::NaiveDateTime::parse_from_str(&event_start(), "%Y-%m-%dT%H:%M").ok()
+                .map(|dt| dt.and_utc().timestamp() as u64) else { return };
+            if event_title().trim().is_empty() || geohash_prefix.is_empty() {
+                return;
+            }
+            let location = Some(event_location()).filter(|l| !l.trim().is_empty());
+            let _ = cmd_tx.send(AppCmd::CreateEvent {
+                title: event_title(),
+                description: event_description(),
+                location,
+                geohash: Some(geohash_prefix.clone()),
+                starts_at,
+                ends_at: starts_at + event_hours() * 3600,
+                capacity: event_capacity().trim().parse().ok(),
+            });
+            let now = chrono::Utc::now().timestamp() as u64;
+            let _ = cmd_tx.send(AppCmd::FetchEvents { geohash_prefix: geohash_prefix.clone(), from: now, to: now + 30 * 86400 });
+            event_title.set(String::new());
+            event_description.set(String::new());
+            event_location.set(String::new());
+            event_start.set(String::new());
+            event_capacity.set(String::new());
+            show_event_form.set(false);
+        }
+    };
+
     let on_post_local = {
         let cmd_tx = cmd_tx.clone();
         let geohash_prefix = geohash_prefix.clone();
@@ -58,6 +98,8 @@ pub fn GeohashComponent() -> Element {
                     attachments: vec![],
                     geohash: Some(geohash_prefix.clone()),
                     announcement: false,
+                    quote_of: None,
+                    publish_at: None,
                 };
                 let _ = cmd_tx.send(cmd);
                 new_local_post.set(String::new());
@@ -167,6 +209,114 @@ pub fn GeohashComponent() -> Element {
                         }
                     }
                     
+                    // Upcoming events in the next 30 days
+                    if !geohash_prefix.is_empty() {
+                        div { class: "panel",
+                            div { class: "panel-header",
+                                h2 { class: "panel-title", "Upcoming Events" }
+                                button {
+                                    class: "btn btn-secondary btn-sm",
+                                    onclick: move |_| show_event_form.set(!show_event_form()),
+                                    if show_event_form() { "Cancel" } else { "+ New Event" }
+                                }
+                            }
+                            if show_event_form() {
+                                div { class: "form-group mb-4",
+                                    input { class: "input mb-2", placeholder: "Title", value: "{event_title}", oninput: move |e| event_title.set(e.value()) }
+                                    textarea { class: "input mb-2", style: "min-height: 60px; resize: none;", placeholder: "What's happening?", value: "{event_description}", oninput: move |e| event_description.set(e.value()) }
+                                    input { class: "input mb-2", placeholder: "Venue (optional)", value: "{event_location}", oninput: move |e| event_location.set(e.value()) }
+                                    div { class: "flex flex-wrap gap-2 items-center",
+                                        input {
+                                            class: "input input-sm w-auto",
+                                            r#type: "datetime-local",
+                                            title: "Start time (UTC)",
+                                            value: "{event_start}",
+                                            oninput: move |e| event_start.set(e.value())
+                                        }
+                                        select {
+                                            class: "input input-sm w-auto",
+                                            onchange: move |e| event_hours.set(e.value().parse().unwrap_or(2)),
+                                            for hours in [1u64, 2, 4, 8, 24] {
+                                                option { value: "{hours}", selected: event_hours() == hours, "{hours}h" }
+                                            }
+                                        }
+                                        input {
+                                            class: "input input-sm w-24",
+                                            r#type: "number",
+                                            min: "1",
+                                            placeholder: "Capacity",
+                                            value: "{event_capacity}",
+                                            oninput: move |e| event_capacity.set(e.value())
+                                        }
+                                        button { class: "btn btn-primary btn-sm", onclick: on_create_event, "Create Event" }
+                                    }
+                                }
+                            }
+                            if app_state.events.read().is_empty() {
+                                p { class: "empty-state-text", "No events planned here yet" }
+                            } else {
+                                div { class: "space-y-2",
+                                    for node in app_state.events.read().iter() {
+                                        if let DagPayload::Event(EventPayload { title, description, location, starts_at, ends_at, capacity, .. }) = &node.payload {
+                                            {
+                                                let event_id = node.id.clone();
+                                                let attendees = app_state.event_attendees.read().get(&event_id).cloned().unwrap_or_default();
+                                                let going = attendees.contains(&app_state.local_peer_id.read().clone());
+                                                let full = capacity.is_some_and(|cap| attendees.len() >= cap);
+                                                let when = chrono::DateTime::from_timestamp(*starts_at as i64, 0)
+                                                    .map(|t| t.format("%a %b %-d, %H:%M UTC").to_string())
+                                                    .unwrap_or_default();
+                                                let hours = ends_at.saturating_sub(*starts_at) / 3600;
+                                                let spots = match capacity {
+                                                    Some(cap) => format!("{}/{} going", attendees.len(), cap),
+                                                    None => format!("{} going", attendees.len()),
+                                                };
+
+                                                use_effect({
+                                                    let cmd_tx = cmd_tx.clone();
+                                                    let eid = event_id.clone();
+                                                    move || {
+                                                        let _ = cmd_tx.send(AppCmd::FetchEventAttendees { event_id: eid.clone() });
+                                                    }
+                                                });
+
+                                                rsx! {
+                                                    div { class: "card", key: "{node.id}",
+                                                        div { class: "flex justify-between items-start gap-2",
+                                                            div {
+                                                                p { class: "font-bold", "📅 {title}" }
+                                                                p { class: "text-xs text-[var(--text-muted)]", "{when} · {hours}h" }
+                                                                if let Some(venue) = location {
+                                                                    p { class: "text-xs text-[var(--text-muted)]", "📍 {venue}" }
+                                                                }
+                                                            }
+                                                            button {
+                                                                class: if going { "btn btn-secondary btn-sm" } else { "btn btn-primary btn-sm" },
+                                                                disabled: full && !going,
+                                                                onclick: {
+                                                                    let cmd_tx = cmd_tx.clone();
+                                                                    let eid = event_id.clone();
+                                                                    move |_| {
+                                                                        let _ = cmd_tx.send(AppCmd::RsvpEvent { event_id: eid.clone(), attending: !going });
+                                                                    }
+                                                                },
+                                                                if going { "Not going" } else if full { "Full" } else { "Going" }
+                                                            }
+                                                        }
+                                                        if !description.is_empty() {
+                                                            p { class: "text-sm mt-2", "{description}" }
+                                                        }
+                                                        span { class: "badge badge-primary mt-2", "{spots}" }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+
                     // Local feed
                     div { class: "panel",
                         div { class: "panel-header",
@@ -226,15 +376,29 @@ pub fn GeohashComponent() -> Element {
                                 {app_state.peers.read().iter().map(|peer_id| {
                                     let pid = peer_id.clone();
                                     let cmd_tx_vouch = cmd_tx.clone();
+                                    let profile = app_state.connected_peer_profiles.read().iter()
+                                        .find(|(id, _)| id == peer_id)
+                                        .and_then(|(_, p)| p.clone());
+                                    let photo = profile.as_ref().and_then(|p| p.photo.clone());
+                                    let display_name = profile.map(|p| p.name);
                                     rsx! {
                                         div {
                                             class: "list-item",
                                             key: "{pid}",
-                                            div { class: "avatar avatar-sm",
-                                                "{pid.get(0..2).unwrap_or(\"??\")}"
+                                            div { class: "avatar avatar-sm overflow-hidden",
+                                                if let Some(cid) = photo {
+                                                    BlobImage { cid: cid, class: Some("w-full h-full object-cover".to_string()) }
+                                                } else {
+                                                    "{pid.get(0..2).unwrap_or(\"??\")}"
+                                                }
                                             }
                                             div { class: "list-item-content",
-                                                p { class: "list-item-title font-mono text-sm truncate", "{pid}" }
+                                                if let Some(name) = display_name {
+                                                    p { class: "list-item-title", "{name}" }
+                                                    p { class: "font-mono text-xs text-[var(--text-muted)] truncate", "{pid}" }
+                                                } else {
+                                                    p { class: "list-item-title font-mono text-sm truncate", "{pid}" }
+                                                }
                                             }
                                             button {
                                                 class: "btn btn-secondary btn-sm",
@@ -249,6 +413,38 @@ pub fn GeohashComponent() -> Element {
                             }
                         }
                     }
+
+                    // Local community: anyone who has been active in this area, online or not
+                    {
+                        let community = app_state.peers_by_geohash.read().get(&geohash_prefix).cloned().unwrap_or_default();
+                        rsx! {
+                            if !community.is_empty() {
+                                div { class: "panel",
+                                    div { class: "panel-header",
+                                        h2 { class: "panel-title", "Local Community" }
+                                        span { class: "badge badge-primary", "{community.len()}" }
+                                    }
+                                    div { class: "flex flex-wrap gap-2",
+                                        for (peer_id, profile) in community.iter() {
+                                            {
+                                                let name = profile.as_ref().map(|p| p.name.clone()).unwrap_or_else(|| format!("{}...", peer_id.get(0..8).unwrap_or(peer_id)));
+                                                let target = peer_id.clone();
+                                                let mut app_state = app_state;
+                                                rsx! {
+                                                    button {
+                                                        key: "{peer_id}",
+                                                        class: "btn btn-secondary btn-sm",
+                                                        onclick: move |_| app_state.browser_url.set(format!("sp://profile.super/{}", target)),
+                                                        "{name}"
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
                 }
 
                 // Sidebar
@@ -1,13 +1,46 @@
 use dioxus::prelude::*;
-use crate::backend::{AppCmd, dag::{DagPayload, ProposalType, VoteType}};
+use crate::backend::{AppCmd, dag::{DagPayload, DelegationScope, ProposalType, VoteType}};
 use crate::components::AppState;
 
+/// One-line summary of what a passed proposal changed
+fn outcome_summary(outcome: &crate::backend::dag::ProposalOutcomePayload) -> String {
+    let when = chrono::DateTime::from_timestamp(outcome.enacted_at as i64, 0).map(|d| d.format("%Y-%m-%d").to_string()).unwrap_or_default();
+    match (&outcome.previous_value, &outcome.new_value) {
+        (Some(previous), Some(new)) => format!("{} from {} to {} on {}", outcome.action_taken, previous, new, when),
+        (None, Some(new)) => format!("{} to {} on {}", outcome.action_taken, new, when),
+        _ => format!("{} on {}", outcome.action_taken, when),
+    }
+}
+
+/// Short label for what a vote delegation covers
+fn delegation_scope_label(scope: &DelegationScope) -> String {
+    match scope {
+        DelegationScope::All => "All proposals".to_string(),
+        DelegationScope::Ministry(ministry) => format!("{} elections", ministry),
+        DelegationScope::ProposalType(ProposalType::SetTax(_)) => "Tax proposals".to_string(),
+        DelegationScope::ProposalType(ProposalType::DefineMinistries(_)) => "Ministry proposals".to_string(),
+        DelegationScope::ProposalType(ProposalType::SetElectionSchedule { .. }) => "Election schedule proposals".to_string(),
+        DelegationScope::ProposalType(t) => format!("{:?} proposals", t),
+    }
+}
+
+/// Countdown to the end of a proposal's voting window
+fn voting_countdown(time_remaining: Option<u64>) -> String {
+    match time_remaining {
+        Some(secs) => format!("⏳ {}d {}h {}m left", secs / 86400, secs % 86400 / 3600, secs % 3600 / 60),
+        None => "Voting closed".to_string(),
+    }
+}
+
 #[component]
 pub fn GovernanceComponent() -> Element {
     let app_state = use_context::<AppState>();
     let cmd_tx = use_context::<tokio::sync::mpsc::UnboundedSender<AppCmd>>();
+    let is_official = !app_state.my_elected_positions.read().is_empty();
     let mut show_create_modal = use_signal(|| false);
+    let mut open_impacts = use_signal(std::collections::HashSet::<String>::new);
     let mut show_candidacy_modal = use_signal(|| false);
+    let mut viewing_candidate = use_signal(|| None::<String>);
     let mut active_tab = use_signal(|| "proposals".to_string());
     
     // Form state for proposals
@@ -16,7 +49,15 @@ pub fn GovernanceComponent() -> Element {
     let mut proposal_type = use_signal(|| "Standard".to_string());
     let mut tax_rate = use_signal(|| 0i64);
     let mut defined_ministries = use_signal(|| vec![]);
+    let mut election_ministry = use_signal(|| "VerificationAndIdentity".to_string());
+    let mut election_start = use_signal(String::new); // YYYY-MM-DD
+    let mut election_end = use_signal(String::new);
     let mut pinned = use_signal(|| false);
+    let mut quadratic = use_signal(|| false);
+
+    // Form state for vote delegation
+    let mut delegate_input = use_signal(|| "".to_string());
+    let mut delegation_scope = use_signal(|| "All".to_string());
     
     // Form state for candidacy
     let mut selected_ministry = use_signal(|| "VerificationAndIdentity".to_string());
@@ -28,6 +69,12 @@ pub fn GovernanceComponent() -> Element {
     let mut recall_reason = use_signal(|| "".to_string());
     let mut recall_ministry = use_signal(|| "VerificationAndIdentity".to_string());
 
+    // Petition form
+    let mut petition_title = use_signal(String::new);
+    let mut petition_body = use_signal(String::new);
+    let mut petition_ministry = use_signal(String::new); // empty = no particular ministry
+    let mut petition_threshold = use_signal(|| 50usize);
+
     // Fetch data on mount
     let cmd_tx_effect = cmd_tx.clone();
     use_effect(move || {
@@ -35,12 +82,29 @@ pub fn GovernanceComponent() -> Element {
         let _ = cmd_tx_effect.send(AppCmd::FetchCandidates);
         let _ = cmd_tx_effect.send(AppCmd::FetchReports);
         let _ = cmd_tx_effect.send(AppCmd::FetchRecalls);
+        let _ = cmd_tx_effect.send(AppCmd::FetchPetitions);
+        let _ = cmd_tx_effect.send(AppCmd::FetchElectionSchedule { ministry: None });
         let _ = cmd_tx_effect.send(AppCmd::FetchOversightCases);
         let _ = cmd_tx_effect.send(AppCmd::FetchJuryDuty);
+        let _ = cmd_tx_effect.send(AppCmd::FetchOversightStatsByMinistry);
         let _ = cmd_tx_effect.send(AppCmd::FetchMinistries);
+        let _ = cmd_tx_effect.send(AppCmd::FetchActiveOfficials);
         let _ = cmd_tx_effect.send(AppCmd::FetchMyCertifications);
-        let _ = cmd_tx_effect.send(AppCmd::FetchTaxRate);
+        let _ = cmd_tx_effect.send(AppCmd::FetchSystemParameters);
+        let _ = cmd_tx_effect.send(AppCmd::FetchMyDelegations);
+    });
+
+    // Look for likely duplicates while a proposal is being drafted
+    let cmd_tx_similar = cmd_tx.clone();
+    use_effect(move || {
+        let (draft_title, draft_description) = (title(), description());
+        if show_create_modal() && !(draft_title.trim().is_empty() && draft_description.trim().is_empty()) {
+            let _ = cmd_tx_similar.send(AppCmd::FetchDraftProposalSimilar { title: draft_title, description: draft_description });
+        }
     });
+    let draft_similar = app_state.similar_proposals.read().get("").cloned().unwrap_or_default();
+
+    let current_tax = app_state.system_params.read().get("tax_rate").and_then(|v| v.as_u64()).unwrap_or(0);
 
     // Check Certifications
     let certifications = app_state.certifications.read();
@@ -61,6 +125,7 @@ pub fn GovernanceComponent() -> Element {
         let proposals = app_state.proposals.read();
         for node in proposals.iter() {
             let _ = cmd_tx_proposals.send(AppCmd::FetchProposalTally { proposal_id: node.id.clone() });
+            let _ = cmd_tx_proposals.send(AppCmd::FetchProposalProgressToQuorum { proposal_id: node.id.clone() });
         }
     });
 
@@ -83,12 +148,24 @@ pub fn GovernanceComponent() -> Element {
     });
 
     let cmd_tx_submit = cmd_tx.clone();
+    let mut similar_proposals = app_state.similar_proposals;
     let on_submit_proposal = move |_| {
         let p_type = match proposal_type().as_str() {
             "Constitutional" => ProposalType::Constitutional,
             "Emergency" => ProposalType::Emergency,
             "SetTax" => ProposalType::SetTax(tax_rate() as u8),
             "DefineMinistries" => ProposalType::DefineMinistries(defined_ministries()),
+            "SetElectionSchedule" => {
+                let to_unix = |date: &str, end_of_day: bool| {
+                    chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+                        .and_then(|d| if end_of_day { d.and_hms_opt(23, 59, 59) } else { d.and_hms_opt(0, 0, 0) })
+                        .map(|dt| dt.and_utc().timestamp() as u64)
+                };
+                match (to_unix(&election_start(), false), to_unix(&election_end(), true)) {
+                    (Some(start_unix), Some(end_unix)) if start_unix < end_unix => ProposalType::SetElectionSchedule { ministry: election_ministry(), start_unix, end_unix },
+                    _ => return, // Invalid window
+                }
+            }
             _ => ProposalType::Standard,
         };
 
@@ -97,11 +174,14 @@ pub fn GovernanceComponent() -> Element {
             description: description(),
             r#type: p_type,
             pinned: pinned(),
+            quadratic: quadratic(),
         });
 
         title.set("".to_string());
         description.set("".to_string());
         pinned.set(false);
+        quadratic.set(false);
+        similar_proposals.write().remove("");
         show_create_modal.set(false);
     };
 
@@ -143,7 +223,7 @@ pub fn GovernanceComponent() -> Element {
                         h1 { class: "page-title", "Governance Portal" }
                         p { class: "text-[var(--text-secondary)]", 
                             "Voice of the People: 1 Human = 1 Vote • Current System Tax: ",
-                            span { class: "font-bold text-green-500", "{app_state.current_tax_rate}%" }
+                            span { class: "font-bold text-green-500", "{current_tax}%" }
                         }
                     }
                     div { class: "flex gap-2",
@@ -166,7 +246,8 @@ pub fn GovernanceComponent() -> Element {
                             "⚠️ Recall Official"
                         }
                         button {
-                            class: "btn btn-primary",
+                            class: if is_official { "btn btn-primary ring-2 ring-yellow-500" } else { "btn btn-primary" },
+                            title: if is_official { "You hold elected office" } else { "" },
                             onclick: move |_: Event<MouseData>| show_create_modal.set(true),
                             "📝 Draft Proposal"
                         }
@@ -191,15 +272,119 @@ pub fn GovernanceComponent() -> Element {
                     onclick: move |_| active_tab.set("recalls".to_string()),
                     "⚠️ Recalls"
                 }
+                button {
+                    class: if active_tab() == "petitions" { "btn btn-primary" } else { "btn btn-secondary" },
+                    onclick: move |_| active_tab.set("petitions".to_string()),
+                    "✍️ Petitions"
+                }
                 button {
                     class: if active_tab() == "moderation" { "btn btn-primary" } else { "btn btn-secondary" },
                     onclick: move |_| active_tab.set("moderation".to_string()),
                     "🛡️ Moderation"
                 }
+                {
+                    let cmd_tx_history = cmd_tx.clone();
+                    rsx! {
+                        button {
+                            class: if active_tab() == "my_votes" { "btn btn-primary" } else { "btn btn-secondary" },
+                            onclick: move |_| {
+                                let _ = cmd_tx_history.send(AppCmd::FetchMyProposalVoteHistory);
+                                active_tab.set("my_votes".to_string());
+                            },
+                            "🗂️ My Votes"
+                        }
+                    }
+                }
+                {
+                    let cmd_tx_outcomes = cmd_tx.clone();
+                    rsx! {
+                        button {
+                            class: if active_tab() == "history" { "btn btn-primary" } else { "btn btn-secondary" },
+                            onclick: move |_| {
+                                let _ = cmd_tx_outcomes.send(AppCmd::FetchProposalOutcomeActions { proposal_id: None });
+                                active_tab.set("history".to_string());
+                            },
+                            "📜 History"
+                        }
+                    }
+                }
             }
 
             // Content based on active tab
             if active_tab() == "proposals" {
+                // Vote delegation
+                div { class: "panel mb-6",
+                    h3 { class: "font-bold mb-2", "🤝 Vote Delegation" }
+                    p { class: "text-xs text-[var(--text-muted)] mb-3", "A trusted verified peer votes for you where you haven't voted yourself. Voting directly always overrides them." }
+                    for node in app_state.my_delegations.read().iter() {
+                        if let DagPayload::VoteDelegation(d) = &node.payload {
+                            {
+                                let scope = d.scope.clone();
+                                let cmd_tx = cmd_tx.clone();
+                                let delegate_short: String = d.delegate.chars().take(12).collect();
+                                rsx! {
+                                    div { key: "{node.id}", class: "flex justify-between items-center text-sm mb-2",
+                                        span { "{delegation_scope_label(&d.scope)} → {delegate_short}…" }
+                                        button {
+                                            class: "btn btn-sm btn-secondary",
+                                            onclick: move |_| {
+                                                let _ = cmd_tx.send(AppCmd::RevokeDelegation { scope: scope.clone() });
+                                            },
+                                            "Revoke"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    div { class: "flex gap-2",
+                        input {
+                            class: "input flex-1",
+                            placeholder: "Delegate peer ID",
+                            value: "{delegate_input}",
+                            oninput: move |e| delegate_input.set(e.value()),
+                        }
+                        select {
+                            class: "input",
+                            value: "{delegation_scope}",
+                            onchange: move |e| delegation_scope.set(e.value()),
+                            option { value: "All", "All proposals" }
+                            option { value: "Standard", "Standard proposals" }
+                            option { value: "Constitutional", "Constitutional proposals" }
+                            option { value: "Emergency", "Emergency proposals" }
+                            option { value: "SetTax", "Tax proposals" }
+                            for m in app_state.ministries.read().iter() {
+                                option { value: "ministry:{m}", "{m} elections" }
+                            }
+                        }
+                        {
+                            let cmd_tx = cmd_tx.clone();
+                            rsx! {
+                                button {
+                                    class: "btn btn-primary btn-sm",
+                                    disabled: delegate_input().trim().is_empty(),
+                                    onclick: move |_| {
+                                        let scope_choice = delegation_scope();
+                                        let scope = match scope_choice.as_str() {
+                                            "Standard" => DelegationScope::ProposalType(ProposalType::Standard),
+                                            "Constitutional" => DelegationScope::ProposalType(ProposalType::Constitutional),
+                                            "Emergency" => DelegationScope::ProposalType(ProposalType::Emergency),
+                                            "SetTax" => DelegationScope::ProposalType(ProposalType::SetTax(0)),
+                                            other => match other.strip_prefix("ministry:") {
+                                                Some(ministry) => DelegationScope::Ministry(ministry.to_string()),
+                                                None => DelegationScope::All,
+                                            },
+                                        };
+                                        let _ = cmd_tx.send(AppCmd::DelegateVote { delegate: delegate_input().trim().to_string(), scope, expires_at: None });
+                                        delegate_input.set("".to_string());
+                                    },
+                                    "Delegate"
+                                }
+                            }
+                        }
+                    }
+                }
+
                 // Proposals List
                 div { class: "grid gap-6",
                     {
@@ -237,7 +422,14 @@ pub fn GovernanceComponent() -> Element {
                                             let (yes, no, abstain, petition, unique_voters, status) = tallies_map
                                                 .get(&pid)
                                                 .cloned()
-                                                .unwrap_or((0, 0, 0, 0, 0, "Unknown".to_string()));
+                                                .unwrap_or((0.0, 0.0, 0.0, 0, 0, "Unknown".to_string()));
+                                            // Quadratic weights are fractional; plain tallies are whole head counts
+                                            let precision = if prop.quadratic { 1 } else { 0 };
+                                            let (yes, no, abstain) = (
+                                                format!("{:.*}", precision, yes),
+                                                format!("{:.*}", precision, no),
+                                                format!("{:.*}", precision, abstain),
+                                            );
                                             
                                             let author_short = &node.author[0..8];
                                             let type_str = match &prop.r#type {
@@ -246,6 +438,7 @@ pub fn GovernanceComponent() -> Element {
                                                 ProposalType::Constitutional => "Constitutional".to_string(),
                                                 ProposalType::Emergency => "Emergency".to_string(),
                                                 ProposalType::Standard => "Standard".to_string(),
+                                                ProposalType::SetElectionSchedule { ministry, .. } => format!("Election Schedule: {}", ministry),
                                             };
                                             
                                             let status_color = match status.as_str() {
@@ -268,6 +461,9 @@ pub fn GovernanceComponent() -> Element {
                                                                 }
                                                                 span { class: "px-2 py-0.5 rounded text-xs bg-[var(--bg-secondary)]", "{type_str}" }
                                                                 span { class: "font-bold text-sm {status_color} border border-current px-2 py-0.5 rounded", "{status}" }
+                                                                if prop.quadratic {
+                                                                    span { class: "px-2 py-0.5 rounded text-xs bg-[var(--bg-secondary)]", title: "Votes are weighted by the square root of each voter's reputation score", "√ Quadratic" }
+                                                                }
                                                             }
                                                             h2 { class: "text-xl font-bold", "{prop.title}" }
                                                             p { class: "text-xs text-[var(--text-muted)] mt-1", "Proposed by {author_short}..." }
@@ -281,6 +477,44 @@ pub fn GovernanceComponent() -> Element {
                                                         "{prop.description}"
                                                     }
 
+                                                    if let Some(DagPayload::ProposalOutcome(outcome)) = app_state.proposal_outcomes.read().get(&pid).map(|n| n.payload.clone()) {
+                                                        p { class: "text-sm text-green-500 mb-4", "This proposal resulted in: {outcome_summary(&outcome)}" }
+                                                    }
+
+                                                    if matches!(prop.r#type, ProposalType::SetTax(_)) {
+                                                        {
+                                                            let is_open = open_impacts.read().contains(&pid);
+                                                            let impact = app_state.proposal_impacts.read().get(&pid).cloned();
+                                                            let pid_impact = pid.clone();
+                                                            let cmd_tx_impact = cmd_tx.clone();
+                                                            rsx! {
+                                                                div { class: "mb-4",
+                                                                    button {
+                                                                        class: "btn btn-secondary btn-sm",
+                                                                        onclick: move |_| {
+                                                                            if is_open {
+                                                                                open_impacts.write().remove(&pid_impact);
+                                                                            } else {
+                                                                                let _ = cmd_tx_impact.send(AppCmd::FetchProposalImpactEstimate { proposal_id: pid_impact.clone() });
+                                                                                open_impacts.write().insert(pid_impact.clone());
+                                                                            }
+                                                                        },
+                                                                        if is_open { "Hide Impact Analysis" } else { "📊 View Impact Analysis" }
+                                                                    }
+                                                                    if is_open {
+                                                                        div { class: "mt-2 p-3 rounded bg-[var(--bg-secondary)] text-sm",
+                                                                            if let Some((narrative, _)) = impact {
+                                                                                p { "{narrative}" }
+                                                                            } else {
+                                                                                p { class: "text-[var(--text-muted)]", "Analysing ledger history..." }
+                                                                            }
+                                                                        }
+                                                                    }
+                                                                }
+                                                            }
+                                                        }
+                                                    }
+
                                                     div { class: "grid grid-cols-4 gap-2 mb-4 text-center text-sm",
                                                         div { class: "p-2 rounded bg-green-900/20",
                                                             div { class: "text-lg font-bold text-green-400", "✅ {yes}" }
@@ -300,6 +534,23 @@ pub fn GovernanceComponent() -> Element {
                                                         }
                                                     }
 
+                                                    if let Some((progress_yes, progress_no, progress_abstain, required_yes, required_voters, progress_pct, time_remaining)) = app_state.proposal_progress.read().get(&pid).cloned() {
+                                                        div { class: "mb-4 text-sm",
+                                                            div { class: "flex justify-between mb-1",
+                                                                span { class: "font-bold", "{progress_yes}/{required_yes} yes votes needed" }
+                                                                span { class: "text-[var(--text-muted)]", "{progress_no} no · {progress_abstain} abstain" }
+                                                                span { class: "text-[var(--text-muted)]", "{voting_countdown(time_remaining)}" }
+                                                            }
+                                                            div { class: "w-full bg-[var(--bg-secondary)] rounded-full h-2",
+                                                                div {
+                                                                    class: "bg-green-500 h-2 rounded-full transition-all",
+                                                                    style: "width: {progress_pct}%"
+                                                                }
+                                                            }
+                                                            p { class: "text-xs text-[var(--text-muted)] mt-1", "Needs {required_voters} supporter(s) to reach quorum" }
+                                                        }
+                                                    }
+
                                                     div { class: "flex flex-wrap gap-2 border-t border-[var(--border-color)] pt-4",
                                                         button {
                                                             class: "btn btn-secondary flex-1",
@@ -309,6 +560,7 @@ pub fn GovernanceComponent() -> Element {
                                                                 move |_| {
                                                                     let _ = cmd_tx.send(AppCmd::VoteProposal { proposal_id: pid.clone(), vote: VoteType::PetitionSignature });
                                                                     let _ = cmd_tx.send(AppCmd::FetchProposalTally { proposal_id: pid.clone() });
+                                                                    let _ = cmd_tx.send(AppCmd::FetchProposalProgressToQuorum { proposal_id: pid.clone() });
                                                                 }
                                                             },
                                                             "✍️ Sign"
@@ -322,6 +574,7 @@ pub fn GovernanceComponent() -> Element {
                                                                 move |_| {
                                                                     let _ = cmd_tx.send(AppCmd::VoteProposal { proposal_id: pid.clone(), vote: VoteType::Yes });
                                                                     let _ = cmd_tx.send(AppCmd::FetchProposalTally { proposal_id: pid.clone() });
+                                                                    let _ = cmd_tx.send(AppCmd::FetchProposalProgressToQuorum { proposal_id: pid.clone() });
                                                                 }
                                                             },
                                                             "✅ Yes"
@@ -335,6 +588,7 @@ pub fn GovernanceComponent() -> Element {
                                                                 move |_| {
                                                                     let _ = cmd_tx.send(AppCmd::VoteProposal { proposal_id: pid.clone(), vote: VoteType::No });
                                                                     let _ = cmd_tx.send(AppCmd::FetchProposalTally { proposal_id: pid.clone() });
+                                                                    let _ = cmd_tx.send(AppCmd::FetchProposalProgressToQuorum { proposal_id: pid.clone() });
                                                                 }
                                                             },
                                                             "❌ No"
@@ -348,6 +602,7 @@ pub fn GovernanceComponent() -> Element {
                                                                 move |_| {
                                                                     let _ = cmd_tx.send(AppCmd::VoteProposal { proposal_id: pid.clone(), vote: VoteType::Abstain });
                                                                     let _ = cmd_tx.send(AppCmd::FetchProposalTally { proposal_id: pid.clone() });
+                                                                    let _ = cmd_tx.send(AppCmd::FetchProposalProgressToQuorum { proposal_id: pid.clone() });
                                                                 }
                                                             },
                                                             "⏸️ Abstain"
@@ -365,6 +620,69 @@ pub fn GovernanceComponent() -> Element {
             } else if active_tab() == "elections" {
                 // Elections Tab
                 div { class: "grid gap-6",
+                    {
+                        let mut officials: Vec<(String, String)> = app_state.active_officials.read().clone().into_iter().collect();
+                        officials.sort();
+                        let connected = app_state.connected_peer_profiles.read().clone();
+                        let cached = app_state.user_profiles.read().clone();
+                        let name_of = move |peer_id: &str| {
+                            connected.iter().find(|(id, _)| id == peer_id).and_then(|(_, p)| p.as_ref().map(|p| p.name.clone()))
+                                .or_else(|| cached.get(peer_id).map(|p| p.name.clone()))
+                                .unwrap_or_else(|| format!("{}...", peer_id.get(0..8).unwrap_or(peer_id)))
+                        };
+                        rsx! {
+                            if !officials.is_empty() {
+                                div { class: "panel",
+                                    h2 { class: "text-xl font-bold mb-4", "🏛️ Current Officials" }
+                                    for (ministry, peer_id) in officials.into_iter() {
+                                        {
+                                            let name = name_of(&peer_id);
+                                            let mut browser_url = app_state.browser_url;
+                                            rsx! {
+                                                div { class: "list-item flex justify-between items-center",
+                                                    div { class: "list-item-content",
+                                                        p { class: "list-item-title", "{name} is the Minister of {ministry}" }
+                                                    }
+                                                    button {
+                                                        class: "btn btn-secondary btn-sm",
+                                                        onclick: move |_| browser_url.set(format!("sp://profile.super/{}", peer_id)),
+                                                        "View Profile"
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    {
+                        let schedule = app_state.election_schedule.read().clone();
+                        let now = chrono::Utc::now().timestamp() as u64;
+                        rsx! {
+                            if !schedule.is_empty() {
+                                div { class: "panel",
+                                    h2 { class: "text-xl font-bold mb-4", "🗓️ Election Schedule" }
+                                    for (ministry, start, end) in schedule.into_iter() {
+                                        {
+                                            let fmt = |ts: u64| chrono::DateTime::from_timestamp(ts as i64, 0).map(|d| d.format("%Y-%m-%d").to_string()).unwrap_or_default();
+                                            let (start_str, end_str) = (fmt(start), fmt(end));
+                                            let state = if now < start { "Upcoming" } else if now <= end { "Open" } else { "Closed" };
+                                            rsx! {
+                                                div { class: "list-item flex justify-between items-center",
+                                                    div { class: "list-item-content",
+                                                        p { class: "list-item-title", "{ministry}" }
+                                                        p { class: "list-item-subtitle", "{start_str} → {end_str}" }
+                                                    }
+                                                    span { class: "badge badge-primary", "{state}" }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
                     {
                         let ministries_list = app_state.ministries.read();
                         rsx! {
@@ -393,25 +711,61 @@ pub fn GovernanceComponent() -> Element {
                                                             let tallies = app_state.candidate_tallies.read();
                                                             let votes = tallies.get(&cid).cloned().unwrap_or(0);
                                                             let author_short = &node.author[0..8];
+                                                            let withdrawn = app_state.candidacy_withdrawals.read().contains_key(&cid);
+                                                            let is_mine = node.author == *app_state.local_peer_id.read();
+                                                            let row_class = if withdrawn { "flex items-center justify-between p-3 bg-[var(--bg-secondary)] rounded-lg mb-2 opacity-50" } else { "flex items-center justify-between p-3 bg-[var(--bg-secondary)] rounded-lg mb-2" };
                                                             rsx! {
-                                                                div { key: "{cid}", class: "flex items-center justify-between p-3 bg-[var(--bg-secondary)] rounded-lg mb-2",
+                                                                div { key: "{cid}", class: "{row_class}",
                                                                     div {
-                                                                        div { class: "font-bold", "{author_short}..." }
-                                                                        div { class: "text-sm text-[var(--text-muted)]", "{c.platform}" }
+                                                                        div { class: "font-bold flex items-center gap-2",
+                                                                            "{author_short}..."
+                                                                            if withdrawn {
+                                                                                span { class: "badge badge-error", "Withdrawn" }
+                                                                            }
+                                                                        }
+                                                                        div { class: "text-sm text-[var(--text-muted)] line-clamp-2", "{c.platform}" }
                                                                     }
                                                                     div { class: "flex items-center gap-3",
                                                                         div { class: "text-lg font-bold", "🗳️ {votes}" }
                                                                         button {
-                                                                            class: "btn btn-primary btn-sm",
+                                                                            class: "btn btn-secondary btn-sm",
                                                                             onclick: {
                                                                                 let cid = cid.clone();
                                                                                 let cmd_tx = cmd_tx_cvote.clone();
                                                                                 move |_| {
-                                                                                    let _ = cmd_tx.send(AppCmd::VoteForCandidate { candidacy_id: cid.clone() });
-                                                                                    let _ = cmd_tx.send(AppCmd::FetchCandidateTally { candidacy_id: cid.clone() });
+                                                                                    let _ = cmd_tx.send(AppCmd::FetchCandidatePlatform { candidacy_id: cid.clone() });
+                                                                                    let _ = cmd_tx.send(AppCmd::FetchCandidacyWithdrawal { candidacy_id: cid.clone() });
+                                                                                    viewing_candidate.set(Some(cid.clone()));
                                                                                 }
                                                                             },
-                                                                            "Vote"
+                                                                            "Details"
+                                                                        }
+                                                                        if !withdrawn {
+                                                                            button {
+                                                                                class: "btn btn-primary btn-sm",
+                                                                                onclick: {
+                                                                                    let cid = cid.clone();
+                                                                                    let cmd_tx = cmd_tx_cvote.clone();
+                                                                                    move |_| {
+                                                                                        let _ = cmd_tx.send(AppCmd::VoteForCandidate { candidacy_id: cid.clone() });
+                                                                                        let _ = cmd_tx.send(AppCmd::FetchCandidateTally { candidacy_id: cid.clone() });
+                                                                                    }
+                                                                                },
+                                                                                "Vote"
+                                                                            }
+                                                                        }
+                                                                        if is_mine && !withdrawn {
+                                                                            button {
+                                                                                class: "btn btn-secondary btn-sm",
+                                                                                onclick: {
+                                                                                    let cid = cid.clone();
+                                                                                    let cmd_tx = cmd_tx_cvote.clone();
+                                                                                    move |_| {
+                                                                                        let _ = cmd_tx.send(AppCmd::WithdrawCandidacy { candidacy_id: cid.clone() });
+                                                                                    }
+                                                                                },
+                                                                                "Withdraw"
+                                                                            }
                                                                         }
                                                                     }
                                                                 }
@@ -514,9 +868,249 @@ pub fn GovernanceComponent() -> Element {
                 }
 
 
+            } else if active_tab() == "petitions" {
+                div { class: "grid gap-6",
+                    div { class: "panel",
+                        h2 { class: "text-xl font-bold mb-4", "✍️ Start a Petition" }
+                        p { class: "text-[var(--text-secondary)] mb-4", "Gather signatures first. Once enough citizens sign, the petition can be turned into a formal proposal." }
+                        input { class: "input mb-2", placeholder: "Title", value: "{petition_title}", oninput: move |e| petition_title.set(e.value()) }
+                        textarea { class: "input mb-2", style: "min-height: 80px; resize: none;", placeholder: "What should change, and why?", value: "{petition_body}", oninput: move |e| petition_body.set(e.value()) }
+                        div { class: "flex flex-wrap gap-2 items-center",
+                            select {
+                                class: "input w-auto",
+                                value: "{petition_ministry}",
+                                onchange: move |e| petition_ministry.set(e.value()),
+                                option { value: "", "No particular ministry" }
+                                for m in app_state.ministries.read().iter() {
+                                    option { value: "{m}", "{m}" }
+                                }
+                            }
+                            label { class: "text-sm", "Signatures needed" }
+                            input {
+                                class: "input w-24",
+                                r#type: "number",
+                                min: "1",
+                                value: "{petition_threshold}",
+                                oninput: move |e| petition_threshold.set(e.value().parse().unwrap_or(1).max(1))
+                            }
+                            {
+                                let cmd_tx = cmd_tx.clone();
+                                rsx! {
+                                    button {
+                                        class: "btn btn-primary",
+                                        disabled: petition_title().trim().is_empty(),
+                                        onclick: move |_| {
+                                            let target_ministry = Some(petition_ministry()).filter(|m| !m.is_empty());
+                                            let _ = cmd_tx.send(AppCmd::CreatePetition { title: petition_title(), body: petition_body(), target_ministry, threshold: petition_threshold() });
+                                            petition_title.set(String::new());
+                                            petition_body.set(String::new());
+                                        },
+                                        "Start Petition"
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    div { class: "panel",
+                        h2 { class: "text-xl font-bold mb-4", "Open Petitions" }
+                        if app_state.petitions.read().is_empty() {
+                            p { class: "text-[var(--text-muted)]", "No petitions yet." }
+                        }
+                        for node in app_state.petitions.read().iter() {
+                            if let DagPayload::Petition(p) = &node.payload {
+                                {
+                                    let pid = node.id.clone();
+                                    let me = app_state.local_peer_id.read().clone();
+                                    let signers = app_state.petition_signatures.read().get(&pid).cloned().unwrap_or_default();
+                                    let signed = signers.contains(&me);
+                                    let reached = signers.len() >= p.threshold;
+                                    let converted = app_state.proposals.read().iter().any(|prop| prop.prev.contains(&pid));
+                                    let can_convert = reached && !converted && (signed || node.author == me);
+                                    let pct = (signers.len() * 100 / p.threshold.max(1)).min(100);
+                                    let author_short = node.author.get(0..8).unwrap_or(&node.author).to_string();
+
+                                    use_effect({
+                                        let cmd_tx = cmd_tx.clone();
+                                        let pid = pid.clone();
+                                        move || {
+                                            let _ = cmd_tx.send(AppCmd::FetchPetitionSignatures { petition_id: pid.clone() });
+                                        }
+                                    });
+
+                                    rsx! {
+                                        div { key: "{pid}", class: "card mb-4",
+                                            div { class: "flex justify-between items-start mb-2",
+                                                div {
+                                                    h3 { class: "font-bold text-lg", "{p.title}" }
+                                                    p { class: "text-xs text-[var(--text-muted)]", "Started by {author_short}..." }
+                                                    if let Some(ministry) = &p.target_ministry {
+                                                        p { class: "text-xs text-[var(--text-muted)]", "To: {ministry}" }
+                                                    }
+                                                }
+                                                if converted {
+                                                    span { class: "badge badge-primary", "Now a proposal" }
+                                                } else if reached {
+                                                    span { class: "badge badge-primary", "Threshold reached" }
+                                                }
+                                            }
+                                            if !p.body.is_empty() {
+                                                p { class: "text-[var(--text-secondary)] mb-3", "{p.body}" }
+                                            }
+                                            div { class: "w-full h-2 rounded bg-[var(--bg-secondary)] mb-1",
+                                                div { class: "h-2 rounded bg-green-500", style: "width: {pct}%;" }
+                                            }
+                                            p { class: "text-xs text-[var(--text-muted)] mb-3", "{signers.len()} / {p.threshold} signatures" }
+                                            div { class: "flex gap-2",
+                                                button {
+                                                    class: "btn btn-secondary btn-sm",
+                                                    disabled: signed,
+                                                    onclick: {
+                                                        let cmd_tx = cmd_tx.clone();
+                                                        let pid = pid.clone();
+                                                        move |_| {
+                                                            let _ = cmd_tx.send(AppCmd::SignPetition { petition_id: pid.clone() });
+                                                        }
+                                                    },
+                                                    if signed { "✍️ Signed" } else { "✍️ Sign" }
+                                                }
+                                                if can_convert {
+                                                    button {
+                                                        class: "btn btn-primary btn-sm",
+                                                        onclick: {
+                                                            let cmd_tx = cmd_tx.clone();
+                                                            let pid = pid.clone();
+                                                            move |_| {
+                                                                let _ = cmd_tx.send(AppCmd::ConvertPetitionToProposal { petition_id: pid.clone() });
+                                                            }
+                                                        },
+                                                        "📋 Convert to Proposal"
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            } else if active_tab() == "history" {
+                // Timeline of enacted proposals
+                div { class: "panel",
+                    h2 { class: "text-xl font-bold mb-4", "📜 Enacted Proposals" }
+                    {
+                        let mut outcomes: Vec<crate::backend::dag::DagNode> = app_state.proposal_outcomes.read().values().cloned().collect();
+                        outcomes.sort_by_key(|n| match &n.payload {
+                            DagPayload::ProposalOutcome(o) => std::cmp::Reverse(o.enacted_at),
+                            _ => std::cmp::Reverse(0),
+                        });
+                        let proposals = app_state.proposals.read().clone();
+                        if outcomes.is_empty() {
+                            rsx! { p { class: "text-[var(--text-muted)]", "No proposals have been enacted yet." } }
+                        } else {
+                            rsx! {
+                                for node in outcomes.into_iter() {
+                                    if let DagPayload::ProposalOutcome(outcome) = &node.payload {
+                                        {
+                                            let title = proposals.iter()
+                                                .find(|p| p.id == outcome.proposal_id)
+                                                .and_then(|p| match &p.payload {
+                                                    DagPayload::Proposal(prop) => Some(prop.title.clone()),
+                                                    _ => None,
+                                                })
+                                                .unwrap_or_else(|| format!("{}...", outcome.proposal_id.get(0..12).unwrap_or(&outcome.proposal_id)));
+                                            let summary = outcome_summary(outcome);
+                                            rsx! {
+                                                div { key: "{node.id}", class: "list-item border-l-4 border-l-green-500",
+                                                    div { class: "list-item-content",
+                                                        p { class: "list-item-title", "{title}" }
+                                                        p { class: "list-item-subtitle", "{summary}" }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            } else if active_tab() == "my_votes" {
+                // Personal voting record
+                div { class: "panel",
+                    h2 { class: "text-xl font-bold mb-4", "🗂️ My Voting History" }
+                    {
+                        let history = app_state.my_vote_history.read().clone();
+                        if history.is_empty() {
+                            rsx! { p { class: "text-[var(--text-muted)]", "You haven't voted on any proposals yet." } }
+                        } else {
+                            rsx! {
+                                for (vote_node, title) in history.into_iter() {
+                                    if let DagPayload::Vote(vote) = &vote_node.payload {
+                                        {
+                                            let when = vote_node.timestamp.format("%Y-%m-%d").to_string();
+                                            let title = title.clone().unwrap_or_else(|| format!("{}...", vote.proposal_id.get(0..12).unwrap_or(&vote.proposal_id)));
+                                            let choice = match vote.vote {
+                                                VoteType::Yes => "✅ Yes",
+                                                VoteType::No => "❌ No",
+                                                VoteType::Abstain => "⏸️ Abstain",
+                                                VoteType::PetitionSignature => "✍️ Signed",
+                                            };
+                                            rsx! {
+                                                div { key: "{vote_node.id}", class: "list-item flex justify-between items-center",
+                                                    div { class: "list-item-content",
+                                                        p { class: "list-item-title", "{title}" }
+                                                        p { class: "list-item-subtitle", "{when}" }
+                                                    }
+                                                    span { class: "badge badge-primary", "{choice}" }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
             } else {
                 // Moderation Tab
                 div { class: "grid gap-6",
+                    {
+                        let mut stats: Vec<(String, crate::backend::OversightStats)> = app_state.oversight_stats.read().clone().into_iter().collect();
+                        stats.sort_by(|a, b| (b.1.0 + b.1.1).cmp(&(a.1.0 + a.1.1)).then_with(|| a.0.cmp(&b.0)));
+                        rsx! {
+                            div { class: "panel",
+                                h2 { class: "text-xl font-bold mb-4", "📊 Ministry Oversight" }
+                                if stats.is_empty() {
+                                    p { class: "text-[var(--text-muted)]", "No oversight cases yet." }
+                                } else {
+                                    div { class: "overflow-x-auto",
+                                        table { class: "w-full text-left border-collapse",
+                                            thead {
+                                                tr { class: "border-b border-[var(--border-default)] text-[var(--text-secondary)] text-sm",
+                                                    th { class: "py-3 px-4 font-medium", "Ministry" }
+                                                    th { class: "py-3 px-4 font-medium", "Open" }
+                                                    th { class: "py-3 px-4 font-medium", "Decided" }
+                                                    th { class: "py-3 px-4 font-medium", "Avg. Resolution" }
+                                                }
+                                            }
+                                            tbody {
+                                                for (ministry, (open, decided, avg_hours)) in stats.into_iter() {
+                                                    tr { key: "{ministry}", class: "border-b border-[var(--border-default)] last:border-0",
+                                                        td { class: "py-3 px-4", "{ministry}" }
+                                                        td { class: "py-3 px-4", "{open}" }
+                                                        td { class: "py-3 px-4", "{decided}" }
+                                                        td { class: "py-3 px-4", if decided > 0 { "{avg_hours}h" } else { "—" } }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
                     div { class: "panel",
                         h2 { class: "text-xl font-bold mb-4", "🚨 Decentralized Moderation Reports" }
                         p { class: "text-[var(--text-secondary)] mb-6", "Review reports submitted by the community. As a verified citizen, your vigilance helps keep the network safe." }
@@ -681,6 +1275,16 @@ pub fn GovernanceComponent() -> Element {
                                                 }
                                             }
                                         }
+                                        {
+                                            let sched_text = if has_civic_literacy { "Schedule an Election" } else { "Schedule an Election (Requires Civic Literacy)" };
+                                            rsx! {
+                                                option {
+                                                    value: "SetElectionSchedule",
+                                                    disabled: "{!has_civic_literacy}",
+                                                    "{sched_text}"
+                                                }
+                                            }
+                                        }
                                     }
                                 }
                                 
@@ -697,7 +1301,7 @@ pub fn GovernanceComponent() -> Element {
                                                     value: "{tax_rate}",
                                                     oninput: move |e| tax_rate.set(e.value().parse().unwrap_or(0)),
                                                 }
-                                                div { class: "text-right font-bold text-lg", "{tax_rate}%" }
+                                                div { class: "text-right font-bold text-lg", "{current_tax}%" }
                                              }
                                         }
                                     } else if proposal_type() == "DefineMinistries" {
@@ -742,6 +1346,24 @@ pub fn GovernanceComponent() -> Element {
                                                 }
                                             }
                                         }
+                                    } else if proposal_type() == "SetElectionSchedule" {
+                                        rsx! {
+                                            div { class: "mb-4 p-4 bg-[var(--bg-secondary)] rounded-lg grid gap-2",
+                                                label { class: "block text-sm font-bold", "Ministry" }
+                                                select {
+                                                    class: "w-full p-2 rounded bg-[var(--bg-primary)] border border-[var(--border-color)]",
+                                                    value: "{election_ministry}",
+                                                    oninput: move |e| election_ministry.set(e.value()),
+                                                    for m in app_state.ministries.read().iter() {
+                                                        option { value: "{m}", "{m}" }
+                                                    }
+                                                }
+                                                label { class: "block text-sm font-bold", "Candidacy Opens" }
+                                                input { class: "input", r#type: "date", value: "{election_start}", oninput: move |e| election_start.set(e.value()) }
+                                                label { class: "block text-sm font-bold", "Candidacy Closes" }
+                                                input { class: "input", r#type: "date", value: "{election_end}", oninput: move |e| election_end.set(e.value()) }
+                                            }
+                                        }
                                     } else {
                                         rsx!({})
                                     }
@@ -777,6 +1399,35 @@ pub fn GovernanceComponent() -> Element {
                                 label { class: "text-sm font-bold", "📌 Pin Proposal (Elected Officials Only)" }
                             }
 
+                            div { class: "form-group flex items-center gap-2 mt-2 p-2 rounded bg-[var(--bg-secondary)] border border-[var(--border-color)]",
+                                input {
+                                    r#type: "checkbox",
+                                    checked: "{quadratic}",
+                                    onchange: move |e| quadratic.set(e.checked()),
+                                    class: "w-4 h-4"
+                                }
+                                label { class: "text-sm font-bold", "√ Quadratic Voting (weigh votes by reputation)" }
+                            }
+
+                            if !draft_similar.is_empty() {
+                                div { class: "p-3 rounded bg-yellow-500/10 border border-yellow-500/30",
+                                    p { class: "text-sm font-bold text-yellow-400 mb-2", "⚠️ Similar proposals exist" }
+                                    for (node, score) in draft_similar.iter() {
+                                        if let DagPayload::Proposal(p) = &node.payload {
+                                            {
+                                                let percent = (score * 100.0).round() as u32;
+                                                rsx! {
+                                                    div { key: "{node.id}", class: "flex justify-between text-sm",
+                                                        span { class: "truncate", "{p.title}" }
+                                                        span { class: "text-[var(--text-muted)]", "{percent}% match" }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+
                             div { class: "flex justify-end gap-3 mt-4",
                                 button {
                                     class: "btn btn-secondary",
@@ -869,6 +1520,108 @@ pub fn GovernanceComponent() -> Element {
                 }
             }
 
+            // Candidate Detail Modal
+            if let Some(candidacy_id) = viewing_candidate() {
+                div { class: "fixed inset-0 bg-black/50 backdrop-blur-sm flex items-center justify-center z-50 p-4",
+                    div { class: "panel w-full max-w-lg max-h-[90vh] overflow-y-auto",
+                        div { class: "flex justify-between items-center mb-6",
+                            div { class: "flex items-center gap-2",
+                                h2 { class: "text-xl font-bold", "🗳️ Candidate Profile" }
+                                {
+                                    let withdrawal = app_state.candidacy_withdrawals.read().get(&candidacy_id).cloned();
+                                    let label = match withdrawal {
+                                        Some(Some(ts)) => chrono::DateTime::from_timestamp(ts as i64, 0)
+                                            .map(|dt| format!("Withdrawn {}", dt.format("%Y-%m-%d")))
+                                            .unwrap_or_else(|| "Withdrawn".to_string()),
+                                        Some(None) => "Withdrawn".to_string(),
+                                        None => String::new(),
+                                    };
+                                    rsx! {
+                                        if withdrawal.is_some() {
+                                            span { class: "badge badge-error", "{label}" }
+                                        }
+                                    }
+                                }
+                            }
+                            button { 
+                                class: "w-8 h-8 rounded-full bg-[var(--bg-secondary)] hover:bg-red-500/20 flex items-center justify-center text-[var(--text-muted)] hover:text-red-400 transition-colors text-lg font-bold",
+                                onclick: move |_| viewing_candidate.set(None),
+                                "×"
+                            }
+                        }
+                        {
+                            let details = app_state.candidate_platforms.read().get(&candidacy_id).cloned();
+                            match details {
+                                Some(details) => {
+                                    let name = details.profile.as_ref().map(|p| p.name.clone())
+                                        .unwrap_or_else(|| format!("{}...", details.node.author.get(0..8).unwrap_or(&details.node.author)));
+                                    let bio = details.profile.as_ref().map(|p| p.bio.clone()).unwrap_or_default();
+                                    let (ministry, platform_text) = match &details.node.payload {
+                                        DagPayload::Candidacy(c) => (format!("{:?}", c.ministry), c.platform.clone()),
+                                        _ => (String::new(), String::new()),
+                                    };
+                                    let score = details.reputation.as_ref().map(|r| r.score).unwrap_or(0);
+                                    let cert_names: Vec<String> = details.certifications.iter()
+                                        .filter_map(|n| match &n.payload {
+                                            DagPayload::Certification(c) => Some(c.certification_type.clone()),
+                                            _ => None,
+                                        })
+                                        .collect();
+                                    let vote_count = details.vote_history.len();
+                                    rsx! {
+                                        div { class: "grid gap-4",
+                                            div {
+                                                div { class: "flex items-center justify-between",
+                                                    h3 { class: "text-lg font-bold", "{name}" }
+                                                    span { class: "badge badge-primary", "⭐ {score}" }
+                                                }
+                                                p { class: "text-sm text-[var(--text-muted)]", "Running for {ministry}" }
+                                                if !bio.is_empty() {
+                                                    p { class: "text-sm mt-2", "{bio}" }
+                                                }
+                                            }
+                                            div {
+                                                p { class: "label mb-1", "Platform" }
+                                                p { class: "whitespace-pre-wrap", "{platform_text}" }
+                                            }
+                                            div {
+                                                p { class: "label mb-1", "Certifications" }
+                                                if cert_names.is_empty() {
+                                                    p { class: "text-sm text-[var(--text-muted)]", "None" }
+                                                }
+                                                div { class: "flex flex-wrap gap-2",
+                                                    for cert in cert_names.iter() {
+                                                        span { class: "badge badge-accent", "{cert}" }
+                                                    }
+                                                }
+                                            }
+                                            div {
+                                                p { class: "label mb-1", "Voting Record ({vote_count} votes)" }
+                                                for (vote_node, title) in details.vote_history.iter().take(5) {
+                                                    if let DagPayload::Vote(v) = &vote_node.payload {
+                                                        {
+                                                            let choice = format!("{:?}", v.vote);
+                                                            let title = title.clone().unwrap_or_else(|| "Unknown proposal".to_string());
+                                                            rsx! {
+                                                                div { key: "{vote_node.id}", class: "flex justify-between text-sm py-1",
+                                                                    span { class: "truncate", "{title}" }
+                                                                    span { class: "font-bold", "{choice}" }
+                                                                }
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                                None => rsx! { div { class: "empty-state py-4", "Loading candidate..." } },
+                            }
+                        }
+                    }
+                }
+            }
+
             // Initiate Recall Modal
             if show_recall_modal() {
                 div { class: "fixed inset-0 bg-black/50 backdrop-blur-sm flex items-center justify-center z-50 p-4",
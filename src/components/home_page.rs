@@ -1,6 +1,6 @@
 use dioxus::prelude::*;
-use crate::backend::AppCmd;
-use crate::backend::dag::{DagPayload, PostPayload, DagNode};
+use crate::backend::{AppCmd, FeedStrategy};
+use crate::backend::dag::{DagPayload, PostPayload, DagNode, SharePayload, PollPayload};
 use base64::{Engine as _, engine::general_purpose};
 
 #[component]
@@ -11,16 +11,35 @@ pub fn HomeComponent() -> Element {
     use crate::components::common::{BlobImage, StoryCircle, StoryViewer};
     
     let mut active_feed_tab = use_signal(|| "global".to_string());
+    let mut feed_strategy = app_state.feed_strategy;
+    let mut trending_window_hours = use_signal(|| 24u8);
+    let mut mentions_seen_at = app_state.mentions_seen_at;
     
-    let posts = if active_feed_tab() == "following" {
+    let posts = if active_feed_tab() == "following" || active_feed_tab() == "topics" {
         app_state.following_posts
+    } else if active_feed_tab() == "saved" {
+        app_state.bookmarks
+    } else if active_feed_tab() == "trending" {
+        app_state.trending_posts
+    } else if active_feed_tab() == "mentions" {
+        app_state.mentions
     } else {
         app_state.posts
     };
     
     let mut new_post_content = use_signal(|| "".to_string());
+    let mut new_hashtag = use_signal(|| "".to_string());
     let mut attached_cids = use_signal(|| Vec::<String>::new());
     let mut announcement = use_signal(|| false);
+    let mut quoting = use_signal(|| None::<String>);
+    let mut schedule_at = use_signal(String::new); // datetime-local value, in UTC; empty = post now
+    let mut poll_options = use_signal(|| None::<Vec<String>>); // Some while composing a poll; the post text is the question
+    let mut poll_multiple = use_signal(|| false);
+    let mut poll_days = use_signal(|| 0u64); // 0 = never closes
+    let mut editing_post = use_signal(|| None::<(String, String)>);
+    let mut context_post = use_signal(|| None::<String>);
+    let mut sharing_post = use_signal(|| None::<(String, String)>); // (post id, comment draft)
+    let is_official = !app_state.my_elected_positions.read().is_empty();
     
     let mut last_processed_blob = use_signal(|| None::<String>);
     let is_uploading_story = use_signal(|| false);
@@ -30,6 +49,17 @@ pub fn HomeComponent() -> Element {
     let mut viewed_story = use_signal(|| None::<DagNode>);
     let mut seen_stories = app_state.seen_stories;
     let user_profiles = app_state.user_profiles;
+
+    // Batch-fetch reputation badges for every visible author
+    let cmd_tx_rep = cmd_tx.clone();
+    use_effect(move || {
+        let mut peer_ids: Vec<String> = posts().iter().map(|n| n.author.clone()).collect();
+        peer_ids.sort();
+        peer_ids.dedup();
+        if !peer_ids.is_empty() {
+            let _ = cmd_tx_rep.send(AppCmd::FetchReputations { peer_ids });
+        }
+    });
     
      use_effect(move || {
         if let Some(blob_id) = app_state.last_created_blob.read().clone() {
@@ -44,29 +74,53 @@ pub fn HomeComponent() -> Element {
 
     let cmd_tx_clone = cmd_tx.clone();
     use_effect(move || {
-        let _ = cmd_tx_clone.send(AppCmd::FetchPosts);
         let _ = cmd_tx_clone.send(AppCmd::FetchStories);
         let _ = cmd_tx_clone.send(AppCmd::FetchFollowingPosts);
+        let _ = cmd_tx_clone.send(AppCmd::FetchFollowingHashtags);
+        let _ = cmd_tx_clone.send(AppCmd::FetchBookmarks);
+        let _ = cmd_tx_clone.send(AppCmd::FetchScheduledPosts);
     });
 
     let cmd_tx_feed = cmd_tx.clone();
     use_effect(move || {
         if active_feed_tab() == "following" {
             let _ = cmd_tx_feed.send(AppCmd::FetchFollowingPosts);
+        } else if active_feed_tab() == "topics" {
+            let _ = cmd_tx_feed.send(AppCmd::FetchFollowingHashtagPosts);
+        } else if active_feed_tab() == "saved" {
+            let _ = cmd_tx_feed.send(AppCmd::FetchBookmarks);
+        } else if active_feed_tab() == "trending" {
+            let _ = cmd_tx_feed.send(AppCmd::FetchTrendingPosts { window_hours: trending_window_hours(), limit: 50 });
+        } else if active_feed_tab() == "mentions" {
+            let _ = cmd_tx_feed.send(AppCmd::FetchMentions { limit: 50 });
+            mentions_seen_at.set(Some(chrono::Utc::now()));
         } else {
-            let _ = cmd_tx_feed.send(AppCmd::FetchPosts);
+            let _ = cmd_tx_feed.send(AppCmd::FetchPersonalizedFeed { strategy: feed_strategy(), limit: 50, offset: 0 });
         }
     });
 
     let on_submit = {
         let cmd_tx = cmd_tx.clone();
         move |_| {
-            if !new_post_content().is_empty() || !attached_cids().is_empty() {
+            if let Some(options) = poll_options() {
+                let closes_at = Some(poll_days())
+                    .filter(|days| *days > 0)
+                    .map(|days| (chrono::Utc::now() + chrono::Duration::days(days as i64)).timestamp() as u64);
+                let _ = cmd_tx.send(AppCmd::CreatePoll { question: new_post_content(), options, closes_at, allow_multiple: poll_multiple() });
+                new_post_content.set("".to_string());
+                poll_options.set(None);
+                poll_multiple.set(false);
+                poll_days.set(0);
+            } else if !new_post_content().is_empty() || !attached_cids().is_empty() {
+                let publish_at = chrono::NaiveDateTime::parse_from_str(&schedule_at(), "%Y-%m-%dT%H:%M").ok()
+                    .map(|dt| dt.and_utc().timestamp() as u64);
                 let cmd = AppCmd::PublishPost {
                     content: new_post_content(),
                     attachments: attached_cids().clone(),
                     geohash: None,
                     announcement: announcement(),
+                    quote_of: quoting(),
+                    publish_at,
                 };
                 if let Err(e) = cmd_tx.send(cmd) {
                     eprintln!("Failed to send PublishPost command: {:?}", e);
@@ -74,6 +128,8 @@ pub fn HomeComponent() -> Element {
                     new_post_content.set("".to_string());
                     attached_cids.set(Vec::new());
                     announcement.set(false);
+                    quoting.set(None);
+                    schedule_at.set(String::new());
                 }
             }
         }
@@ -218,11 +274,55 @@ pub fn HomeComponent() -> Element {
                 }
             }
 
+            // Post Context Modal
+            if let Some(focus_id) = context_post() {
+                div { class: "fixed inset-0 bg-black/50 backdrop-blur-sm flex items-center justify-center z-50 p-4",
+                    div { class: "panel w-full max-w-lg max-h-[90vh] overflow-y-auto",
+                        div { class: "flex justify-between items-center mb-4",
+                            h2 { class: "text-xl font-bold", "🧵 In Context" }
+                            button {
+                                class: "btn btn-sm btn-ghost",
+                                onclick: move |_| context_post.set(None),
+                                "×"
+                            }
+                        }
+                        {
+                            let context = app_state.post_context.read().clone();
+                            match context {
+                                Some((center_id, context_posts, center_index)) if center_id == focus_id => rsx! {
+                                    for (idx, ctx) in context_posts.iter().enumerate() {
+                                        if let DagPayload::Post(p) = &ctx.payload {
+                                            div {
+                                                key: "{ctx.id}",
+                                                class: if idx == center_index { "card mb-2 border-2 border-[var(--primary)]" } else { "card mb-2 opacity-70" },
+                                                div { class: "flex justify-between text-xs text-[var(--text-muted)] mb-1",
+                                                    span { "{ctx.author.get(0..12).unwrap_or(&ctx.author)}..." }
+                                                    span { "{ctx.timestamp}" }
+                                                }
+                                                p { "{p.content}" }
+                                            }
+                                        }
+                                    }
+                                },
+                                _ => rsx! { div { class: "empty-state py-4", "Loading context..." } },
+                            }
+                        }
+                    }
+                }
+            }
+
             // Compose area
             div { class: "panel mb-8",
                 div { class: "panel-header",
                     h2 { class: "panel-title", "Share something" }
                 }
+
+                if let Some(quoted_id) = quoting() {
+                    div { class: "flex items-center justify-between mb-2 p-2 rounded bg-[var(--bg-secondary)] text-sm",
+                        span { "🔁 Quoting {quoted_id.get(0..12).unwrap_or(&quoted_id)}..." }
+                        button { class: "btn btn-secondary btn-sm", onclick: move |_| quoting.set(None), "Cancel" }
+                    }
+                }
                 
                 div { class: "form-group",
                     textarea {
@@ -234,14 +334,16 @@ pub fn HomeComponent() -> Element {
                     }
                 }
                 
-                div { class: "form-group flex items-center gap-2 mb-4 p-2 rounded bg-[var(--bg-secondary)] border border-[var(--border-color)]",
-                    input {
-                        r#type: "checkbox",
-                        checked: "{announcement}",
-                        onchange: move |e| announcement.set(e.checked()),
-                        class: "w-4 h-4 cursor-pointer"
+                if is_official {
+                    div { class: "form-group flex items-center gap-2 mb-4 p-2 rounded bg-[var(--bg-secondary)] border border-[var(--border-color)]",
+                        input {
+                            r#type: "checkbox",
+                            checked: "{announcement}",
+                            onchange: move |e| announcement.set(e.checked()),
+                            class: "w-4 h-4 cursor-pointer"
+                        }
+                        label { class: "text-sm font-bold cursor-pointer", onclick: move |_| announcement.set(!announcement()), "📢 Official Announcement (Elected Officials Only)" }
                     }
-                    label { class: "text-sm font-bold cursor-pointer", onclick: move |_| announcement.set(!announcement()), "📢 Official Announcement (Elected Officials Only)" }
                 }
                 
                 if !attached_cids().is_empty() {
@@ -261,22 +363,113 @@ pub fn HomeComponent() -> Element {
                     }
                 }
 
+                if let Some(options) = poll_options() {
+                    div { class: "form-group p-2 rounded bg-[var(--bg-secondary)] border border-[var(--border-color)]",
+                        for (idx, option) in options.iter().enumerate() {
+                            input {
+                                key: "{idx}",
+                                class: "input input-sm mb-1",
+                                placeholder: "Option {idx + 1}",
+                                value: "{option}",
+                                oninput: move |e| {
+                                    if let Some(options) = poll_options.write().as_mut() {
+                                        options[idx] = e.value();
+                                    }
+                                }
+                            }
+                        }
+                        div { class: "flex flex-wrap items-center gap-2 mt-1",
+                            button {
+                                class: "btn btn-xs btn-ghost",
+                                onclick: move |_| {
+                                    if let Some(options) = poll_options.write().as_mut() {
+                                        options.push(String::new());
+                                    }
+                                },
+                                "+ Option"
+                            }
+                            label { class: "text-sm flex items-center gap-1",
+                                input {
+                                    r#type: "checkbox",
+                                    checked: poll_multiple(),
+                                    onchange: move |e| poll_multiple.set(e.checked())
+                                }
+                                "Allow several choices"
+                            }
+                            select {
+                                class: "input input-sm w-auto",
+                                onchange: move |e| poll_days.set(e.value().parse().unwrap_or(0)),
+                                option { value: "0", selected: poll_days() == 0, "Never closes" }
+                                option { value: "1", selected: poll_days() == 1, "Closes in 1 day" }
+                                option { value: "3", selected: poll_days() == 3, "Closes in 3 days" }
+                                option { value: "7", selected: poll_days() == 7, "Closes in 7 days" }
+                            }
+                        }
+                    }
+                }
+
                 div { class: "flex justify-between items-center pt-4 border-t border-[var(--border-subtle)]",
-                    div { class: "relative",
-                        button { class: "btn btn-secondary btn-sm",
-                            "📷 Add Image"
+                    div { class: "flex gap-2",
+                        div { class: "relative",
+                            button { class: "btn btn-secondary btn-sm",
+                                "📷 Add Image"
+                            }
+                            input {
+                                class: "absolute inset-0 w-full h-full opacity-0 cursor-pointer",
+                                r#type: "file",
+                                accept: "image/*",
+                                onchange: upload_file
+                            }
                         }
+                        button {
+                            class: "btn btn-secondary btn-sm",
+                            onclick: move |_| {
+                                let composing = poll_options().is_some();
+                                poll_options.set(if composing { None } else { Some(vec![String::new(), String::new()]) });
+                            },
+                            if poll_options().is_some() { "Remove Poll" } else { "📊 Poll" }
+                        }
+                    }
+                    div { class: "flex items-center gap-2",
                         input {
-                            class: "absolute inset-0 w-full h-full opacity-0 cursor-pointer",
-                            r#type: "file",
-                            accept: "image/*",
-                            onchange: upload_file
+                            class: "input input-sm",
+                            r#type: "datetime-local",
+                            title: "Schedule for later (UTC)",
+                            value: "{schedule_at}",
+                            oninput: move |e| schedule_at.set(e.value())
+                        }
+                        button {
+                            class: "btn btn-primary",
+                            onclick: on_submit,
+                            if poll_options().is_some() { "Create Poll" } else if schedule_at().is_empty() { "Post" } else { "Schedule" }
                         }
                     }
-                    button {
-                        class: "btn btn-primary",
-                        onclick: on_submit,
-                        "Post"
+                }
+
+                if !app_state.scheduled_posts.read().is_empty() {
+                    div { class: "mt-4 pt-4 border-t border-[var(--border-subtle)]",
+                        p { class: "text-sm font-bold mb-2", "🕒 Scheduled" }
+                        for node in app_state.scheduled_posts.read().clone() {
+                            if let DagPayload::Post(PostPayload { content, publish_at: Some(publish_at), .. }) = &node.payload {
+                                div { class: "flex justify-between items-center gap-2 text-sm mb-1", key: "{node.id}",
+                                    span { class: "truncate", "{content}" }
+                                    span { class: "text-xs text-[var(--text-muted)] whitespace-nowrap",
+                                        {chrono::DateTime::from_timestamp(*publish_at as i64, 0).map(|t| t.format("%Y-%m-%d %H:%M UTC").to_string()).unwrap_or_default()}
+                                    }
+                                    button {
+                                        class: "btn btn-xs btn-ghost",
+                                        onclick: {
+                                            let cmd_tx = cmd_tx.clone();
+                                            let cid = node.id.clone();
+                                            move |_| {
+                                                let _ = cmd_tx.send(AppCmd::CancelScheduledPost { cid: cid.clone() });
+                                            }
+                                        },
+                                        "Cancel"
+                                    }
+                                }
+                            }
+                        }
                     }
                 }
             }
@@ -287,16 +480,155 @@ pub fn HomeComponent() -> Element {
                     button { 
                         class: if active_feed_tab() == "global" { "btn btn-sm btn-primary" } else { "btn btn-sm btn-ghost" },
                         onclick: move |_| active_feed_tab.set("global".to_string()),
-                        "Global"
+                        "Recent"
+                    }
+                    button { 
+                        class: if active_feed_tab() == "trending" { "btn btn-sm btn-primary" } else { "btn btn-sm btn-ghost" },
+                        onclick: move |_| active_feed_tab.set("trending".to_string()),
+                        "Trending"
                     }
                     button { 
                         class: if active_feed_tab() == "following" { "btn btn-sm btn-primary" } else { "btn btn-sm btn-ghost" },
                         onclick: move |_| active_feed_tab.set("following".to_string()),
                         "Following"
                     }
+                    button { 
+                        class: if active_feed_tab() == "topics" { "btn btn-sm btn-primary" } else { "btn btn-sm btn-ghost" },
+                        onclick: move |_| active_feed_tab.set("topics".to_string()),
+                        "Topics"
+                    }
+                    button { 
+                        class: if active_feed_tab() == "saved" { "btn btn-sm btn-primary" } else { "btn btn-sm btn-ghost" },
+                        onclick: move |_| active_feed_tab.set("saved".to_string()),
+                        "Saved"
+                    }
+                    button { 
+                        class: if active_feed_tab() == "mentions" { "btn btn-sm btn-primary" } else { "btn btn-sm btn-ghost" },
+                        onclick: move |_| active_feed_tab.set("mentions".to_string()),
+                        "Mentions"
+                    }
+                }
+
+                if active_feed_tab() == "global" {
+                    div { class: "p-4 border-b border-[var(--border-color)]",
+                        div { class: "flex flex-wrap gap-2 mb-2",
+                            for strategy in FeedStrategy::ALL {
+                                button {
+                                    key: "{strategy.label()}",
+                                    class: if feed_strategy() == strategy { "btn btn-xs btn-secondary" } else { "btn btn-xs btn-ghost" },
+                                    onclick: move |_| feed_strategy.set(strategy),
+                                    "{strategy.label()}"
+                                }
+                            }
+                        }
+                        p { class: "text-xs text-[var(--text-muted)]", "How this feed is ordered: {feed_strategy().description()}" }
+                    }
+                }
+
+                if active_feed_tab() == "trending" {
+                    div { class: "p-4 border-b border-[var(--border-color)]",
+                        div { class: "flex flex-wrap gap-2 mb-2",
+                            for (hours, label) in [(6u8, "6 hours"), (24, "24 hours"), (168, "7 days")] {
+                                button {
+                                    key: "{hours}",
+                                    class: if trending_window_hours() == hours { "btn btn-xs btn-secondary" } else { "btn btn-xs btn-ghost" },
+                                    onclick: move |_| trending_window_hours.set(hours),
+                                    "{label}"
+                                }
+                            }
+                        }
+                        p { class: "text-xs text-[var(--text-muted)]", "Score = 2 × likes + comments + quotes, for posts from the last {trending_window_hours()} hours." }
+                    }
+                }
+
+                if active_feed_tab() == "topics" {
+                    div { class: "p-4 border-b border-[var(--border-color)]",
+                        div { class: "flex flex-wrap gap-2 mb-3",
+                            if app_state.following_hashtags.read().is_empty() {
+                                span { class: "text-sm text-[var(--text-muted)]", "Follow a hashtag to build your topic feed." }
+                            }
+                            for tag in app_state.following_hashtags.read().iter() {
+                                {
+                                    let tag = tag.clone();
+                                    let cmd_tx = cmd_tx.clone();
+                                    rsx! {
+                                        span { key: "{tag}", class: "badge badge-primary flex items-center gap-1",
+                                            "#{tag}"
+                                            button {
+                                                class: "ml-1",
+                                                title: "Unfollow",
+                                                onclick: move |_| {
+                                                    let _ = cmd_tx.send(AppCmd::FollowHashtag { tag: tag.clone(), follow: false });
+                                                    let _ = cmd_tx.send(AppCmd::FetchFollowingHashtagPosts);
+                                                },
+                                                "✕"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        div { class: "flex gap-2",
+                            input {
+                                class: "input flex-1",
+                                placeholder: "#topic",
+                                value: "{new_hashtag}",
+                                oninput: move |e| new_hashtag.set(e.value()),
+                            }
+                            {
+                                let cmd_tx = cmd_tx.clone();
+                                rsx! {
+                                    button {
+                                        class: "btn btn-primary btn-sm",
+                                        disabled: new_hashtag().trim().trim_start_matches('#').is_empty(),
+                                        onclick: move |_| {
+                                            let _ = cmd_tx.send(AppCmd::FollowHashtag { tag: new_hashtag(), follow: true });
+                                            let _ = cmd_tx.send(AppCmd::FetchFollowingHashtagPosts);
+                                            new_hashtag.set("".to_string());
+                                        },
+                                        "Follow"
+                                    }
+                                }
+                            }
+                        }
+                    }
                 }
                 
-                if posts().is_empty() {
+                if posts().is_empty() && active_feed_tab() == "following" && !app_state.follow_suggestions.read().is_empty() {
+                    div { class: "p-4",
+                        h3 { class: "font-bold mb-3", "👋 People You May Know" }
+                        for (peer_id, profile, reason) in app_state.follow_suggestions.read().iter() {
+                            {
+                                let name = profile.as_ref().map(|p| p.name.clone()).unwrap_or_else(|| format!("{}...", peer_id.get(0..12).unwrap_or(peer_id)));
+                                let target = peer_id.clone();
+                                let local_id = app_state.local_peer_id.read().clone();
+                                let cmd_tx = cmd_tx.clone();
+                                let mut suggestions = app_state.follow_suggestions;
+                                rsx! {
+                                    div { key: "{peer_id}", class: "list-item",
+                                        div { class: "avatar avatar-sm",
+                                            "{peer_id.get(0..2).unwrap_or(\"??\")}"
+                                        }
+                                        div { class: "list-item-content",
+                                            p { class: "list-item-title", "{name}" }
+                                            p { class: "text-xs text-[var(--text-muted)]", "{reason}" }
+                                        }
+                                        button {
+                                            class: "btn btn-primary btn-sm",
+                                            onclick: move |_| {
+                                                let _ = cmd_tx.send(AppCmd::FollowUser { target: target.clone(), follow: true });
+                                                let _ = cmd_tx.send(AppCmd::FetchFollowing { target: local_id.clone() });
+                                                let _ = cmd_tx.send(AppCmd::FetchFollowingPosts);
+                                                suggestions.write().retain(|(id, _, _)| *id != target);
+                                            },
+                                            "Follow"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                } else if posts().is_empty() {
                     div { class: "empty-state",
                         div { class: "empty-state-icon", "📝" }
                         p { class: "empty-state-title", "No posts yet" }
@@ -304,12 +636,14 @@ pub fn HomeComponent() -> Element {
                     }
                 } else {
                     for node in posts() {
-                        if let DagPayload::Post(PostPayload { content, attachments, announcement, .. }) = &node.payload {
+                        if let DagPayload::Post(PostPayload { content, attachments, announcement, quote_of, .. }) = &node.payload {
                             {
                                 let post_id = node.id.clone();
                                 let app_state = app_state.clone();
                                 let cmd_tx = cmd_tx.clone();
                                 let is_announcement = *announcement;
+                                let is_own_post = node.author == local_peer_id_val;
+                                let edit_draft = editing_post().filter(|(id, _)| id == &post_id).map(|(_, draft)| draft);
                                 
                                 let mut show_reply = use_signal(|| false);
                                 let mut reply_content = use_signal(|| "".to_string());
@@ -324,6 +658,19 @@ pub fn HomeComponent() -> Element {
                                 let likes_map = app_state.likes.read();
                                 let (like_count, is_liked_by_me) = likes_map.get(&post_id).cloned().unwrap_or((0, false));
                                 drop(likes_map);
+                                let reactions = app_state.reactions.read().get(&post_id).cloned().unwrap_or_default();
+                                let is_bookmarked = app_state.bookmarks.read().iter().any(|b| b.id == post_id);
+
+                                let engagement_score = app_state.engagement_scores.read().get(&post_id).copied().unwrap_or(0);
+                                let quote_count = app_state.cross_post_history.read().get(&post_id).map(|r| r.len()).unwrap_or(0);
+                                let share_count = app_state.shares.read().get(&post_id).copied().unwrap_or(0);
+                                let share_draft = sharing_post().filter(|(id, _)| id == &post_id).map(|(_, draft)| draft);
+                                let author_score = app_state.author_reputations.read().get(&node.author).map(|r| r.score);
+                                let author_offices = crate::components::common::offices_held(&app_state.active_officials.read(), &node.author)
+                                    .iter()
+                                    .map(|ministry| format!("Minister of {}", ministry))
+                                    .collect::<Vec<_>>()
+                                    .join(", ");
                                 
                                 // Fetch on mount
                                 use_effect({
@@ -332,6 +679,10 @@ pub fn HomeComponent() -> Element {
                                     move || {
                                         let _ = cmd_tx.send(AppCmd::FetchComments { parent_id: pid.clone() });
                                         let _ = cmd_tx.send(AppCmd::FetchLikes { target_id: pid.clone() });
+                                        let _ = cmd_tx.send(AppCmd::FetchReactions { target_id: pid.clone() });
+                                        let _ = cmd_tx.send(AppCmd::FetchPostEngagementScore { post_id: pid.clone() });
+                                        let _ = cmd_tx.send(AppCmd::FetchCrossPostHistory { post_id: pid.clone() });
+                                        let _ = cmd_tx.send(AppCmd::FetchShares { cid: pid.clone() });
                                     }
                                 });
 
@@ -347,6 +698,9 @@ pub fn HomeComponent() -> Element {
                                         if is_announcement {
                                             div { class: "bg-yellow-500 text-black text-xs font-bold px-3 py-1 -mt-4 -ml-4 -mr-4 mb-4 rounded-t-lg flex items-center gap-2",
                                                 span { "📢 OFFICIAL ANNOUNCEMENT" }
+                                                if !author_offices.is_empty() {
+                                                    span { class: "font-normal", "· {author_offices}" }
+                                                }
                                             }
                                         }
                                         
@@ -366,11 +720,64 @@ pub fn HomeComponent() -> Element {
                                                     },
                                                     "{node.author.get(0..12).unwrap_or(&node.author)}..."
                                                 }
+                                                if let Some(score) = author_score {
+                                                    span { class: "badge badge-primary ml-2", title: "Reputation", "⭐ {score}" }
+                                                }
                                             }
                                             span { class: "post-time", "{node.timestamp}" }
                                         }
                                         
-                                        p { class: "post-content", "{content}" }
+                                        if let Some(original) = quote_of {
+                                            button {
+                                                class: "text-xs text-[var(--text-muted)] mb-1 hover:underline bg-transparent border-none p-0 text-left cursor-pointer",
+                                                onclick: {
+                                                    let original = original.clone();
+                                                    let cmd_tx = cmd_tx.clone();
+                                                    move |_| {
+                                                        let _ = cmd_tx.send(AppCmd::FetchPostContextWindow { post_id: original.clone(), window: 3, same_author: false });
+                                                        context_post.set(Some(original.clone()));
+                                                    }
+                                                },
+                                                "🔁 Quoting {original.get(0..12).unwrap_or(original)}..."
+                                            }
+                                        }
+                                        if let Some(draft) = edit_draft {
+                                            div { class: "mb-2",
+                                                textarea {
+                                                    class: "input w-full",
+                                                    rows: "3",
+                                                    value: "{draft}",
+                                                    oninput: {
+                                                        let pid = post_id.clone();
+                                                        move |e: FormEvent| editing_post.set(Some((pid.clone(), e.value())))
+                                                    }
+                                                }
+                                                div { class: "flex gap-2 mt-2",
+                                                    button {
+                                                        class: "btn btn-primary btn-sm",
+                                                        disabled: draft.trim().is_empty(),
+                                                        onclick: {
+                                                            let cmd_tx = cmd_tx.clone();
+                                                            let pid = post_id.clone();
+                                                            let attachments = attachments.clone();
+                                                            let draft = draft.clone();
+                                                            move |_| {
+                                                                let _ = cmd_tx.send(AppCmd::EditPost { original_cid: pid.clone(), new_content: draft.clone(), attachments: attachments.clone() });
+                                                                editing_post.set(None);
+                                                            }
+                                                        },
+                                                        "Save"
+                                                    }
+                                                    button {
+                                                        class: "btn btn-secondary btn-sm",
+                                                        onclick: move |_| editing_post.set(None),
+                                                        "Cancel"
+                                                    }
+                                                }
+                                            }
+                                        } else {
+                                            p { class: "post-content", "{content}" }
+                                        }
                                         
                                         if !attachments.is_empty() {
                                             div { class: "post-attachments",
@@ -402,8 +809,126 @@ pub fn HomeComponent() -> Element {
                                                 span { class: "icon", "💬" }
                                                 span { class: "count", "{comment_count}" }
                                             }
+                                            button {
+                                                class: "post-action-btn",
+                                                title: "Quote this post",
+                                                onclick: {
+                                                    let pid = node.id.clone();
+                                                    move |_| quoting.set(Some(pid.clone()))
+                                                },
+                                                span { class: "icon", "🔁" }
+                                                span { class: "count", "{quote_count}" }
+                                            }
+                                            button {
+                                                class: "post-action-btn",
+                                                title: "Share with your followers",
+                                                onclick: {
+                                                    let pid = node.id.clone();
+                                                    move |_| sharing_post.set(Some((pid.clone(), String::new())))
+                                                },
+                                                span { class: "icon", "📣" }
+                                                span { class: "count", "{share_count}" }
+                                            }
+                                            button {
+                                                class: "post-action-btn",
+                                                title: if is_bookmarked { "Remove bookmark" } else { "Save for later" },
+                                                onclick: {
+                                                    let cmd_tx = cmd_tx.clone();
+                                                    let pid = node.id.clone();
+                                                    move |_| {
+                                                        let _ = cmd_tx.send(AppCmd::BookmarkPost { cid: pid.clone(), remove: is_bookmarked });
+                                                    }
+                                                },
+                                                span { class: "icon", if is_bookmarked { "🔖" } else { "📑" } }
+                                            }
+                                            if is_own_post {
+                                                button {
+                                                    class: "post-action-btn",
+                                                    title: "Edit this post",
+                                                    onclick: {
+                                                        let pid = post_id.clone();
+                                                        let content = content.clone();
+                                                        move |_| editing_post.set(Some((pid.clone(), content.clone())))
+                                                    },
+                                                    span { class: "icon", "✏️" }
+                                                }
+                                                button {
+                                                    class: "post-action-btn",
+                                                    title: "Delete this post",
+                                                    onclick: {
+                                                        let cmd_tx = cmd_tx.clone();
+                                                        let pid = post_id.clone();
+                                                        move |_| {
+                                                            let _ = cmd_tx.send(AppCmd::DeletePost { cid: pid.clone(), reason: None });
+                                                        }
+                                                    },
+                                                    span { class: "icon", "🗑️" }
+                                                }
+                                            }
+                                            if engagement_score > 0 {
+                                                span { class: "post-action-btn", title: "Engagement score",
+                                                    span { class: "icon", "🔥" }
+                                                    span { class: "count", "{engagement_score}" }
+                                                }
+                                            }
                                         }
                                         
+                                        div { class: "flex flex-wrap gap-1 px-2",
+                                            for emoji in crate::backend::dag::ReactionPayload::ALLOWED {
+                                                {
+                                                    let (count, reacted) = reactions.get(emoji).copied().unwrap_or((0, false));
+                                                    let cmd_tx = cmd_tx.clone();
+                                                    let pid = node.id.clone();
+                                                    rsx! {
+                                                        button {
+                                                            key: "{emoji}",
+                                                            class: if reacted { "btn btn-xs btn-secondary" } else { "btn btn-xs btn-ghost" },
+                                                            onclick: move |_| {
+                                                                let _ = cmd_tx.send(AppCmd::ReactToPost { target_id: pid.clone(), emoji: emoji.to_string(), remove: reacted });
+                                                            },
+                                                            "{emoji}"
+                                                            if count > 0 {
+                                                                span { class: "count ml-1", "{count}" }
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+
+                                        if let Some(draft) = share_draft {
+                                            div { class: "post-comment-input",
+                                                input {
+                                                    class: "input",
+                                                    placeholder: "Add a comment (optional)...",
+                                                    value: "{draft}",
+                                                    oninput: {
+                                                        let pid = post_id.clone();
+                                                        move |e: FormEvent| sharing_post.set(Some((pid.clone(), e.value())))
+                                                    }
+                                                }
+                                                button {
+                                                    class: "btn btn-primary btn-sm",
+                                                    onclick: {
+                                                        let cmd_tx = cmd_tx.clone();
+                                                        let pid = post_id.clone();
+                                                        let draft = draft.clone();
+                                                        move |_| {
+                                                            let comment = Some(draft.clone()).filter(|c| !c.trim().is_empty());
+                                                            let _ = cmd_tx.send(AppCmd::SharePost { original_cid: pid.clone(), comment });
+                                                            sharing_post.set(None);
+                                                        }
+                                                    },
+                                                    "Share"
+                                                }
+                                                button {
+                                                    class: "btn btn-secondary btn-sm",
+                                                    onclick: move |_| sharing_post.set(None),
+                                                    "Cancel"
+                                                }
+                                            }
+                                        }
+
                                         // Comments section
                                         if !comments.is_empty() || show_reply() {
                                             div { class: "post-comments",
@@ -454,6 +979,122 @@ pub fn HomeComponent() -> Element {
                                     }
                                 }
                             }
+                        } else if let DagPayload::Share(SharePayload { original_cid, comment }) = &node.payload {
+                            {
+                                let profiles = user_profiles.read();
+                                let name_of = |peer: &str| profiles.get(peer).map(|p| p.name.clone()).unwrap_or_else(|| format!("{}...", peer.get(0..12).unwrap_or(peer)));
+                                let sharer = name_of(&node.author);
+                                let original = posts().into_iter().find(|p| &p.id == original_cid);
+                                let original_author = original.as_ref().map(|o| name_of(&o.author));
+                                drop(profiles);
+
+                                rsx! {
+                                    div { class: "post", key: "{node.id}",
+                                        div { class: "text-xs text-[var(--text-muted)] mb-2 flex justify-between",
+                                            span { "📣 {sharer} shared" }
+                                            span { class: "post-time", "{node.timestamp}" }
+                                        }
+                                        if let Some(comment) = comment {
+                                            p { class: "post-content mb-2", "{comment}" }
+                                        }
+                                        div { class: "card",
+                                            if let (Some(DagNode { payload: DagPayload::Post(post), .. }), Some(author)) = (&original, &original_author) {
+                                                p { class: "post-author mb-1", "{author}" }
+                                                p { class: "post-content", "{post.content}" }
+                                            } else {
+                                                button {
+                                                    class: "text-sm text-[var(--text-muted)] hover:underline bg-transparent border-none p-0 text-left cursor-pointer",
+                                                    onclick: {
+                                                        let original = original_cid.clone();
+                                                        let cmd_tx = cmd_tx.clone();
+                                                        move |_| {
+                                                            let _ = cmd_tx.send(AppCmd::FetchPostContextWindow { post_id: original.clone(), window: 3, same_author: false });
+                                                            context_post.set(Some(original.clone()));
+                                                        }
+                                                    },
+                                                    "View original post {original_cid.get(0..12).unwrap_or(original_cid)}..."
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        } else if let DagPayload::Poll(PollPayload { question, options, allow_multiple, .. }) = &node.payload {
+                            {
+                                let poll_id = node.id.clone();
+                                let allow_multiple = *allow_multiple;
+                                let (tally, closed) = app_state.poll_results.read().get(&poll_id).cloned()
+                                    .unwrap_or_else(|| (options.iter().map(|o| (o.clone(), 0)).collect(), false));
+                                let total: usize = tally.iter().map(|(_, count)| count).sum();
+                                let mut selected = use_signal(Vec::<usize>::new);
+
+                                use_effect({
+                                    let cmd_tx = cmd_tx.clone();
+                                    let pid = poll_id.clone();
+                                    move || {
+                                        let _ = cmd_tx.send(AppCmd::FetchPollResults { poll_id: pid.clone() });
+                                    }
+                                });
+
+                                rsx! {
+                                    div { class: "post", key: "{node.id}",
+                                        div { class: "post-header",
+                                            div { class: "avatar", "{node.author.get(0..2).unwrap_or(\"??\")}" }
+                                            div { class: "flex-1 post-author", "📊 {node.author.get(0..12).unwrap_or(&node.author)}... asks" }
+                                            span { class: "post-time", "{node.timestamp}" }
+                                        }
+                                        p { class: "post-content font-bold", "{question}" }
+                                        for (idx, (option, count)) in tally.into_iter().enumerate() {
+                                            button {
+                                                key: "{idx}",
+                                                class: if selected().contains(&idx) { "btn btn-sm btn-secondary w-full mb-1 flex justify-between" } else { "btn btn-sm btn-ghost w-full mb-1 flex justify-between" },
+                                                disabled: closed,
+                                                onclick: {
+                                                    let cmd_tx = cmd_tx.clone();
+                                                    let pid = poll_id.clone();
+                                                    move |_| {
+                                                        if allow_multiple {
+                                                            let mut choices = selected.write();
+                                                            if let Some(pos) = choices.iter().position(|c| *c == idx) {
+                                                                choices.remove(pos);
+                                                            } else {
+                                                                choices.push(idx);
+                                                            }
+                                                        } else {
+                                                            selected.set(vec![idx]);
+                                                            let _ = cmd_tx.send(AppCmd::VotePoll { poll_id: pid.clone(), choices: vec![idx] });
+                                                        }
+                                                    }
+                                                },
+                                                span { "{option}" }
+                                                span { class: "count", "{count}" }
+                                            }
+                                        }
+                                        div { class: "flex justify-between items-center mt-1",
+                                            span { class: "text-xs text-[var(--text-muted)]",
+                                                if closed { "{total} votes · Closed" } else { "{total} votes" }
+                                            }
+                                            if allow_multiple && !closed {
+                                                button {
+                                                    class: "btn btn-primary btn-sm",
+                                                    disabled: selected().is_empty(),
+                                                    onclick: {
+                                                        let cmd_tx = cmd_tx.clone();
+                                                        let pid = poll_id.clone();
+                                                        move |_| {
+                                                            let _ = cmd_tx.send(AppCmd::VotePoll { poll_id: pid.clone(), choices: selected() });
+                                                        }
+                                                    },
+                                                    "Vote"
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        } else if let DagPayload::Comment(_) = &node.payload {
+                            // Only the Mentions tab lists comments
+                            CommentComponent { key: "{node.id}", node: node.clone() }
                         }
                     }
                 }
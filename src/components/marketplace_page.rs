@@ -14,14 +14,28 @@ pub fn MarketplaceComponent() -> Element {
     let mut category = use_signal(|| "".to_string());
     let mut filter_certified_only = use_signal(|| false);
     let mut filter_local = use_signal(|| false);
+    let mut filter_category = use_signal(String::new);
+    let mut filter_price_min = use_signal(String::new);
+    let mut filter_price_max = use_signal(String::new);
     let mut tag_location = use_signal(|| true); // Default to tagging with location
 
     // Fetch listings on mount
     let cmd_tx_effect = cmd_tx.clone();
     let current_geohash = app_state.geohash.read().clone();
     use_effect(move || {
-        if filter_local() && current_geohash != "Global" {
-            let prefix = current_geohash.chars().take(4).collect::<String>();
+        let local_prefix = if filter_local() && current_geohash != "Global" {
+            Some(current_geohash.chars().take(4).collect::<String>())
+        } else {
+            None
+        };
+        if !filter_category().is_empty() {
+            let _ = cmd_tx_effect.send(AppCmd::FetchLocalListingsByCategory {
+                geohash_prefix: local_prefix.unwrap_or_default(),
+                category: filter_category(),
+                price_min: filter_price_min().parse().ok(),
+                price_max: filter_price_max().parse().ok(),
+            });
+        } else if let Some(prefix) = local_prefix {
             let _ = cmd_tx_effect.send(AppCmd::FetchLocalListings { geohash_prefix: prefix });
         } else {
             let _ = cmd_tx_effect.send(AppCmd::FetchListings);
@@ -72,6 +86,16 @@ pub fn MarketplaceComponent() -> Element {
 
     let my_id = app_state.local_peer_id.read().clone();
 
+    // Fetch sales history whenever the visible listings change
+    let cmd_tx_buyers = cmd_tx.clone();
+    use_effect(move || {
+        let all_listings = app_state.listings.read();
+        let local_listings = app_state.local_listings.read();
+        for node in all_listings.iter().chain(local_listings.iter()) {
+            let _ = cmd_tx_buyers.send(AppCmd::FetchListingBuyerHistory { listing_id: node.id.clone() });
+        }
+    });
+
     // Use absolute path to Route
     use crate::Route;
 
@@ -154,6 +178,30 @@ pub fn MarketplaceComponent() -> Element {
                 }
             }
 
+            // Category & price filters
+            div { class: "panel mb-6 flex flex-col md:flex-row gap-2",
+                input {
+                    class: "input flex-1",
+                    placeholder: "Category (e.g. Electronics)",
+                    value: "{filter_category}",
+                    oninput: move |e| filter_category.set(e.value())
+                }
+                input {
+                    class: "input w-32",
+                    r#type: "number",
+                    placeholder: "Min price",
+                    value: "{filter_price_min}",
+                    oninput: move |e| filter_price_min.set(e.value())
+                }
+                input {
+                    class: "input w-32",
+                    r#type: "number",
+                    placeholder: "Max price",
+                    value: "{filter_price_max}",
+                    oninput: move |e| filter_price_max.set(e.value())
+                }
+            }
+
             // Create listing form
             if show_create_form() {
                 div { class: "panel mb-6",
@@ -212,7 +260,7 @@ pub fn MarketplaceComponent() -> Element {
             {
                 let all_listings = app_state.listings.read();
                 let local_listings = app_state.local_listings.read();
-                let current_listings = if filter_local() { &*local_listings } else { &*all_listings };
+                let current_listings = if filter_local() || !filter_category().is_empty() { &*local_listings } else { &*all_listings };
 
                 if current_listings.is_empty() {
                     rsx! {
@@ -239,6 +287,7 @@ pub fn MarketplaceComponent() -> Element {
                                         let cmd_tx_status = cmd_tx.clone();
                                         let cmd_tx_cancel = cmd_tx.clone();
                                         let listing_id_cancel = listing_id.clone();
+                                        let (purchase_count, buyers) = app_state.listing_buyers.read().get(&listing_id).cloned().unwrap_or_default();
                                         
                                         rsx! {
                                             div {
@@ -264,6 +313,27 @@ pub fn MarketplaceComponent() -> Element {
                                                     }
 
                                                     p { class: "text-[var(--text-secondary)] text-sm mb-4 line-clamp-2", "{item_desc}" }
+
+                                                    if purchase_count > 0 {
+                                                        p { class: "text-xs text-[var(--text-muted)] mb-2",
+                                                            if purchase_count == 1 { "🛒 Purchased once" } else { "🛒 Purchased {purchase_count} times" }
+                                                        }
+                                                    }
+                                                    if is_my_listing && !buyers.is_empty() {
+                                                        div { class: "mb-4 text-xs",
+                                                            for (buyer, amount, ts) in buyers.iter() {
+                                                                {
+                                                                    let when = chrono::DateTime::from_timestamp(*ts as i64, 0).map(|d| d.format("%Y-%m-%d %H:%M").to_string()).unwrap_or_default();
+                                                                    rsx! {
+                                                                        div { class: "flex justify-between py-1 border-b border-[var(--border-color)]",
+                                                                            span { "{buyer.get(0..12).unwrap_or(buyer)}..." }
+                                                                            span { "{amount} SUPER · {when}" }
+                                                                        }
+                                                                    }
+                                                                }
+                                                            }
+                                                        }
+                                                    }
                                                     
                                                     div { class: "flex justify-between items-center pt-4 border-t border-[var(--border-color)]",
                                                         div {
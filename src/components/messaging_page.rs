@@ -1,5 +1,5 @@
 use dioxus::prelude::*;
-use crate::backend::{AppCmd, dag::DagPayload};
+use crate::backend::{AppCmd, dag::{DagPayload, GroupActivityEvent}};
 use crate::components::AppState;
 use tokio::sync::mpsc::UnboundedSender;
 use aes_gcm::{Aes256Gcm, KeyInit, aead::Aead, aead::AeadCore};
@@ -7,6 +7,17 @@ use aes_gcm::{Aes256Gcm, KeyInit, aead::Aead, aead::AeadCore};
 use rand::rngs::OsRng;
 use base64::{Engine as _, engine::general_purpose};
 
+/// Everyone in a group, including members who joined by invitation
+fn group_recipients(app_state: &AppState, group_id: &str) -> Vec<String> {
+    if let Some(members) = app_state.group_members.read().get(group_id) {
+        return members.iter().map(|(m, _)| m.clone()).collect();
+    }
+    match app_state.groups.read().iter().find(|n| n.id == group_id).map(|n| &n.payload) {
+        Some(DagPayload::Group(g)) => g.members.clone(),
+        _ => Vec::new(),
+    }
+}
+
 #[component]
 pub fn MessagingComponent() -> Element {
     let app_state = use_context::<AppState>();
@@ -24,6 +35,14 @@ pub fn MessagingComponent() -> Element {
     let mut new_group_name = use_signal(|| String::new());
     let mut selected_peers_for_group = use_signal(|| std::collections::HashSet::<String>::new());
     
+    // Group invite form
+    let mut invite_peer_id = use_signal(String::new);
+    let mut show_policy = use_signal(|| false);
+    let mut policy_draft = use_signal(crate::backend::dag::GroupPolicy::default);
+    let mut policy_max_members = use_signal(String::new);
+    let mut policy_min_reputation = use_signal(String::new);
+    let mut moderated_member = use_signal(|| Option::<String>::None);
+
     // New Chat Form - direct peer ID input
     let mut new_chat_peer_id = use_signal(|| String::new());
 
@@ -40,6 +59,27 @@ pub fn MessagingComponent() -> Element {
     let target = target_peer.read().clone();
     let current_group = active_group.read().clone();
     let local_id = app_state.local_peer_id.read().clone();
+    let group_activity = current_group.as_ref()
+        .and_then(|gid| app_state.group_activity.read().get(gid).cloned())
+        .unwrap_or_default();
+    let invitations = app_state.group_invitations.read().clone();
+    let is_group_owner = current_group.as_ref()
+        .and_then(|gid| app_state.groups.read().iter().find(|n| n.id == *gid).cloned())
+        .is_some_and(|n| matches!(&n.payload, DagPayload::Group(g) if g.owner == local_id));
+    let group_leaderboard = current_group.as_ref()
+        .and_then(|gid| app_state.group_leaderboards.read().get(gid).cloned())
+        .unwrap_or_default();
+    let moderated_members: Vec<String> = current_group.as_ref()
+        .and_then(|gid| app_state.group_members.read().get(gid).cloned())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(m, _)| m)
+        .filter(|m| *m != local_id)
+        .collect();
+    let has_ratchet = app_state.ratchet_sessions.read().contains(&target);
+    let member_activity = current_group.clone()
+        .zip(moderated_member())
+        .and_then(|key| app_state.group_member_activity.read().get(&key).cloned());
 
     // Effects for fetching messages
     let cmd_tx_effect = cmd_tx.clone();
@@ -53,16 +93,23 @@ pub fn MessagingComponent() -> Element {
         if !t.is_empty() {
              *viewed_profile.write() = None;
              let _ = cmd_tx_effect.send(AppCmd::FetchMessages { peer_id: t.clone() });
-             let _ = cmd_tx_effect.send(AppCmd::FetchUserProfile { peer_id: t });
+             let _ = cmd_tx_effect.send(AppCmd::FetchUserProfile { peer_id: t.clone() });
+             let _ = cmd_tx_effect.send(AppCmd::InitRatchet { peer_id: t });
         }
+        let _ = cmd_tx_effect.send(AppCmd::FetchMessageThreads);
     });
 
     use_effect(move || {
         let g = group_effect.clone();
         if let Some(gid) = g {
-            let _ = cmd_tx_effect2.send(AppCmd::FetchGroupMessages { group_id: gid });
+            let _ = cmd_tx_effect2.send(AppCmd::FetchGroupMessages { group_id: gid.clone() });
+            let _ = cmd_tx_effect2.send(AppCmd::FetchGroupActivity { group_id: gid.clone() });
+            let _ = cmd_tx_effect2.send(AppCmd::FetchGroupMembers { group_id: gid.clone() });
+            let _ = cmd_tx_effect2.send(AppCmd::FetchGroupPolicy { group_id: gid.clone() });
+            let _ = cmd_tx_effect2.send(AppCmd::FetchGroupLeaderboard { group_id: gid, window_days: 30 });
         } else {
              let _ = cmd_tx_effect2.send(AppCmd::FetchGroups);
+             let _ = cmd_tx_effect2.send(AppCmd::FetchGroupInvitations);
         }
     });
 
@@ -197,7 +244,7 @@ pub fn MessagingComponent() -> Element {
                     }
                     div { class: "chat-info",
                         div { class: "chat-name", "{g.name}" }
-                        div { class: "chat-preview", "{g.members.len()} members" }
+                        div { class: "chat-preview", "{group_recipients(&app_state, &group_node.id).len()} members" }
                     }
                 }
             });
@@ -205,7 +252,48 @@ pub fn MessagingComponent() -> Element {
         None
     });
 
-    let peer_list_items = peers.iter().filter(|p| **p != local_id).filter_map(|peer| {
+    // Conversations, most recent first
+    let threads = app_state.message_threads.read().clone();
+    let thread_list_items = threads.iter().filter_map(|(peer, last_msg, preview)| {
+        if !query.is_empty() && !peer.to_lowercase().contains(&query) && !preview.to_lowercase().contains(&query) {
+            return None;
+        }
+        let p_clone = peer.clone();
+        let is_active = *peer == target && current_group.is_none();
+        let bg_class = if is_active { "chat-item chat-item-active" } else { "chat-item" };
+        let short_id = if peer.len() > 12 { format!("{}...", &peer[0..12]) } else { peer.clone() };
+        let sent_by_me = if last_msg.author == local_id { "You: " } else { "" };
+        let time = if last_msg.timestamp.date_naive() == chrono::Utc::now().date_naive() {
+            last_msg.timestamp.format("%H:%M").to_string()
+        } else {
+            last_msg.timestamp.format("%b %d").to_string()
+        };
+
+        Some(rsx! {
+            div {
+                key: "{peer}",
+                class: "{bg_class}",
+                onclick: move |_| {
+                    target_peer.set(p_clone.clone());
+                    active_group.set(None);
+                },
+                div { class: "chat-avatar",
+                    span { "{peer.chars().next().unwrap_or('?')}" }
+                }
+                div { class: "chat-info",
+                    div { class: "chat-name", "{short_id}" }
+                    div { class: "chat-preview", "{sent_by_me}{preview}" }
+                }
+                span { class: "chat-preview", "{time}" }
+            }
+        })
+    });
+
+    // Connected peers we have not talked to yet
+    let peer_list_items = peers.iter()
+        .filter(|p| **p != local_id)
+        .filter(|p| !threads.iter().any(|(peer, _, _)| peer == *p))
+        .filter_map(|peer| {
         if !query.is_empty() && !peer.to_lowercase().contains(&query) {
             return None;
         }
@@ -253,7 +341,11 @@ pub fn MessagingComponent() -> Element {
             div { class: "messaging-sidebar",
                 // Header
                 div { class: "sidebar-header",
-                    div { class: "sidebar-title", "Messages" }
+                    div { class: "sidebar-title", "Messages"
+                        if !invitations.is_empty() {
+                            span { class: "badge badge-primary ml-2", title: "Group invitations", "{invitations.len()}" }
+                        }
+                    }
                     div { class: "sidebar-actions",
                         button { 
                             class: "icon-btn",
@@ -282,7 +374,43 @@ pub fn MessagingComponent() -> Element {
 
                 // Chat List
                 div { class: "chat-list",
-                    if groups.is_empty() && peers.len() <= 1 {
+                    // Pending group invitations
+                    for invite in invitations.iter() {
+                        if let DagPayload::GroupInvite(gi) = &invite.payload {
+                            {
+                                let invite_id = invite.id.clone();
+                                let cmd_tx_accept = cmd_tx.clone();
+                                let cmd_tx_decline = cmd_tx.clone();
+                                let invite_id_decline = invite_id.clone();
+                                let group_label = format!("Group {}...", gi.group_id.get(0..8).unwrap_or(&gi.group_id));
+                                let inviter = format!("Invited by {}...", invite.author.get(0..8).unwrap_or(&invite.author));
+                                rsx! {
+                                    div { class: "chat-item", key: "{invite_id}",
+                                        div { class: "chat-avatar chat-avatar-group", span { "✉️" } }
+                                        div { class: "chat-info",
+                                            div { class: "chat-name", "{group_label}" }
+                                            div { class: "chat-preview", "{inviter}" }
+                                        }
+                                        button {
+                                            class: "btn btn-primary btn-sm",
+                                            onclick: move |_| {
+                                                let _ = cmd_tx_accept.send(AppCmd::RespondToGroupInvite { invite_id: invite_id.clone(), accept: true });
+                                            },
+                                            "Join"
+                                        }
+                                        button {
+                                            class: "btn btn-secondary btn-sm",
+                                            onclick: move |_| {
+                                                let _ = cmd_tx_decline.send(AppCmd::RespondToGroupInvite { invite_id: invite_id_decline.clone(), accept: false });
+                                            },
+                                            "Decline"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    if groups.is_empty() && peers.len() <= 1 && threads.is_empty() {
                         div { class: "empty-chats",
                             div { class: "empty-icon", "💬" }
                             div { class: "empty-text", "No chats yet" }
@@ -290,6 +418,7 @@ pub fn MessagingComponent() -> Element {
                         }
                     } else {
                         {group_list_items}
+                        {thread_list_items}
                         {peer_list_items}
                     }
                 }
@@ -320,11 +449,193 @@ pub fn MessagingComponent() -> Element {
                                 div { class: "chat-header-name", "{header_name}" }
                                 div { class: "chat-header-status", 
                                     if current_group.is_some() { "Group Chat" } else { "Direct Message" } 
+                                    if current_group.is_none() && has_ratchet {
+                                        span { class: "ml-2", title: "Each message uses a fresh key; past messages stay private if keys leak", "🔒 Forward secrecy" }
+                                    }
+                                }
+                            }
+                        }
+                        if is_group_owner {
+                            {
+                                let cmd_tx_invite = cmd_tx.clone();
+                                let cmd_tx_rotate = cmd_tx.clone();
+                                rsx! {
+                                    div { class: "flex items-center gap-2",
+                                        input {
+                                            class: "search-input",
+                                            placeholder: "Peer ID to invite",
+                                            value: "{invite_peer_id}",
+                                            oninput: move |evt| invite_peer_id.set(evt.value())
+                                        }
+                                        button {
+                                            class: "btn btn-secondary btn-sm",
+                                            disabled: invite_peer_id.read().trim().is_empty(),
+                                            onclick: move |_| {
+                                                if let Some(gid) = active_group.read().clone() {
+                                                    let invitee = invite_peer_id.read().trim().to_string();
+                                                    let _ = cmd_tx_invite.send(AppCmd::InviteToGroup { group_id: gid, invitee_peer_id: invitee });
+                                                    invite_peer_id.set(String::new());
+                                                }
+                                            },
+                                            "Invite"
+                                        }
+                                        button {
+                                            class: "btn btn-ghost btn-sm",
+                                            title: "Group policy",
+                                            onclick: move |_| {
+                                                if !show_policy() {
+                                                    // Start editing from the policy currently in force
+                                                    let current = active_group.read().as_ref()
+                                                        .and_then(|gid| app_state.group_policies.read().get(gid).cloned())
+                                                        .unwrap_or_default();
+                                                    policy_max_members.set(current.max_members.map(|m| m.to_string()).unwrap_or_default());
+                                                    policy_min_reputation.set(current.min_reputation.map(|r| r.to_string()).unwrap_or_default());
+                                                    policy_draft.set(current);
+                                                }
+                                                show_policy.set(!show_policy());
+                                            },
+                                            "⚙️"
+                                        }
+                                        button {
+                                            class: "btn btn-ghost btn-sm",
+                                            title: "Issue a new group key, e.g. after someone leaves",
+                                            onclick: move |_| {
+                                                if let Some(gid) = active_group.read().clone() {
+                                                    let _ = cmd_tx_rotate.send(AppCmd::RotateGroupKey { group_id: gid });
+                                                }
+                                            },
+                                            "🔑"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    if is_group_owner && show_policy() {
+                        {
+                            let cmd_tx_policy = cmd_tx.clone();
+                            let draft = policy_draft();
+                            rsx! {
+                                div { class: "px-4 py-3 border-b border-[var(--border-subtle)] flex flex-wrap items-center gap-4 text-sm",
+                                    label { class: "flex items-center gap-2",
+                                        input {
+                                            r#type: "checkbox",
+                                            checked: draft.allow_new_members_join,
+                                            onchange: move |e| policy_draft.write().allow_new_members_join = e.checked()
+                                        }
+                                        "Open to new members"
+                                    }
+                                    label { class: "flex items-center gap-2",
+                                        input {
+                                            r#type: "checkbox",
+                                            checked: draft.require_approval,
+                                            onchange: move |e| policy_draft.write().require_approval = e.checked()
+                                        }
+                                        "Owner approves new members"
+                                    }
+                                    input {
+                                        class: "search-input w-32",
+                                        r#type: "number",
+                                        placeholder: "Max members",
+                                        value: "{policy_max_members}",
+                                        oninput: move |e| policy_max_members.set(e.value())
+                                    }
+                                    input {
+                                        class: "search-input w-32",
+                                        r#type: "number",
+                                        placeholder: "Min reputation",
+                                        value: "{policy_min_reputation}",
+                                        oninput: move |e| policy_min_reputation.set(e.value())
+                                    }
+                                    button {
+                                        class: "btn btn-primary btn-sm",
+                                        onclick: move |_| {
+                                            if let Some(gid) = active_group.read().clone() {
+                                                let mut policy = policy_draft();
+                                                policy.max_members = policy_max_members.read().trim().parse().ok();
+                                                policy.min_reputation = policy_min_reputation.read().trim().parse().ok();
+                                                let _ = cmd_tx_policy.send(AppCmd::SetGroupPolicy { group_id: gid, policy });
+                                                show_policy.set(false);
+                                            }
+                                        },
+                                        "Save Policy"
+                                    }
                                 }
                             }
                         }
                     }
                     
+                    // Group activity feed (latest few events)
+                    if !group_activity.is_empty() {
+                        div { class: "px-4 py-2 text-xs text-[var(--text-secondary)] border-b border-[var(--border-subtle)] flex flex-col gap-1",
+                            for event in group_activity.iter().take(3) {
+                                {
+                                    let text = match event {
+                                        GroupActivityEvent::MemberAdded(m) => format!("➕ {}... joined", m.get(0..8).unwrap_or(m)),
+                                        GroupActivityEvent::MemberRemoved(m) => format!("➖ {}... left", m.get(0..8).unwrap_or(m)),
+                                        GroupActivityEvent::PostPublished(node) => format!("📝 New group post by {}...", node.author.get(0..8).unwrap_or(&node.author)),
+                                        GroupActivityEvent::DescriptionUpdated(d) => format!("✏️ Description updated: {}", d),
+                                        GroupActivityEvent::MessageMilestone(n) => format!("🎉 {} messages sent", n),
+                                    };
+                                    rsx! { span { "{text}" } }
+                                }
+                            }
+                        }
+                    }
+
+                    // Top contributors (last 30 days)
+                    if group_leaderboard.iter().any(|(_, score)| *score > 0) {
+                        div { class: "px-4 py-2 text-xs border-b border-[var(--border-subtle)] flex items-center gap-3",
+                            span { class: "font-bold", "🏆 Top Contributors This Month" }
+                            for (rank, (member, score)) in group_leaderboard.iter().filter(|(_, s)| *s > 0).take(3).enumerate() {
+                                span { class: "badge badge-primary", "#{rank + 1} {member.get(0..8).unwrap_or(member)}… ({score})" }
+                            }
+                        }
+                    }
+
+                    // Member moderation (owner only)
+                    if is_group_owner && !moderated_members.is_empty() {
+                        div { class: "px-4 py-2 text-xs border-b border-[var(--border-subtle)] flex flex-wrap items-center gap-2",
+                            span { class: "font-bold", "🛡️ Members" }
+                            for member in moderated_members.iter().cloned() {
+                                {
+                                    let cmd_tx_moderate = cmd_tx.clone();
+                                    let selected = moderated_member.read().as_deref() == Some(member.as_str());
+                                    let label = format!("{}…", member.get(0..8).unwrap_or(&member));
+                                    rsx! {
+                                        button {
+                                            class: if selected { "badge badge-primary" } else { "badge" },
+                                            onclick: move |_| {
+                                                if let Some(gid) = active_group.read().clone() {
+                                                    let _ = cmd_tx_moderate.send(AppCmd::FetchGroupMemberActivity { group_id: gid, member_peer_id: member.clone() });
+                                                    moderated_member.set(Some(member.clone()));
+                                                }
+                                            },
+                                            "{label}"
+                                        }
+                                    }
+                                }
+                            }
+                            if let Some(((messages, posts, reports, last_active), flagged)) = member_activity {
+                                {
+                                    let last_seen = last_active
+                                        .and_then(|ts| chrono::DateTime::from_timestamp(ts as i64, 0))
+                                        .map(|dt| dt.format("%Y-%m-%d").to_string())
+                                        .unwrap_or_else(|| "never".to_string());
+                                    rsx! {
+                                        span { class: "text-[var(--text-secondary)]",
+                                            "{messages} messages · {posts} posts · {reports} reports · last active {last_seen}"
+                                        }
+                                        if flagged {
+                                            span { class: "badge badge-error", "⚠️ Review suggested" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+
                     // Messages Area
                     div { class: "messages-area",
                         div { class: "messages-container",
@@ -364,16 +675,11 @@ pub fn MessagingComponent() -> Element {
         
                                                  if !content.is_empty() {
                                                     if let Some(gid) = group_id {
-                                                        if let Some(g_node) = app_state.groups.read().iter().find(|n| n.id == gid) {
-                                                            if let DagPayload::Group(g) = &g_node.payload {
-                                                                for member in &g.members {
-                                                                     let _ = cmd_tx_input.send(AppCmd::SendMessage { recipient: member.clone(), content: content.clone(), group_id: Some(gid.clone()) });
-                                                                }
-                                                            }
-                                                        }
+                                                        let _ = cmd_tx_input.send(AppCmd::SendMessage { recipient: String::new(), content, group_id: Some(gid) });
                                                         input_msg.set(String::new());
                                                     } else if !recipient.is_empty() {
                                                         let _ = cmd_tx_input.send(AppCmd::SendMessage { recipient, content, group_id: None });
+                                                        let _ = cmd_tx_input.send(AppCmd::FetchMessageThreads);
                                                         input_msg.set(String::new());
                                                     }
                                                  }
@@ -397,16 +703,11 @@ pub fn MessagingComponent() -> Element {
         
                                              if !content.is_empty() {
                                                 if let Some(gid) = group_id {
-                                                    if let Some(g_node) = app_state.groups.read().iter().find(|n| n.id == gid) {
-                                                        if let DagPayload::Group(g) = &g_node.payload {
-                                                            for member in &g.members {
-                                                                 let _ = cmd_tx_btn.send(AppCmd::SendMessage { recipient: member.clone(), content: content.clone(), group_id: Some(gid.clone()) });
-                                                            }
-                                                        }
-                                                    }
+                                                    let _ = cmd_tx_btn.send(AppCmd::SendMessage { recipient: String::new(), content, group_id: Some(gid) });
                                                     input_msg.set(String::new());
                                                 } else if !recipient.is_empty() {
                                                     let _ = cmd_tx_btn.send(AppCmd::SendMessage { recipient, content, group_id: None });
+                                                    let _ = cmd_tx_btn.send(AppCmd::FetchMessageThreads);
                                                     input_msg.set(String::new());
                                                 }
                                              }
@@ -674,6 +975,18 @@ fn MessageItem(msg: crate::backend::dag::DagNode, is_me: bool, content: String)
     };
 
     let bubble_class = if is_me { "message-bubble message-bubble-me" } else { "message-bubble message-bubble-them" };
+    let app_state = use_context::<AppState>();
+    let cmd_tx = use_context::<UnboundedSender<AppCmd>>();
+    let read_at = app_state.message_receipts.read().get(&msg.id).map(|(_, read_at)| *read_at);
+    let is_direct = matches!(&msg.payload, DagPayload::Message(m) if m.group_id.is_none());
+
+    // Showing a direct message to us counts as reading it; the backend sends one receipt per message
+    let msg_id = msg.id.clone();
+    use_effect(move || {
+        if !is_me && is_direct {
+            let _ = cmd_tx.send(AppCmd::MarkMessageRead { message_cid: msg_id.clone() });
+        }
+    });
 
     rsx! {
         div {
@@ -687,8 +1000,12 @@ fn MessageItem(msg: crate::backend::dag::DagNode, is_me: bool, content: String)
                 }
                 div { class: "message-time",
                     "{msg.timestamp}"
-                    if is_me {
-                         span { class: "message-status", " ✓✓" }
+                    if is_me && is_direct {
+                        if let Some(read_at) = read_at {
+                            span { class: "message-status", title: "Read at {read_at}", " ✓✓" }
+                        } else {
+                            span { class: "message-status", title: "Sent", " ✓" }
+                        }
                     }
                 }
             }
@@ -39,6 +39,7 @@ pub struct AppState {
     pub local_peer_id: Signal<String>,
     pub profile: Signal<Option<crate::backend::dag::ProfilePayload>>,
     pub balance: Signal<i64>,
+    #[allow(dead_code)]
     pub pending_transfers: Signal<Vec<DagNode>>,
     pub geohash: Signal<String>,
     pub ubi_timer: Signal<Option<u64>>,
@@ -49,6 +50,8 @@ pub struct AppState {
     pub blob_cache: Signal<std::collections::HashMap<String, String>>, // CID -> Base64 Data
     pub last_created_blob: Signal<Option<String>>,
     pub storage_stats: Signal<(usize, usize)>, // (block_count, total_bytes)
+    pub storage_compression: Signal<(u8, f64, usize)>, // (zstd level, ratio, saved_bytes)
+    pub sync_bytes_saved: Signal<usize>, // Replication bytes skipped thanks to peer sync filters
     pub local_posts: Signal<Vec<DagNode>>,
     pub listings: Signal<Vec<DagNode>>,
     pub local_listings: Signal<Vec<DagNode>>,
@@ -61,16 +64,21 @@ pub struct AppState {
     pub active_contract_history: Signal<Vec<DagNode>>,
     pub pending_contracts: Signal<Vec<DagNode>>, // Contracts awaiting my acceptance
     pub contract_states: Signal<std::collections::HashMap<String, String>>, // ContractID -> JSON State
+    pub contract_simulations: Signal<std::collections::HashMap<String, crate::backend::ContractSimulation>>, // ContractID -> (Simulated state, Gas used, Error)
+    pub contract_events: Signal<std::collections::HashMap<String, Vec<crate::backend::vm::ContractEvent>>>, // ContractID -> Emitted events, oldest first
     
     // Governance
     pub proposals: Signal<Vec<DagNode>>,
     #[allow(dead_code)]
     pub proposal_votes: Signal<std::collections::HashMap<String, Vec<DagNode>>>,
-    pub proposal_tallies: Signal<std::collections::HashMap<String, (usize, usize, usize, usize, usize, String)>>,
+    pub proposal_tallies: Signal<std::collections::HashMap<String, crate::backend::WeightedTally>>,
+    #[allow(dead_code)]
     pub current_tax_rate: Signal<u8>,
     pub candidates: Signal<Vec<DagNode>>,
     pub candidate_tallies: Signal<std::collections::HashMap<String, usize>>,
     pub recalls: Signal<Vec<DagNode>>,
+    pub petitions: Signal<Vec<DagNode>>,
+    pub petition_signatures: Signal<std::collections::HashMap<String, Vec<String>>>, // PetitionID -> Signer PeerIDs
     pub recall_tallies: Signal<std::collections::HashMap<String, (usize, usize, usize)>>,
     #[allow(dead_code)]
     pub oversight_cases: Signal<Vec<DagNode>>,
@@ -94,6 +102,11 @@ pub struct AppState {
     pub file_search_results: Signal<Vec<DagNode>>,
     pub ministries: Signal<Vec<String>>,
     pub likes: Signal<std::collections::HashMap<String, (usize, bool)>>, // TargetID -> (Count, IsLikedByMe)
+    pub reactions: Signal<std::collections::HashMap<String, crate::backend::ReactionCounts>>, // TargetID -> Emoji -> (Count, ReactedByMe)
+    pub shares: Signal<std::collections::HashMap<String, usize>>, // PostID -> Repost count
+    pub poll_results: Signal<std::collections::HashMap<String, crate::backend::PollResult>>, // PollID -> (Votes per option, Closed)
+    pub events: Signal<Vec<DagNode>>, // Upcoming events in the viewed area
+    pub event_attendees: Signal<std::collections::HashMap<String, Vec<String>>>, // EventID -> Attending PeerIDs
     pub stories: Signal<Vec<DagNode>>,
     pub seen_stories: Signal<std::collections::HashSet<String>>,
     pub local_stories: Signal<Vec<DagNode>>,
@@ -102,6 +115,7 @@ pub struct AppState {
     pub courses: Signal<Vec<DagNode>>,
     pub exams: Signal<Vec<DagNode>>,
     pub certifications: Signal<Vec<DagNode>>,
+    pub expiring_certifications: Signal<HashSet<String>>, // Certification types lapsed or about to
     pub active_exam: Signal<Option<DagNode>>,
     // Verification Application System
     pub pending_applications: Signal<Vec<DagNode>>,
@@ -109,6 +123,86 @@ pub struct AppState {
     pub exam_result: Signal<Option<(String, u8, bool)>>, // (exam_id, score, passed)
     // Wiki homepage
     pub all_web_pages: Signal<Vec<DagNode>>,
+    pub contract_audits: Signal<std::collections::HashMap<String, Vec<crate::backend::vm::AuditFinding>>>, // ContractID -> Audit findings
+    pub application_approvers: Signal<std::collections::HashMap<String, Vec<crate::backend::PeerProfile>>>, // ApplicationID -> (Approver, Profile)
+    pub inflation_stats: Signal<Option<crate::backend::TokenInflationStats>>, // Token supply stats for the economy dashboard
+    pub engagement_scores: Signal<std::collections::HashMap<String, u32>>, // PostID -> Engagement score
+    pub author_reputations: Signal<std::collections::HashMap<String, crate::backend::dag::ReputationDetails>>, // PeerID -> Reputation (feed badges)
+    pub contract_deployers: Signal<std::collections::HashMap<String, crate::backend::ContractDeployerInfo>>, // ContractID -> Deployer trust info
+    pub my_elected_positions: Signal<Vec<crate::backend::dag::Ministry>>, // Offices held by the local user
+    pub incoming_transfers: Signal<Vec<crate::backend::IncomingTransfer>>, // Unclaimed transfers with sender profiles
+    pub web_stats: Signal<std::collections::HashMap<String, (usize, usize, usize)>>, // URL -> (total_views, unique_viewers, views_last_7_days)
+    pub group_activity: Signal<std::collections::HashMap<String, Vec<crate::backend::dag::GroupActivityEvent>>>, // GroupID -> Activity feed
+    pub proposal_impacts: Signal<std::collections::HashMap<String, crate::backend::ProposalImpact>>, // ProposalID -> (Narrative, Metrics)
+    pub my_vote_history: Signal<Vec<(DagNode, Option<String>)>>, // (Vote node, Proposal title)
+    pub election_schedule: Signal<Vec<crate::backend::dag::ElectionWindow>>, // (Ministry, start_unix, end_unix)
+    pub cross_post_history: Signal<std::collections::HashMap<String, Vec<DagNode>>>, // PostID -> Quoting posts
+    pub group_leaderboards: Signal<std::collections::HashMap<String, Vec<(String, usize)>>>, // GroupID -> (PeerID, Score) rankings
+    pub network_growth: Signal<Option<crate::backend::NetworkGrowthStats>>, // Daily growth series for governance
+    pub governance_records: Signal<std::collections::HashMap<String, crate::backend::GovernanceRecord>>, // PeerID -> Proposals, votes and candidacies
+    pub system_params: Signal<std::collections::HashMap<String, serde_json::Value>>, // Governance parameters by name (tax_rate, required_approvals, ...)
+    pub jury_stats: Signal<Option<crate::backend::JuryStats>>, // Local juror performance record
+    pub cert_leaderboard: Signal<std::collections::HashMap<String, Vec<crate::backend::CertifiedPeer>>>, // CertType -> Certified members, earliest first
+    pub connected_peer_profiles: Signal<Vec<crate::backend::PeerProfile>>, // Connected peers with their published profiles
+    pub candidate_platforms: Signal<std::collections::HashMap<String, crate::backend::CandidatePlatform>>, // CandidacyID -> Composite candidate view
+    pub mutual_followers: Signal<std::collections::HashMap<String, Vec<crate::backend::PeerProfile>>>, // PeerID -> People I follow who follow them
+    pub post_context: Signal<Option<crate::backend::PostContext>>, // (center_post_id, surrounding posts, center_index)
+    pub contract_parties: Signal<std::collections::HashMap<String, crate::backend::ContractParties>>, // ContractID -> (Provider, Consumer) with profiles
+    pub follow_suggestions: Signal<Vec<crate::backend::FollowSuggestion>>, // People You May Know: (PeerID, Profile, Reason)
+    pub peers_by_geohash: Signal<std::collections::HashMap<String, Vec<crate::backend::PeerProfile>>>, // Geohash prefix -> Peers active in that area
+    pub group_invitations: Signal<Vec<crate::backend::dag::DagNode>>, // Pending group invitations addressed to me
+    pub group_members: Signal<std::collections::HashMap<String, Vec<(String, crate::backend::dag::GroupRole)>>>, // GroupID -> Members with roles
+    pub contract_milestones: Signal<std::collections::HashMap<String, Vec<crate::backend::ContractMilestone>>>, // ContractID -> Milestones with completion state
+    pub similar_proposals: Signal<std::collections::HashMap<String, Vec<(crate::backend::dag::DagNode, f32)>>>, // ProposalID -> Similar proposals with scores ("" for the draft being written)
+    pub my_storage_usage: Signal<std::collections::HashMap<String, (usize, usize)>>, // Node type -> (count, bytes) published by me
+    pub candidacy_withdrawals: Signal<std::collections::HashMap<String, Option<u64>>>, // Withdrawn CandidacyID -> Withdrawal time (unix), if known
+    pub message_threads: Signal<Vec<crate::backend::MessageThread>>, // Direct conversations, most recent first
+    pub web_page_embeds: Signal<std::collections::HashMap<String, Vec<String>>>, // URL -> Blob CIDs embedded in the page
+    pub active_officials: Signal<std::collections::HashMap<String, String>>, // Ministry -> Peer ID of the current office holder
+    pub listing_buyers: Signal<std::collections::HashMap<String, (usize, Vec<crate::backend::ListingPurchase>)>>, // Listing ID -> (purchase count, buyers; seller only)
+    pub active_course: Signal<Option<crate::backend::CourseDetail>>, // Course opened in the detail view
+    pub proposal_outcomes: Signal<std::collections::HashMap<String, crate::backend::dag::DagNode>>, // Proposal ID -> recorded outcome
+    pub peer_courses: Signal<std::collections::HashMap<String, crate::backend::PeerCourseHistory>>, // Peer ID -> (courses completed, courses in progress)
+    pub group_policies: Signal<std::collections::HashMap<String, crate::backend::dag::GroupPolicy>>, // Group ID -> resolved moderation policy
+    pub template_deployments: Signal<std::collections::HashMap<String, usize>>, // Template contract ID -> number of deployments
+    pub endorsements: Signal<std::collections::HashMap<String, Vec<crate::backend::Endorsement>>>, // Peer ID -> skill endorsements received
+    pub oversight_stats: Signal<std::collections::HashMap<String, crate::backend::OversightStats>>, // Ministry -> (open cases, decided cases, avg resolution hours)
+    pub verification_paths: Signal<std::collections::HashMap<String, Vec<(String, String)>>>, // Peer ID -> trust chain as (voucher, vouchee) pairs
+    pub nodes_by_author_type: Signal<std::collections::HashMap<(String, String), Vec<crate::backend::dag::DagNode>>>, // (Peer ID, node type) -> that peer's newest nodes of the type
+    pub trust_scores: Signal<std::collections::HashMap<String, f32>>, // Peer ID -> trust score on [0, 1]
+    pub escrow_statuses: Signal<std::collections::HashMap<String, crate::backend::EscrowInfo>>, // Contract ID -> (locked amount, escrow status, counterparty)
+    pub following_hashtags: Signal<Vec<String>>, // Hashtags the local user follows (without #)
+    pub hashtag_posts: Signal<Vec<DagNode>>, // Result of the latest hashtag lookup, newest first
+    pub bookmarks: Signal<Vec<DagNode>>, // Locally saved posts, most recently bookmarked first
+    pub my_delegations: Signal<Vec<DagNode>>, // Our vote delegations still in force, one per scope
+    pub muted_users: Signal<Vec<String>>, // Peers whose posts and stories we hide (local only)
+    pub blocked_users: Signal<Vec<String>>, // Peers we ignore entirely (local only)
+    pub network_diagnostics: Signal<Option<crate::backend::NetworkDiagnostics>>, // Latest swarm bandwidth and replication snapshot
+    pub peer_scores: Signal<std::collections::HashMap<String, crate::backend::PeerScore>>, // Peer ID -> replication reliability
+    pub full_sync: Signal<Option<(usize, Option<usize>, bool)>>, // (Nodes received, estimated total, finished) of the latest full sync
+    pub multisig_proposals: Signal<Vec<crate::backend::MultiSigRecord>>, // Multi-sig transfers I proposed or must co-sign
+    pub transaction_history: Signal<(String, Vec<crate::backend::TransactionRecord>, usize)>, // (Peer ID, current page of token movements, total count)
+    pub spending_limit: Signal<(Option<u64>, u64)>, // (Daily limit, spent today)
+    pub spending_limit_exceeded: Signal<Option<(u64, u64, u64)>>, // Last transfer the limit stopped: (attempted, limit, spent today)
+    pub pinned_blocks: Signal<Vec<String>>, // CIDs kept through vacuuming
+    pub pin_policy: Signal<Vec<String>>, // Node types the backend pins as they arrive
+    pub last_vacuum: Signal<Option<(usize, usize)>>, // (Blocks removed, bytes freed) by the last vacuum
+    pub index_rebuild: Signal<(bool, Option<(usize, u64)>)>, // (Rebuild running, last (blocks scanned, ms taken))
+    pub store_root: Signal<Option<(String, usize)>>, // (Merkle root hex, block count) for comparing stores with peers
+    pub identity_backup: Signal<Option<String>>, // Latest password-protected export, Base64
+    pub identity_import_error: Signal<Option<String>>, // Why the last identity import failed
+    pub peer_count_low: Signal<Option<(usize, usize)>>, // (connected, minimum) while the backend is re-bootstrapping
+    pub trending_posts: Signal<Vec<DagNode>>, // Most engaged-with posts in the chosen window
+    pub scheduled_posts: Signal<Vec<DagNode>>, // Our queued posts, soonest first
+    pub mentions: Signal<Vec<DagNode>>, // Posts and comments that @-mention us, newest first
+    pub mentions_seen_at: Signal<Option<chrono::DateTime<chrono::Utc>>>, // When the Mentions tab was last opened
+    pub group_member_activity: Signal<std::collections::HashMap<(String, String), (crate::backend::GroupMemberActivity, bool)>>, // (Group ID, Peer ID) -> (activity, moderation flag)
+    pub proposal_progress: Signal<std::collections::HashMap<String, crate::backend::QuorumProgress>>, // Proposal ID -> distance to passing
+    pub my_application_status: Signal<Option<crate::backend::ApplicationStatus>>, // My latest application and its votes
+    pub contract_versions: Signal<std::collections::HashMap<String, Vec<crate::backend::dag::DagNode>>>, // Contract ID -> its upgrade chain, oldest first
+    pub feed_strategy: Signal<crate::backend::FeedStrategy>, // How the global feed is ranked, chosen by the user
+    pub ratchet_sessions: Signal<std::collections::HashSet<String>>, // Peers we have a forward-secret message session with
+    pub message_receipts: Signal<std::collections::HashMap<String, (String, u64)>>, // Sent message CID -> (reader, read at)
 }
 
 impl AppState {
@@ -134,6 +228,8 @@ impl AppState {
             blob_cache: use_signal(|| std::collections::HashMap::new()),
             last_created_blob: use_signal(|| None),
             storage_stats: use_signal(|| (0, 0)),
+            storage_compression: use_signal(|| (0, 1.0, 0)),
+            sync_bytes_saved: use_signal(|| 0),
             local_posts: use_signal(|| vec![]),
             listings: use_signal(|| vec![]),
             local_listings: use_signal(|| vec![]),
@@ -143,13 +239,17 @@ impl AppState {
             active_contract_history: use_signal(|| vec![]),
             pending_contracts: use_signal(|| vec![]),
             contract_states: use_signal(|| std::collections::HashMap::new()),
+            contract_simulations: use_signal(std::collections::HashMap::new),
+            contract_events: use_signal(std::collections::HashMap::new),
             proposals: use_signal(|| vec![]),
             proposal_votes: use_signal(|| std::collections::HashMap::new()),
-            proposal_tallies: use_signal(|| std::collections::HashMap::new()),
+            proposal_tallies: use_signal(std::collections::HashMap::new),
             current_tax_rate: use_signal(|| 0),
             candidates: use_signal(|| vec![]),
             candidate_tallies: use_signal(|| std::collections::HashMap::new()),
             recalls: use_signal(|| vec![]),
+            petitions: use_signal(Vec::new),
+            petition_signatures: use_signal(std::collections::HashMap::new),
             recall_tallies: use_signal(|| std::collections::HashMap::new()),
             oversight_cases: use_signal(|| vec![]),
             jury_duty: use_signal(|| vec![]),
@@ -169,6 +269,11 @@ impl AppState {
             ministries: use_signal(|| vec![]),
             comments: use_signal(|| std::collections::HashMap::new()),
             likes: use_signal(|| std::collections::HashMap::new()),
+            reactions: use_signal(std::collections::HashMap::new),
+            shares: use_signal(std::collections::HashMap::new),
+            poll_results: use_signal(std::collections::HashMap::new),
+            events: use_signal(Vec::new),
+            event_attendees: use_signal(std::collections::HashMap::new),
             stories: use_signal(|| vec![]),
             seen_stories: use_signal(|| std::collections::HashSet::new()),
             local_stories: use_signal(|| vec![]),
@@ -176,11 +281,92 @@ impl AppState {
             courses: use_signal(|| vec![]),
             exams: use_signal(|| vec![]),
             certifications: use_signal(|| vec![]),
+            expiring_certifications: use_signal(HashSet::new),
             active_exam: use_signal(|| None),
             pending_applications: use_signal(|| vec![]),
             exam_answers: use_signal(|| vec![]),
             exam_result: use_signal(|| None),
             all_web_pages: use_signal(|| vec![]),
+            contract_audits: use_signal(std::collections::HashMap::new),
+            application_approvers: use_signal(std::collections::HashMap::new),
+            inflation_stats: use_signal(|| None),
+            engagement_scores: use_signal(std::collections::HashMap::new),
+            author_reputations: use_signal(std::collections::HashMap::new),
+            contract_deployers: use_signal(std::collections::HashMap::new),
+            my_elected_positions: use_signal(Vec::new),
+            incoming_transfers: use_signal(Vec::new),
+            web_stats: use_signal(std::collections::HashMap::new),
+            group_activity: use_signal(std::collections::HashMap::new),
+            proposal_impacts: use_signal(std::collections::HashMap::new),
+            my_vote_history: use_signal(Vec::new),
+            election_schedule: use_signal(Vec::new),
+            cross_post_history: use_signal(std::collections::HashMap::new),
+            group_leaderboards: use_signal(std::collections::HashMap::new),
+            network_growth: use_signal(|| None),
+            governance_records: use_signal(std::collections::HashMap::new),
+            system_params: use_signal(std::collections::HashMap::new),
+            jury_stats: use_signal(|| None),
+            cert_leaderboard: use_signal(std::collections::HashMap::new),
+            connected_peer_profiles: use_signal(Vec::new),
+            candidate_platforms: use_signal(std::collections::HashMap::new),
+            mutual_followers: use_signal(std::collections::HashMap::new),
+            post_context: use_signal(|| None),
+            contract_parties: use_signal(std::collections::HashMap::new),
+            follow_suggestions: use_signal(Vec::new),
+            peers_by_geohash: use_signal(std::collections::HashMap::new),
+            group_invitations: use_signal(Vec::new),
+            group_members: use_signal(std::collections::HashMap::new),
+            contract_milestones: use_signal(std::collections::HashMap::new),
+            similar_proposals: use_signal(std::collections::HashMap::new),
+            my_storage_usage: use_signal(std::collections::HashMap::new),
+            candidacy_withdrawals: use_signal(std::collections::HashMap::new),
+            message_threads: use_signal(Vec::new),
+            web_page_embeds: use_signal(std::collections::HashMap::new),
+            active_officials: use_signal(std::collections::HashMap::new),
+            listing_buyers: use_signal(std::collections::HashMap::new),
+            active_course: use_signal(|| None),
+            proposal_outcomes: use_signal(std::collections::HashMap::new),
+            peer_courses: use_signal(std::collections::HashMap::new),
+            group_policies: use_signal(std::collections::HashMap::new),
+            template_deployments: use_signal(std::collections::HashMap::new),
+            endorsements: use_signal(std::collections::HashMap::new),
+            oversight_stats: use_signal(std::collections::HashMap::new),
+            verification_paths: use_signal(std::collections::HashMap::new),
+            nodes_by_author_type: use_signal(std::collections::HashMap::new),
+            trust_scores: use_signal(std::collections::HashMap::new),
+            escrow_statuses: use_signal(std::collections::HashMap::new),
+            following_hashtags: use_signal(Vec::new),
+            hashtag_posts: use_signal(Vec::new),
+            bookmarks: use_signal(Vec::new),
+            my_delegations: use_signal(Vec::new),
+            muted_users: use_signal(Vec::new),
+            blocked_users: use_signal(Vec::new),
+            network_diagnostics: use_signal(|| None),
+            peer_scores: use_signal(std::collections::HashMap::new),
+            full_sync: use_signal(|| None),
+            multisig_proposals: use_signal(Vec::new),
+            transaction_history: use_signal(|| (String::new(), Vec::new(), 0)),
+            spending_limit: use_signal(|| (None, 0)),
+            spending_limit_exceeded: use_signal(|| None),
+            pinned_blocks: use_signal(Vec::new),
+            pin_policy: use_signal(|| crate::backend::DEFAULT_PIN_POLICY.iter().map(|t| t.to_string()).collect()),
+            last_vacuum: use_signal(|| None),
+            index_rebuild: use_signal(|| (false, None)),
+            store_root: use_signal(|| None),
+            identity_backup: use_signal(|| None),
+            identity_import_error: use_signal(|| None),
+            peer_count_low: use_signal(|| None),
+            trending_posts: use_signal(Vec::new),
+            scheduled_posts: use_signal(Vec::new),
+            mentions: use_signal(Vec::new),
+            mentions_seen_at: use_signal(|| None),
+            group_member_activity: use_signal(std::collections::HashMap::new),
+            proposal_progress: use_signal(std::collections::HashMap::new),
+            my_application_status: use_signal(|| None),
+            contract_versions: use_signal(std::collections::HashMap::new),
+            feed_strategy: use_signal(crate::backend::FeedStrategy::default),
+            ratchet_sessions: use_signal(std::collections::HashSet::new),
+            message_receipts: use_signal(std::collections::HashMap::new),
         }
     }
 }
\ No newline at end of file
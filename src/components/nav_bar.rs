@@ -5,6 +5,14 @@ use crate::Route;
 pub fn NavComponent() -> Element {
     let app_state = use_context::<crate::components::AppState>();
     let verification_status = app_state.verification_status.read();
+    let my_positions = app_state.my_elected_positions.read().clone();
+    let seen_at = (app_state.mentions_seen_at)();
+    let unread_mentions = app_state.mentions.read().iter().filter(|n| seen_at.is_none_or(|t| n.timestamp > t)).count();
+
+    let cmd_tx = use_context::<tokio::sync::mpsc::UnboundedSender<crate::backend::AppCmd>>();
+    use_effect(move || {
+        let _ = cmd_tx.send(crate::backend::AppCmd::FetchMentions { limit: 50 });
+    });
 
     rsx! {
         div { class: "min-h-screen flex flex-col",
@@ -27,6 +35,20 @@ pub fn NavComponent() -> Element {
                             },
                             _ => rsx! {}
                         }
+                        for ministry in my_positions {
+                            span { class: "badge badge-primary ml-2", title: "Elected Official", "🏛️ {ministry}" }
+                        }
+                    }
+                    if unread_mentions > 0 {
+                        button {
+                            class: "badge badge-primary ml-2 cursor-pointer",
+                            title: "Unread mentions",
+                            onclick: move |_| {
+                                let mut app_state = use_context::<crate::components::AppState>();
+                                app_state.browser_url.set("sp://home.super".to_string());
+                            },
+                            "🔔 {unread_mentions}"
+                        }
                     }
                 }
             }
@@ -2,6 +2,8 @@ use dioxus::prelude::*;
 use crate::backend::AppCmd;
 use crate::components::common::BlobImage;
 
+const HISTORY_PAGE_SIZE: usize = 10;
+
 #[component]
 pub fn ProfileComponent(peer_id: Option<String>) -> Element {
     let app_state = use_context::<crate::components::AppState>();
@@ -16,10 +18,20 @@ pub fn ProfileComponent(peer_id: Option<String>) -> Element {
     let mut bio = use_signal(|| "".to_string());
     let mut recipient_id = use_signal(|| "".to_string());
     let mut amount = use_signal(|| "".to_string());
+    let mut multisig_signers = use_signal(String::new);
+    let mut multisig_threshold = use_signal(|| "2".to_string());
+    let mut history_offset = use_signal(|| 0usize);
+    let mut spending_limit_input = use_signal(String::new);
+    let mut backup_password = use_signal(String::new);
+    let mut import_bundle = use_signal(String::new);
     
 
     // Tabs
     let mut active_tab = use_signal(|| "info".to_string());
+    let mut author_page_query = use_signal(String::new);
+    let mut include_cancelled_listings = use_signal(|| false);
+    let mut endorse_skill = use_signal(String::new);
+    let mut endorse_evidence = use_signal(String::new);
     let user_posts = app_state.user_posts;
 
     // File Upload State
@@ -90,20 +102,41 @@ pub fn ProfileComponent(peer_id: Option<String>) -> Element {
         if is_own_profile {
             let _ = cmd_tx_fetch.send(AppCmd::FetchMyProfile);
             let _ = cmd_tx_fetch.send(AppCmd::FetchBalance);
-            let _ = cmd_tx_fetch.send(AppCmd::FetchPendingTransfers);
+            let _ = cmd_tx_fetch.send(AppCmd::FetchIncomingTransfers);
+            let _ = cmd_tx_fetch.send(AppCmd::FetchMultiSigProposals);
+            let _ = cmd_tx_fetch.send(AppCmd::FetchSpendingLimit);
+            let _ = cmd_tx_fetch.send(AppCmd::FetchTransactionHistory { peer_id: target_id_fetch.clone(), offset: 0, limit: HISTORY_PAGE_SIZE });
             let _ = cmd_tx_fetch.send(AppCmd::FetchUbiTimer);
             let _ = cmd_tx_fetch.send(AppCmd::FetchStorageStats);
+            let _ = cmd_tx_fetch.send(AppCmd::FetchStoreRoot);
+            let _ = cmd_tx_fetch.send(AppCmd::FetchMyStorageQuotaUsageByType);
+            let _ = cmd_tx_fetch.send(AppCmd::FetchNetworkDiagnostics);
+            let _ = cmd_tx_fetch.send(AppCmd::FetchPeerScores);
             let _ = cmd_tx_fetch.send(AppCmd::FetchMyWebPages);
             let _ = cmd_tx_fetch.send(AppCmd::FetchMyFiles);
+            let _ = cmd_tx_fetch.send(AppCmd::FetchPinnedBlocks);
             let _ = cmd_tx_fetch.send(AppCmd::FetchReputation { peer_id: target_id_fetch.clone() });
             let _ = cmd_tx_fetch.send(AppCmd::FetchMyCertifications);
+            let _ = cmd_tx_fetch.send(AppCmd::FetchMyProposals);
+            let _ = cmd_tx_fetch.send(AppCmd::FetchMyJuryStats);
+            let _ = cmd_tx_fetch.send(AppCmd::FetchMyEscrows);
         } else {
             let _ = cmd_tx_fetch.send(AppCmd::FetchUserProfile { peer_id: target_id_fetch.clone() });
             let _ = cmd_tx_fetch.send(AppCmd::FetchReputation { peer_id: target_id_fetch.clone() });
             let _ = cmd_tx_fetch.send(AppCmd::FetchCertifications { peer_id: target_id_fetch.clone() });
+            let _ = cmd_tx_fetch.send(AppCmd::FetchProposalsByAuthor { peer_id: target_id_fetch.clone() });
+            let _ = cmd_tx_fetch.send(AppCmd::FetchMutualFollowers { peer_id: target_id_fetch.clone() });
+            let _ = cmd_tx_fetch.send(AppCmd::FetchWebSearchResultsFromPeer { query: String::new(), peer_id: target_id_fetch.clone() });
+            let _ = cmd_tx_fetch.send(AppCmd::FetchMutedUsers);
+            let _ = cmd_tx_fetch.send(AppCmd::FetchBlockedUsers);
         }
         // Always fetch posts for the profile we are viewing
         let _ = cmd_tx_fetch.send(AppCmd::FetchGivenUserPosts { peer_id: target_id_fetch.clone() });
+        let _ = cmd_tx_fetch.send(AppCmd::FetchActiveOfficials);
+        let _ = cmd_tx_fetch.send(AppCmd::FetchPeerCoursesCompleted { peer_id: target_id_fetch.clone() });
+        let _ = cmd_tx_fetch.send(AppCmd::FetchEndorsements { peer_id: target_id_fetch.clone() });
+        let _ = cmd_tx_fetch.send(AppCmd::FetchTrustScore { peer_id: target_id_fetch.clone() });
+        let _ = cmd_tx_fetch.send(AppCmd::FetchNodeByAuthorAndType { peer_id: target_id_fetch.clone(), node_type: "course".to_string(), limit: 10, offset: 0 });
     }
 
     // UBI Timer - compute once before RSX
@@ -140,13 +173,20 @@ pub fn ProfileComponent(peer_id: Option<String>) -> Element {
 
     // Pre-read all state BEFORE RSX to avoid reactive issues
     let balance = *app_state.balance.read();
-    let pending_transfers = app_state.pending_transfers.read().clone();
+    let incoming_transfers = app_state.incoming_transfers.read().clone();
     let reputation = app_state.reputation.read().clone();
     let contracts = app_state.contracts.read().clone();
     let contract_states = app_state.contract_states.read().clone();
     let web_pages = app_state.my_web_pages.read().clone();
+    let web_stats = app_state.web_stats.read().clone();
     let my_files = app_state.files.read().clone();
     let verification_status = app_state.verification_status.read().clone();
+    let my_positions = app_state.my_elected_positions.read().clone();
+    let serving_as = crate::components::common::offices_held(&app_state.active_officials.read(), &target_id)
+        .iter()
+        .map(|ministry| format!("Minister of {}", ministry))
+        .collect::<Vec<_>>()
+        .join(", ");
     let following = app_state.following.read();
     let is_following = following.contains(&target_id);
     
@@ -172,12 +212,31 @@ pub fn ProfileComponent(peer_id: Option<String>) -> Element {
     let on_mint = move |_| { let _ = cmd_tx_mint.send(AppCmd::ClaimUbi); };
 
     let cmd_tx_send = cmd_tx.clone();
+    let mut spending_limit_exceeded = app_state.spending_limit_exceeded;
     let on_send = move |_| {
         if let Ok(amt) = amount().parse::<u64>() {
+            spending_limit_exceeded.set(None);
             let _ = cmd_tx_send.send(AppCmd::SendToken { recipient: recipient_id(), amount: amt });
+            let _ = cmd_tx_send.send(AppCmd::FetchSpendingLimit);
+        }
+    };
+
+    // Large transfers can wait on co-signers instead of going out at once
+    let cmd_tx_multisig = cmd_tx.clone();
+    let on_send_multisig = move |_| {
+        let signers: Vec<String> = multisig_signers().split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect();
+        if let (Ok(amt), Ok(threshold)) = (amount().parse::<u64>(), multisig_threshold().parse::<usize>()) {
+            let _ = cmd_tx_multisig.send(AppCmd::ProposeMultiSigTransfer {
+                recipients: vec![(recipient_id(), amt)],
+                required_signers: signers,
+                threshold,
+                expires_at: chrono::Utc::now() + chrono::Duration::days(7),
+            });
         }
     };
 
+    let cmd_tx_pin = cmd_tx.clone();
+
     let cmd_tx_vouch = cmd_tx.clone();
     let target_id_vouch = target_id.clone();
     let on_vouch = move |_| {
@@ -190,6 +249,19 @@ pub fn ProfileComponent(peer_id: Option<String>) -> Element {
         let _ = cmd_tx_follow.send(AppCmd::FollowUser { target: target_id_follow.clone(), follow: !is_following });
     };
 
+    let is_muted = app_state.muted_users.read().contains(&target_id);
+    let is_blocked = app_state.blocked_users.read().contains(&target_id);
+    let cmd_tx_mute = cmd_tx.clone();
+    let target_id_mute = target_id.clone();
+    let on_mute = move |_| {
+        let _ = cmd_tx_mute.send(AppCmd::MuteUser { peer_id: target_id_mute.clone(), unmute: is_muted });
+    };
+    let cmd_tx_block = cmd_tx.clone();
+    let target_id_block = target_id.clone();
+    let on_block = move |_| {
+        let _ = cmd_tx_block.send(AppCmd::BlockUser { peer_id: target_id_block.clone(), unblock: is_blocked });
+    };
+
 
 
 
@@ -207,6 +279,57 @@ pub fn ProfileComponent(peer_id: Option<String>) -> Element {
                         p { class: "text-[var(--text-secondary)]", 
                             if is_own_profile { "Manage your identity" } else { "View user details" }
                         }
+                        if is_own_profile && !my_positions.is_empty() {
+                            div { class: "flex flex-wrap gap-2 mt-2",
+                                for ministry in my_positions.iter() {
+                                    span { class: "badge badge-primary", "🏛️ Elected: {ministry}" }
+                                }
+                            }
+                        }
+                        if !serving_as.is_empty() {
+                            p { class: "text-sm font-bold mt-2", "Currently serving as: {serving_as}" }
+                        }
+                        if let Some(trust) = app_state.trust_scores.read().get(&target_id).copied() {
+                            {
+                                let percent = (trust * 100.0).round() as u32;
+                                rsx! {
+                                    span { class: "badge mt-2", title: "Trust score: vouch chain, vouchers, certifications, participation, jury duty and account age", "🛡️ Trust {percent}%" }
+                                }
+                            }
+                        }
+                        if !is_own_profile {
+                            {
+                                let mutual = app_state.mutual_followers.read().get(&target_id).cloned().unwrap_or_default();
+                                let names: Vec<String> = mutual.iter()
+                                    .map(|(id, p)| p.as_ref().map(|p| p.name.clone()).unwrap_or_else(|| format!("{}...", id.get(0..8).unwrap_or(id))))
+                                    .collect();
+                                let count = mutual.len();
+                                let label = if count == 1 { "mutual connection" } else { "mutual connections" };
+                                let summary = names.iter().take(3).cloned().collect::<Vec<_>>().join(", ");
+                                let more = count.saturating_sub(3);
+                                rsx! {
+                                    if count > 0 {
+                                        div { class: "flex items-center gap-2 mt-2 text-sm text-[var(--text-secondary)]",
+                                            div { class: "flex -space-x-2",
+                                                for (id, profile) in mutual.iter().take(3) {
+                                                    div { key: "{id}", class: "avatar avatar-sm overflow-hidden border-2 border-[var(--bg-primary)]",
+                                                        if let Some(cid) = profile.as_ref().and_then(|p| p.photo.clone()) {
+                                                            BlobImage { cid: cid, class: Some("w-full h-full object-cover".to_string()) }
+                                                        } else {
+                                                            "{id.get(0..2).unwrap_or(\"??\")}"
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                            span { "{count} {label}: {summary}" }
+                                            if more > 0 {
+                                                span { " and {more} more" }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
                     }
                     div { class: "flex items-center gap-2",
                         if !is_own_profile {
@@ -219,6 +342,20 @@ pub fn ProfileComponent(peer_id: Option<String>) -> Element {
                         if !is_own_profile && is_verified_viewer {
                             button { class: "btn btn-primary", onclick: on_vouch, "✓ Vouch" }
                         }
+                        if !is_own_profile {
+                            button {
+                                class: "btn btn-secondary",
+                                title: "Hide their posts and stories from your feeds",
+                                onclick: on_mute,
+                                if is_muted { "🔊 Unmute" } else { "🔇 Mute" }
+                            }
+                            button {
+                                class: if is_blocked { "btn btn-secondary" } else { "btn btn-destructive" },
+                                title: "Also ignore their messages and everything else they publish",
+                                onclick: on_block,
+                                if is_blocked { "Unblock" } else { "🚫 Block" }
+                            }
+                        }
                     }
                 }
             }
@@ -235,6 +372,30 @@ pub fn ProfileComponent(peer_id: Option<String>) -> Element {
                     onclick: move |_| active_tab.set("posts".to_string()),
                     "Posts"
                 }
+                div { 
+                    class: if active_tab() == "governance" { "nav-button active cursor-pointer" } else { "nav-button cursor-pointer" },
+                    onclick: move |_| active_tab.set("governance".to_string()),
+                    "Governance Activity"
+                }
+                div { 
+                    class: if active_tab() == "education" { "nav-button active cursor-pointer" } else { "nav-button cursor-pointer" },
+                    onclick: move |_| active_tab.set("education".to_string()),
+                    "Education"
+                }
+                {
+                    let cmd_tx_market = cmd_tx.clone();
+                    let target_id_market = target_id.clone();
+                    rsx! {
+                        div { 
+                            class: if active_tab() == "market" { "nav-button active cursor-pointer" } else { "nav-button cursor-pointer" },
+                            onclick: move |_| {
+                                let _ = cmd_tx_market.send(AppCmd::FetchPeerListingHistory { peer_id: target_id_market.clone(), include_closed: include_cancelled_listings() });
+                                active_tab.set("market".to_string());
+                            },
+                            "Marketplace History"
+                        }
+                    }
+                }
             }
 
             if active_tab() == "posts" {
@@ -268,6 +429,231 @@ pub fn ProfileComponent(peer_id: Option<String>) -> Element {
                         }
                     }
                 }
+            } else if active_tab() == "market" {
+                {
+                    let listings: Vec<crate::backend::dag::DagNode> = app_state.listings.read().iter()
+                        .filter(|n| n.author == target_id)
+                        .cloned()
+                        .collect();
+                    let sold_count = listings.iter()
+                        .filter(|n| matches!(&n.payload, crate::backend::dag::DagPayload::Listing(l) if l.status == crate::backend::dag::ListingStatus::Sold))
+                        .count();
+                    let cmd_tx_toggle = cmd_tx.clone();
+                    let target_id_toggle = target_id.clone();
+                    rsx! {
+                        div { class: "flex justify-between items-center mb-4",
+                            p { class: "text-sm text-[var(--text-secondary)]", "{listings.len()} listings · {sold_count} sold" }
+                            label { class: "flex items-center gap-2 text-sm cursor-pointer",
+                                input {
+                                    r#type: "checkbox",
+                                    checked: include_cancelled_listings(),
+                                    onchange: move |e| {
+                                        include_cancelled_listings.set(e.checked());
+                                        let _ = cmd_tx_toggle.send(AppCmd::FetchPeerListingHistory { peer_id: target_id_toggle.clone(), include_closed: e.checked() });
+                                    }
+                                }
+                                "Include cancelled"
+                            }
+                        }
+                        if listings.is_empty() {
+                            div { class: "empty-state py-12",
+                                p { class: "empty-state-text", "No listings yet" }
+                            }
+                        }
+                        for node in listings.iter() {
+                            if let crate::backend::dag::DagPayload::Listing(l) = &node.payload {
+                                {
+                                    let (badge_class, status_label) = match l.status {
+                                        crate::backend::dag::ListingStatus::Active => ("badge badge-primary", "Active"),
+                                        crate::backend::dag::ListingStatus::Sold => ("badge badge-success", "Sold"),
+                                        crate::backend::dag::ListingStatus::Cancelled => ("badge", "Cancelled"),
+                                    };
+                                    let when = node.timestamp.format("%Y-%m-%d").to_string();
+                                    rsx! {
+                                        div { key: "{node.id}", class: "list-item flex justify-between items-center",
+                                            div { class: "list-item-content",
+                                                p { class: "list-item-title", "{l.title}" }
+                                                p { class: "list-item-subtitle", "{l.price} SUPER · {when}" }
+                                            }
+                                            span { class: "{badge_class}", "{status_label}" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            } else if active_tab() == "education" {
+                {
+                    let (completed, in_progress) = app_state.peer_courses.read().get(&target_id).cloned().unwrap_or_default();
+                    let cert_count = app_state.certifications.read().len();
+                    let course_rows = |results: Vec<crate::backend::CourseResult>| -> Vec<(String, String, u8, String)> {
+                        results.into_iter().filter_map(|(course, submission)| match (&course.payload, &submission.payload) {
+                            (crate::backend::dag::DagPayload::Course(c), crate::backend::dag::DagPayload::ExamSubmission(s)) => {
+                                Some((submission.id.clone(), c.title.clone(), s.score, submission.timestamp.format("%Y-%m-%d").to_string()))
+                            }
+                            _ => None,
+                        }).collect()
+                    };
+                    let completed_rows = course_rows(completed);
+                    let in_progress_rows = course_rows(in_progress);
+                    let taken = completed_rows.len() + in_progress_rows.len();
+                    let taught = app_state.nodes_by_author_type.read()
+                        .get(&(target_id.clone(), "course".to_string()))
+                        .cloned()
+                        .unwrap_or_default();
+                    rsx! {
+                        div { class: "grid grid-cols-3 gap-4 mb-6",
+                            div { class: "card text-center p-2",
+                                p { class: "text-xs text-[var(--text-secondary)]", "Courses Taken" }
+                                p { class: "text-lg font-bold", "{taken}" }
+                            }
+                            div { class: "card text-center p-2",
+                                p { class: "text-xs text-[var(--text-secondary)]", "Completed" }
+                                p { class: "text-lg font-bold", "{completed_rows.len()}" }
+                            }
+                            div { class: "card text-center p-2",
+                                p { class: "text-xs text-[var(--text-secondary)]", "Certifications" }
+                                p { class: "text-lg font-bold", "{cert_count}" }
+                            }
+                        }
+                        div { class: "panel mb-4",
+                            h3 { class: "font-bold mb-2", "🎓 Completed Courses" }
+                            if completed_rows.is_empty() {
+                                p { class: "text-sm text-[var(--text-muted)]", "No courses completed yet." }
+                            }
+                            for (id, title, score, when) in completed_rows.into_iter() {
+                                div { key: "{id}", class: "list-item flex justify-between items-center",
+                                    div { class: "list-item-content",
+                                        p { class: "list-item-title", "{title}" }
+                                        p { class: "list-item-subtitle", "Passed on {when}" }
+                                    }
+                                    span { class: "badge badge-primary", "{score}%" }
+                                }
+                            }
+                        }
+                        if !taught.is_empty() {
+                            div { class: "panel mb-4",
+                                h3 { class: "font-bold mb-2", "👩‍🏫 Courses Taught" }
+                                for node in taught.iter() {
+                                    if let crate::backend::dag::DagPayload::Course(c) = &node.payload {
+                                        div { key: "{node.id}", class: "list-item flex justify-between items-center",
+                                            div { class: "list-item-content",
+                                                p { class: "list-item-title", "{c.title}" }
+                                                p { class: "list-item-subtitle", {format!("{:?}", c.category)} }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        if !in_progress_rows.is_empty() {
+                            div { class: "panel",
+                                h3 { class: "font-bold mb-2", "📖 In Progress" }
+                                for (id, title, score, when) in in_progress_rows.into_iter() {
+                                    div { key: "{id}", class: "list-item flex justify-between items-center",
+                                        div { class: "list-item-content",
+                                            p { class: "list-item-title", "{title}" }
+                                            p { class: "list-item-subtitle", "Best attempt on {when}" }
+                                        }
+                                        span { class: "badge", "Best: {score}%" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            } else if active_tab() == "governance" {
+                {
+                    let record = app_state.governance_records.read().get(&target_id).cloned().unwrap_or_default();
+                    let proposal_count = record.proposals.len();
+                    let candidacy_count = record.candidacies.len();
+                    rsx! {
+                        div { class: "grid grid-cols-3 gap-4 mb-6",
+                            div { class: "card text-center p-2",
+                                p { class: "text-xs text-[var(--text-secondary)]", "Proposals Authored" }
+                                p { class: "text-lg font-bold", "{proposal_count}" }
+                            }
+                            div { class: "card text-center p-2",
+                                p { class: "text-xs text-[var(--text-secondary)]", "Votes Cast" }
+                                p { class: "text-lg font-bold", "{record.votes_cast}" }
+                            }
+                            div { class: "card text-center p-2",
+                                p { class: "text-xs text-[var(--text-secondary)]", "Candidacies" }
+                                p { class: "text-lg font-bold", "{candidacy_count}" }
+                            }
+                        }
+                        if is_own_profile {
+                            if let Some(stats) = app_state.jury_stats.read().clone() {
+                                div { class: "panel mb-6",
+                                    div { class: "panel-header",
+                                        h2 { class: "panel-title", "⚖️ Jury Duty" }
+                                    }
+                                    div { class: "grid grid-cols-2 md:grid-cols-4 gap-4",
+                                        div { class: "card text-center p-2",
+                                            p { class: "text-xs text-[var(--text-secondary)]", "Cases Assigned" }
+                                            p { class: "text-lg font-bold", "{stats.cases_assigned}" }
+                                        }
+                                        div { class: "card text-center p-2",
+                                            p { class: "text-xs text-[var(--text-secondary)]", "Votes Cast" }
+                                            p { class: "text-lg font-bold", "{stats.votes_cast}" }
+                                        }
+                                        div { class: "card text-center p-2",
+                                            p { class: "text-xs text-[var(--text-secondary)]", "Cases Decided" }
+                                            p { class: "text-lg font-bold", "{stats.cases_decided}" }
+                                        }
+                                        div { class: "card text-center p-2",
+                                            p { class: "text-xs text-[var(--text-secondary)]", "Avg. Time to Vote" }
+                                            p { class: "text-lg font-bold", "{stats.avg_time_to_vote_hours:.1}h" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        div { class: "panel mb-6",
+                            div { class: "panel-header",
+                                h2 { class: "panel-title", "Proposals" }
+                            }
+                            if record.proposals.is_empty() {
+                                div { class: "empty-state py-4", "No proposals authored" }
+                            }
+                            for node in record.proposals.iter() {
+                                if let crate::backend::dag::DagPayload::Proposal(ref p) = node.payload {
+                                    {
+                                        let date = node.timestamp.format("%Y-%m-%d").to_string();
+                                        rsx! {
+                                            div { key: "{node.id}", class: "card mb-2 flex justify-between items-center",
+                                                span { class: "font-bold", "{p.title}" }
+                                                span { class: "text-xs text-[var(--text-muted)]", "{date}" }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        div { class: "panel",
+                            div { class: "panel-header",
+                                h2 { class: "panel-title", "Candidacies" }
+                            }
+                            if record.candidacies.is_empty() {
+                                div { class: "empty-state py-4", "No candidacies declared" }
+                            }
+                            for node in record.candidacies.iter() {
+                                if let crate::backend::dag::DagPayload::Candidacy(ref c) = node.payload {
+                                    {
+                                        let date = node.timestamp.format("%Y-%m-%d").to_string();
+                                        rsx! {
+                                            div { key: "{node.id}", class: "card mb-2 flex justify-between items-center",
+                                                span { class: "font-bold", "{c.ministry:?}" }
+                                                span { class: "text-xs text-[var(--text-muted)]", "{date}" }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
             } else {
             // Grid
             div { class: "grid grid-cols-1 lg:grid-cols-2 gap-6",
@@ -352,6 +738,8 @@ pub fn ProfileComponent(peer_id: Option<String>) -> Element {
                                 let cont = rep.breakdown.content;
                                 let gov = rep.breakdown.governance;
                                 let stor = rep.breakdown.storage;
+                                let civic = rep.breakdown.civic_participation;
+                                let seller = rep.breakdown.seller;
                                 rsx! {
                                     div { class: "grid grid-cols-2 gap-4",
                                         div { class: "card text-center p-2",
@@ -370,6 +758,14 @@ pub fn ProfileComponent(peer_id: Option<String>) -> Element {
                                             p { class: "text-xs text-[var(--text-secondary)]", "Storage" }
                                             p { class: "text-lg font-bold", "{stor}" }
                                         }
+                                        div { class: "card text-center p-2",
+                                            p { class: "text-xs text-[var(--text-secondary)]", "Civic Participation" }
+                                            p { class: "text-lg font-bold", "{civic}" }
+                                        }
+                                        div { class: "card text-center p-2",
+                                            p { class: "text-xs text-[var(--text-secondary)]", "Seller" }
+                                            p { class: "text-lg font-bold", "{seller}" }
+                                        }
                                     }
                                 }
                             }
@@ -378,6 +774,78 @@ pub fn ProfileComponent(peer_id: Option<String>) -> Element {
                         }
                     }
 
+                    // Skill endorsements from other peers
+                    {
+                        let endorsements = app_state.endorsements.read().get(&target_id).cloned().unwrap_or_default();
+                        let skill_counts = reputation.as_ref().map(|r| r.endorsements_by_skill.clone()).unwrap_or_default();
+                        let cmd_tx_endorse = cmd_tx.clone();
+                        let target_id_endorse = target_id.clone();
+                        rsx! {
+                            div { class: "panel",
+                                div { class: "panel-header",
+                                    h2 { class: "panel-title", "Skills & Endorsements" }
+                                }
+                                if !skill_counts.is_empty() {
+                                    div { class: "flex flex-wrap gap-2 mb-4",
+                                        for (skill, count) in skill_counts.into_iter() {
+                                            span { key: "{skill}", class: "badge badge-primary", "{skill} · {count}" }
+                                        }
+                                    }
+                                }
+                                if endorsements.is_empty() {
+                                    p { class: "text-sm text-[var(--text-muted)]", "No endorsements yet" }
+                                }
+                                for (node, endorser_name) in endorsements.into_iter() {
+                                    if let crate::backend::dag::DagPayload::Endorsement(e) = &node.payload {
+                                        div { key: "{node.id}", class: "list-item",
+                                            div { class: "list-item-content",
+                                                p { class: "list-item-title", "{e.skill}" }
+                                                p { class: "list-item-subtitle", "Endorsed by {endorser_name}" }
+                                                if !e.evidence.is_empty() {
+                                                    p { class: "text-sm text-[var(--text-secondary)] mt-1", "{e.evidence}" }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                                if !is_own_profile && is_verified_viewer {
+                                    div { class: "form-group mt-4",
+                                        label { class: "form-label", "Endorse a skill" }
+                                        input {
+                                            class: "input mb-2",
+                                            placeholder: "Skill (e.g. Rust, Carpentry)",
+                                            value: "{endorse_skill}",
+                                            oninput: move |e| endorse_skill.set(e.value())
+                                        }
+                                        input {
+                                            class: "input",
+                                            placeholder: "Evidence (optional)",
+                                            value: "{endorse_evidence}",
+                                            oninput: move |e| endorse_evidence.set(e.value())
+                                        }
+                                    }
+                                    div { class: "action-group",
+                                        button {
+                                            class: "btn btn-secondary",
+                                            disabled: endorse_skill().trim().is_empty(),
+                                            onclick: move |_| {
+                                                let _ = cmd_tx_endorse.send(AppCmd::EndorseSkill {
+                                                    target_peer_id: target_id_endorse.clone(),
+                                                    skill: endorse_skill(),
+                                                    evidence: endorse_evidence(),
+                                                });
+                                                let _ = cmd_tx_endorse.send(AppCmd::FetchReputation { peer_id: target_id_endorse.clone() });
+                                                endorse_skill.set(String::new());
+                                                endorse_evidence.set(String::new());
+                                            },
+                                            "Endorse"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+
                     // Profile form / view
                     div { class: "panel",
                         div { class: "panel-header",
@@ -419,6 +887,61 @@ pub fn ProfileComponent(peer_id: Option<String>) -> Element {
                             }
                         }
                     }
+
+                    // Search within this author's published pages
+                    if !is_own_profile {
+                        {
+                            let author_pages: Vec<(String, String)> = app_state.web_search_results.read().iter()
+                                .filter(|n| n.author == target_id)
+                                .filter_map(|n| match &n.payload {
+                                    crate::backend::dag::DagPayload::Web(w) => Some((w.url.clone(), w.title.clone())),
+                                    _ => None,
+                                })
+                                .collect();
+                            let cmd_tx_search = cmd_tx.clone();
+                            let target_id_search = target_id.clone();
+                            rsx! {
+                                div { class: "panel",
+                                    div { class: "panel-header",
+                                        h2 { class: "panel-title", "SuperWeb Pages" }
+                                    }
+                                    div { class: "flex gap-2 mb-3",
+                                        input {
+                                            class: "input flex-1",
+                                            placeholder: "Search this author's pages...",
+                                            value: "{author_page_query}",
+                                            oninput: move |e| author_page_query.set(e.value())
+                                        }
+                                        button {
+                                            class: "btn btn-secondary btn-sm",
+                                            onclick: move |_| {
+                                                let _ = cmd_tx_search.send(AppCmd::FetchWebSearchResultsFromPeer { query: author_page_query(), peer_id: target_id_search.clone() });
+                                            },
+                                            "Search"
+                                        }
+                                    }
+                                    if author_pages.is_empty() {
+                                        p { class: "text-sm text-[var(--text-muted)]", "No pages found" }
+                                    }
+                                    for (page_url, page_title) in author_pages.into_iter() {
+                                        {
+                                            let mut browser_url = app_state.browser_url;
+                                            let link = format!("sp://{}", page_url);
+                                            rsx! {
+                                                div { class: "list-item cursor-pointer",
+                                                    onclick: move |_| browser_url.set(link.clone()),
+                                                    div { class: "list-item-content",
+                                                        p { class: "list-item-title", "{page_title}" }
+                                                        p { class: "list-item-subtitle", "sp://{page_url}" }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
                 }
 
                 // Right column (Wallet & more) - only for own profile
@@ -448,6 +971,40 @@ pub fn ProfileComponent(peer_id: Option<String>) -> Element {
                                 p { class: "text-xs text-[var(--text-muted)] mt-2", "Next claim: {time_remaining}" }
                             }
 
+                            // Locked funds: escrows still awaiting release or refund
+                            {
+                                let mut locked: Vec<(String, crate::backend::EscrowInfo)> = app_state.escrow_statuses.read().iter()
+                                    .filter(|(_, (_, status, _))| matches!(status, crate::backend::dag::EscrowStatus::Locked | crate::backend::dag::EscrowStatus::Disputed))
+                                    .map(|(contract_id, info)| (contract_id.clone(), info.clone()))
+                                    .collect();
+                                locked.sort_by(|a, b| a.0.cmp(&b.0));
+                                rsx! {
+                                    if !locked.is_empty() {
+                                        p { class: "font-medium mb-2", "Locked Funds" }
+                                        for (contract_id, (locked_amount, status, counterparty)) in locked.into_iter() {
+                                            {
+                                                let incoming = counterparty == local_id;
+                                                let direction = if incoming { "Incoming" } else { "Outgoing" };
+                                                let disputed = status == crate::backend::dag::EscrowStatus::Disputed;
+                                                rsx! {
+                                                    div { key: "{contract_id}", class: "list-item flex justify-between items-center",
+                                                        div { class: "list-item-content",
+                                                            p { class: "list-item-title", "{locked_amount} SUPER · {direction}" }
+                                                            p { class: "list-item-subtitle", "Contract {contract_id.get(0..12).unwrap_or(&contract_id)}..." }
+                                                        }
+                                                        if disputed {
+                                                            span { class: "badge", "Disputed" }
+                                                        } else {
+                                                            span { class: "badge badge-primary", "Locked" }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+
                             // Send
                             div { class: "divider" }
                             p { class: "font-medium mb-4", "Send Tokens" }
@@ -471,30 +1028,94 @@ pub fn ProfileComponent(peer_id: Option<String>) -> Element {
                                 }
                             }
                             button { class: "btn btn-primary", onclick: on_send, "Send" }
+                            if let Some((attempted, limit, spent_today)) = spending_limit_exceeded() {
+                                p { class: "text-sm text-[var(--error)] mt-2",
+                                    "Sending {attempted} SUPER would exceed your daily limit of {limit} ({spent_today} already spent today)."
+                                }
+                            }
 
-                            // Pending transfers
-                            if !pending_transfers.is_empty() {
+                            // Daily spending limit
+                            {
+                                let (daily_limit, spent_today) = (app_state.spending_limit)();
+                                let limit_label = match daily_limit {
+                                    Some(limit) => format!("{} of {} SUPER spent today", spent_today, limit),
+                                    None => format!("No daily limit ({} SUPER spent today)", spent_today),
+                                };
+                                let cmd_tx_limit = cmd_tx.clone();
+                                rsx! {
+                                    div { class: "form-group mt-4",
+                                        label { class: "form-label", "Daily spending limit" }
+                                        div { class: "flex gap-2",
+                                            input {
+                                                class: "input",
+                                                r#type: "number",
+                                                placeholder: "0 = unlimited",
+                                                value: "{spending_limit_input}",
+                                                oninput: move |e| spending_limit_input.set(e.value())
+                                            }
+                                            button {
+                                                class: "btn btn-secondary",
+                                                onclick: move |_| {
+                                                    if let Ok(daily_limit) = spending_limit_input().parse::<u64>() {
+                                                        let _ = cmd_tx_limit.send(AppCmd::SetSpendingLimit { daily_limit });
+                                                    }
+                                                },
+                                                "Set"
+                                            }
+                                        }
+                                        p { class: "text-xs text-[var(--text-muted)] mt-1", "{limit_label}" }
+                                    }
+                                }
+                            }
+
+                            // Multi-sig: the same transfer, held until enough co-signers approve
+                            div { class: "form-group mt-4",
+                                label { class: "form-label", "Co-signers (optional)" }
+                                input {
+                                    class: "input",
+                                    placeholder: "Peer IDs, comma separated",
+                                    value: "{multisig_signers}",
+                                    oninput: move |e| multisig_signers.set(e.value())
+                                }
+                            }
+                            div { class: "form-group",
+                                label { class: "form-label", "Approvals required" }
+                                input {
+                                    class: "input",
+                                    r#type: "number",
+                                    value: "{multisig_threshold}",
+                                    oninput: move |e| multisig_threshold.set(e.value())
+                                }
+                            }
+                            button { class: "btn btn-secondary", onclick: on_send_multisig, "Propose Multi-sig Transfer" }
+                            p { class: "text-xs text-[var(--text-muted)] mt-2", "Proposals expire after 7 days without enough approvals." }
+
+                            if !app_state.multisig_proposals.read().is_empty() {
                                 div { class: "divider" }
-                                p { class: "font-medium mb-2", "Incoming Transfers" }
-                                for node in pending_transfers.iter() {
-                                    if let crate::backend::dag::DagPayload::Token(ref t) = node.payload {
+                                p { class: "font-medium mb-2", "Multi-sig Transfers" }
+                                for (node, approvers, transfer) in app_state.multisig_proposals.read().iter() {
+                                    if let crate::backend::dag::DagPayload::MultiSigProposal(ref proposal) = node.payload {
                                         {
-                                            let amt = t.amount;
-                                            let sender = node.author.clone();
-                                            let burn_cid = node.id.clone();
-                                            let cmd_tx_claim = cmd_tx.clone();
+                                            let total: u64 = proposal.recipients.iter().map(|(_, amt)| amt).sum();
+                                            let status = if transfer.is_some() { "Executed".to_string() } else { format!("{}/{} approvals", approvers.len(), proposal.threshold) };
+                                            let can_approve = transfer.is_none() && proposal.required_signers.contains(&local_id) && !approvers.contains(&local_id);
+                                            let direction = if node.author == local_id { "You proposed" } else { "Awaiting your signature:" };
+                                            let proposal_cid = node.id.clone();
+                                            let cmd_tx_approve = cmd_tx.clone();
                                             rsx! {
                                                 div { class: "list-item flex justify-between items-center",
                                                     div { class: "list-item-content",
-                                                        p { class: "list-item-title", "{amt} SUPER" }
-                                                        p { class: "list-item-subtitle truncate", "From: {sender.get(0..12).unwrap_or(&sender)}..." }
+                                                        p { class: "list-item-title", "{direction} {total} SUPER to {proposal.recipients.len()} recipient(s)" }
+                                                        p { class: "list-item-subtitle", "{status}" }
                                                     }
-                                                    button { 
-                                                        class: "btn btn-success btn-sm",
-                                                        onclick: move |_| { 
-                                                            let _ = cmd_tx_claim.send(AppCmd::ClaimToken { burn_cid: burn_cid.clone() }); 
-                                                        },
-                                                        "Claim"
+                                                    if can_approve {
+                                                        button {
+                                                            class: "btn btn-success btn-sm",
+                                                            onclick: move |_| {
+                                                                let _ = cmd_tx_approve.send(AppCmd::ApproveMultiSigTransfer { proposal_cid: proposal_cid.clone() });
+                                                            },
+                                                            "Approve"
+                                                        }
                                                     }
                                                 }
                                             }
@@ -502,6 +1123,100 @@ pub fn ProfileComponent(peer_id: Option<String>) -> Element {
                                     }
                                 }
                             }
+
+                            // Transaction history, a page at a time
+                            {
+                                let (history_peer, records, total) = (app_state.transaction_history)();
+                                let offset = history_offset();
+                                let page_end = (offset + records.len()).min(total);
+                                let cmd_tx_prev = cmd_tx.clone();
+                                let cmd_tx_next = cmd_tx.clone();
+                                let peer_prev = local_id.clone();
+                                let peer_next = local_id.clone();
+                                rsx! {
+                                    if history_peer == local_id && total > 0 {
+                                        div { class: "divider" }
+                                        p { class: "font-medium mb-2", "History" }
+                                        for record in records.iter() {
+                                            {
+                                                let when = chrono::DateTime::from_timestamp(record.timestamp as i64, 0).map(|d| d.format("%Y-%m-%d %H:%M").to_string()).unwrap_or_default();
+                                                let is_tax = record.memo.as_deref().is_some_and(|m| m.starts_with(crate::backend::TAX_WITHHELD_MEMO));
+                                                let label = match record.action {
+                                                    _ if is_tax => "Tax withheld".to_string(),
+                                                    crate::backend::dag::TokenAction::Mint | crate::backend::dag::TokenAction::MintReward => "Minted".to_string(),
+                                                    crate::backend::dag::TokenAction::TransferClaim => "Claimed".to_string(),
+                                                    crate::backend::dag::TokenAction::Escrow => "Escrowed".to_string(),
+                                                    crate::backend::dag::TokenAction::Burn => "Transfer".to_string(),
+                                                };
+                                                let party = record.counterparty.as_ref().map(|p| format!(" · {}...", p.get(0..12).unwrap_or(p))).unwrap_or_default();
+                                                let memo = record.memo.clone().filter(|_| !is_tax).unwrap_or_default();
+                                                rsx! {
+                                                    div { class: "list-item",
+                                                        div { class: "list-item-content",
+                                                            p { class: "list-item-title", "{label}: {record.amount} SUPER{party}" }
+                                                            p { class: "list-item-subtitle", "{when} {memo}" }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                        div { class: "flex justify-between items-center mt-2",
+                                            button {
+                                                class: "btn btn-sm btn-secondary",
+                                                disabled: offset == 0,
+                                                onclick: move |_| {
+                                                    let prev = offset.saturating_sub(HISTORY_PAGE_SIZE);
+                                                    history_offset.set(prev);
+                                                    let _ = cmd_tx_prev.send(AppCmd::FetchTransactionHistory { peer_id: peer_prev.clone(), offset: prev, limit: HISTORY_PAGE_SIZE });
+                                                },
+                                                "Newer"
+                                            }
+                                            span { class: "text-xs text-[var(--text-muted)]", "{offset + 1}-{page_end} of {total}" }
+                                            button {
+                                                class: "btn btn-sm btn-secondary",
+                                                disabled: page_end >= total,
+                                                onclick: move |_| {
+                                                    let next = offset + HISTORY_PAGE_SIZE;
+                                                    history_offset.set(next);
+                                                    let _ = cmd_tx_next.send(AppCmd::FetchTransactionHistory { peer_id: peer_next.clone(), offset: next, limit: HISTORY_PAGE_SIZE });
+                                                },
+                                                "Older"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+
+                            // Pending transfers
+                            if !incoming_transfers.is_empty() {
+                                div { class: "divider" }
+                                p { class: "font-medium mb-2", "Incoming Transfers" }
+                                for (node, sender_profile, amt) in incoming_transfers.iter() {
+                                    {
+                                        let sender_name = match sender_profile {
+                                            Some(p) => p.name.clone(),
+                                            None => format!("{}...", node.author.get(0..12).unwrap_or(&node.author)),
+                                        };
+                                        let burn_cid = node.id.clone();
+                                        let cmd_tx_claim = cmd_tx.clone();
+                                        rsx! {
+                                            div { class: "list-item flex justify-between items-center",
+                                                div { class: "list-item-content",
+                                                    p { class: "list-item-title", "{sender_name} sent you {amt} SUPER" }
+                                                }
+                                                button { 
+                                                    class: "btn btn-success btn-sm",
+                                                    onclick: move |_| { 
+                                                        let _ = cmd_tx_claim.send(AppCmd::ClaimToken { burn_cid: burn_cid.clone() }); 
+                                                        let _ = cmd_tx_claim.send(AppCmd::FetchIncomingTransfers);
+                                                    },
+                                                    "Claim"
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
                         }
 
                         // Storage Stats - Device storage info
@@ -515,6 +1230,7 @@ pub fn ProfileComponent(peer_id: Option<String>) -> Element {
                                             class: "btn btn-sm btn-secondary",
                                             onclick: move |_| {
                                                 let _ = cmd_tx_storage.send(AppCmd::FetchStorageStats);
+                                                let _ = cmd_tx_storage.send(AppCmd::FetchMyStorageQuotaUsageByType);
                                             },
                                             "Refresh"
                                         }
@@ -545,6 +1261,35 @@ pub fn ProfileComponent(peer_id: Option<String>) -> Element {
                                         }
                                     }
                                     
+                                    // Personal footprint: what I have published, largest first
+                                    {
+                                        let mut usage: Vec<(String, (usize, usize))> = app_state.my_storage_usage.read().clone().into_iter().collect();
+                                        usage.sort_by_key(|(_, (_, bytes))| std::cmp::Reverse(*bytes));
+                                        let format_size = |bytes: usize| {
+                                            if bytes >= 1024 * 1024 {
+                                                format!("{:.1}MB", bytes as f64 / 1024.0 / 1024.0)
+                                            } else {
+                                                format!("{:.0}KB", (bytes as f64 / 1024.0).ceil())
+                                            }
+                                        };
+                                        let summary = usage.iter()
+                                            .map(|(node_type, (count, bytes))| {
+                                                let label = node_type.replace('_', " ");
+                                                let plural = if *count == 1 { "" } else { "s" };
+                                                format!("{} {}{} ({})", count, label, plural, format_size(*bytes))
+                                            })
+                                            .collect::<Vec<_>>()
+                                            .join(", ");
+                                        rsx! {
+                                            if !usage.is_empty() {
+                                                div { class: "mt-4 pt-4 border-t border-[var(--border-subtle)]",
+                                                    p { class: "text-sm font-medium text-[var(--text-primary)] mb-1", "Your Footprint" }
+                                                    p { class: "text-sm text-[var(--text-secondary)]", "You've published {summary}" }
+                                                }
+                                            }
+                                        }
+                                    }
+
                                     // Storage Quota Setting
                                     div { class: "mt-4 pt-4 border-t border-[var(--border-subtle)]",
                                         p { class: "text-sm font-medium text-[var(--text-primary)] mb-2", "Storage Quota (optional)" }
@@ -582,14 +1327,341 @@ pub fn ProfileComponent(peer_id: Option<String>) -> Element {
                                             "Default: Unlimited. Quota only affects local storage, not network replication."
                                         }
                                     }
+
+                                    // Block Compression Setting
+                                    {
+                                        let (level, ratio, saved) = (app_state.storage_compression)();
+                                        let saved_display = if saved >= 1024 * 1024 {
+                                            format!("{:.1} MB", saved as f64 / 1024.0 / 1024.0)
+                                        } else {
+                                            format!("{:.0} KB", saved as f64 / 1024.0)
+                                        };
+                                        let level_label = if level == 0 { "Off".to_string() } else { format!("zstd level {}", level) };
+                                        let cmd_tx_c1 = cmd_tx.clone();
+                                        let cmd_tx_c2 = cmd_tx.clone();
+                                        let cmd_tx_c3 = cmd_tx.clone();
+                                        rsx! {
+                                            div { class: "mt-4 pt-4 border-t border-[var(--border-subtle)]",
+                                                p { class: "text-sm font-medium text-[var(--text-primary)] mb-2", "Compression: {level_label}" }
+                                                div { class: "flex gap-2 flex-wrap",
+                                                    button {
+                                                        class: "btn btn-sm btn-secondary",
+                                                        onclick: move |_| { let _ = cmd_tx_c1.send(AppCmd::SetCompressionLevel { level: 0 }); },
+                                                        "Off"
+                                                    }
+                                                    button {
+                                                        class: "btn btn-sm btn-secondary",
+                                                        onclick: move |_| { let _ = cmd_tx_c2.send(AppCmd::SetCompressionLevel { level: 3 }); },
+                                                        "Fast"
+                                                    }
+                                                    button {
+                                                        class: "btn btn-sm btn-secondary",
+                                                        onclick: move |_| { let _ = cmd_tx_c3.send(AppCmd::SetCompressionLevel { level: 19 }); },
+                                                        "Smallest"
+                                                    }
+                                                }
+                                                p { class: "text-xs text-[var(--text-muted)] mt-2",
+                                                    "Ratio {ratio:.2}x, saving {saved_display}. Applies to newly stored blocks."
+                                                }
+                                            }
+                                        }
+                                    }
                                     
+                                    // Vacuum: evict unpinned blocks to make room
+                                    {
+                                        let cmd_tx_v1 = cmd_tx.clone();
+                                        let cmd_tx_v2 = cmd_tx.clone();
+                                        rsx! {
+                                            div { class: "mt-4 pt-4 border-t border-[var(--border-subtle)]",
+                                                p { class: "text-sm font-medium text-[var(--text-primary)] mb-2", "Free Up Space" }
+                                                div { class: "flex gap-2 flex-wrap",
+                                                    button {
+                                                        class: "btn btn-sm btn-secondary",
+                                                        onclick: move |_| { let _ = cmd_tx_v1.send(AppCmd::VacuumStore { target_free_mb: 100 }); },
+                                                        "100 MB"
+                                                    }
+                                                    button {
+                                                        class: "btn btn-sm btn-secondary",
+                                                        onclick: move |_| { let _ = cmd_tx_v2.send(AppCmd::VacuumStore { target_free_mb: 1024 }); },
+                                                        "1 GB"
+                                                    }
+                                                }
+                                                p { class: "text-xs text-[var(--text-muted)] mt-2",
+                                                    "Evicts cached media, unused blobs, then old posts. Pinned files and bookmarks are kept."
+                                                }
+                                                p { class: "text-sm font-medium text-[var(--text-primary)] mt-3 mb-1", "Always keep received" }
+                                                div { class: "flex gap-3 flex-wrap",
+                                                    for (node_type, type_label) in [("profile", "Profiles"), ("web", "Web pages"), ("post", "Posts"), ("file", "Files")] {
+                                                        {
+                                                            let mut pin_policy = app_state.pin_policy;
+                                                            let enabled = pin_policy.read().iter().any(|t| t == node_type);
+                                                            let cmd_tx_policy = cmd_tx.clone();
+                                                            rsx! {
+                                                                label { class: "flex items-center gap-1 text-sm",
+                                                                    input {
+                                                                        r#type: "checkbox",
+                                                                        class: "w-4 h-4",
+                                                                        checked: "{enabled}",
+                                                                        onchange: move |e| {
+                                                                            let mut types = pin_policy();
+                                                                            types.retain(|t| t != node_type);
+                                                                            if e.checked() {
+                                                                                types.push(node_type.to_string());
+                                                                            }
+                                                                            pin_policy.set(types.clone());
+                                                                            let _ = cmd_tx_policy.send(AppCmd::SetPinPolicy { types });
+                                                                        }
+                                                                    }
+                                                                    "{type_label}"
+                                                                }
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                                if let Some((removed, freed)) = (app_state.last_vacuum)() {
+                                                    p { class: "text-xs text-[var(--text-muted)] mt-1",
+                                                        "Last vacuum removed {removed} blocks, freeing {freed / 1024} KB."
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+
+                                    // Index repair: rebuild search indexes from the stored blocks
+                                    {
+                                        let cmd_tx_ri = cmd_tx.clone();
+                                        let (rebuilding, last_rebuild) = (app_state.index_rebuild)();
+                                        rsx! {
+                                            div { class: "mt-4 pt-4 border-t border-[var(--border-subtle)]",
+                                                p { class: "text-sm font-medium text-[var(--text-primary)] mb-2", "Repair Indexes" }
+                                                button {
+                                                    class: "btn btn-sm btn-secondary",
+                                                    disabled: rebuilding,
+                                                    onclick: move |_| { let _ = cmd_tx_ri.send(AppCmd::RebuildIndexes); },
+                                                    if rebuilding { "Rebuilding..." } else { "Rebuild Indexes" }
+                                                }
+                                                p { class: "text-xs text-[var(--text-muted)] mt-2",
+                                                    "Use if hashtags, mentions or search results look incomplete. Feeds pause until it finishes."
+                                                }
+                                                if let Some((scanned, ms)) = last_rebuild {
+                                                    p { class: "text-xs text-[var(--text-muted)] mt-1",
+                                                        "Last rebuild scanned {scanned} blocks in {ms} ms."
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+
                                     p { class: "text-xs text-[var(--text-muted)] mt-4 text-center",
                                         "DAG storage on this device. Data is replicated across network peers."
                                     }
+                                    if let Some((root_hex, node_count)) = (app_state.store_root)() {
+                                        p { class: "text-xs text-[var(--text-muted)] mt-1 text-center font-mono",
+                                            title: "Merkle root of all {node_count} block CIDs; peers with the same blocks show the same root",
+                                            "Store root {root_hex.get(0..16).unwrap_or(&root_hex)}..."
+                                        }
+                                    }
+                                    {
+                                        let sync_saved_kb = (app_state.sync_bytes_saved)() as f64 / 1024.0;
+                                        rsx! {
+                                            if sync_saved_kb > 0.0 {
+                                                p { class: "text-xs text-[var(--text-muted)] mt-1 text-center",
+                                                    "Skipped {sync_saved_kb:.0} KB of transfers peers already had."
+                                                }
+                                            }
+                                        }
+                                    }
                                 }
                             }
                         }
 
+                        // Identity Backup - export or restore our keys
+                        div { class: "panel",
+                            div { class: "panel-header",
+                                h2 { class: "panel-title", "🔑 Identity Backup" }
+                            }
+                            {
+                                let cmd_tx_export = cmd_tx.clone();
+                                let cmd_tx_import = cmd_tx.clone();
+                                let mut identity_backup = app_state.identity_backup;
+                                let mut identity_import_error = app_state.identity_import_error;
+                                rsx! {
+                                    div { class: "form-group",
+                                        label { class: "form-label", "Password" }
+                                        input {
+                                            class: "input",
+                                            r#type: "password",
+                                            placeholder: "Protects the backup",
+                                            value: "{backup_password}",
+                                            oninput: move |e| backup_password.set(e.value())
+                                        }
+                                    }
+                                    div { class: "flex gap-2",
+                                        button {
+                                            class: "btn btn-sm btn-secondary",
+                                            disabled: backup_password().is_empty(),
+                                            onclick: move |_| {
+                                                identity_backup.set(None);
+                                                let _ = cmd_tx_export.send(AppCmd::ExportIdentity { password: backup_password() });
+                                            },
+                                            "Export"
+                                        }
+                                        button {
+                                            class: "btn btn-sm btn-secondary",
+                                            disabled: backup_password().is_empty() || import_bundle().trim().is_empty(),
+                                            onclick: move |_| {
+                                                identity_import_error.set(None);
+                                                let _ = cmd_tx_import.send(AppCmd::ImportIdentity { bundle_b64: import_bundle(), password: backup_password() });
+                                            },
+                                            "Import"
+                                        }
+                                    }
+                                    if let Some(bundle) = identity_backup() {
+                                        p { class: "text-xs text-[var(--text-muted)] mt-3", "Keep this somewhere safe. Anyone with it and the password can act as you." }
+                                        textarea {
+                                            class: "input font-mono text-xs",
+                                            style: "min-height: 80px; resize: none;",
+                                            readonly: true,
+                                            value: "{bundle}",
+                                        }
+                                    }
+                                    div { class: "form-group mt-3",
+                                        label { class: "form-label", "Restore from backup" }
+                                        textarea {
+                                            class: "input font-mono text-xs",
+                                            style: "min-height: 80px; resize: none;",
+                                            placeholder: "Paste an exported backup, then enter its password above",
+                                            value: "{import_bundle}",
+                                            oninput: move |e| import_bundle.set(e.value())
+                                        }
+                                    }
+                                    if let Some(reason) = identity_import_error() {
+                                        p { class: "text-sm text-[var(--error)]", "Import failed: {reason}" }
+                                    }
+                                }
+                            }
+                        }
+
+                        // Network Diagnostics - swarm and bandwidth info
+                        div { class: "panel",
+                            div { class: "panel-header",
+                                h2 { class: "panel-title", "📡 Network" }
+                                {
+                                    let cmd_tx_net = cmd_tx.clone();
+                                    rsx! {
+                                        button {
+                                            class: "btn btn-sm btn-secondary",
+                                            onclick: move |_| {
+                                                let _ = cmd_tx_net.send(AppCmd::FetchNetworkDiagnostics);
+                                                let _ = cmd_tx_net.send(AppCmd::FetchPeerScores);
+                                            },
+                                            "Refresh"
+                                        }
+                                    }
+                                }
+                            }
+                            if let Some((current, minimum)) = (app_state.peer_count_low)() {
+                                p { class: "text-sm text-[var(--error)] mb-2", "⚠️ Only {current} of {minimum} peers connected, reconnecting to bootstrap nodes..." }
+                            }
+                            {
+                                // Catch up after time offline by paging through the most reliable connected peer's blocks
+                                let scores = (app_state.peer_scores)();
+                                let sync_peer = (app_state.connected_peer_profiles)().into_iter()
+                                    .map(|(peer_id, _)| peer_id)
+                                    .max_by(|a, b| {
+                                        let score = |p: &String| scores.get(p).map(|s| s.reliability()).unwrap_or(0.5);
+                                        score(a).total_cmp(&score(b))
+                                    });
+                                let syncing = matches!((app_state.full_sync)(), Some((_, _, false)));
+                                let mut full_sync = app_state.full_sync;
+                                let cmd_tx_sync = cmd_tx.clone();
+                                rsx! {
+                                    div { class: "flex items-center gap-3 mb-3",
+                                        button {
+                                            class: "btn btn-sm btn-secondary",
+                                            disabled: syncing || sync_peer.is_none(),
+                                            onclick: move |_| {
+                                                if let Some(peer_id) = sync_peer.clone() {
+                                                    full_sync.set(Some((0, None, false)));
+                                                    let _ = cmd_tx_sync.send(AppCmd::RequestFullSync { peer_id, since: None });
+                                                }
+                                            },
+                                            "Full Sync"
+                                        }
+                                        {
+                                            match (app_state.full_sync)() {
+                                                Some((received, _, true)) => rsx! {
+                                                    span { class: "text-xs text-[var(--text-muted)]", "Sync finished: {received} new blocks." }
+                                                },
+                                                Some((received, Some(total), false)) => rsx! {
+                                                    span { class: "text-xs text-[var(--text-muted)]", "Syncing... {received} of about {total} new blocks" }
+                                                },
+                                                Some((received, None, false)) => rsx! {
+                                                    span { class: "text-xs text-[var(--text-muted)]", "Syncing... {received} new blocks" }
+                                                },
+                                                None => rsx! {},
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                            if let Some(diag) = (app_state.network_diagnostics)() {
+                                {
+                                    let inbound_kb = diag.inbound_bytes as f64 / 1024.0;
+                                    let outbound_kb = diag.outbound_bytes as f64 / 1024.0;
+                                    rsx! {
+                                        div { class: "grid grid-cols-2 gap-4",
+                                            div { class: "card text-center",
+                                                p { class: "text-2xl font-bold text-[var(--primary)]", "{diag.connected_peers}" }
+                                                p { class: "text-sm text-[var(--text-secondary)]", "Connected peers" }
+                                            }
+                                            div { class: "card text-center",
+                                                p { class: "text-2xl font-bold text-[var(--primary)]", "{diag.kad_routing_table_size}" }
+                                                p { class: "text-sm text-[var(--text-secondary)]", "Routing table" }
+                                            }
+                                            div { class: "card text-center",
+                                                p { class: "text-2xl font-bold text-[var(--primary)]", "{diag.gossipsub_mesh_size}" }
+                                                p { class: "text-sm text-[var(--text-secondary)]", "Gossip mesh" }
+                                            }
+                                            div { class: "card text-center",
+                                                p { class: "text-2xl font-bold text-[var(--primary)]", "{diag.pending_replications} / {diag.timed_out_replications}" }
+                                                p { class: "text-sm text-[var(--text-secondary)]", "Replications pending / timed out" }
+                                            }
+                                        }
+                                        p { class: "text-xs text-[var(--text-muted)] mt-3 text-center",
+                                            "Received {inbound_kb:.0} KB, sent {outbound_kb:.0} KB this session."
+                                        }
+                                        {
+                                            let mut scores: Vec<(String, crate::backend::PeerScore)> = (app_state.peer_scores)().into_iter().collect();
+                                            scores.sort_by(|a, b| b.1.reliability().total_cmp(&a.1.reliability()));
+                                            rsx! {
+                                                if !scores.is_empty() {
+                                                    p { class: "text-sm font-medium text-[var(--text-primary)] mt-4 mb-2", "Peer reliability" }
+                                                    for (peer, score) in scores {
+                                                        {
+                                                            let percent = score.reliability() * 100.0;
+                                                            let short_peer = peer.chars().take(12).collect::<String>();
+                                                            rsx! {
+                                                                div { class: "flex justify-between text-xs text-[var(--text-secondary)]",
+                                                                    span { class: "font-mono", "{short_peer}..." }
+                                                                    span { class: if percent < 20.0 { "text-[var(--error)]" } else { "" },
+                                                                        "{percent:.0}% of {score.cid_acks + score.cid_nacks}, {score.latency_ms_avg} ms"
+                                                                    }
+                                                                }
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            } else {
+                                div { class: "empty-state py-4",
+                                    p { class: "empty-state-text", "Loading network stats..." }
+                                }
+                            }
+                        }
 
                         // SuperWeb Pages
                         div { class: "panel",
@@ -606,12 +1678,18 @@ pub fn ProfileComponent(peer_id: Option<String>) -> Element {
                                         {
                                             let page_url = w.url.clone();
                                             let full_url = format!("sp://{}", w.url);
+                                            let stats = web_stats.get(&w.url).cloned();
                                             rsx! {
                                                 div { class: "list-item",
                                                     div { class: "list-item-content",
                                                         p { class: "list-item-title", "{page_url}" }
                                                         p { class: "list-item-subtitle", "{full_url}" }
                                                     }
+                                                    if let Some((total, unique, week)) = stats {
+                                                        span { class: "text-xs text-[var(--text-secondary)]", title: "Total views / unique viewers / last 7 days",
+                                                            "👁 {total} · {unique} unique · {week} this week"
+                                                        }
+                                                    }
                                                 }
                                             }
                                         }
@@ -677,6 +1755,9 @@ pub fn ProfileComponent(peer_id: Option<String>) -> Element {
                                                 format!("{} KB", f.size / 1024)
                                             };
                                             let blob_cid = f.blob_cid.clone();
+                                            let pin_cid = f.blob_cid.clone();
+                                            let pinned = app_state.pinned_blocks.read().contains(&f.blob_cid);
+                                            let cmd_tx_pin = cmd_tx_pin.clone();
                                             
                                             rsx! {
                                                 div { class: "list-item flex justify-between items-center group",
@@ -693,6 +1774,15 @@ pub fn ProfileComponent(peer_id: Option<String>) -> Element {
                                                         },
                                                         "Copy CID"
                                                     }
+                                                    button {
+                                                        class: "btn btn-ghost btn-sm",
+                                                        title: "Pinned files are never evicted when freeing space",
+                                                        onclick: move |_| {
+                                                            let cid = pin_cid.clone();
+                                                            let _ = cmd_tx_pin.send(if pinned { AppCmd::UnpinBlock { cid } } else { AppCmd::PinBlock { cid } });
+                                                        },
+                                                        if pinned { "📌 Pinned" } else { "Pin" }
+                                                    }
                                                 }
                                             }
                                         }
@@ -1,8 +1,10 @@
 use dioxus::prelude::*;
+use dioxus::core::use_drop;
 use crate::components::AppState;
 use crate::backend::AppCmd;
 use tokio::sync::mpsc::UnboundedSender;
-use crate::backend::dag::DagPayload;
+use crate::backend::dag::{DagPayload, EscrowStatus, Milestone};
+use crate::backend::vm::AuditSeverity;
 use serde_json;
 
 #[component]
@@ -16,6 +18,7 @@ pub fn SmartContractsPage() -> Element {
     // State
     let mut show_create_wizard = use_signal(|| false);
     let mut selected_contract_id = use_signal(|| None::<String>);
+    let mut template_filter = use_signal(|| None::<String>);
 
     // Fetch contracts on mount
     let cmd_tx_effect = cmd_tx.clone();
@@ -92,11 +95,18 @@ pub fn SmartContractsPage() -> Element {
             } else if let Some(cid) = selected_contract_id.read().clone() {
                 ContractDetail { 
                     contract_id: cid.clone(),
-                    on_back: move |_| selected_contract_id.set(None)
+                    on_back: move |_| selected_contract_id.set(None),
+                    on_select_version: move |cid: String| selected_contract_id.set(Some(cid)),
+                    on_view_instances: move |template_id: String| {
+                        template_filter.set(Some(template_id));
+                        selected_contract_id.set(None);
+                    }
                 }
             } else {
                 ContractList { 
-                    on_select: move |cid: String| selected_contract_id.set(Some(cid))
+                    template_filter: template_filter(),
+                    on_select: move |cid: String| selected_contract_id.set(Some(cid)),
+                    on_clear_filter: move |_| template_filter.set(None)
                 }
             }
         }
@@ -104,15 +114,21 @@ pub fn SmartContractsPage() -> Element {
 }
 
 #[component]
-fn ContractList(on_select: EventHandler<String>) -> Element {
+fn ContractList(template_filter: Option<String>, on_select: EventHandler<String>, on_clear_filter: EventHandler<()>) -> Element {
     let app_state = use_context::<AppState>();
+    let cmd_tx = use_context::<UnboundedSender<AppCmd>>();
     let contracts = app_state.contracts.read();
     
     let my_id = app_state.local_peer_id.read().clone();
+    let filter_label = template_filter.as_ref().map(|t| {
+        let count = app_state.template_deployments.read().get(t).copied().unwrap_or(0);
+        format!("Instances of template {}... ({})", t.get(0..8).unwrap_or(t), count)
+    });
     
-    // Filter contracts where I am relevant
+    // Filter contracts where I am relevant; when auditing a template, show every instance
     let my_contracts: Vec<_> = contracts.iter()
         .filter(|node| {
+            if template_filter.is_some() { return true; }
             if let DagPayload::Contract(c) = &node.payload {
                 if node.author == my_id { return true; }
                 let params: serde_json::Value = serde_json::from_str(&c.init_params).unwrap_or(serde_json::json!({}));
@@ -127,6 +143,19 @@ fn ContractList(on_select: EventHandler<String>) -> Element {
     
     rsx! {
         div { class: "grid gap-4",
+            if let Some(label) = filter_label {
+                div { class: "flex justify-between items-center",
+                    span { class: "font-semibold", "{label}" }
+                    button {
+                        class: "btn btn-ghost btn-sm",
+                        onclick: move |_| {
+                            let _ = cmd_tx.send(AppCmd::FetchContracts);
+                            on_clear_filter.call(());
+                        },
+                        "Show all"
+                    }
+                }
+            }
             if my_contracts.is_empty() {
                 div { class: "text-center opacity-50 py-8", "No agreements involving you found." }
             }
@@ -175,6 +204,18 @@ fn ContractList(on_select: EventHandler<String>) -> Element {
     }
 }
 
+/// Parse "description | amount" lines into milestones, skipping malformed lines
+fn parse_milestones(text: &str) -> Vec<Milestone> {
+    text.lines()
+        .filter_map(|line| {
+            let (description, amount) = line.rsplit_once('|')?;
+            let description = description.trim();
+            let amount = amount.trim().parse::<u64>().ok()?;
+            (!description.is_empty()).then(|| Milestone { description: description.to_string(), amount, completed: false, completion_cid: None })
+        })
+        .collect()
+}
+
 #[component]
 fn ContractWizard(on_close: EventHandler<()>, on_create: EventHandler<()>) -> Element {
     let app_state = use_context::<AppState>();
@@ -194,7 +235,16 @@ fn ContractWizard(on_close: EventHandler<()>, on_create: EventHandler<()>) -> El
     let mut interest_rate = use_signal(|| "".to_string());
     let mut loan_duration = use_signal(|| "".to_string()); // in months
     let mut repayment_interval = use_signal(|| "Monthly".to_string());
+
+    // Service Fields: one "description | amount" milestone per line
+    let mut service_provider_id = use_signal(|| "".to_string());
+    let mut milestone_lines = use_signal(|| "".to_string());
     
+    // Optional template: reuse the code of an existing contract
+    let mut template_id = use_signal(String::new);
+    // Optional earlier version of one of my contracts that this one supersedes
+    let mut upgrade_of = use_signal(String::new);
+
     // Validation State
     let mut error_msg = use_signal(|| "".to_string());
     
@@ -211,6 +261,9 @@ fn ContractWizard(on_close: EventHandler<()>, on_create: EventHandler<()>) -> El
              if borrower_id.read().trim().is_empty() { error_msg.set("Borrower Peer ID is required".into()); return; }
              if loan_amount.read().trim().is_empty() { error_msg.set("Principal is required".into()); return; }
              if interest_rate.read().trim().is_empty() { error_msg.set("Interest Rate is required".into()); return; }
+        } else if c_type.as_str() == "Service" {
+             if service_provider_id.read().trim().is_empty() { error_msg.set("Provider Peer ID is required".into()); return; }
+             if parse_milestones(&milestone_lines.read()).is_empty() { error_msg.set("At least one milestone is required".into()); return; }
         }
 
         let title_val = if title.read().is_empty() {
@@ -247,6 +300,14 @@ fn ContractWizard(on_close: EventHandler<()>, on_create: EventHandler<()>) -> El
                     }
                  }).to_string()
             }
+            "Service" => {
+                 serde_json::json!({
+                    "metadata": { "title": title_val, "type_label": "Service" },
+                    "parties": { "provider": *service_provider_id.read(), "consumer": local_peer_id },
+                    "payment_terms": { "type": "milestone", "currency": "Tokens" },
+                    "milestones": parse_milestones(&milestone_lines.read())
+                 }).to_string()
+            }
             _ => "{}".to_string()
         };
         
@@ -254,7 +315,15 @@ fn ContractWizard(on_close: EventHandler<()>, on_create: EventHandler<()>) -> El
             // Standard Agreement
         "#.to_string();
 
-        let _ = cmd_tx_deploy.send(AppCmd::DeployContract { code, init_params: params }); // error ignored in context
+        let template = template_id.read().trim().to_string();
+        let parent = upgrade_of.read().trim().to_string();
+        if !parent.is_empty() {
+            let _ = cmd_tx_deploy.send(AppCmd::UpgradeContract { contract_id: parent, code, init_params: params });
+        } else if template.is_empty() {
+            let _ = cmd_tx_deploy.send(AppCmd::DeployContract { code, init_params: params }); // error ignored in context
+        } else {
+            let _ = cmd_tx_deploy.send(AppCmd::DeployFromTemplate { template_id: template, init_params: params });
+        }
         on_create.call(());
     };
 
@@ -274,6 +343,7 @@ fn ContractWizard(on_close: EventHandler<()>, on_create: EventHandler<()>) -> El
                         onchange: move |evt| contract_type.set(evt.value()),
                         option { value: "Payment", "Recurring Payment" }
                         option { value: "Loan", "Loan" }
+                        option { value: "Service", "Milestone-based Service" }
                     }
                 }
                 
@@ -287,6 +357,28 @@ fn ContractWizard(on_close: EventHandler<()>, on_create: EventHandler<()>) -> El
                         oninput: move |e| title.set(e.value()) 
                     }
                 }
+
+                // Template
+                div { class: "form-control w-full",
+                    label { class: "label", span { class: "label-text", "Template Contract ID (Optional)" } }
+                    input { 
+                        class: "input input-bordered", 
+                        placeholder: "Deploy using an existing contract's code",
+                        value: "{template_id}", 
+                        oninput: move |e| template_id.set(e.value()) 
+                    }
+                }
+
+                // Upgrade
+                div { class: "form-control w-full",
+                    label { class: "label", span { class: "label-text", "Upgrades Contract ID (Optional)" } }
+                    input {
+                        class: "input input-bordered",
+                        placeholder: "Publish as a new version of one of your contracts",
+                        value: "{upgrade_of}",
+                        oninput: move |e| upgrade_of.set(e.value())
+                    }
+                }
                 
                 div { class: "divider", "Details" }
                 
@@ -352,6 +444,23 @@ fn ContractWizard(on_close: EventHandler<()>, on_create: EventHandler<()>) -> El
                     }
                 }
 
+                // Service Fields
+                if *contract_type.read() == "Service" {
+                    div { class: "form-control",
+                        label { class: "label", "Provider Peer ID *" }
+                        input { class: "input input-bordered", placeholder: "Paste peer ID here", value: "{service_provider_id}", oninput: move |e| service_provider_id.set(e.value()) }
+                    }
+                    div { class: "form-control",
+                        label { class: "label", "Milestones * (one per line: description | amount)" }
+                        textarea {
+                            class: "textarea textarea-bordered",
+                            placeholder: "Design mockups | 200\nImplementation | 500",
+                            value: "{milestone_lines}",
+                            oninput: move |e| milestone_lines.set(e.value())
+                        }
+                    }
+                }
+
                 // Quick Pick for Peer IDs
                 if !app_state.peers.read().is_empty() {
                     div { class: "mt-4 p-4 bg-base-200 rounded-lg",
@@ -367,6 +476,8 @@ fn ContractWizard(on_close: EventHandler<()>, on_create: EventHandler<()>) -> El
                                             onclick: move |_| {
                                                 if *contract_type.read() == "Payment" {
                                                     landlord_id.set(peer_id.clone());
+                                                } else if *contract_type.read() == "Service" {
+                                                    service_provider_id.set(peer_id.clone());
                                                 } else {
                                                     borrower_id.set(peer_id.clone());
                                                 }
@@ -393,7 +504,7 @@ fn ContractWizard(on_close: EventHandler<()>, on_create: EventHandler<()>) -> El
 }
 
 #[component]
-fn ContractDetail(contract_id: String, on_back: EventHandler<()>) -> Element {
+fn ContractDetail(contract_id: String, on_back: EventHandler<()>, on_select_version: EventHandler<String>, on_view_instances: EventHandler<String>) -> Element {
     let app_state = use_context::<AppState>();
     let cmd_tx = use_context::<UnboundedSender<AppCmd>>();
     let history = app_state.active_contract_history.read();
@@ -401,17 +512,54 @@ fn ContractDetail(contract_id: String, on_back: EventHandler<()>) -> Element {
     // Fetch history on mount
     let cmd_tx_effect = cmd_tx.clone();
     use_effect(use_reactive(&contract_id, move |cid| {
-        let _ = cmd_tx_effect.send(AppCmd::FetchContractHistory { contract_id: cid });
+        let _ = cmd_tx_effect.send(AppCmd::FetchContractHistory { contract_id: cid.clone() });
+        let _ = cmd_tx_effect.send(AppCmd::FetchContractSecurityAudit { contract_id: cid.clone() });
+        let _ = cmd_tx_effect.send(AppCmd::FetchContractDeployerReputation { contract_id: cid.clone() });
+        let _ = cmd_tx_effect.send(AppCmd::FetchContractMilestones { contract_id: cid.clone() });
+        let _ = cmd_tx_effect.send(AppCmd::FetchContractEscrowStatus { contract_id: cid.clone() });
+        let _ = cmd_tx_effect.send(AppCmd::FetchContractVersionHistory { contract_id: cid.clone() });
+        let _ = cmd_tx_effect.send(AppCmd::FetchContractState { contract_id: cid.clone() });
+        let _ = cmd_tx_effect.send(AppCmd::SubscribeContractEvents { contract_id: cid.clone() });
+        let _ = cmd_tx_effect.send(AppCmd::FetchContractParties { contract_id: cid });
     }));
+    let cmd_tx_drop = cmd_tx.clone();
+    let cid_drop = contract_id.clone();
+    use_drop(move || {
+        let _ = cmd_tx_drop.send(AppCmd::UnsubscribeContractEvents { contract_id: cid_drop.clone() });
+    });
+    let mut escrow_amount = use_signal(String::new);
+    let mut sim_method = use_signal(|| "set".to_string());
+    let mut sim_params = use_signal(|| r#"{"key": "", "value": ""}"#.to_string());
 
-    // Find the contract definition
+    let findings = app_state.contract_audits.read().get(&contract_id).cloned().unwrap_or_default();
+    let deployer = app_state.contract_deployers.read().get(&contract_id).cloned();
+    let party_cards = app_state.contract_parties.read().get(&contract_id).cloned();
+    let milestones = app_state.contract_milestones.read().get(&contract_id).cloned().unwrap_or_default();
+    let escrow = app_state.escrow_statuses.read().get(&contract_id).cloned();
+    let versions = app_state.contract_versions.read().get(&contract_id).cloned().unwrap_or_default();
+    let my_id = app_state.local_peer_id.read().clone();
+
+    // Find the contract definition; superseded versions may only be known through an upgrade chain
     let contracts = app_state.contracts.read();
-    let contract_node = contracts.iter().find(|n| n.id == contract_id);
+    let contract_node = contracts.iter().find(|n| n.id == contract_id).cloned()
+        .or_else(|| app_state.contract_versions.read().values().flatten().find(|n| n.id == contract_id).cloned());
 
     if contract_node.is_none() {
         return rsx! { div { "Contract not found" } };
     }
-    let node = contract_node.unwrap();
+    let node = &contract_node.unwrap();
+
+    // "Version 3 of 3 (upgraded from V2 on Jan 15)"
+    let version_index = versions.iter().position(|v| v.id == contract_id);
+    let version_label = version_index.filter(|_| versions.len() > 1).map(|i| {
+        let upgraded = if i > 0 {
+            format!(" (upgraded from V{} on {})", i, node.timestamp.format("%b %-d"))
+        } else {
+            String::new()
+        };
+        format!("Version {} of {}{}", i + 1, versions.len(), upgraded)
+    });
+    let is_superseded = version_index.is_some_and(|i| i + 1 < versions.len());
     let params: serde_json::Value = if let DagPayload::Contract(c) = &node.payload {
         serde_json::from_str(&c.init_params).unwrap_or(serde_json::json!({}))
     } else {
@@ -423,6 +571,11 @@ fn ContractDetail(contract_id: String, on_back: EventHandler<()>) -> Element {
     let parties = &params["parties"];
 
     let title = metadata["title"].as_str().unwrap_or("Smart Agreement");
+    let template_id = match &node.payload {
+        DagPayload::Contract(c) => c.template_id.clone(),
+        _ => None,
+    };
+    let deployments = app_state.template_deployments.read().get(&contract_id).copied();
     let p_type = payment["type"].as_str().unwrap_or("unknown");
     
     // Unified Logic: Provider (Payee) vs Consumer (Payer)
@@ -470,6 +623,7 @@ fn ContractDetail(contract_id: String, on_back: EventHandler<()>) -> Element {
         let _ = cmd_tx_pay.send(AppCmd::PayContract {
              contract_id: cid_pay.clone(),
              amount,
+             milestone_index: None,
         });
     };
 
@@ -479,9 +633,108 @@ fn ContractDetail(contract_id: String, on_back: EventHandler<()>) -> Element {
             
             div { class: "card bg-base-100 shadow-xl p-6",
                 h2 { class: "text-2xl font-bold mb-4", "{title}" }
+
+                // Upgrade chain
+                if let Some(label) = version_label {
+                    div { class: "flex flex-wrap items-center gap-2 mb-4",
+                        span { class: if is_superseded { "badge badge-warning" } else { "badge badge-info" }, "{label}" }
+                        if is_superseded {
+                            span { class: "text-xs opacity-75", "A newer version of this contract exists" }
+                        }
+                        for (i, version) in versions.iter().enumerate() {
+                            {
+                                let vid = version.id.clone();
+                                let is_current = vid == contract_id;
+                                rsx! {
+                                    button {
+                                        key: "{vid}",
+                                        class: if is_current { "btn btn-primary btn-xs" } else { "btn btn-ghost btn-xs" },
+                                        disabled: is_current,
+                                        onclick: move |_| on_select_version.call(vid.clone()),
+                                        "V{i + 1}"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                // Deployer trust card
+                if let Some(info) = deployer {
+                    div { class: "card bg-base-200 p-4 mb-4",
+                        div { class: "flex justify-between items-center mb-2",
+                            h4 { class: "font-bold", "👤 Deployed by" }
+                            span { class: "text-xs opacity-75 truncated", "{info.deployer_peer_id}" }
+                        }
+                        div { class: "flex gap-4 text-sm",
+                            if let Some(rep) = &info.reputation {
+                                span { "⭐ Reputation: " strong { "{rep.score}" } }
+                            }
+                            span { "🤝 Vouches: " strong { "{info.vouch_count}" } }
+                        }
+                        if !info.certifications.is_empty() {
+                            div { class: "flex flex-wrap gap-2 mt-2",
+                                for cert in info.certifications.iter() {
+                                    if let DagPayload::Certification(c) = &cert.payload {
+                                        span { class: "badge badge-success badge-sm", "🎓 {c.certification_type}" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
                 
+                // Party cards
+                if let Some((provider_party, consumer_party)) = party_cards {
+                    div { class: "grid grid-cols-2 gap-4 mb-4",
+                        for (role, (peer_id, profile)) in [("Provider (Payee)", provider_party), ("Consumer (Payer)", consumer_party)] {
+                            {
+                                let name = profile.as_ref().map(|p| p.name.clone()).unwrap_or_else(|| "Unknown".to_string());
+                                let score = app_state.author_reputations.read().get(&peer_id).map(|r| r.score);
+                                let is_me = peer_id == my_id;
+                                rsx! {
+                                    div { key: "{role}", class: "card bg-base-200 p-4",
+                                        p { class: "text-xs opacity-75", "{role}" }
+                                        div { class: "flex items-center gap-2",
+                                            h4 { class: "font-bold", "{name}" }
+                                            if is_me {
+                                                span { class: "badge badge-info badge-sm", "You" }
+                                            }
+                                            if let Some(score) = score {
+                                                span { class: "badge badge-primary badge-sm", "⭐ {score}" }
+                                            }
+                                        }
+                                        p { class: "text-xs opacity-75 truncated", "{peer_id}" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
                 div { class: "grid grid-cols-2 gap-4 text-sm mb-6",
                     div { class: "font-semibold", "Contract ID:" } div { class: "opacity-75 truncated", "{contract_id}" }
+                    if let Some(template_id) = template_id {
+                        div { class: "font-semibold", "Template:" } div { class: "opacity-75 truncated", "{template_id}" }
+                    }
+                    div { class: "font-semibold", "Used as Template:" }
+                    div {
+                        if let Some(count) = deployments {
+                            span { class: "mr-2", "{count} deployments" }
+                        }
+                        button {
+                            class: "btn btn-ghost btn-xs",
+                            onclick: {
+                                let cmd_tx = cmd_tx.clone();
+                                let cid = contract_id.clone();
+                                move |_| {
+                                    let _ = cmd_tx.send(AppCmd::FetchContractsByTemplate { template_id: cid.clone() });
+                                    on_view_instances.call(cid.clone());
+                                }
+                            },
+                            "🔍 Audit instances"
+                        }
+                    }
                     div { class: "font-semibold", "Provider (Payee):" } div { class: "opacity-75 truncated", "{provider}" }
                     div { class: "font-semibold", "Consumer (Payer):" } div { class: "opacity-75 truncated", "{consumer}" }
 
@@ -497,21 +750,217 @@ fn ContractDetail(contract_id: String, on_back: EventHandler<()>) -> Element {
                          div { class: "font-semibold", "Interest:" } div { "{payment[\"interest_rate\"]}%" }
                          div { class: "font-semibold", "Repaid:" } div { "{total_paid}" }
                          div { class: "font-semibold", "Remaining:" } div { "{payment[\"principal\"].as_f64().unwrap_or(0.0) - total_paid}" }
+                    } else if p_type == "milestone" {
+                         div { class: "font-semibold", "Type:" } div { "Milestone-based Service" }
+                         div { class: "font-semibold", "Milestones:" } div { "{milestones.len()}" }
+                         div { class: "font-semibold", "Total Paid:" } div { "{total_paid}" }
                     } else {
                          // Fallback for distinct legacy types or custom
                          div { class: "col-span-2 opacity-50", "Type: {p_type}" }
                     }
                 }
 
+                // Security audit, shown before any tokens are committed
+                if !findings.is_empty() {
+                    div { class: "alert alert-warning flex flex-col items-start mb-4",
+                        h4 { class: "font-bold", "⚠️ Security Audit" }
+                        for finding in findings.iter() {
+                            {
+                                let badge = match finding.severity {
+                                    AuditSeverity::High => "badge-error",
+                                    AuditSeverity::Medium => "badge-warning",
+                                    AuditSeverity::Low => "badge-info",
+                                    AuditSeverity::Info => "badge-ghost",
+                                };
+                                let line = finding.line_hint.map(|l| format!(" (line {})", l)).unwrap_or_default();
+                                rsx! {
+                                    div { class: "text-sm",
+                                        span { class: "badge {badge} badge-sm mr-2", "{finding.severity:?}" }
+                                        "{finding.description}{line}"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                // Milestones: the provider marks work complete, then the consumer pays for it
+                if !milestones.is_empty() {
+                    div { class: "flex flex-col gap-2 mb-4",
+                        h4 { class: "font-bold", "Milestones" }
+                        for (index, (description, amount, completed, _completion_cid)) in milestones.iter().cloned().enumerate() {
+                            {
+                                let memo = format!("Contract milestone {} payment: {}", index, contract_id);
+                                let paid = history.iter().any(|n| matches!(&n.payload, DagPayload::Token(t) if t.memo.as_deref() == Some(memo.as_str())));
+                                let cid_complete = contract_id.clone();
+                                let cid_milestone_pay = contract_id.clone();
+                                let cmd_tx_complete = cmd_tx.clone();
+                                let cmd_tx_milestone_pay = cmd_tx.clone();
+                                rsx! {
+                                    div { key: "{index}", class: "card bg-base-200 p-3 flex flex-row items-center justify-between gap-2",
+                                        div {
+                                            p { class: "font-semibold", "{index + 1}. {description}" }
+                                            p { class: "text-xs opacity-75", "{amount} Tokens" }
+                                        }
+                                        div { class: "flex items-center gap-2",
+                                            if paid {
+                                                span { class: "badge badge-success", "Paid" }
+                                            } else if completed {
+                                                span { class: "badge badge-info", "Completed" }
+                                            } else {
+                                                span { class: "badge badge-ghost", "In Progress" }
+                                            }
+                                            if provider == my_id && !completed {
+                                                button {
+                                                    class: "btn btn-sm btn-secondary",
+                                                    onclick: move |_| {
+                                                        let _ = cmd_tx_complete.send(AppCmd::CompleteMilestone { contract_id: cid_complete.clone(), milestone_index: index });
+                                                    },
+                                                    "Mark Complete"
+                                                }
+                                            }
+                                            if consumer == my_id && completed && !paid {
+                                                button {
+                                                    class: "btn btn-sm btn-primary",
+                                                    onclick: move |_| {
+                                                        let _ = cmd_tx_milestone_pay.send(AppCmd::PayContract { contract_id: cid_milestone_pay.clone(), amount, milestone_index: Some(index) });
+                                                    },
+                                                    "Pay ({amount})"
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                // Escrow: locked funds are out of the consumer's balance until released or refunded
+                {
+                    let active = matches!(escrow, Some((_, EscrowStatus::Locked | EscrowStatus::Disputed, _)));
+                    let settle = |outcome: EscrowStatus| {
+                        let cmd_tx = cmd_tx.clone();
+                        let cid = contract_id.clone();
+                        move |_| {
+                            let _ = cmd_tx.send(AppCmd::SettleEscrow { contract_id: cid.clone(), outcome });
+                        }
+                    };
+                    let cmd_tx_lock = cmd_tx.clone();
+                    let cid_lock = contract_id.clone();
+                    rsx! {
+                        div { class: "card bg-base-200 p-4 mb-4",
+                            div { class: "flex justify-between items-center",
+                                h4 { class: "font-bold", "🔒 Escrow" }
+                                match escrow.as_ref().map(|(_, status, _)| *status) {
+                                    Some(EscrowStatus::Locked) => rsx! { span { class: "badge badge-warning", "Locked" } },
+                                    Some(EscrowStatus::Disputed) => rsx! { span { class: "badge badge-error", "Disputed" } },
+                                    Some(EscrowStatus::Released) => rsx! { span { class: "badge badge-success", "Released" } },
+                                    Some(EscrowStatus::Refunded) => rsx! { span { class: "badge badge-info", "Refunded" } },
+                                    None => rsx! { span { class: "badge badge-ghost", "None" } },
+                                }
+                            }
+                            if let Some((amount, _, counterparty)) = escrow.as_ref() {
+                                p { class: "text-sm mt-2", "{amount} Tokens for {counterparty}" }
+                            } else {
+                                p { class: "text-sm opacity-75 mt-2", "No funds are held in escrow for this contract." }
+                            }
+                            div { class: "flex gap-2 justify-end mt-2",
+                                if active && consumer == my_id {
+                                    button { class: "btn btn-sm btn-primary", onclick: settle(EscrowStatus::Released), "Release" }
+                                }
+                                if active && provider == my_id {
+                                    button { class: "btn btn-sm btn-secondary", onclick: settle(EscrowStatus::Refunded), "Refund" }
+                                }
+                                if matches!(escrow, Some((_, EscrowStatus::Locked, _))) && (consumer == my_id || provider == my_id) {
+                                    button { class: "btn btn-sm btn-ghost", onclick: settle(EscrowStatus::Disputed), "Dispute" }
+                                }
+                                if !active && consumer == my_id {
+                                    input {
+                                        class: "input input-sm w-32",
+                                        r#type: "number",
+                                        placeholder: "Amount",
+                                        value: "{escrow_amount}",
+                                        oninput: move |e| escrow_amount.set(e.value())
+                                    }
+                                    button {
+                                        class: "btn btn-sm btn-primary",
+                                        disabled: escrow_amount().parse::<u64>().is_err(),
+                                        onclick: move |_| {
+                                            if let Ok(amount) = escrow_amount().parse::<u64>() {
+                                                let _ = cmd_tx_lock.send(AppCmd::CreateEscrow { contract_id: cid_lock.clone(), amount });
+                                                escrow_amount.set(String::new());
+                                            }
+                                        },
+                                        "Lock Funds"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                // Payments come from the consumer only
                 div { class: "flex gap-2 justify-end",
-                    if p_type == "recurring" {
+                    if consumer == my_id && p_type == "recurring" {
                         button { class: "btn btn-primary", onclick: handle_payment, "Pay {payment[\"interval\"].as_str().unwrap_or(\"Payment\")} ({pay_amount})" }
-                    } else if p_type == "loan" {
+                    } else if consumer == my_id && p_type == "loan" {
                         button { class: "btn btn-primary", onclick: handle_payment, "Repay ({pay_amount})" }
                     }
                 }
             }
 
+            // Dry-run a call against the current state; nothing is published
+            {
+                let simulation = app_state.contract_simulations.read().get(&contract_id).cloned();
+                let cmd_tx_sim = cmd_tx.clone();
+                let cid_sim = contract_id.clone();
+                rsx! {
+                    div { class: "card bg-base-100 shadow-xl p-6 mb-6",
+                        h3 { class: "font-bold text-lg mb-4", "🧪 Test a Call" }
+                        div { class: "flex gap-2 mb-2",
+                            input { class: "input input-sm w-32", placeholder: "Method", value: "{sim_method}", oninput: move |e| sim_method.set(e.value()) }
+                            input { class: "input input-sm flex-1 font-mono", placeholder: "Params (JSON)", value: "{sim_params}", oninput: move |e| sim_params.set(e.value()) }
+                            button {
+                                class: "btn btn-sm btn-secondary",
+                                onclick: move |_| {
+                                    let _ = cmd_tx_sim.send(AppCmd::SimulateContractCall { contract_id: cid_sim.clone(), method: sim_method(), params: sim_params() });
+                                },
+                                "Simulate"
+                            }
+                        }
+                        if let Some((state, gas, error)) = simulation {
+                            if let Some(error) = error {
+                                div { class: "alert alert-error", span { "Simulation failed: {error}" } }
+                            } else {
+                                p { class: "text-xs opacity-75 mb-1", "Gas used: {gas}" }
+                                pre { class: "bg-base-200 p-2 rounded text-xs overflow-x-auto", "{state}" }
+                            }
+                        }
+                    }
+                }
+            }
+
+            // Events emitted by calls, newest first; new ones arrive while this view is open
+            {
+                let events = app_state.contract_events.read().get(&contract_id).cloned().unwrap_or_default();
+                rsx! {
+                    if !events.is_empty() {
+                        div { class: "card bg-base-100 shadow-xl p-6 mb-6",
+                            h3 { class: "font-bold text-lg mb-4", "📣 Events" }
+                            for (i, event) in events.iter().enumerate().rev() {
+                                div { key: "{i}", class: "flex gap-2 items-center text-sm mb-1",
+                                    span { class: "badge badge-info", "{event.name}" }
+                                    if !event.data.is_null() {
+                                        span { class: "font-mono text-xs opacity-75 truncate", "{event.data}" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
             // History Section
             div { class: "card bg-base-100 shadow-xl p-6",
                 h3 { class: "font-bold text-lg mb-4", "Activity History" }
@@ -529,6 +978,7 @@ fn ContractDetail(contract_id: String, on_back: EventHandler<()>) -> Element {
                                         match &item.payload {
                                             DagPayload::ContractCall(c) => rsx!{ span { class: "badge badge-info", "Call: {c.method}" } },
                                             DagPayload::Token(_) => rsx!{ span { class: "badge badge-success", "Payment" } },
+                                            DagPayload::MilestoneCompletion(_) => rsx!{ span { class: "badge badge-info", "Milestone" } },
                                             _ => rsx!{ span { "Event" } }
                                         }
                                     }
@@ -536,6 +986,7 @@ fn ContractDetail(contract_id: String, on_back: EventHandler<()>) -> Element {
                                         match &item.payload {
                                             DagPayload::ContractCall(c) => "{c.params}",
                                             DagPayload::Token(t) => "Amount: {t.amount}",
+                                            DagPayload::MilestoneCompletion(_) => "Milestone completed",
                                             _ => "ID: {item.id}" // fixed string interpolation
                                         }
                                     }
@@ -42,16 +42,31 @@ pub fn SuperWebShell() -> Element {
             // Fetch and display web content
             let cmd_tx = cmd_tx.clone();
             let url_clone = url.clone();
+            let cmd_tx_embeds = cmd_tx.clone();
+            let url_embeds = url.clone();
             use_effect(move || {
                 let _ = cmd_tx.send(AppCmd::FetchWebPage { url: url_clone.clone() });
             });
+            // Once the page has rendered, pull in the blobs it embeds
+            use_effect(move || {
+                if app_state.web_content.read().is_some() {
+                    let _ = cmd_tx_embeds.send(AppCmd::FetchWebPageEmbeds { url: url_embeds.clone() });
+                }
+            });
             
-            let web_content = app_state.web_content.read().clone();
+            let web_content = app_state.web_content.read().clone()
+                .map(|html| crate::components::common::inline_blob_embeds(&html, &app_state.blob_cache.read()));
+            let pending_embeds = app_state.web_page_embeds.read().get(&url)
+                .map(|cids| cids.iter().filter(|cid| !app_state.blob_cache.read().contains_key(*cid)).count())
+                .unwrap_or(0);
             rsx! {
                 div { class: "page-container py-8 animate-fade-in",
                     div { class: "panel",
                         div { class: "panel-header",
                             h2 { class: "panel-title", "{url}" }
+                            if pending_embeds > 0 {
+                                span { class: "text-xs text-muted", "Loading {pending_embeds} embedded files..." }
+                            }
                         }
                         if let Some(html_content) = web_content {
                             div { 
@@ -12,6 +12,8 @@ pub fn TransparencyComponent() -> Element {
     use_effect(move || {
         let _ = cmd_tx_clone.send(AppCmd::FetchPublicLedger);
         let _ = cmd_tx_clone.send(AppCmd::FetchStorageStats);
+        let _ = cmd_tx_clone.send(AppCmd::FetchTokenInflationRate { window_days: 30 });
+        let _ = cmd_tx_clone.send(AppCmd::FetchNetworkGrowthStats { window_days: 30 });
     });
     
     // Periodically refresh
@@ -40,6 +42,8 @@ pub fn TransparencyComponent() -> Element {
     };
     
     let ledger_events = app_state.public_ledger.read();
+    let inflation = app_state.inflation_stats.read().clone();
+    let growth = app_state.network_growth.read().clone();
 
     rsx! {
         div { class: "page-container py-8 animate-fade-in",
@@ -66,7 +70,76 @@ pub fn TransparencyComponent() -> Element {
                     div { class: "text-4xl opacity-20", "💾" }
                 }
             }
+
+            // Monetary Supply
+            if let Some(stats) = inflation {
+                div { class: "card p-6 mb-8",
+                    p { class: "label mb-3", "Token Supply (last {stats.window_days} days)" }
+                    div { class: "grid grid-cols-2 md:grid-cols-4 gap-4",
+                        div {
+                            p { class: "text-xs text-[var(--text-muted)]", "Minted" }
+                            p { class: "text-xl font-bold text-[var(--text-primary)]", "{stats.total_minted}" }
+                        }
+                        div {
+                            p { class: "text-xs text-[var(--text-muted)]", "Burned" }
+                            p { class: "text-xl font-bold text-[var(--text-primary)]", "{stats.total_burned}" }
+                        }
+                        div {
+                            p { class: "text-xs text-[var(--text-muted)]", "Net Issuance" }
+                            p { class: "text-xl font-bold text-[var(--text-primary)]", "{stats.net_issuance}" }
+                        }
+                        div {
+                            p { class: "text-xs text-[var(--text-muted)]", "Inflation" }
+                            p { class: "text-xl font-bold text-[var(--text-primary)]", "{stats.inflation_rate_pct:.2}%" }
+                        }
+                    }
+                }
+            }
             
+            // Network Growth
+            if let Some(growth) = growth {
+                {
+                    let new_profiles: usize = growth.daily_new_profiles.iter().map(|(_, v)| v).sum();
+                    let new_verified: usize = growth.daily_new_verified.iter().map(|(_, v)| v).sum();
+                    let peak_active = growth.daily_active_peers.iter().map(|(_, v)| *v).max().unwrap_or(0);
+                    let tx_volume: u64 = growth.daily_tx_volume.iter().map(|(_, v)| v).sum();
+                    rsx! {
+                        div { class: "card p-6 mb-8",
+                            p { class: "label mb-3", "Network Growth (last {growth.window_days} days)" }
+                            div { class: "grid grid-cols-2 md:grid-cols-4 gap-4 mb-4",
+                                div {
+                                    p { class: "text-xs text-[var(--text-muted)]", "New Profiles" }
+                                    p { class: "text-xl font-bold text-[var(--text-primary)]", "{new_profiles}" }
+                                }
+                                div {
+                                    p { class: "text-xs text-[var(--text-muted)]", "Newly Verified" }
+                                    p { class: "text-xl font-bold text-[var(--text-primary)]", "{new_verified}" }
+                                }
+                                div {
+                                    p { class: "text-xs text-[var(--text-muted)]", "Peak Daily Active" }
+                                    p { class: "text-xl font-bold text-[var(--text-primary)]", "{peak_active}" }
+                                }
+                                div {
+                                    p { class: "text-xs text-[var(--text-muted)]", "Transfer Volume" }
+                                    p { class: "text-xl font-bold text-[var(--text-primary)]", "{tx_volume} SUPER" }
+                                }
+                            }
+                            // Daily active peers
+                            div { class: "flex items-end gap-1 h-16",
+                                for (day, count) in growth.daily_active_peers.iter() {
+                                    div {
+                                        key: "{day}",
+                                        class: "flex-1 bg-[var(--primary)] rounded-t opacity-70",
+                                        style: "height: {count * 100 / peak_active.max(1)}%",
+                                        title: "{count} active",
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
             // Ledger Table
             div { class: "panel",
                 div { class: "panel-header border-b border-[var(--border-default)] pb-4 mb-4",
@@ -1,16 +1,20 @@
 use dioxus::prelude::*;
 use crate::backend::{AppCmd, VerificationStatus, dag::DagPayload};
-use crate::components::common::BlobImage;
+use crate::components::common::{trust_chain_steps, BlobImage};
 
 #[component]
 pub fn VerificationPage() -> Element {
     let cmd_tx = use_context::<tokio::sync::mpsc::UnboundedSender<AppCmd>>();
     let app_state = use_context::<crate::components::AppState>();
 
+    let local_id = app_state.local_peer_id.read().clone();
+
     let check_status = {
         let cmd_tx = cmd_tx.clone();
+        let local_id = local_id.clone();
         move |_| {
             let _ = cmd_tx.send(AppCmd::CheckVerificationStatus);
+            let _ = cmd_tx.send(AppCmd::FetchPeerVerificationPath { peer_id: local_id.clone() });
         }
     };
 
@@ -21,14 +25,50 @@ pub fn VerificationPage() -> Element {
 
     // Fetch pending applications on mount for verified users
     let cmd_tx_effect = cmd_tx.clone();
+    let local_id_effect = local_id.clone();
     use_effect(move || {
         let _ = cmd_tx_effect.send(AppCmd::FetchPendingApplications);
         let _ = cmd_tx_effect.send(AppCmd::CheckVerificationStatus);
+        let _ = cmd_tx_effect.send(AppCmd::FetchMyApplicationStatus);
+        let _ = cmd_tx_effect.send(AppCmd::FetchPeerVerificationPath { peer_id: local_id_effect.clone() });
     });
 
     let verification_status = app_state.verification_status.read().clone();
     let is_verified = matches!(verification_status, VerificationStatus::Verified | VerificationStatus::Founder);
     let pending_apps = app_state.pending_applications.read().clone();
+    let my_application_status = app_state.my_application_status.read().clone();
+
+    // My own application, if I was verified through the application system
+    let my_application_id = pending_apps.iter()
+        .find(|n| n.author == *app_state.local_peer_id.read())
+        .map(|n| n.id.clone());
+    let cmd_tx_approvers = cmd_tx.clone();
+    use_effect(use_reactive(&my_application_id, move |app_id| {
+        if let Some(application_id) = app_id {
+            let _ = cmd_tx_approvers.send(AppCmd::FetchApplicationApprovers { application_id });
+        }
+    }));
+    let my_approvers = my_application_id.as_ref()
+        .and_then(|id| app_state.application_approvers.read().get(id).cloned())
+        .unwrap_or_default();
+
+    // How trust flows from the network to me, rendered as "Alice → Bob → You"
+    let trust_chain = app_state.verification_paths.read().get(&local_id)
+        .map(|path| trust_chain_steps(path))
+        .unwrap_or_default();
+    let connected = app_state.connected_peer_profiles.read().clone();
+    let cached = app_state.user_profiles.read().clone();
+    let name_of = move |peer_id: &str| {
+        if peer_id == "network" {
+            "🌐 Network".to_string()
+        } else if peer_id == local_id {
+            "You".to_string()
+        } else {
+            connected.iter().find(|(id, _)| id == peer_id).and_then(|(_, p)| p.as_ref().map(|p| p.name.clone()))
+                .or_else(|| cached.get(peer_id).map(|p| p.name.clone()))
+                .unwrap_or_else(|| format!("{}...", peer_id.get(0..8).unwrap_or(peer_id)))
+        }
+    };
 
     // Submit application handler
     let cmd_tx_submit = cmd_tx.clone();
@@ -38,6 +78,7 @@ pub fn VerificationPage() -> Element {
         let p = photo_cid.read().clone();
         if !n.is_empty() {
             let _ = cmd_tx_submit.send(AppCmd::SubmitApplication { name: n, bio: b, photo_cid: p });
+            let _ = cmd_tx_submit.send(AppCmd::FetchMyApplicationStatus);
         }
     };
 
@@ -119,6 +160,30 @@ pub fn VerificationPage() -> Element {
                             }
                         }
                     } else if verification_status == VerificationStatus::Unverified {
+                        // Progress of my latest application, if I have submitted one
+                        if let Some((_, approvals, rejections, required, is_approved, is_rejected)) = my_application_status {
+                            div { class: "mb-6 p-4 rounded bg-[var(--bg-secondary)]",
+                                if is_approved {
+                                    p { class: "font-bold text-green-400", "🎉 Application approved!" }
+                                    p { class: "text-xs text-[var(--text-muted)] mt-1", "Refresh your status to finish verification." }
+                                } else if is_rejected {
+                                    p { class: "font-bold text-red-400", "Application rejected ({rejections} rejections)" }
+                                    p { class: "text-xs text-[var(--text-muted)] mt-1", "You can submit a new application below." }
+                                } else {
+                                    p { class: "font-bold mb-2", "{approvals} of {required} required approvals received" }
+                                    div { class: "w-full bg-[var(--bg-primary)] rounded-full h-2",
+                                        div {
+                                            class: "bg-[var(--primary)] h-2 rounded-full transition-all",
+                                            style: "width: {(approvals.min(required) * 100 / required.max(1))}%"
+                                        }
+                                    }
+                                    if rejections > 0 {
+                                        p { class: "text-xs text-[var(--text-muted)] mt-2", "{rejections} reviewer(s) voted to reject" }
+                                    }
+                                }
+                            }
+                        }
+
                         // Application form for unverified users
                         div { class: "panel-header",
                             h2 { class: "panel-title", "📝 Submit Verification Application" }
@@ -184,6 +249,51 @@ pub fn VerificationPage() -> Element {
                         p { class: "text-[var(--text-secondary)]", 
                             "You can vote on pending applications to help grow the network." 
                         }
+                        if !trust_chain.is_empty() {
+                            div { class: "mt-4",
+                                p { class: "text-xs text-[var(--text-muted)] mb-2", "Trust chain:" }
+                                div { class: "flex flex-wrap items-center gap-2",
+                                    for (i, step) in trust_chain.iter().enumerate() {
+                                        {
+                                            let label = step.iter().map(|peer_id| name_of(peer_id)).collect::<Vec<_>>().join(" + ");
+                                            rsx! {
+                                                if i > 0 {
+                                                    span { class: "text-[var(--text-muted)]", "→" }
+                                                }
+                                                span { class: "px-2 py-1 rounded-full bg-[var(--bg-elevated)] text-sm", "{label}" }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        if !my_approvers.is_empty() {
+                            div { class: "mt-4",
+                                p { class: "text-xs text-[var(--text-muted)] mb-2", "Verified by:" }
+                                div { class: "flex flex-wrap gap-2",
+                                    for (peer_id, profile) in my_approvers.iter() {
+                                        {
+                                            let display_name = profile.as_ref()
+                                                .map(|p| p.name.clone())
+                                                .unwrap_or_else(|| format!("{}...", peer_id.get(0..12).unwrap_or(peer_id)));
+                                            let photo = profile.as_ref().and_then(|p| p.photo.clone());
+                                            rsx! {
+                                                div { class: "flex items-center gap-2 px-2 py-1 rounded-full bg-[var(--bg-elevated)]",
+                                                    if let Some(cid) = photo {
+                                                        div { class: "w-6 h-6 rounded-full overflow-hidden",
+                                                            BlobImage { cid, class: Some("w-full h-full object-cover".to_string()) }
+                                                        }
+                                                    } else {
+                                                        span { "👤" }
+                                                    }
+                                                    span { class: "text-sm", "{display_name}" }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
                     }
                 }
 
@@ -46,9 +46,14 @@ struct EventSignals {
     viewed_profile: Signal<Option<backend::dag::ProfilePayload>>,
     web_content: Signal<Option<String>>,
     posts: Signal<Vec<DagNode>>,
+    trending_posts: Signal<Vec<DagNode>>,
+    scheduled_posts: Signal<Vec<DagNode>>,
+    mentions: Signal<Vec<DagNode>>,
     blob_cache: Signal<std::collections::HashMap<String, String>>,
     last_created_blob: Signal<Option<String>>,
     storage_stats: Signal<(usize, usize)>,
+    storage_compression: Signal<(u8, f64, usize)>,
+    sync_bytes_saved: Signal<usize>,
     local_posts: Signal<Vec<DagNode>>,
     listings: Signal<Vec<DagNode>>,
     local_listings: Signal<Vec<DagNode>>,
@@ -57,13 +62,17 @@ struct EventSignals {
     active_contract_history: Signal<Vec<DagNode>>,
     pending_contracts: Signal<Vec<DagNode>>,
     contract_states: Signal<std::collections::HashMap<String, String>>,
+    contract_simulations: Signal<std::collections::HashMap<String, backend::ContractSimulation>>,
+    contract_events: Signal<std::collections::HashMap<String, Vec<backend::vm::ContractEvent>>>,
     proposals: Signal<Vec<DagNode>>,
     proposal_votes: Signal<std::collections::HashMap<String, Vec<DagNode>>>,
-    proposal_tallies: Signal<std::collections::HashMap<String, (usize, usize, usize, usize, usize, String)>>,
+    proposal_tallies: Signal<std::collections::HashMap<String, backend::WeightedTally>>,
     current_tax_rate: Signal<u8>,
     candidates: Signal<Vec<DagNode>>,
     candidate_tallies: Signal<std::collections::HashMap<String, usize>>,
     recalls: Signal<Vec<DagNode>>,
+    petitions: Signal<Vec<DagNode>>,
+    petition_signatures: Signal<std::collections::HashMap<String, Vec<String>>>,
     recall_tallies: Signal<std::collections::HashMap<String, (usize, usize, usize)>>,
     oversight_cases: Signal<Vec<DagNode>>,
     jury_duty: Signal<Vec<DagNode>>,
@@ -79,6 +88,11 @@ struct EventSignals {
     ministries: Signal<Vec<String>>,
     comments: Signal<std::collections::HashMap<String, Vec<DagNode>>>,
     likes: Signal<std::collections::HashMap<String, (usize, bool)>>,
+    reactions: Signal<std::collections::HashMap<String, backend::ReactionCounts>>,
+    shares: Signal<std::collections::HashMap<String, usize>>,
+    poll_results: Signal<std::collections::HashMap<String, backend::PollResult>>,
+    events: Signal<Vec<DagNode>>,
+    event_attendees: Signal<std::collections::HashMap<String, Vec<String>>>,
     stories: Signal<Vec<DagNode>>,
     local_stories: Signal<Vec<DagNode>>,
     seen_stories: Signal<HashSet<String>>,
@@ -88,12 +102,98 @@ struct EventSignals {
     courses: Signal<Vec<DagNode>>,
     exams: Signal<Vec<DagNode>>,
     certifications: Signal<Vec<DagNode>>,
+    expiring_certifications: Signal<HashSet<String>>,
     pending_applications: Signal<Vec<DagNode>>,
     exam_result: Signal<Option<(String, u8, bool)>>,
+    contract_audits: Signal<std::collections::HashMap<String, Vec<backend::vm::AuditFinding>>>,
+    application_approvers: Signal<std::collections::HashMap<String, Vec<backend::PeerProfile>>>,
+    inflation_stats: Signal<Option<backend::TokenInflationStats>>,
+    engagement_scores: Signal<std::collections::HashMap<String, u32>>,
+    author_reputations: Signal<std::collections::HashMap<String, backend::dag::ReputationDetails>>,
+    contract_deployers: Signal<std::collections::HashMap<String, backend::ContractDeployerInfo>>,
+    my_elected_positions: Signal<Vec<backend::dag::Ministry>>,
+    incoming_transfers: Signal<Vec<backend::IncomingTransfer>>,
+    web_stats: Signal<std::collections::HashMap<String, (usize, usize, usize)>>,
+    group_activity: Signal<std::collections::HashMap<String, Vec<backend::dag::GroupActivityEvent>>>,
+    proposal_impacts: Signal<std::collections::HashMap<String, backend::ProposalImpact>>,
+    my_vote_history: Signal<Vec<(DagNode, Option<String>)>>,
+    election_schedule: Signal<Vec<backend::dag::ElectionWindow>>,
+    cross_post_history: Signal<std::collections::HashMap<String, Vec<DagNode>>>,
+    group_leaderboards: Signal<std::collections::HashMap<String, Vec<(String, usize)>>>,
+    network_growth: Signal<Option<crate::backend::NetworkGrowthStats>>,
+    governance_records: Signal<std::collections::HashMap<String, crate::backend::GovernanceRecord>>,
+    system_params: Signal<std::collections::HashMap<String, serde_json::Value>>,
+    jury_stats: Signal<Option<crate::backend::JuryStats>>,
+    cert_leaderboard: Signal<std::collections::HashMap<String, Vec<crate::backend::CertifiedPeer>>>,
+    connected_peer_profiles: Signal<Vec<crate::backend::PeerProfile>>,
+    candidate_platforms: Signal<std::collections::HashMap<String, crate::backend::CandidatePlatform>>,
+    mutual_followers: Signal<std::collections::HashMap<String, Vec<crate::backend::PeerProfile>>>,
+    post_context: Signal<Option<crate::backend::PostContext>>,
+    contract_parties: Signal<std::collections::HashMap<String, crate::backend::ContractParties>>,
+    follow_suggestions: Signal<Vec<crate::backend::FollowSuggestion>>,
+    peers_by_geohash: Signal<std::collections::HashMap<String, Vec<crate::backend::PeerProfile>>>,
+    group_invitations: Signal<Vec<crate::backend::dag::DagNode>>,
+    group_members: Signal<std::collections::HashMap<String, Vec<(String, crate::backend::dag::GroupRole)>>>,
+    contract_milestones: Signal<std::collections::HashMap<String, Vec<crate::backend::ContractMilestone>>>,
+    similar_proposals: Signal<std::collections::HashMap<String, Vec<(crate::backend::dag::DagNode, f32)>>>,
+    my_storage_usage: Signal<std::collections::HashMap<String, (usize, usize)>>,
+    candidacy_withdrawals: Signal<std::collections::HashMap<String, Option<u64>>>,
+    message_threads: Signal<Vec<crate::backend::MessageThread>>,
+    web_page_embeds: Signal<std::collections::HashMap<String, Vec<String>>>,
+    active_officials: Signal<std::collections::HashMap<String, String>>,
+    listing_buyers: Signal<std::collections::HashMap<String, (usize, Vec<backend::ListingPurchase>)>>,
+    active_course: Signal<Option<backend::CourseDetail>>,
+    proposal_outcomes: Signal<std::collections::HashMap<String, backend::dag::DagNode>>,
+    peer_courses: Signal<std::collections::HashMap<String, backend::PeerCourseHistory>>,
+    group_policies: Signal<std::collections::HashMap<String, backend::dag::GroupPolicy>>,
+    template_deployments: Signal<std::collections::HashMap<String, usize>>,
+    endorsements: Signal<std::collections::HashMap<String, Vec<backend::Endorsement>>>,
+    oversight_stats: Signal<std::collections::HashMap<String, backend::OversightStats>>,
+    verification_paths: Signal<std::collections::HashMap<String, Vec<(String, String)>>>,
+    nodes_by_author_type: Signal<std::collections::HashMap<(String, String), Vec<backend::dag::DagNode>>>,
+    trust_scores: Signal<std::collections::HashMap<String, f32>>,
+    escrow_statuses: Signal<std::collections::HashMap<String, backend::EscrowInfo>>,
+    following_hashtags: Signal<Vec<String>>,
+    hashtag_posts: Signal<Vec<DagNode>>,
+    bookmarks: Signal<Vec<DagNode>>,
+    my_delegations: Signal<Vec<DagNode>>,
+    muted_users: Signal<Vec<String>>,
+    blocked_users: Signal<Vec<String>>,
+    network_diagnostics: Signal<Option<backend::NetworkDiagnostics>>,
+    peer_scores: Signal<std::collections::HashMap<String, backend::PeerScore>>,
+    full_sync: Signal<Option<(usize, Option<usize>, bool)>>,
+    multisig_proposals: Signal<Vec<backend::MultiSigRecord>>,
+    transaction_history: Signal<(String, Vec<backend::TransactionRecord>, usize)>,
+    spending_limit: Signal<(Option<u64>, u64)>,
+    spending_limit_exceeded: Signal<Option<(u64, u64, u64)>>,
+    pinned_blocks: Signal<Vec<String>>,
+    last_vacuum: Signal<Option<(usize, usize)>>,
+    index_rebuild: Signal<(bool, Option<(usize, u64)>)>,
+    store_root: Signal<Option<(String, usize)>>,
+    identity_backup: Signal<Option<String>>,
+    identity_import_error: Signal<Option<String>>,
+    peer_count_low: Signal<Option<(usize, usize)>>,
+    group_member_activity: Signal<std::collections::HashMap<(String, String), (backend::GroupMemberActivity, bool)>>,
+    proposal_progress: Signal<std::collections::HashMap<String, backend::QuorumProgress>>,
+    my_application_status: Signal<Option<backend::ApplicationStatus>>,
+    contract_versions: Signal<std::collections::HashMap<String, Vec<backend::dag::DagNode>>>,
+    feed_strategy: Signal<backend::FeedStrategy>,
+    ratchet_sessions: Signal<HashSet<String>>,
+    message_receipts: Signal<std::collections::HashMap<String, (String, u64)>>,
 }
 
 fn handle_app_event(event: AppEvent, sigs: &mut EventSignals, cmd_tx: &tokio::sync::mpsc::UnboundedSender<AppCmd>) {
     match event {
+        AppEvent::GroupKeyRotated { group_id } => {
+            // Messages under a key that just arrived can be decrypted now
+            let _ = cmd_tx.send(AppCmd::FetchGroupMessages { group_id });
+        }
+        AppEvent::ReceiptReceived { message_cid, reader_peer_id, timestamp } => {
+            sigs.message_receipts.write().insert(message_cid, (reader_peer_id, timestamp));
+        }
+        AppEvent::RatchetInitialized { peer_id } => {
+            sigs.ratchet_sessions.write().insert(peer_id);
+        }
         AppEvent::MessageReceived(node, content) => {
             let author = node.author.clone();
             if !sigs.peers.read().contains(&author) {
@@ -114,6 +214,38 @@ fn handle_app_event(event: AppEvent, sigs: &mut EventSignals, cmd_tx: &tokio::sy
         AppEvent::PeerDiscovered(peer) => {
             sigs.peers.write().insert(peer);
         }
+        AppEvent::PeerConnected(_) => {
+            sigs.peer_count_low.set(None);
+            let _ = cmd_tx.send(AppCmd::FetchConnectedPeerProfiles);
+        }
+        AppEvent::PeerCountLow { current, minimum } => {
+            sigs.peer_count_low.set(Some((current, minimum)));
+        }
+        AppEvent::RelayedConnectionEstablished { peer_id, relay } => {
+            println!("Reached {} through relay {}", peer_id, relay);
+        }
+        AppEvent::CandidatePlatformFetched { candidacy_id, node, profile, certifications, reputation, vote_history } => {
+            sigs.candidate_platforms.write().insert(candidacy_id, backend::CandidatePlatform { node, profile, certifications, reputation, vote_history });
+        }
+        AppEvent::PeersByGeohashFetched { geohash_prefix, peers } => {
+            sigs.peers_by_geohash.write().insert(geohash_prefix, peers);
+        }
+        AppEvent::FollowingSuggestionsFetched(suggestions) => {
+            sigs.follow_suggestions.set(suggestions);
+        }
+        AppEvent::EscrowStatusFetched { contract_id, locked_amount, status, counterparty } => {
+            sigs.escrow_statuses.write().insert(contract_id, (locked_amount, status, counterparty));
+        }
+        AppEvent::ContractPartiesFetched { contract_id, provider, consumer } => {
+            let _ = cmd_tx.send(AppCmd::FetchReputations { peer_ids: vec![provider.0.clone(), consumer.0.clone()] });
+            sigs.contract_parties.write().insert(contract_id, (provider, consumer));
+        }
+        AppEvent::MutualFollowersFetched { peer_id, mutual } => {
+            sigs.mutual_followers.write().insert(peer_id, mutual);
+        }
+        AppEvent::ConnectedPeerProfilesFetched(profiles) => {
+            sigs.connected_peer_profiles.set(profiles);
+        }
         AppEvent::MyIdentity(id) => {
             sigs.local_peer_id.set(id.clone());
             let _ = cmd_tx.send(AppCmd::FetchFollowing { target: id });
@@ -127,6 +259,50 @@ fn handle_app_event(event: AppEvent, sigs: &mut EventSignals, cmd_tx: &tokio::sy
         AppEvent::PendingTransfersFetched(nodes) => {
             sigs.pending_transfers.set(nodes);
         }
+        AppEvent::IdentityExported { bundle_b64 } => {
+            sigs.identity_backup.set(Some(bundle_b64));
+        }
+        AppEvent::ImportFailed { reason } => {
+            sigs.identity_import_error.set(Some(reason));
+        }
+        AppEvent::VacuumComplete { blocks_removed, bytes_freed } => {
+            sigs.last_vacuum.set(Some((blocks_removed, bytes_freed)));
+            let _ = cmd_tx.send(AppCmd::FetchMyStorageQuotaUsageByType);
+        }
+        AppEvent::StoreRootFetched { root_hex, node_count } => {
+            sigs.store_root.set(Some((root_hex, node_count)));
+        }
+        AppEvent::IndexRebuildStarted => {
+            let last = sigs.index_rebuild.read().1;
+            sigs.index_rebuild.set((true, last));
+        }
+        AppEvent::IndexRebuildComplete { nodes_scanned, duration_ms } => {
+            sigs.index_rebuild.set((false, Some((nodes_scanned, duration_ms))));
+        }
+        AppEvent::PinnedBlocksFetched(cids) => {
+            sigs.pinned_blocks.set(cids);
+        }
+        AppEvent::SpendingLimitFetched { daily_limit, spent_today } => {
+            sigs.spending_limit.set((daily_limit, spent_today));
+        }
+        AppEvent::SpendingLimitExceeded { attempted, limit, spent_today } => {
+            sigs.spending_limit.set((Some(limit), spent_today));
+            sigs.spending_limit_exceeded.set(Some((attempted, limit, spent_today)));
+        }
+        AppEvent::TransactionHistoryFetched { peer_id, records, total } => {
+            sigs.transaction_history.set((peer_id, records, total));
+        }
+        AppEvent::MultiSigProposalsFetched(records) => {
+            sigs.multisig_proposals.set(records);
+        }
+        AppEvent::MultiSigExecuted { proposal_cid, transfer_cid } => {
+            println!("Multi-sig transfer {} executed as {}", proposal_cid, transfer_cid);
+            let _ = cmd_tx.send(AppCmd::FetchMultiSigProposals);
+            let _ = cmd_tx.send(AppCmd::FetchBalance);
+        }
+        AppEvent::IncomingTransfersFetched { transfers } => {
+            sigs.incoming_transfers.set(transfers);
+        }
         AppEvent::GeohashDetected(hash) => {
             sigs.geohash.set(hash);
         }
@@ -150,11 +326,67 @@ fn handle_app_event(event: AppEvent, sigs: &mut EventSignals, cmd_tx: &tokio::sy
         AppEvent::HistoryFetched(fetched_posts) => {
             sigs.posts.set(fetched_posts);
         }
+        // Rankings for a strategy the user has since switched away from fall through and are dropped
+        AppEvent::PersonalizedFeedFetched { strategy, posts } if strategy == *sigs.feed_strategy.read() => {
+            sigs.posts.set(posts);
+        }
+        AppEvent::TrendingPostsFetched(posts) => {
+            sigs.trending_posts.set(posts);
+        }
+        AppEvent::ScheduledPostsFetched(posts) => {
+            sigs.scheduled_posts.set(posts);
+        }
+        AppEvent::MentionsFetched(nodes) => {
+            sigs.mentions.set(nodes);
+        }
+        AppEvent::PostEdited(edited) => {
+            // Swap the content in place; the original id, always the edit's last prev link, stays the post's identity
+            for mut list in [sigs.posts, sigs.following_posts, sigs.user_posts, sigs.local_posts] {
+                for post in list.write().iter_mut() {
+                    if edited.prev.last() == Some(&post.id) && post.author == edited.author {
+                        post.payload = edited.payload.clone();
+                    }
+                }
+            }
+        }
+        AppEvent::PostDeleted { cid } => {
+            for mut list in [sigs.posts, sigs.trending_posts, sigs.following_posts, sigs.user_posts, sigs.local_posts, sigs.hashtag_posts, sigs.bookmarks, sigs.mentions] {
+                list.write().retain(|post| post.id != cid);
+            }
+        }
         AppEvent::BlockReceived(node) => {
+            let me = sigs.local_peer_id.read().clone();
+            let mention = format!("@{}", me);
+            let mentions_me = !me.is_empty() && match &node.payload {
+                backend::dag::DagPayload::Post(p) => p.content.contains(&mention),
+                backend::dag::DagPayload::Comment(c) => c.content.contains(&mention),
+                _ => false,
+            };
+            if mentions_me {
+                let _ = cmd_tx.send(AppCmd::FetchMentions { limit: 50 });
+            }
             match node.r#type.as_str() {
-                "post:v1" => {
+                "post:v1" | "share:v1" | "poll:v1" => {
                     sigs.posts.write().insert(0, node.clone());
                 }
+                "poll_vote:v1" => {
+                    if let backend::dag::DagPayload::PollVote(vote) = &node.payload {
+                        let _ = cmd_tx.send(AppCmd::FetchPollResults { poll_id: vote.poll_id.clone() });
+                    }
+                }
+                "petition:v1" => {
+                    sigs.petitions.write().insert(0, node.clone());
+                }
+                "signature:v1" => {
+                    if let backend::dag::DagPayload::Signature(signature) = &node.payload {
+                        let _ = cmd_tx.send(AppCmd::FetchPetitionSignatures { petition_id: signature.petition_id.clone() });
+                    }
+                }
+                "rsvp:v1" => {
+                    if let backend::dag::DagPayload::Rsvp(rsvp) = &node.payload {
+                        let _ = cmd_tx.send(AppCmd::FetchEventAttendees { event_id: rsvp.event_id.clone() });
+                    }
+                }
                 "blob:v1" => {
                     if let backend::dag::DagPayload::Blob(blob) = &node.payload {
                         sigs.blob_cache.write().insert(node.id.clone(), format!("data:{};base64,{}", blob.mime_type, blob.data));
@@ -208,8 +440,13 @@ fn handle_app_event(event: AppEvent, sigs: &mut EventSignals, cmd_tx: &tokio::sy
                 }
             }
         }
-        AppEvent::StorageStatsFetched { block_count, total_bytes } => {
+        AppEvent::MyStorageUsageFetched(usage) => {
+            sigs.my_storage_usage.set(usage);
+        }
+        AppEvent::StorageStatsFetched { block_count, total_bytes, compression_level, compression_ratio, saved_bytes, sync_bytes_saved } => {
             sigs.storage_stats.set((block_count, total_bytes));
+            sigs.storage_compression.set((compression_level, compression_ratio, saved_bytes));
+            sigs.sync_bytes_saved.set(sync_bytes_saved);
         }
         AppEvent::LocalPostsFetched(fetched_posts) => {
             sigs.local_posts.set(fetched_posts);
@@ -226,42 +463,116 @@ fn handle_app_event(event: AppEvent, sigs: &mut EventSignals, cmd_tx: &tokio::sy
         AppEvent::ContractsFetched(fetched_contracts) => {
             sigs.contracts.set(fetched_contracts);
         }
+        AppEvent::TemplateDeploymentCountFetched { template_id, count } => {
+            sigs.template_deployments.write().insert(template_id, count);
+        }
+        AppEvent::ContractVersionHistoryFetched { contract_id, versions } => {
+            sigs.contract_versions.write().insert(contract_id, versions);
+        }
         AppEvent::ContractStateFetched { contract_id, state } => {
+            // The full event history is replayed right after the state
+            sigs.contract_events.write().remove(&contract_id);
             sigs.contract_states.write().insert(contract_id, state);
         }
+        AppEvent::ContractEvent { contract_id, event } => {
+            sigs.contract_events.write().entry(contract_id).or_default().push(event);
+        }
+        AppEvent::ContractSimulationResult { contract_id, resulting_state, gas_consumed, error } => {
+            sigs.contract_simulations.write().insert(contract_id, (resulting_state, gas_consumed, error));
+        }
+        AppEvent::ContractExecutionFailed { contract_id, reason } => {
+            println!("Contract {} failed: {}", contract_id, reason);
+        }
         AppEvent::ContractHistoryFetched { contract_id: _, history } => {
             sigs.active_contract_history.set(history);
         }
         AppEvent::PendingContractsFetched(contracts) => {
             sigs.pending_contracts.set(contracts);
         }
+        AppEvent::ContractAuditFetched { contract_id, findings } => {
+            sigs.contract_audits.write().insert(contract_id, findings);
+        }
+        AppEvent::ContractDeployerInfoFetched { contract_id, deployer_peer_id, reputation, certifications, vouch_count } => {
+            sigs.contract_deployers.write().insert(contract_id, backend::ContractDeployerInfo { deployer_peer_id, reputation, certifications, vouch_count });
+        }
+        AppEvent::CertificationLeaderboardFetched { cert_type, certified_peers } => {
+            sigs.cert_leaderboard.write().insert(cert_type, certified_peers);
+        }
+        AppEvent::JuryStatsFetched { cases_assigned, votes_cast, cases_decided, avg_time_to_vote_hours } => {
+            sigs.jury_stats.set(Some(backend::JuryStats { cases_assigned, votes_cast, cases_decided, avg_time_to_vote_hours }));
+        }
+        AppEvent::OversightStatsFetched { stats } => {
+            sigs.oversight_stats.set(stats);
+        }
+        AppEvent::ProposalsByAuthorFetched { peer_id, record } => {
+            sigs.governance_records.write().insert(peer_id, record);
+        }
         AppEvent::ProposalsFetched(fetched_proposals) => {
             sigs.proposals.set(fetched_proposals);
         }
         AppEvent::ProposalVotesFetched { proposal_id, votes } => {
             sigs.proposal_votes.write().insert(proposal_id, votes);
         }
-        AppEvent::ProposalTallyFetched { proposal_id, yes, no, abstain, petition, unique_voters, status } => {
-            sigs.proposal_tallies.write().insert(proposal_id, (yes, no, abstain, petition, unique_voters, status));
+        AppEvent::ProposalTallyFetched { proposal_id, yes_weight, no_weight, abstain_weight, petition, unique_voters, status, .. } => {
+            sigs.proposal_tallies.write().insert(proposal_id, (yes_weight, no_weight, abstain_weight, petition, unique_voters, status));
+        }
+        AppEvent::ProposalProgressFetched { proposal_id, yes, no, abstain, required_yes, required_unique_voters, progress_pct, time_remaining } => {
+            sigs.proposal_progress.write().insert(proposal_id, (yes, no, abstain, required_yes, required_unique_voters, progress_pct, time_remaining));
+        }
+        AppEvent::SystemParametersFetched { params } => {
+            sigs.system_params.set(params);
         }
         AppEvent::TaxRateFetched(rate) => {
             sigs.current_tax_rate.set(rate);
+            sigs.system_params.write().insert("tax_rate".to_string(), serde_json::json!(rate));
+        }
+        AppEvent::NetworkGrowthStatsFetched(stats) => {
+            sigs.network_growth.set(Some(stats));
+        }
+        AppEvent::TokenInflationRateFetched { window_days, total_minted, total_burned, net_issuance, inflation_rate_pct } => {
+            sigs.inflation_stats.set(Some(backend::TokenInflationStats { window_days, total_minted, total_burned, net_issuance, inflation_rate_pct }));
         }
         AppEvent::CandidatesFetched(fetched_candidates) => {
             sigs.candidates.set(fetched_candidates);
         }
-        AppEvent::CandidateTallyFetched { candidacy_id, votes } => {
+        AppEvent::CandidateTallyFetched { candidacy_id, votes, withdrawn } => {
+            if withdrawn && !sigs.candidacy_withdrawals.read().contains_key(&candidacy_id) {
+                sigs.candidacy_withdrawals.write().insert(candidacy_id.clone(), None);
+            }
             sigs.candidate_tallies.write().insert(candidacy_id, votes);
         }
+        AppEvent::CandidacyWithdrawalFetched { candidacy_id, withdrawn, withdrawn_at } => {
+            if withdrawn {
+                sigs.candidacy_withdrawals.write().insert(candidacy_id, withdrawn_at);
+            } else {
+                sigs.candidacy_withdrawals.write().remove(&candidacy_id);
+            }
+        }
         AppEvent::RecallsFetched(fetched_recalls) => {
             sigs.recalls.set(fetched_recalls);
         }
+        AppEvent::PetitionsFetched(petitions) => {
+            sigs.petitions.set(petitions);
+        }
+        AppEvent::PetitionSignaturesFetched { petition_id, signers } => {
+            sigs.petition_signatures.write().insert(petition_id, signers);
+        }
+        AppEvent::PetitionThresholdReached { petition_id } => {
+            println!("Petition {} reached its signature threshold", petition_id);
+            let _ = cmd_tx.send(AppCmd::FetchPetitionSignatures { petition_id });
+        }
         AppEvent::RecallTallyFetched { recall_id, remove, keep, unique_voters } => {
             sigs.recall_tallies.write().insert(recall_id, (remove, keep, unique_voters));
         }
         AppEvent::ReputationFetched(details) => {
             sigs.reputation.set(Some(details));
         }
+        AppEvent::ReputationsBatchFetched(reputations) => {
+            sigs.author_reputations.write().extend(reputations);
+        }
+        AppEvent::TrustScoreFetched { peer_id, score, .. } => {
+            sigs.trust_scores.write().insert(peer_id, score);
+        }
         AppEvent::OversightCasesFetched(cases) => {
             sigs.oversight_cases.set(cases);
         }
@@ -269,8 +580,16 @@ fn handle_app_event(event: AppEvent, sigs: &mut EventSignals, cmd_tx: &tokio::sy
             sigs.jury_duty.set(cases);
         }
         AppEvent::MyWebPagesFetched(pages) => {
+            for page in &pages {
+                if let backend::dag::DagPayload::Web(ref w) = page.payload {
+                    let _ = cmd_tx.send(AppCmd::FetchWebStats { url: w.url.clone() });
+                }
+            }
             sigs.my_web_pages.set(pages);
         }
+        AppEvent::WebStatsFetched { url, total_views, unique_viewers, views_last_7_days } => {
+            sigs.web_stats.write().insert(url, (total_views, unique_viewers, views_last_7_days));
+        }
         AppEvent::AllWebPagesFetched(pages) => {
             sigs.all_web_pages.set(pages);
         }
@@ -302,24 +621,154 @@ fn handle_app_event(event: AppEvent, sigs: &mut EventSignals, cmd_tx: &tokio::sy
         AppEvent::FileSearchResults(results) => {
             sigs.file_search_results.set(results);
         }
+        AppEvent::GroupLeaderboardFetched { group_id, rankings } => {
+            sigs.group_leaderboards.write().insert(group_id, rankings);
+        }
+        AppEvent::SimilarProposalsFetched { proposal_id, similar } => {
+            sigs.similar_proposals.write().insert(proposal_id, similar);
+        }
+        AppEvent::ContractMilestonesFetched { contract_id, milestones } => {
+            sigs.contract_milestones.write().insert(contract_id, milestones);
+        }
+        AppEvent::WebPageEmbedsFetched { url, blob_cids } => {
+            sigs.web_page_embeds.write().insert(url, blob_cids);
+        }
+        AppEvent::MessageThreadsFetched(threads) => {
+            sigs.message_threads.set(threads);
+        }
+        AppEvent::GroupMembersFetched { group_id, members } => {
+            sigs.group_members.write().insert(group_id, members);
+        }
+        AppEvent::GroupInvitationsFetched(invites) => {
+            sigs.group_invitations.set(invites);
+        }
+        AppEvent::GroupActivityFetched { group_id, events } => {
+            sigs.group_activity.write().insert(group_id, events);
+        }
+        AppEvent::GroupMemberActivityFetched { group_id, member_peer_id, message_count, post_count, reports_against, last_active, moderation_flag } => {
+            sigs.group_member_activity.write().insert((group_id, member_peer_id), ((message_count, post_count, reports_against, last_active), moderation_flag));
+        }
+        AppEvent::ElectionScheduleFetched { schedule } => {
+            sigs.election_schedule.set(schedule);
+        }
+        AppEvent::MyVoteHistoryFetched { votes } => {
+            sigs.my_vote_history.set(votes);
+        }
+        AppEvent::ProposalImpactEstimated { proposal_id, narrative, metrics } => {
+            sigs.proposal_impacts.write().insert(proposal_id, (narrative, metrics));
+        }
         AppEvent::MinistriesFetched(m) => {
             sigs.ministries.set(m);
         }
+        AppEvent::MyElectedPositionsFetched { positions } => {
+            sigs.my_elected_positions.set(positions);
+        }
+        AppEvent::GroupPolicyFetched { group_id, policy } => {
+            sigs.group_policies.write().insert(group_id, policy);
+        }
+        AppEvent::PeerCoursesCompletedFetched { peer_id, completed, in_progress } => {
+            sigs.peer_courses.write().insert(peer_id, (completed, in_progress));
+        }
+        AppEvent::EndorsementsFetched { peer_id, endorsements } => {
+            sigs.endorsements.write().insert(peer_id, endorsements);
+        }
+        AppEvent::ProposalOutcomesFetched(outcomes) => {
+            let mut recorded = sigs.proposal_outcomes.write();
+            for node in outcomes {
+                if let backend::dag::DagPayload::ProposalOutcome(ref o) = node.payload {
+                    recorded.insert(o.proposal_id.clone(), node.clone());
+                }
+            }
+        }
+        AppEvent::CourseFetched { node, exam, progress } => {
+            sigs.active_course.set(Some((node, exam, progress)));
+        }
+        AppEvent::ListingBuyerHistoryFetched { listing_id, purchase_count, buyers } => {
+            sigs.listing_buyers.write().insert(listing_id, (purchase_count, buyers));
+        }
+        AppEvent::ActiveOfficialsFetched { officials } => {
+            sigs.active_officials.set(officials);
+        }
         AppEvent::CommentsFetched { parent_id, comments: c } => {
             sigs.comments.write().insert(parent_id, c);
         }
         AppEvent::LikesFetched { target_id, count, is_liked_by_me } => {
             sigs.likes.write().insert(target_id, (count, is_liked_by_me));
         }
+        AppEvent::ReactionsFetched { target_id, counts } => {
+            sigs.reactions.write().insert(target_id, counts);
+        }
+        AppEvent::SharesFetched { cid, count } => {
+            sigs.shares.write().insert(cid, count);
+        }
+        AppEvent::PollResultsFetched { poll_id, tally, closed } => {
+            sigs.poll_results.write().insert(poll_id, (tally, closed));
+        }
+        AppEvent::EventsFetched(events) => {
+            sigs.events.set(events);
+        }
+        AppEvent::AttendeesFetched { event_id, attendees } => {
+            sigs.event_attendees.write().insert(event_id, attendees);
+        }
+        AppEvent::PostContextFetched { center_post_id, posts, center_index } => {
+            sigs.post_context.set(Some((center_post_id, posts, center_index)));
+        }
+        AppEvent::CrossPostHistoryFetched { original_id, reposts } => {
+            sigs.cross_post_history.write().insert(original_id, reposts);
+        }
+        AppEvent::PostEngagementFetched { post_id, score, .. } => {
+            sigs.engagement_scores.write().insert(post_id, score);
+        }
         AppEvent::FollowingFetched(f) => {
+            // New users with an empty following feed get "People You May Know"
+            if f.is_empty() {
+                let _ = cmd_tx.send(AppCmd::FetchFollowingSuggestions { limit: 10 });
+            }
             sigs.following.set(f);
         }
         AppEvent::UserPostsFetched(p) => {
             sigs.user_posts.set(p);
         }
+        AppEvent::NodesByAuthorTypeFetched { peer_id, node_type, nodes, .. } => {
+            sigs.nodes_by_author_type.write().insert((peer_id, node_type), nodes);
+        }
         AppEvent::FollowingPostsFetched(p) => {
             sigs.following_posts.set(p);
         }
+        AppEvent::FollowingHashtagsFetched(tags) => {
+            sigs.following_hashtags.set(tags);
+        }
+        AppEvent::HashtagPostsFetched(p) => {
+            sigs.hashtag_posts.set(p);
+        }
+        AppEvent::BookmarksFetched(b) => {
+            sigs.bookmarks.set(b);
+        }
+        AppEvent::DelegationActivated { delegator, delegate } => {
+            println!("{} delegated their vote to {}", delegator, delegate);
+        }
+        AppEvent::MyDelegationsFetched(d) => {
+            sigs.my_delegations.set(d);
+        }
+        AppEvent::MutedUsersFetched(peers) => {
+            sigs.muted_users.set(peers);
+        }
+        AppEvent::BlockedUsersFetched(peers) => {
+            sigs.blocked_users.set(peers);
+        }
+        AppEvent::NetworkDiagnostics(diag) => {
+            sigs.network_diagnostics.set(Some(diag));
+        }
+        AppEvent::PeerScoresFetched(scores) => {
+            sigs.peer_scores.set(scores);
+        }
+        AppEvent::SyncProgress { received, estimated_total } => {
+            sigs.full_sync.set(Some((received, estimated_total, false)));
+        }
+        AppEvent::SyncComplete { received } => {
+            sigs.full_sync.set(Some((received, None, true)));
+            let _ = cmd_tx.send(AppCmd::FetchStorageStats);
+        }
         AppEvent::CoursesFetched(c) => {
             sigs.courses.set(c);
         }
@@ -328,6 +777,21 @@ fn handle_app_event(event: AppEvent, sigs: &mut EventSignals, cmd_tx: &tokio::sy
         }
         AppEvent::CertificationsFetched(c) => {
             sigs.certifications.set(c);
+            // Expiry warnings for the fresh list follow this event
+            sigs.expiring_certifications.write().clear();
+        }
+        AppEvent::VouchRevoked { proof_cid } => {
+            // Our own standing may have rested on the revoked vouch, however far up the chain
+            println!("Vouch {} revoked", proof_cid);
+            let _ = cmd_tx.send(AppCmd::CheckVerificationStatus);
+            let _ = cmd_tx.send(AppCmd::FetchPeerVerificationPath { peer_id: sigs.local_peer_id.read().clone() });
+        }
+        AppEvent::CertificationRevoked { cert_cid, peer_id, reason } => {
+            println!("Certification {} of {} revoked: {}", cert_cid, peer_id, reason);
+            sigs.certifications.write().retain(|n| n.id != cert_cid);
+        }
+        AppEvent::CertificationExpired { peer_id, certification_type } if peer_id == *sigs.local_peer_id.read() => {
+            sigs.expiring_certifications.write().insert(certification_type);
         }
         AppEvent::ExamSubmitted { exam_id, score, passed } => {
             println!("Exam {} submitted: score={}, passed={}", exam_id, score, passed);
@@ -337,6 +801,15 @@ fn handle_app_event(event: AppEvent, sigs: &mut EventSignals, cmd_tx: &tokio::sy
             sigs.pending_applications.set(apps);
         }
         AppEvent::ApplicationVotesFetched { .. } => {}
+        AppEvent::MyApplicationStatusFetched { application_id, approvals, rejections, required, is_approved, is_rejected } => {
+            sigs.my_application_status.set(Some((application_id, approvals, rejections, required, is_approved, is_rejected)));
+        }
+        AppEvent::ApplicationApproversFetched { application_id, approvers } => {
+            sigs.application_approvers.write().insert(application_id, approvers);
+        }
+        AppEvent::VerificationPathFetched { peer_id, path } => {
+            sigs.verification_paths.write().insert(peer_id, path);
+        }
         _ => {}
     }
 }
@@ -356,7 +829,7 @@ fn App() -> Element {
     let browser_url = use_signal(|| "sp://welcome".to_string());
     let browser_content = use_signal(|| None::<String>);
     let active_tab = use_signal(|| "feed".to_string());
-    let peers = use_signal(|| HashSet::<String>::new());
+    let peers = use_signal(HashSet::<String>::new);
     let local_peer_id = use_signal(|| String::new());
     let profile = use_signal(|| None::<backend::dag::ProfilePayload>);
     let balance = use_signal(|| 0i64);
@@ -367,9 +840,15 @@ fn App() -> Element {
     let viewed_profile = use_signal(|| None::<backend::dag::ProfilePayload>);
     let web_content = use_signal(|| None::<String>);
     let posts = use_signal(|| Vec::<DagNode>::new());
+    let trending_posts = use_signal(Vec::<DagNode>::new);
+    let scheduled_posts = use_signal(Vec::<DagNode>::new);
+    let mentions = use_signal(Vec::<DagNode>::new);
+    let mentions_seen_at = use_signal(|| None::<chrono::DateTime<chrono::Utc>>);
     let blob_cache = use_signal(|| std::collections::HashMap::<String, String>::new());
     let last_created_blob = use_signal(|| None::<String>);
     let storage_stats = use_signal(|| (0usize, 0usize));
+    let storage_compression = use_signal(|| (0u8, 1.0f64, 0usize));
+    let sync_bytes_saved = use_signal(|| 0usize);
     let local_posts = use_signal(|| Vec::<DagNode>::new());
     let listings = use_signal(|| Vec::<DagNode>::new());
     let local_listings = use_signal(|| Vec::<DagNode>::new());
@@ -379,13 +858,17 @@ fn App() -> Element {
     let active_contract_history = use_signal(|| Vec::<DagNode>::new());
     let pending_contracts = use_signal(|| Vec::<DagNode>::new());
     let contract_states = use_signal(|| std::collections::HashMap::<String, String>::new());
+    let contract_simulations = use_signal(std::collections::HashMap::<String, backend::ContractSimulation>::new);
+    let contract_events = use_signal(std::collections::HashMap::<String, Vec<backend::vm::ContractEvent>>::new);
     let proposals = use_signal(|| Vec::<DagNode>::new());
     let proposal_votes = use_signal(|| std::collections::HashMap::<String, Vec<DagNode>>::new());
-    let proposal_tallies = use_signal(|| std::collections::HashMap::<String, (usize, usize, usize, usize, usize, String)>::new());
+    let proposal_tallies = use_signal(std::collections::HashMap::<String, backend::WeightedTally>::new);
     let current_tax_rate = use_signal(|| 0u8);
     let candidates = use_signal(|| Vec::<DagNode>::new());
     let candidate_tallies = use_signal(|| std::collections::HashMap::<String, usize>::new());
     let recalls = use_signal(|| Vec::<DagNode>::new());
+    let petitions = use_signal(Vec::<DagNode>::new);
+    let petition_signatures = use_signal(std::collections::HashMap::<String, Vec<String>>::new);
     let recall_tallies = use_signal(|| std::collections::HashMap::<String, (usize, usize, usize)>::new());
     let oversight_cases = use_signal(|| Vec::<DagNode>::new());
     let jury_duty = use_signal(|| Vec::<DagNode>::new());
@@ -398,9 +881,14 @@ fn App() -> Element {
     let ministries = use_signal(|| Vec::<String>::new());
     let comments = use_signal(|| std::collections::HashMap::<String, Vec<DagNode>>::new());
     let likes = use_signal(|| std::collections::HashMap::<String, (usize, bool)>::new());
+    let reactions = use_signal(std::collections::HashMap::<String, backend::ReactionCounts>::new);
+    let shares = use_signal(std::collections::HashMap::<String, usize>::new);
+    let poll_results = use_signal(std::collections::HashMap::<String, backend::PollResult>::new);
+    let events = use_signal(Vec::<DagNode>::new);
+    let event_attendees = use_signal(std::collections::HashMap::<String, Vec<String>>::new);
     let stories = use_signal(|| Vec::<DagNode>::new());
     let local_stories = use_signal(|| Vec::<DagNode>::new());
-    let seen_stories = use_signal(|| HashSet::<String>::new());
+    let seen_stories = use_signal(HashSet::<String>::new);
     let following = use_signal(|| Vec::<String>::new());
     let user_posts = use_signal(|| Vec::<DagNode>::new());
     let following_posts = use_signal(|| Vec::<DagNode>::new());
@@ -408,22 +896,99 @@ fn App() -> Element {
     let courses = use_signal(|| Vec::<DagNode>::new());
     let exams = use_signal(|| Vec::<DagNode>::new());
     let certifications = use_signal(|| Vec::<DagNode>::new());
+    let expiring_certifications = use_signal(HashSet::<String>::new);
     let active_exam = use_signal(|| None::<DagNode>);
     let pending_applications = use_signal(|| Vec::<DagNode>::new());
     let exam_answers = use_signal(|| Vec::<Option<usize>>::new());
     let exam_result = use_signal(|| None::<(String, u8, bool)>);
     // Wiki homepage
     let all_web_pages = use_signal(|| Vec::<DagNode>::new());
+    let contract_audits = use_signal(std::collections::HashMap::<String, Vec<backend::vm::AuditFinding>>::new);
+    let application_approvers = use_signal(std::collections::HashMap::new);
+    let inflation_stats = use_signal(|| None);
+    let engagement_scores = use_signal(std::collections::HashMap::new);
+    let author_reputations = use_signal(std::collections::HashMap::new);
+    let contract_deployers = use_signal(std::collections::HashMap::new);
+    let my_elected_positions = use_signal(Vec::new);
+    let incoming_transfers = use_signal(Vec::new);
+    let web_stats = use_signal(std::collections::HashMap::new);
+    let group_activity = use_signal(std::collections::HashMap::new);
+    let proposal_impacts = use_signal(std::collections::HashMap::new);
+    let my_vote_history = use_signal(Vec::new);
+    let election_schedule = use_signal(Vec::new);
+    let cross_post_history = use_signal(std::collections::HashMap::new);
+    let group_leaderboards = use_signal(std::collections::HashMap::new);
+    let network_growth = use_signal(|| None);
+    let governance_records = use_signal(std::collections::HashMap::new);
+    let system_params = use_signal(std::collections::HashMap::new);
+    let jury_stats = use_signal(|| None);
+    let cert_leaderboard = use_signal(std::collections::HashMap::new);
+    let connected_peer_profiles = use_signal(Vec::new);
+    let candidate_platforms = use_signal(std::collections::HashMap::new);
+    let mutual_followers = use_signal(std::collections::HashMap::new);
+    let post_context = use_signal(|| None);
+    let contract_parties = use_signal(std::collections::HashMap::new);
+    let follow_suggestions = use_signal(Vec::new);
+    let peers_by_geohash = use_signal(std::collections::HashMap::new);
+    let group_invitations = use_signal(Vec::new);
+    let group_members = use_signal(std::collections::HashMap::new);
+    let contract_milestones = use_signal(std::collections::HashMap::new);
+    let similar_proposals = use_signal(std::collections::HashMap::new);
+    let my_storage_usage = use_signal(std::collections::HashMap::new);
+    let candidacy_withdrawals = use_signal(std::collections::HashMap::new);
+    let message_threads = use_signal(Vec::new);
+    let web_page_embeds = use_signal(std::collections::HashMap::new);
+    let active_officials = use_signal(std::collections::HashMap::new);
+    let listing_buyers = use_signal(std::collections::HashMap::new);
+    let active_course = use_signal(|| None);
+    let proposal_outcomes = use_signal(std::collections::HashMap::new);
+    let peer_courses = use_signal(std::collections::HashMap::new);
+    let group_policies = use_signal(std::collections::HashMap::new);
+    let template_deployments = use_signal(std::collections::HashMap::new);
+    let endorsements = use_signal(std::collections::HashMap::new);
+    let oversight_stats = use_signal(std::collections::HashMap::new);
+    let verification_paths = use_signal(std::collections::HashMap::new);
+    let nodes_by_author_type = use_signal(std::collections::HashMap::new);
+    let trust_scores = use_signal(std::collections::HashMap::new);
+    let escrow_statuses = use_signal(std::collections::HashMap::new);
+    let following_hashtags = use_signal(Vec::new);
+    let hashtag_posts = use_signal(Vec::<DagNode>::new);
+    let bookmarks = use_signal(Vec::<DagNode>::new);
+    let my_delegations = use_signal(Vec::<DagNode>::new);
+    let muted_users = use_signal(Vec::new);
+    let blocked_users = use_signal(Vec::new);
+    let network_diagnostics = use_signal(|| None);
+    let peer_scores = use_signal(std::collections::HashMap::new);
+    let full_sync = use_signal(|| None);
+    let multisig_proposals = use_signal(Vec::new);
+    let transaction_history = use_signal(|| (String::new(), Vec::new(), 0));
+    let spending_limit = use_signal(|| (None, 0));
+    let spending_limit_exceeded = use_signal(|| None);
+    let pinned_blocks = use_signal(Vec::new);
+    let pin_policy = use_signal(|| backend::DEFAULT_PIN_POLICY.iter().map(|t| t.to_string()).collect());
+    let last_vacuum = use_signal(|| None);
+    let index_rebuild = use_signal(|| (false, None));
+    let store_root = use_signal(|| None);
+    let identity_backup = use_signal(|| None);
+    let identity_import_error = use_signal(|| None);
+    let peer_count_low = use_signal(|| None);
+    let group_member_activity = use_signal(std::collections::HashMap::new);
+    let proposal_progress = use_signal(std::collections::HashMap::new);
+    let my_application_status = use_signal(|| None);
+    let contract_versions = use_signal(std::collections::HashMap::new);
+    let feed_strategy = use_signal(backend::FeedStrategy::default);
+    let ratchet_sessions = use_signal(HashSet::new);
+    let message_receipts = use_signal(std::collections::HashMap::new);
         
     // Groups
     let groups = use_signal(|| Vec::<DagNode>::new());
     let group_messages = use_signal(|| std::collections::HashMap::<String, Vec<(DagNode, String)>>::new());
     
-    use_context_provider(|| AppState { messages, blocks, history, user_profiles, page_title, browser_url, browser_content, active_tab, peers, local_peer_id, profile, balance, pending_transfers, geohash, ubi_timer, verification_status, viewed_profile, web_content, posts, blob_cache, last_created_blob, storage_stats, local_posts, listings, local_listings, web_search_results, contracts, smart_contracts, active_contract_history, pending_contracts, contract_states, proposals, proposal_votes, proposal_tallies, current_tax_rate, candidates, candidate_tallies, recalls,
+    use_context_provider(|| AppState { messages, blocks, history, user_profiles, page_title, browser_url, browser_content, active_tab, peers, local_peer_id, profile, balance, pending_transfers, geohash, ubi_timer, verification_status, viewed_profile, web_content, posts, blob_cache, last_created_blob, storage_stats, storage_compression, sync_bytes_saved, local_posts, listings, local_listings, web_search_results, contracts, smart_contracts, active_contract_history, pending_contracts, contract_states, contract_simulations, contract_events, proposals, proposal_votes, proposal_tallies, current_tax_rate, candidates, candidate_tallies, recalls, petitions, petition_signatures,
         recall_tallies,
         oversight_cases,
         jury_duty,
-        reputation, my_web_pages, reports, groups, group_messages, files, public_ledger, file_search_results, ministries, comments, likes, stories, local_stories, seen_stories, following, user_posts, following_posts, courses, exams, certifications, active_exam, pending_applications, exam_answers, exam_result, all_web_pages });
+        reputation, my_web_pages, reports, groups, group_messages, files, public_ledger, file_search_results, ministries, comments, likes, reactions, shares, poll_results, events, event_attendees, stories, local_stories, seen_stories, following, user_posts, following_posts, courses, exams, certifications, expiring_certifications, active_exam, pending_applications, exam_answers, exam_result, all_web_pages, contract_audits, application_approvers, inflation_stats, engagement_scores, author_reputations, contract_deployers, my_elected_positions, incoming_transfers, web_stats, group_activity, proposal_impacts, my_vote_history, election_schedule, cross_post_history, group_leaderboards, network_growth, governance_records, system_params, jury_stats, cert_leaderboard, connected_peer_profiles, candidate_platforms, mutual_followers, post_context, contract_parties, follow_suggestions, peers_by_geohash, group_invitations, group_members, contract_milestones, similar_proposals, my_storage_usage, candidacy_withdrawals, message_threads, web_page_embeds, active_officials, listing_buyers, active_course, proposal_outcomes, peer_courses, group_policies, template_deployments, endorsements, oversight_stats, verification_paths, nodes_by_author_type, trust_scores, escrow_statuses, following_hashtags, hashtag_posts, bookmarks, my_delegations, muted_users, blocked_users, network_diagnostics, peer_scores, full_sync, multisig_proposals, transaction_history, spending_limit, spending_limit_exceeded, pinned_blocks, pin_policy, last_vacuum, index_rebuild, store_root, identity_backup, identity_import_error, peer_count_low, trending_posts, scheduled_posts, mentions, mentions_seen_at, group_member_activity, proposal_progress, my_application_status, contract_versions, feed_strategy, ratchet_sessions, message_receipts });
 
     // Initialize backend and context
     use_context_provider(|| {
@@ -458,9 +1023,14 @@ fn App() -> Element {
                 viewed_profile,
                 web_content,
                 posts,
+                trending_posts,
+                scheduled_posts,
+                mentions,
                 blob_cache,
                 last_created_blob,
                 storage_stats,
+                storage_compression,
+                sync_bytes_saved,
                 local_posts,
                 listings,
                 local_listings,
@@ -469,6 +1039,8 @@ fn App() -> Element {
                 active_contract_history,
                 pending_contracts,
                 contract_states,
+                contract_simulations,
+                contract_events,
                 proposals,
                 proposal_votes,
                 proposal_tallies,
@@ -476,6 +1048,8 @@ fn App() -> Element {
                 candidates,
                 candidate_tallies,
                 recalls,
+                petitions,
+                petition_signatures,
                 recall_tallies,
                 oversight_cases,
                 jury_duty,
@@ -491,6 +1065,11 @@ fn App() -> Element {
                 ministries,
                 comments,
                 likes,
+                reactions,
+                shares,
+                poll_results,
+                events,
+                event_attendees,
                 stories,
                 local_stories,
                 seen_stories,
@@ -500,8 +1079,84 @@ fn App() -> Element {
                 courses,
                 exams,
                 certifications,
+                expiring_certifications,
                 pending_applications,
                 exam_result,
+                contract_audits,
+                application_approvers,
+                inflation_stats,
+                engagement_scores,
+                author_reputations,
+                contract_deployers,
+                my_elected_positions,
+                incoming_transfers,
+                web_stats,
+                group_activity,
+                proposal_impacts,
+                my_vote_history,
+                election_schedule,
+                cross_post_history,
+                group_leaderboards,
+                network_growth,
+                governance_records,
+                system_params,
+                jury_stats,
+                cert_leaderboard,
+                connected_peer_profiles,
+                candidate_platforms,
+                mutual_followers,
+                post_context,
+                contract_parties,
+                follow_suggestions,
+                peers_by_geohash,
+                group_invitations,
+                group_members,
+                contract_milestones,
+                similar_proposals,
+                my_storage_usage,
+                candidacy_withdrawals,
+                message_threads,
+                web_page_embeds,
+                active_officials,
+                listing_buyers,
+                active_course,
+                proposal_outcomes,
+                peer_courses,
+                group_policies,
+                template_deployments,
+                endorsements,
+                oversight_stats,
+                verification_paths,
+                nodes_by_author_type,
+                trust_scores,
+                escrow_statuses,
+                following_hashtags,
+                hashtag_posts,
+                bookmarks,
+                my_delegations,
+                muted_users,
+                blocked_users,
+                network_diagnostics,
+                peer_scores,
+                full_sync,
+                multisig_proposals,
+                transaction_history,
+                spending_limit,
+                spending_limit_exceeded,
+                pinned_blocks,
+                last_vacuum,
+                index_rebuild,
+                store_root,
+                identity_backup,
+                identity_import_error,
+                peer_count_low,
+                group_member_activity,
+                proposal_progress,
+                my_application_status,
+                contract_versions,
+                feed_strategy,
+                ratchet_sessions,
+                message_receipts,
             };
             while let Some(event) = event_rx.recv().await {
                 println!("UI Received event: {:?}", event);
@@ -513,7 +1168,9 @@ fn App() -> Element {
         let _ = cmd_tx.send(AppCmd::CheckVerificationStatus);
         let _ = cmd_tx.send(AppCmd::FetchStorageStats);
         let _ = cmd_tx.send(AppCmd::FetchMinistries);
-        let _ = cmd_tx.send(AppCmd::FetchTaxRate);
+        let _ = cmd_tx.send(AppCmd::FetchMyElectedPositions);
+        let _ = cmd_tx.send(AppCmd::FetchActiveOfficials);
+        let _ = cmd_tx.send(AppCmd::FetchSystemParameters);
 
         // Return the sender to be stored in context
         cmd_tx